@@ -73,6 +73,8 @@ fn generate_bindings(include_dirs: &[PathBuf]) {
         .allowlist_function("rte_thread_set_affinity")
         .allowlist_function("rte_thread_register")
         .allowlist_function("rte_thread_unregister")
+        .allowlist_function("rte_thread_self")
+        .allowlist_function("rte_thread_set_name")
         .allowlist_function("rte_pktmbuf_pool_create")
         .allowlist_function("rte_mempool_free")
         .allowlist_function("rte_mempool_lookup")
@@ -80,10 +82,13 @@ fn generate_bindings(include_dirs: &[PathBuf]) {
         .allowlist_function("rte_eal_process_type")
         .allowlist_function("rte_pktmbuf_free_bulk")
         .allowlist_function("rte_mempool_avail_count") // this can be removed
+        .allowlist_function("rte_mempool_in_use_count")
         .allowlist_function("rte_eth_dev_info_get")
         .allowlist_function("rte_eth_dev_count_avail")
         .allowlist_function("rte_eth_macaddr_get")
         .allowlist_function("rte_eth_stats_get")
+        .allowlist_function("rte_eth_xstats_get_names")
+        .allowlist_function("rte_eth_xstats_get")
         .allowlist_function("rte_eth_dev_socket_id")
         .allowlist_function("rte_eth_dev_configure")
         .allowlist_function("rte_eth_dev_start")
@@ -93,10 +98,17 @@ fn generate_bindings(include_dirs: &[PathBuf]) {
         .allowlist_function("rte_eth_tx_queue_setup")
         .allowlist_function("rte_eth_promiscuous_enable")
         .allowlist_function("rte_eth_promiscuous_disable")
+        .allowlist_function("rte_eth_allmulticast_enable")
+        .allowlist_function("rte_eth_allmulticast_disable")
+        .allowlist_function("rte_eth_allmulticast_get")
+        .allowlist_function("rte_eth_dev_set_mtu")
+        .allowlist_function("rte_eth_dev_get_mtu")
         .allowlist_function("rte_eth_dev_rss_reta_update")
         .allowlist_function("rte_eth_dev_rss_reta_query")
         .allowlist_function("rte_eth_dev_rss_hash_update")
         .allowlist_function("rte_eth_dev_rss_hash_conf_get")
+        .allowlist_function("rte_eth_dev_callback_register")
+        .allowlist_function("rte_eth_dev_callback_unregister")
         .allowlist_function("rte_eal_init")
         .allowlist_function("rte_eal_cleanup")
         // Lcore management functions
@@ -110,6 +122,8 @@ fn generate_bindings(include_dirs: &[PathBuf]) {
         .allowlist_function("rte_lcore_to_socket_id")
         .allowlist_function("rte_lcore_to_cpu_id")
         .allowlist_function("rte_get_next_lcore")
+        // Service cores
+        .allowlist_function("rte_service_.*")
         // Lcore wrapper functions for inlines
         .allowlist_function("rust_rte_lcore_id")
         .allowlist_function("rust_rte_get_main_lcore")
@@ -125,16 +139,23 @@ fn generate_bindings(include_dirs: &[PathBuf]) {
         .allowlist_type("rte_lcore_state_t")
         .allowlist_type("rte_lcore_role_t")
         .allowlist_type("lcore_function_t")
+        .allowlist_type("rte_service_spec")
         // generate useful dpdk macros defined in rte_build_config.h.
         .allowlist_var("RTE_MAX_LCORE")
         .allowlist_var("LCORE_ID_ANY")
         .allowlist_var("RTE_MAX_NUMA_NODES")
+        .allowlist_var("RTE_SERVICE_NAME_MAX")
         .allowlist_var("RTE_MBUF_MAX_NB_SEGS")
         .allowlist_var("RTE_MBUF_DEFAULT_DATAROOM")
         .allowlist_var("RTE_PKTMBUF_HEADROOM")
+        .allowlist_var("RTE_MEMPOOL_CACHE_MAX_SIZE")
         .allowlist_var("RTE_ETHDEV_QUEUE_STAT_CNTRS")
         // RSS hash type constants (from wrapper.h static consts)
         .allowlist_var("RUST_RTE_ETH_RSS_.*")
+        .allowlist_var("RUST_RTE_ETH_EVENT_.*")
+        .allowlist_var("RUST_RTE_ETH_RX_OFFLOAD_.*")
+        .allowlist_var("RUST_RTE_ETH_TX_OFFLOAD_.*")
+        .allowlist_var("RUST_RTE_MBUF_F_.*")
         .header("include/wrapper.h");
 
     let bindings = bgbuilder
@@ -8,6 +8,7 @@
 #include <rte_mbuf.h>
 #include <rte_lcore.h>
 #include <rte_launch.h>
+#include <rte_service_component.h>
 
 // Wrapper functions for accessing rte_errno (per-lcore macro)
 int rust_get_rte_errno(void);
@@ -29,6 +30,10 @@ char* rust_pktmbuf_adj(struct rte_mbuf *m, uint16_t len);
 int rust_pktmbuf_trim(struct rte_mbuf *m, uint16_t len);
 void rust_pktmbuf_reset(struct rte_mbuf *m);
 uint16_t rust_pktmbuf_data_room_size(struct rte_mempool *mp);
+uint64_t rust_pktmbuf_ol_flags(const struct rte_mbuf *m);
+// Parse the Ethernet/IP headers of a frame and request IPv4 header and
+// TCP/UDP checksum offload for it; other frames are left untouched.
+void rust_pktmbuf_tx_cksum_offload(struct rte_mbuf *m);
 
 // Ethernet RX/TX burst wrappers (static inline functions)
 uint16_t rust_eth_rx_burst(uint16_t port_id, uint16_t queue_id,
@@ -36,6 +41,37 @@ uint16_t rust_eth_rx_burst(uint16_t port_id, uint16_t queue_id,
 uint16_t rust_eth_tx_burst(uint16_t port_id, uint16_t queue_id,
                            struct rte_mbuf **tx_pkts, uint16_t nb_pkts);
 
+// Link status without the bitfields of struct rte_eth_link
+int rust_eth_link_get_nowait(uint16_t port_id, uint32_t *speed, int *up, int *full_duplex);
+
+// Flow rules steering IPv4 traffic to an RX queue, without the bitfields and
+// unions of the rte_flow item structs. Addresses and ports in host order; a
+// zero mask leaves the field unmatched. proto is IPPROTO_TCP, IPPROTO_UDP or
+// 0 (any; ports are ignored then).
+struct rust_flow_match {
+    uint8_t proto;
+    uint32_t src_ip;
+    uint32_t src_ip_mask;
+    uint32_t dst_ip;
+    uint32_t dst_ip_mask;
+    uint16_t src_port;
+    uint16_t src_port_mask;
+    uint16_t dst_port;
+    uint16_t dst_port_mask;
+};
+
+struct rte_flow;
+
+// Both return rte_errno-style failures and point *message at the driver's
+// explanation (static storage, may be NULL).
+int rust_flow_validate_queue(uint16_t port_id, uint32_t priority,
+                             const struct rust_flow_match *m, uint16_t queue,
+                             const char **message);
+struct rte_flow *rust_flow_create_queue(uint16_t port_id, uint32_t priority,
+                                        const struct rust_flow_match *m, uint16_t queue,
+                                        const char **message);
+int rust_flow_destroy(uint16_t port_id, struct rte_flow *flow, const char **message);
+
 // Lcore wrapper functions (for inline functions)
 unsigned rust_rte_lcore_id(void);
 unsigned rust_rte_get_main_lcore(void);
@@ -61,4 +97,23 @@ static const uint64_t RUST_RTE_ETH_RSS_IP = RTE_ETH_RSS_IP;
 static const uint64_t RUST_RTE_ETH_RSS_TCP = RTE_ETH_RSS_TCP;
 static const uint64_t RUST_RTE_ETH_RSS_UDP = RTE_ETH_RSS_UDP;
 
+// Checksum offload capabilities (expanded from RTE_BIT64 macros for bindgen)
+static const uint64_t RUST_RTE_ETH_RX_OFFLOAD_IPV4_CKSUM = RTE_ETH_RX_OFFLOAD_IPV4_CKSUM;
+static const uint64_t RUST_RTE_ETH_RX_OFFLOAD_UDP_CKSUM = RTE_ETH_RX_OFFLOAD_UDP_CKSUM;
+static const uint64_t RUST_RTE_ETH_RX_OFFLOAD_TCP_CKSUM = RTE_ETH_RX_OFFLOAD_TCP_CKSUM;
+static const uint64_t RUST_RTE_ETH_TX_OFFLOAD_IPV4_CKSUM = RTE_ETH_TX_OFFLOAD_IPV4_CKSUM;
+static const uint64_t RUST_RTE_ETH_TX_OFFLOAD_UDP_CKSUM = RTE_ETH_TX_OFFLOAD_UDP_CKSUM;
+static const uint64_t RUST_RTE_ETH_TX_OFFLOAD_TCP_CKSUM = RTE_ETH_TX_OFFLOAD_TCP_CKSUM;
+
+// RX checksum status in mbuf ol_flags
+static const uint64_t RUST_RTE_MBUF_F_RX_IP_CKSUM_MASK = RTE_MBUF_F_RX_IP_CKSUM_MASK;
+static const uint64_t RUST_RTE_MBUF_F_RX_IP_CKSUM_BAD = RTE_MBUF_F_RX_IP_CKSUM_BAD;
+static const uint64_t RUST_RTE_MBUF_F_RX_IP_CKSUM_GOOD = RTE_MBUF_F_RX_IP_CKSUM_GOOD;
+static const uint64_t RUST_RTE_MBUF_F_RX_L4_CKSUM_MASK = RTE_MBUF_F_RX_L4_CKSUM_MASK;
+static const uint64_t RUST_RTE_MBUF_F_RX_L4_CKSUM_BAD = RTE_MBUF_F_RX_L4_CKSUM_BAD;
+static const uint64_t RUST_RTE_MBUF_F_RX_L4_CKSUM_GOOD = RTE_MBUF_F_RX_L4_CKSUM_GOOD;
+
+// Ethdev event types used by callbacks
+static const enum rte_eth_event_type RUST_RTE_ETH_EVENT_INTR_LSC = RTE_ETH_EVENT_INTR_LSC;
+
 #endif // DPDK_WRAPPER_H
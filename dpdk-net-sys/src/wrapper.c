@@ -1,5 +1,11 @@
 #include "wrapper.h"
+#include <string.h>
+#include <netinet/in.h>
 #include <rte_errno.h>
+#include <rte_flow.h>
+#include <rte_ip.h>
+#include <rte_tcp.h>
+#include <rte_udp.h>
 
 int rust_get_rte_errno(void) {
     return rte_errno;
@@ -70,6 +76,73 @@ uint16_t rust_pktmbuf_data_room_size(struct rte_mempool *mp) {
     return rte_pktmbuf_data_room_size(mp);
 }
 
+uint64_t rust_pktmbuf_ol_flags(const struct rte_mbuf *m) {
+    return m->ol_flags;
+}
+
+void rust_pktmbuf_tx_cksum_offload(struct rte_mbuf *m) {
+    char *data = rte_pktmbuf_mtod(m, char *);
+    uint16_t len = m->data_len;
+    uint16_t l2_len = sizeof(struct rte_ether_hdr);
+    uint64_t flags;
+    uint16_t l3_len;
+    uint8_t proto;
+
+    if (len < l2_len) {
+        return;
+    }
+    uint16_t ether_type = rte_be_to_cpu_16(((struct rte_ether_hdr *)data)->ether_type);
+    void *l3 = data + l2_len;
+
+    if (ether_type == RTE_ETHER_TYPE_IPV4) {
+        if (len < l2_len + sizeof(struct rte_ipv4_hdr)) {
+            return;
+        }
+        struct rte_ipv4_hdr *ip = l3;
+        l3_len = rte_ipv4_hdr_len(ip);
+        if (len < l2_len + l3_len) {
+            return;
+        }
+        flags = RTE_MBUF_F_TX_IPV4 | RTE_MBUF_F_TX_IP_CKSUM;
+        ip->hdr_checksum = 0;
+        // An L4 checksum covers the whole datagram, not one fragment
+        if (rte_be_to_cpu_16(ip->fragment_offset) &
+            (RTE_IPV4_HDR_MF_FLAG | RTE_IPV4_HDR_OFFSET_MASK)) {
+            proto = 0;
+        } else {
+            proto = ip->next_proto_id;
+        }
+    } else if (ether_type == RTE_ETHER_TYPE_IPV6) {
+        l3_len = sizeof(struct rte_ipv6_hdr);
+        if (len < l2_len + l3_len) {
+            return;
+        }
+        flags = RTE_MBUF_F_TX_IPV6;
+        proto = ((struct rte_ipv6_hdr *)l3)->proto;
+    } else {
+        return;
+    }
+
+    char *l4 = (char *)l3 + l3_len;
+    if (proto == IPPROTO_TCP && len >= l2_len + l3_len + sizeof(struct rte_tcp_hdr)) {
+        flags |= RTE_MBUF_F_TX_TCP_CKSUM;
+    } else if (proto == IPPROTO_UDP && len >= l2_len + l3_len + sizeof(struct rte_udp_hdr)) {
+        flags |= RTE_MBUF_F_TX_UDP_CKSUM;
+    }
+    m->l2_len = l2_len;
+    m->l3_len = l3_len;
+    m->ol_flags |= flags;
+
+    // The NIC expects the L4 checksum field seeded with the pseudo-header sum
+    uint16_t phdr = flags & RTE_MBUF_F_TX_IPV4 ? rte_ipv4_phdr_cksum(l3, m->ol_flags)
+                                              : rte_ipv6_phdr_cksum(l3, m->ol_flags);
+    if (flags & RTE_MBUF_F_TX_TCP_CKSUM) {
+        ((struct rte_tcp_hdr *)l4)->cksum = phdr;
+    } else if (flags & RTE_MBUF_F_TX_UDP_CKSUM) {
+        ((struct rte_udp_hdr *)l4)->dgram_cksum = phdr;
+    }
+}
+
 uint16_t rust_eth_rx_burst(uint16_t port_id, uint16_t queue_id,
                            struct rte_mbuf **rx_pkts, uint16_t nb_pkts) {
     return rte_eth_rx_burst(port_id, queue_id, rx_pkts, nb_pkts);
@@ -80,6 +153,102 @@ uint16_t rust_eth_tx_burst(uint16_t port_id, uint16_t queue_id,
     return rte_eth_tx_burst(port_id, queue_id, tx_pkts, nb_pkts);
 }
 
+int rust_eth_link_get_nowait(uint16_t port_id, uint32_t *speed, int *up, int *full_duplex) {
+    struct rte_eth_link link;
+    int ret = rte_eth_link_get_nowait(port_id, &link);
+    if (ret == 0) {
+        *speed = link.link_speed;
+        *up = link.link_status == RTE_ETH_LINK_UP;
+        *full_duplex = link.link_duplex == RTE_ETH_LINK_FULL_DUPLEX;
+    }
+    return ret;
+}
+
+// Flow rule wrappers
+struct rust_flow_rule {
+    struct rte_flow_attr attr;
+    struct rte_flow_item pattern[4];
+    struct rte_flow_action actions[2];
+    struct rte_flow_item_ipv4 ip_spec, ip_mask;
+    struct rte_flow_item_tcp tcp_spec, tcp_mask;
+    struct rte_flow_item_udp udp_spec, udp_mask;
+    struct rte_flow_action_queue queue;
+};
+
+// Fill `r` with pattern ETH / IPV4 / [TCP|UDP] / END and action QUEUE / END.
+static void rust_flow_build(struct rust_flow_rule *r, uint32_t priority,
+                            const struct rust_flow_match *m, uint16_t queue) {
+    memset(r, 0, sizeof(*r));
+    r->attr.priority = priority;
+    r->attr.ingress = 1;
+
+    r->ip_spec.hdr.src_addr = rte_cpu_to_be_32(m->src_ip & m->src_ip_mask);
+    r->ip_mask.hdr.src_addr = rte_cpu_to_be_32(m->src_ip_mask);
+    r->ip_spec.hdr.dst_addr = rte_cpu_to_be_32(m->dst_ip & m->dst_ip_mask);
+    r->ip_mask.hdr.dst_addr = rte_cpu_to_be_32(m->dst_ip_mask);
+
+    r->pattern[0].type = RTE_FLOW_ITEM_TYPE_ETH;
+    r->pattern[1].type = RTE_FLOW_ITEM_TYPE_IPV4;
+    r->pattern[1].spec = &r->ip_spec;
+    r->pattern[1].mask = &r->ip_mask;
+    if (m->proto == IPPROTO_TCP) {
+        r->tcp_spec.hdr.src_port = rte_cpu_to_be_16(m->src_port & m->src_port_mask);
+        r->tcp_mask.hdr.src_port = rte_cpu_to_be_16(m->src_port_mask);
+        r->tcp_spec.hdr.dst_port = rte_cpu_to_be_16(m->dst_port & m->dst_port_mask);
+        r->tcp_mask.hdr.dst_port = rte_cpu_to_be_16(m->dst_port_mask);
+        r->pattern[2].type = RTE_FLOW_ITEM_TYPE_TCP;
+        r->pattern[2].spec = &r->tcp_spec;
+        r->pattern[2].mask = &r->tcp_mask;
+        r->pattern[3].type = RTE_FLOW_ITEM_TYPE_END;
+    } else if (m->proto == IPPROTO_UDP) {
+        r->udp_spec.hdr.src_port = rte_cpu_to_be_16(m->src_port & m->src_port_mask);
+        r->udp_mask.hdr.src_port = rte_cpu_to_be_16(m->src_port_mask);
+        r->udp_spec.hdr.dst_port = rte_cpu_to_be_16(m->dst_port & m->dst_port_mask);
+        r->udp_mask.hdr.dst_port = rte_cpu_to_be_16(m->dst_port_mask);
+        r->pattern[2].type = RTE_FLOW_ITEM_TYPE_UDP;
+        r->pattern[2].spec = &r->udp_spec;
+        r->pattern[2].mask = &r->udp_mask;
+        r->pattern[3].type = RTE_FLOW_ITEM_TYPE_END;
+    } else {
+        r->pattern[2].type = RTE_FLOW_ITEM_TYPE_END;
+    }
+
+    r->queue.index = queue;
+    r->actions[0].type = RTE_FLOW_ACTION_TYPE_QUEUE;
+    r->actions[0].conf = &r->queue;
+    r->actions[1].type = RTE_FLOW_ACTION_TYPE_END;
+}
+
+int rust_flow_validate_queue(uint16_t port_id, uint32_t priority,
+                             const struct rust_flow_match *m, uint16_t queue,
+                             const char **message) {
+    struct rust_flow_rule rule;
+    struct rte_flow_error error = {0};
+    rust_flow_build(&rule, priority, m, queue);
+    int ret = rte_flow_validate(port_id, &rule.attr, rule.pattern, rule.actions, &error);
+    *message = error.message;
+    return ret;
+}
+
+struct rte_flow *rust_flow_create_queue(uint16_t port_id, uint32_t priority,
+                                        const struct rust_flow_match *m, uint16_t queue,
+                                        const char **message) {
+    struct rust_flow_rule rule;
+    struct rte_flow_error error = {0};
+    rust_flow_build(&rule, priority, m, queue);
+    struct rte_flow *flow =
+        rte_flow_create(port_id, &rule.attr, rule.pattern, rule.actions, &error);
+    *message = error.message;
+    return flow;
+}
+
+int rust_flow_destroy(uint16_t port_id, struct rte_flow *flow, const char **message) {
+    struct rte_flow_error error = {0};
+    int ret = rte_flow_destroy(port_id, flow, &error);
+    *message = error.message;
+    return ret;
+}
+
 // Lcore wrapper implementations
 unsigned rust_rte_lcore_id(void) {
     return rte_lcore_id();
@@ -13,9 +13,10 @@
 
 use dpdk_net::api::rte::eal::EalBuilder;
 use dpdk_net::socket::TcpListener;
+use dpdk_net::topology::recommend_queues;
 use dpdk_net_test::app::echo_server::{EchoServer, ServerStats};
 use dpdk_net_test::manual::tcp::{get_default_gateway, get_interface_ipv4, get_pci_addr};
-use dpdk_net_test::util::{ensure_hugepages, get_ethtool_channels};
+use dpdk_net_test::util::ensure_hugepages;
 use dpdk_net_util::{DpdkApp, WorkerContext};
 use smoltcp::wire::Ipv4Address;
 use std::sync::Arc;
@@ -42,10 +43,9 @@ fn main() {
     let gateway = get_default_gateway().unwrap_or(Ipv4Address::new(10, 0, 0, 1));
 
     // Auto-detect hardware queues (before EAL init)
-    let hw_queues = get_ethtool_channels(INTERFACE)
-        .map(|ch| ch.combined_count as usize)
-        .expect("Failed to get hardware queues via ethtool");
-    let core_list = format!("0-{}", hw_queues.saturating_sub(1));
+    let plan =
+        recommend_queues(INTERFACE, None).expect("Failed to get hardware queues via ethtool");
+    let core_list = plan.core_list;
 
     // Initialize DPDK EAL with core list matching hw queues
     let pci_addr = get_pci_addr(INTERFACE).expect("Failed to get PCI address");
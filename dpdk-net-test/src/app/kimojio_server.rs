@@ -42,6 +42,9 @@ const INITIAL_BUF_SIZE: usize = 1024;
 /// Maximum buffer size for headers (64 KB)
 const MAX_BUF_SIZE: usize = 64 * 1024;
 
+/// Default maximum request body size (16 MB)
+pub const DEFAULT_MAX_BODY_SIZE: usize = 16 * 1024 * 1024;
+
 /// Error type for HTTP parsing
 #[derive(Debug)]
 pub enum ParseError {
@@ -61,6 +64,8 @@ pub enum ParseError {
     HeadersTooLarge,
     /// Invalid Content-Length
     InvalidContentLength,
+    /// Body larger than the parser's `max_body_size`
+    BodyTooLarge,
     /// Connection closed
     ConnectionClosed,
 }
@@ -76,6 +81,7 @@ impl fmt::Display for ParseError {
             ParseError::InvalidUri => write!(f, "Invalid URI"),
             ParseError::HeadersTooLarge => write!(f, "Headers too large"),
             ParseError::InvalidContentLength => write!(f, "Invalid Content-Length"),
+            ParseError::BodyTooLarge => write!(f, "Body too large"),
             ParseError::ConnectionClosed => write!(f, "Connection closed"),
         }
     }
@@ -121,6 +127,15 @@ async fn create_server_socket_reuseport(port: u16) -> Result<kimojio::OwnedFd, k
     Ok(server_fd)
 }
 
+/// Trailer fields sent after a chunked request body.
+///
+/// [`KimojioHttpParser::parse_request`] stores these in the request's
+/// extensions when a chunked body ends with trailers, e.g.
+/// `req.extensions().get::<Trailers>()`. Requests without trailers have no
+/// `Trailers` extension.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Trailers(pub HeaderMap);
+
 /// Get Content-Length from headers.
 fn get_content_length(headers: &HeaderMap) -> Option<usize> {
     headers
@@ -131,6 +146,28 @@ fn get_content_length(headers: &HeaderMap) -> Option<usize> {
         .ok()
 }
 
+/// Split a `Name: value` trailer line.
+fn parse_trailer(line: &str) -> Result<(HeaderName, HeaderValue), ParseError> {
+    let (name, value) = line.split_once(':').ok_or(ParseError::InvalidHeader)?;
+    let name = HeaderName::from_bytes(name.as_bytes()).map_err(|_| ParseError::InvalidHeader)?;
+    let value = HeaderValue::from_str(value.trim()).map_err(|_| ParseError::InvalidHeader)?;
+    Ok((name, value))
+}
+
+/// Provisional response sent to clients waiting on `Expect: 100-continue`.
+const CONTINUE_RESPONSE: &[u8] = b"HTTP/1.1 100 Continue\r\n\r\n";
+
+/// Check if the client waits for `100 Continue` before sending the body.
+///
+/// HTTP/1.0 clients do not know 100-continue, so the header is ignored for them.
+fn expects_continue(headers: &HeaderMap, version: Version) -> bool {
+    version == Version::HTTP_11
+        && headers
+            .get(header::EXPECT)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.eq_ignore_ascii_case("100-continue"))
+}
+
 /// Check if Transfer-Encoding is chunked.
 fn is_chunked(headers: &HeaderMap) -> bool {
     headers
@@ -167,6 +204,9 @@ fn reason_phrase(status: StatusCode) -> &'static str {
     status.canonical_reason().unwrap_or("Unknown")
 }
 
+/// Request line and headers of a request whose body is still unread.
+type RequestHead = (Method, String, Version, HeaderMap);
+
 /// HTTP request parser for kimojio's completion-based I/O.
 ///
 /// This parser is designed for completion-based I/O where reads return
@@ -176,14 +216,26 @@ pub struct KimojioHttpParser {
     buf: Vec<u8>,
     /// Number of valid bytes in the buffer
     len: usize,
+    /// Largest body accepted, in bytes
+    max_body_size: usize,
 }
 
 impl KimojioHttpParser {
-    /// Create a new parser.
+    /// Create a new parser accepting bodies up to [`DEFAULT_MAX_BODY_SIZE`].
     pub fn new() -> Self {
+        Self::new_with_limits(DEFAULT_MAX_BODY_SIZE)
+    }
+
+    /// Create a new parser accepting bodies up to `max_body_size` bytes.
+    ///
+    /// A larger `Content-Length` is rejected with [`ParseError::BodyTooLarge`]
+    /// before anything is allocated for it; a chunked body fails as soon as
+    /// its chunks would add up past the limit.
+    pub fn new_with_limits(max_body_size: usize) -> Self {
         Self {
             buf: vec![0u8; INITIAL_BUF_SIZE],
             len: 0,
+            max_body_size,
         }
     }
 
@@ -193,10 +245,123 @@ impl KimojioHttpParser {
     /// with data already available in the buffer.
     ///
     /// Returns `None` if the connection was closed cleanly before any data was received.
+    /// Trailers after a chunked body are attached as a [`Trailers`] extension.
     pub async fn parse_request<R>(
         &mut self,
         reader: &mut R,
     ) -> Result<Option<Request<Bytes>>, ParseError>
+    where
+        R: KimojioAsyncRead,
+    {
+        let Some(head) = self.read_head(reader).await? else {
+            return Ok(None);
+        };
+        self.finish_request(reader, head).await.map(Some)
+    }
+
+    /// Like [`parse_request`](Self::parse_request), but answers
+    /// `Expect: 100-continue` on `writer`.
+    ///
+    /// Clients sending that header hold the body back until the server
+    /// says `100 Continue`. It is written once the headers are parsed,
+    /// unless the request is already rejected (a `Content-Length` over
+    /// `max_body_size` fails with [`ParseError::BodyTooLarge`] without it,
+    /// so the client never sends the body) or the body has already arrived.
+    pub async fn parse_request_with_continue<R, W>(
+        &mut self,
+        reader: &mut R,
+        writer: &mut W,
+    ) -> Result<Option<Request<Bytes>>, ParseError>
+    where
+        R: KimojioAsyncRead,
+        W: KimojioAsyncWrite,
+    {
+        let Some(head) = self.read_head(reader).await? else {
+            return Ok(None);
+        };
+        self.send_continue(&head, writer).await?;
+        self.finish_request(reader, head).await.map(Some)
+    }
+
+    /// Parse the request line and headers, leaving the body to be pulled
+    /// through the returned [`BodyReader`].
+    ///
+    /// Nothing of the body is buffered beyond what arrived with the headers.
+    /// A `Content-Length` over `max_body_size` fails here with
+    /// [`ParseError::BodyTooLarge`]; a chunked body fails once it grows past
+    /// the limit. Read the body to the end (or [`BodyReader::drain`] it)
+    /// before parsing the next request on the connection.
+    pub async fn parse_request_streaming<'a, R>(
+        &'a mut self,
+        reader: &'a mut R,
+    ) -> Result<Option<(Request<()>, BodyReader<'a, R>)>, ParseError>
+    where
+        R: KimojioAsyncRead,
+    {
+        let Some(head) = self.read_head(reader).await? else {
+            return Ok(None);
+        };
+        self.start_streaming(reader, head).map(Some)
+    }
+
+    /// Write `100 Continue` if the client of `head` waits for it.
+    ///
+    /// Skipped when the body has already arrived or is too large to accept
+    /// (then [`ParseError::BodyTooLarge`] is returned instead).
+    async fn send_continue<W>(&self, head: &RequestHead, writer: &mut W) -> Result<(), ParseError>
+    where
+        W: KimojioAsyncWrite,
+    {
+        let (_, _, version, headers) = head;
+        if expects_continue(headers, *version) && self.len == 0 {
+            if get_content_length(headers).is_some_and(|len| len > self.max_body_size) {
+                return Err(ParseError::BodyTooLarge);
+            }
+            writer.write_all(CONTINUE_RESPONSE).await?;
+        }
+        Ok(())
+    }
+
+    /// Build the body-less request for `head` and a reader for its body.
+    fn start_streaming<'a, R>(
+        &'a mut self,
+        reader: &'a mut R,
+        head: RequestHead,
+    ) -> Result<(Request<()>, BodyReader<'a, R>), ParseError>
+    where
+        R: KimojioAsyncRead,
+    {
+        let (method, uri, version, headers) = head;
+        let framing = if is_chunked(&headers) {
+            BodyFraming::ChunkSize
+        } else {
+            match get_content_length(&headers) {
+                Some(len) if len > self.max_body_size => return Err(ParseError::BodyTooLarge),
+                Some(len) => BodyFraming::Length(len),
+                None => BodyFraming::Done,
+            }
+        };
+
+        let mut builder = Request::builder().method(method).uri(uri).version(version);
+        if let Some(h) = builder.headers_mut() {
+            *h = headers;
+        }
+        let request = builder.body(()).map_err(|_| ParseError::InvalidUri)?;
+
+        Ok((
+            request,
+            BodyReader {
+                parser: self,
+                reader,
+                framing,
+                read: 0,
+                trailers: HeaderMap::new(),
+            },
+        ))
+    }
+
+    /// Parse the request line and headers, leaving only body bytes in the buffer.
+    async fn read_head<R>(&mut self, reader: &mut R) -> Result<Option<RequestHead>, ParseError>
     where
         R: KimojioAsyncRead,
     {
@@ -210,8 +375,23 @@ impl KimojioHttpParser {
         self.buf.copy_within(header_len..self.len, 0);
         self.len -= header_len;
 
+        Ok(Some((method, uri, version, headers)))
+    }
+
+    /// Read the body for `head` and build the request.
+    async fn finish_request<R>(
+        &mut self,
+        reader: &mut R,
+        head: RequestHead,
+    ) -> Result<Request<Bytes>, ParseError>
+    where
+        R: KimojioAsyncRead,
+    {
+        let (method, uri, version, headers) = head;
+
         // Read body based on Content-Length or Transfer-Encoding
-        let body = self.read_body(reader, &headers).await?;
+        let mut trailers = HeaderMap::new();
+        let body = self.read_body(reader, &headers, &mut trailers).await?;
 
         // Build the request
         let mut builder = Request::builder().method(method).uri(uri).version(version);
@@ -220,11 +400,14 @@ impl KimojioHttpParser {
             *h = headers;
         }
 
-        let request = builder
+        let mut request = builder
             .body(Bytes::from(body))
             .map_err(|_| ParseError::InvalidUri)?;
+        if !trailers.is_empty() {
+            request.extensions_mut().insert(Trailers(trailers));
+        }
 
-        Ok(Some(request))
+        Ok(request)
     }
 
     /// Read data until headers are complete and parse them.
@@ -305,17 +488,18 @@ impl KimojioHttpParser {
         Ok(n)
     }
 
-    /// Read the request body.
+    /// Read the request body, collecting any chunked trailers into `trailers`.
     async fn read_body<R>(
         &mut self,
         reader: &mut R,
         headers: &HeaderMap,
+        trailers: &mut HeaderMap,
     ) -> Result<Vec<u8>, ParseError>
     where
         R: KimojioAsyncRead,
     {
         if is_chunked(headers) {
-            return self.read_chunked_body(reader).await;
+            return self.read_chunked_body(reader, trailers).await;
         }
 
         if let Some(content_length) = get_content_length(headers) {
@@ -334,6 +518,9 @@ impl KimojioHttpParser {
     where
         R: KimojioAsyncRead,
     {
+        if length > self.max_body_size {
+            return Err(ParseError::BodyTooLarge);
+        }
         let mut body = Vec::with_capacity(length);
 
         // Use any data already in buffer
@@ -357,7 +544,14 @@ impl KimojioHttpParser {
     }
 
     /// Read a chunked transfer-encoded body.
-    async fn read_chunked_body<R>(&mut self, reader: &mut R) -> Result<Vec<u8>, ParseError>
+    ///
+    /// Trailer fields between the zero-length chunk and the final empty
+    /// line are appended to `trailers`.
+    async fn read_chunked_body<R>(
+        &mut self,
+        reader: &mut R,
+        trailers: &mut HeaderMap,
+    ) -> Result<Vec<u8>, ParseError>
     where
         R: KimojioAsyncRead,
     {
@@ -371,11 +565,14 @@ impl KimojioHttpParser {
                 .map_err(|_| ParseError::InvalidContentLength)?;
 
             if chunk_size == 0 {
-                // Read trailing CRLF
-                let _ = self.read_line(reader).await?;
+                self.read_trailers(reader, trailers).await?;
                 break;
             }
 
+            if chunk_size > self.max_body_size - body.len() {
+                return Err(ParseError::BodyTooLarge);
+            }
+
             // Read chunk data
             let chunk = self.read_exact(reader, chunk_size).await?;
             body.extend_from_slice(&chunk);
@@ -387,6 +584,51 @@ impl KimojioHttpParser {
         Ok(body)
     }
 
+    /// Read trailer fields up to the empty line ending a chunked message.
+    async fn read_trailers<R>(
+        &mut self,
+        reader: &mut R,
+        trailers: &mut HeaderMap,
+    ) -> Result<(), ParseError>
+    where
+        R: KimojioAsyncRead,
+    {
+        loop {
+            let line = self.read_line(reader).await?;
+            if line.is_empty() {
+                return Ok(());
+            }
+            if trailers.len() >= MAX_HEADERS {
+                return Err(ParseError::HeadersTooLarge);
+            }
+            let (name, value) = parse_trailer(&line)?;
+            trailers.append(name, value);
+        }
+    }
+
+    /// Read up to `max` bytes, from the buffer if it holds any, else with
+    /// a single read from the stream.
+    async fn read_some<R>(&mut self, reader: &mut R, max: usize) -> Result<Bytes, ParseError>
+    where
+        R: KimojioAsyncRead,
+    {
+        if self.len > 0 {
+            let n = self.len.min(max);
+            let data = Bytes::copy_from_slice(&self.buf[..n]);
+            self.buf.copy_within(n..self.len, 0);
+            self.len -= n;
+            return Ok(data);
+        }
+
+        let mut chunk = vec![0u8; max];
+        let n = reader.try_read(&mut chunk).await?;
+        if n == 0 {
+            return Err(ParseError::ConnectionClosed);
+        }
+        chunk.truncate(n);
+        Ok(Bytes::from(chunk))
+    }
+
     /// Read a line (up to CRLF) from buffer/stream.
     async fn read_line<R>(&mut self, reader: &mut R) -> Result<String, ParseError>
     where
@@ -446,9 +688,17 @@ impl KimojioHttpParser {
         Ok(data)
     }
 
-    /// Reset the parser for reuse.
+    /// Reset the parser for the next request on the same connection.
+    ///
+    /// Bytes already read past the end of the last request (the start of a
+    /// pipelined request) are kept and parsed first. A buffer grown for
+    /// large headers is shrunk back once it is no longer needed.
     pub fn reset(&mut self) {
-        self.len = 0;
+        let keep = self.len.max(INITIAL_BUF_SIZE);
+        if self.buf.len() > keep {
+            self.buf.truncate(keep);
+            self.buf.shrink_to_fit();
+        }
     }
 }
 
@@ -458,6 +708,122 @@ impl Default for KimojioHttpParser {
     }
 }
 
+/// Largest piece [`BodyReader::chunk`] returns (8 KB).
+pub const STREAM_CHUNK_SIZE: usize = 8 * 1024;
+
+/// Where a [`BodyReader`] is in the body's framing.
+#[derive(Debug, Clone, Copy)]
+enum BodyFraming {
+    /// `Content-Length` body with this many bytes left.
+    Length(usize),
+    /// Chunked body, next is a chunk-size line.
+    ChunkSize,
+    /// Chunked body, this many bytes left in the current chunk.
+    ChunkData(usize),
+    /// Body fully read.
+    Done,
+}
+
+/// Pulls a request body from the connection on demand.
+///
+/// Returned by [`KimojioHttpParser::parse_request_streaming`]. Follows the
+/// request's `Content-Length` or chunked framing, so a handler can process
+/// an upload piece by piece, with memory bounded by [`STREAM_CHUNK_SIZE`]
+/// instead of the body size.
+pub struct BodyReader<'a, R> {
+    parser: &'a mut KimojioHttpParser,
+    reader: &'a mut R,
+    framing: BodyFraming,
+    /// Body bytes returned so far
+    read: usize,
+    trailers: HeaderMap,
+}
+
+impl<R: KimojioAsyncRead> BodyReader<'_, R> {
+    /// The next piece of the body, or `None` once it has been read entirely.
+    ///
+    /// Pieces are at most [`STREAM_CHUNK_SIZE`] bytes and never span two
+    /// HTTP chunks. A connection closing mid-body fails with
+    /// [`ParseError::ConnectionClosed`].
+    pub async fn chunk(&mut self) -> Result<Option<Bytes>, ParseError> {
+        loop {
+            match self.framing {
+                BodyFraming::Done | BodyFraming::Length(0) => {
+                    self.framing = BodyFraming::Done;
+                    return Ok(None);
+                }
+                BodyFraming::Length(left) => {
+                    let data = self.read_data(left).await?;
+                    self.framing = BodyFraming::Length(left - data.len());
+                    return Ok(Some(data));
+                }
+                BodyFraming::ChunkSize => {
+                    let size_line = self.parser.read_line(self.reader).await?;
+                    let size_str = size_line.split(';').next().unwrap_or(&size_line).trim();
+                    let size = usize::from_str_radix(size_str, 16)
+                        .map_err(|_| ParseError::InvalidContentLength)?;
+                    if size == 0 {
+                        self.parser
+                            .read_trailers(self.reader, &mut self.trailers)
+                            .await?;
+                        self.framing = BodyFraming::Done;
+                        return Ok(None);
+                    }
+                    if size > self.parser.max_body_size - self.read {
+                        return Err(ParseError::BodyTooLarge);
+                    }
+                    self.framing = BodyFraming::ChunkData(size);
+                }
+                BodyFraming::ChunkData(left) => {
+                    let data = self.read_data(left).await?;
+                    let left = left - data.len();
+                    if left == 0 {
+                        // CRLF after the chunk data
+                        self.parser.read_line(self.reader).await?;
+                        self.framing = BodyFraming::ChunkSize;
+                    } else {
+                        self.framing = BodyFraming::ChunkData(left);
+                    }
+                    return Ok(Some(data));
+                }
+            }
+        }
+    }
+
+    /// Read and discard the rest of the body.
+    ///
+    /// Needed before the next request on a keep-alive connection can be
+    /// parsed.
+    pub async fn drain(&mut self) -> Result<(), ParseError> {
+        while self.chunk().await?.is_some() {}
+        Ok(())
+    }
+
+    /// Returns true once the whole body has been read.
+    pub fn is_finished(&self) -> bool {
+        matches!(self.framing, BodyFraming::Done | BodyFraming::Length(0))
+    }
+
+    /// Body bytes returned so far.
+    pub fn bytes_read(&self) -> usize {
+        self.read
+    }
+
+    /// Trailers of a chunked body; empty until the body is finished.
+    pub fn trailers(&self) -> &HeaderMap {
+        &self.trailers
+    }
+
+    async fn read_data(&mut self, left: usize) -> Result<Bytes, ParseError> {
+        let data = self
+            .parser
+            .read_some(self.reader, left.min(STREAM_CHUNK_SIZE))
+            .await?;
+        self.read += data.len();
+        Ok(data)
+    }
+}
+
 /// Trait for kimojio-style async read operations.
 ///
 /// This trait abstracts over kimojio's `AsyncStreamRead` for testing
@@ -538,11 +904,33 @@ where
     W: KimojioAsyncWrite,
     F: Fn(Request<Bytes>) -> Fut + Clone,
     Fut: Future<Output = Response<Bytes>>,
+{
+    handle_http_connection_with_state(reader, writer, (), |_, req| handler(req)).await
+}
+
+/// Like [`handle_http_connection`], passing `state` to every handler call.
+///
+/// `state` lives as long as the connection. For state shared by all
+/// connections on a core (a cache, a client pool, metrics), pass an `Rc`
+/// clone; kimojio runs each core single-threaded, so nothing needs to be
+/// `Send` or `Sync`. The handler's future cannot borrow from `&S`: clone
+/// what it needs out of the state first.
+pub async fn handle_http_connection_with_state<R, W, S, F, Fut>(
+    reader: &mut R,
+    writer: &mut W,
+    state: S,
+    handler: F,
+) -> Result<(), ParseError>
+where
+    R: KimojioAsyncRead,
+    W: KimojioAsyncWrite,
+    F: Fn(&S, Request<Bytes>) -> Fut,
+    Fut: Future<Output = Response<Bytes>>,
 {
     let mut parser = KimojioHttpParser::new();
 
     loop {
-        let request = match parser.parse_request(reader).await {
+        let request = match parser.parse_request_with_continue(reader, writer).await {
             Ok(Some(req)) => req,
             Ok(None) => {
                 // Clean connection close
@@ -553,20 +941,14 @@ where
             }
             Err(e) => {
                 // Try to send error response
-                let response = Response::builder()
-                    .status(StatusCode::BAD_REQUEST)
-                    .header(header::CONNECTION, "close")
-                    .body(Bytes::from(format!("Parse error: {}", e)))
-                    .unwrap();
-                let _ = write_response(writer, &response).await;
-                return Err(e);
+                return reject(writer, e).await;
             }
         };
 
         let keep_alive = should_keep_alive(request.headers(), request.version());
 
         // Call handler
-        let mut response = handler(request).await;
+        let mut response = handler(&state, request).await;
 
         // Set Connection header based on keep-alive
         if !keep_alive {
@@ -587,6 +969,74 @@ where
     }
 }
 
+/// Like [`handle_http_connection`], streaming each request body to the handler.
+///
+/// The handler gets the request head and a [`BodyReader`] to pull the body
+/// from as it goes. Whatever body it leaves unread is drained after it
+/// returns, so keep-alive still works. `Expect: 100-continue` is answered
+/// before the handler runs.
+pub async fn handle_http_connection_streaming<R, W, F>(
+    reader: &mut R,
+    writer: &mut W,
+    handler: F,
+) -> Result<(), ParseError>
+where
+    R: KimojioAsyncRead,
+    W: KimojioAsyncWrite,
+    F: AsyncFn(Request<()>, &mut BodyReader<'_, R>) -> Response<Bytes>,
+{
+    let mut parser = KimojioHttpParser::new();
+
+    loop {
+        let head = match parser.read_head(reader).await {
+            Ok(Some(head)) => head,
+            Ok(None) | Err(ParseError::ConnectionClosed) => return Ok(()),
+            Err(e) => return reject(writer, e).await,
+        };
+        if let Err(e) = parser.send_continue(&head, writer).await {
+            return reject(writer, e).await;
+        }
+        let (request, mut body) = match parser.start_streaming(reader, head) {
+            Ok(started) => started,
+            Err(e) => return reject(writer, e).await,
+        };
+
+        let keep_alive = should_keep_alive(request.headers(), request.version());
+
+        let mut response = handler(request, &mut body).await;
+        if keep_alive {
+            body.drain().await?;
+        } else {
+            response
+                .headers_mut()
+                .insert(header::CONNECTION, HeaderValue::from_static("close"));
+        }
+
+        write_response(writer, &response).await?;
+
+        if !keep_alive {
+            return Ok(());
+        }
+
+        parser.reset();
+    }
+}
+
+/// Answer a request that failed to parse, then return the error.
+async fn reject<W: KimojioAsyncWrite>(writer: &mut W, e: ParseError) -> Result<(), ParseError> {
+    let status = match e {
+        ParseError::BodyTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+        _ => StatusCode::BAD_REQUEST,
+    };
+    let response = Response::builder()
+        .status(status)
+        .header(header::CONNECTION, "close")
+        .body(Bytes::from(format!("Parse error: {}", e)))
+        .unwrap();
+    let _ = write_response(writer, &response).await;
+    Err(e)
+}
+
 /// Simple echo handler for testing - echoes the request body back.
 pub async fn simple_echo_handler(req: Request<Bytes>) -> Response<Bytes> {
     Response::builder()
@@ -626,6 +1076,49 @@ pub fn run_kimojio_thread_per_core_server<F, Fut>(port: u16, handler: F, busy_po
 where
     F: Fn(Request<Bytes>) -> Fut + Clone + Send + Sync + 'static,
     Fut: Future<Output = Response<Bytes>> + 'static,
+{
+    run_kimojio_thread_per_core_server_with_state(
+        port,
+        |_core_id| (),
+        move |_, req| handler(req),
+        busy_poll,
+    );
+}
+
+/// Like [`run_kimojio_thread_per_core_server`], with per-core handler state.
+///
+/// Each worker thread calls `init` with its core ID to build its own state,
+/// so the state itself does not have to be `Send`. Every connection accepted
+/// on that core shares it through an `Rc` handed to the handler.
+///
+/// # Example
+///
+/// ```ignore
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+///
+/// // Count requests per core without a global
+/// run_kimojio_thread_per_core_server_with_state(
+///     8080,
+///     |_core_id| Cell::new(0u64),
+///     |count: &Rc<Cell<u64>>, _req| {
+///         count.set(count.get() + 1);
+///         let body = Bytes::from(count.get().to_string());
+///         async move { Response::new(body) }
+///     },
+///     false,
+/// );
+/// ```
+pub fn run_kimojio_thread_per_core_server_with_state<S, I, F, Fut>(
+    port: u16,
+    init: I,
+    handler: F,
+    busy_poll: bool,
+) where
+    S: 'static,
+    I: Fn(usize) -> S + Clone + Send + Sync + 'static,
+    F: Fn(&std::rc::Rc<S>, Request<Bytes>) -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = Response<Bytes>> + 'static,
 {
     use kimojio::configuration::{BusyPoll, Configuration};
     use std::sync::Arc;
@@ -648,6 +1141,7 @@ where
 
     // Spawn worker threads for all cores
     for core_id in 0..num_cores {
+        let init = init.clone();
         let handler = handler.clone();
         let shutdown = shutdown.clone();
 
@@ -672,7 +1166,10 @@ where
                 // Run the kimojio runtime with thread index (core_id)
                 let result = kimojio::run_with_configuration(
                     core_id as u8,
-                    async move { run_kimojio_accept_loop(core_id, port, handler, shutdown).await },
+                    async move {
+                        let state = std::rc::Rc::new(init(core_id));
+                        run_kimojio_accept_loop(core_id, port, state, handler, shutdown).await
+                    },
                     config,
                 );
 
@@ -709,37 +1206,91 @@ where
     println!("[kimojio] Server stopped");
 }
 
+/// How often an idle accept loop checks its shutdown flag.
+const SHUTDOWN_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(50);
+
+/// Which of two raced futures finished first.
+enum Race<A, B> {
+    First(A),
+    Second(B),
+}
+
+/// Poll both futures until one completes; the other is left as is.
+async fn race<A, B>(mut first: A, mut second: B) -> Race<A::Output, B::Output>
+where
+    A: Future + Unpin,
+    B: Future + Unpin,
+{
+    use std::pin::Pin;
+    use std::task::Poll;
+
+    std::future::poll_fn(|cx| {
+        if let Poll::Ready(v) = Pin::new(&mut first).poll(cx) {
+            return Poll::Ready(Race::First(v));
+        }
+        if let Poll::Ready(v) = Pin::new(&mut second).poll(cx) {
+            return Poll::Ready(Race::Second(v));
+        }
+        Poll::Pending
+    })
+    .await
+}
+
+/// Accept a connection, or return `Ok(None)` once `shutdown` is set.
+///
+/// The flag is a plain `AtomicBool` set from another thread (the Ctrl+C
+/// handler), which cannot wake an io_uring wait. So the accept races a
+/// timer: each time [`SHUTDOWN_POLL_INTERVAL`] elapses the flag is checked
+/// and, if still clear, the same accept keeps waiting with a new timer.
+/// An idle server notices shutdown within one interval.
+async fn accept_or_shutdown(
+    server_fd: &kimojio::OwnedFd,
+    shutdown: &std::sync::atomic::AtomicBool,
+) -> Result<Option<kimojio::OwnedFd>, kimojio::Errno> {
+    use kimojio::operations;
+    use std::sync::atomic::Ordering;
+
+    let mut accept = std::pin::pin!(operations::accept(server_fd));
+    loop {
+        if shutdown.load(Ordering::Relaxed) {
+            return Ok(None);
+        }
+        let tick = std::pin::pin!(operations::sleep(SHUTDOWN_POLL_INTERVAL));
+        if let Race::First(result) = race(accept.as_mut(), tick).await {
+            return result.map(Some);
+        }
+    }
+}
+
 /// Run the accept loop for a single kimojio core.
-async fn run_kimojio_accept_loop<F, Fut>(
+///
+/// `state` is this core's handler state, shared by all its connections.
+async fn run_kimojio_accept_loop<S, F, Fut>(
     core_id: usize,
     port: u16,
+    state: std::rc::Rc<S>,
     handler: F,
     shutdown: std::sync::Arc<std::sync::atomic::AtomicBool>,
 ) -> Result<(), kimojio::Errno>
 where
-    F: Fn(Request<Bytes>) -> Fut + Clone + 'static,
+    S: 'static,
+    F: Fn(&std::rc::Rc<S>, Request<Bytes>) -> Fut + Clone + 'static,
     Fut: Future<Output = Response<Bytes>> + 'static,
 {
     use kimojio::SplittableStream;
-    use kimojio::operations::{self, spawn_task};
+    use kimojio::operations::spawn_task;
 
     // Create server socket with SO_REUSEPORT for thread-per-core load balancing
     let server_fd = create_server_socket_reuseport(port).await?;
 
     println!("[kimojio] core={} Listening on port {}", core_id, port);
 
-    loop {
-        // Check for shutdown
-        if shutdown.load(std::sync::atomic::Ordering::Relaxed) {
-            break;
-        }
-
-        // Accept with a timeout so we can check shutdown periodically
-        // For now, we'll just accept and spawn handlers
-        let client_fd = operations::accept(&server_fd).await?;
+    // Returns once shutdown is set, even if no connection ever arrives
+    while let Some(client_fd) = accept_or_shutdown(&server_fd, &shutdown).await? {
         let stream = OwnedFdStream::new(client_fd);
 
         let handler = handler.clone();
+        let state = state.clone();
         spawn_task(async move {
             // Split the stream into read and write halves
             let (mut reader, mut writer) = match stream.split().await {
@@ -750,11 +1301,8 @@ where
                 }
             };
 
-            if let Err(e) = handle_http_connection(&mut reader, &mut writer, |req| {
-                let handler = handler.clone();
-                async move { handler(req).await }
-            })
-            .await
+            if let Err(e) =
+                handle_http_connection_with_state(&mut reader, &mut writer, state, handler).await
             {
                 // Connection errors are expected (client disconnect, etc.)
                 if !matches!(e, ParseError::ConnectionClosed) {
@@ -826,6 +1374,8 @@ impl KimojioAsyncWrite for OwnedFdStreamWrite {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
 
     /// A simple in-memory reader for testing
     struct TestReader {
@@ -901,6 +1451,52 @@ mod tests {
         let request = parser.parse_request(&mut reader).await.unwrap().unwrap();
         assert_eq!(request.method(), Method::POST);
         assert_eq!(request.body().as_ref(), b"Hello World");
+        assert!(request.extensions().get::<Trailers>().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_parse_chunked_request_with_trailers() {
+        let request_data = b"POST /upload HTTP/1.1\r\nHost: localhost\r\nTransfer-Encoding: chunked\r\nTrailer: X-Checksum\r\n\r\n5\r\nHello\r\n0\r\nX-Checksum: 8b1a9953\r\n\r\nGET /next HTTP/1.1\r\nHost: localhost\r\n\r\n";
+        let mut reader = TestReader::new(request_data);
+        let mut parser = KimojioHttpParser::new();
+
+        let request = parser.parse_request(&mut reader).await.unwrap().unwrap();
+        assert_eq!(request.body().as_ref(), b"Hello");
+        let trailers = request.extensions().get::<Trailers>().unwrap();
+        assert_eq!(trailers.0.len(), 1);
+        assert_eq!(trailers.0["x-checksum"], "8b1a9953");
+
+        // The trailer section is consumed entirely, so the next request parses
+        let next = parser.parse_request(&mut reader).await.unwrap().unwrap();
+        assert_eq!(next.uri().path(), "/next");
+        assert!(next.extensions().get::<Trailers>().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_fixed_body_over_limit() {
+        let request_data =
+            b"POST /upload HTTP/1.1\r\nHost: localhost\r\nContent-Length: 999999999\r\n\r\nHello";
+        let mut reader = TestReader::new(request_data);
+        let mut parser = KimojioHttpParser::new_with_limits(1024);
+
+        let result = parser.parse_request(&mut reader).await;
+        assert!(matches!(result, Err(ParseError::BodyTooLarge)));
+    }
+
+    #[tokio::test]
+    async fn test_chunked_body_over_limit() {
+        // Each chunk fits, but together they pass the 8-byte limit
+        let request_data = b"POST /upload HTTP/1.1\r\nHost: localhost\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nHello\r\n6\r\n World\r\n0\r\n\r\n";
+        let mut reader = TestReader::new(request_data);
+        let mut parser = KimojioHttpParser::new_with_limits(8);
+
+        let result = parser.parse_request(&mut reader).await;
+        assert!(matches!(result, Err(ParseError::BodyTooLarge)));
+
+        let mut reader = TestReader::new(request_data);
+        let mut parser = KimojioHttpParser::new_with_limits(11);
+        let request = parser.parse_request(&mut reader).await.unwrap().unwrap();
+        assert_eq!(request.body().as_ref(), b"Hello World");
     }
 
     #[tokio::test]
@@ -933,6 +1529,202 @@ mod tests {
         assert!(response_text.starts_with("HTTP/1.1 200 OK\r\n"));
     }
 
+    #[tokio::test]
+    async fn test_handle_pipelined_requests() {
+        // Both requests arrive in a single read
+        let request_data = b"POST /a HTTP/1.1\r\nHost: localhost\r\nContent-Length: 5\r\n\r\nfirstPOST /b HTTP/1.1\r\nHost: localhost\r\nContent-Length: 6\r\nConnection: close\r\n\r\nsecond";
+        let mut reader = TestReader::new(request_data);
+        let mut writer = TestWriter::new();
+
+        let result = handle_http_connection(&mut reader, &mut writer, simple_echo_handler).await;
+        assert!(result.is_ok());
+
+        let response_text = String::from_utf8_lossy(&writer.data);
+        assert_eq!(response_text.matches("HTTP/1.1 200 OK\r\n").count(), 2);
+        let first = response_text.find("\r\n\r\nfirst").unwrap();
+        let second = response_text.find("\r\n\r\nsecond").unwrap();
+        assert!(first < second);
+    }
+
+    /// A client that holds its body back until the server sends `100 Continue`.
+    struct ContinueClient {
+        head: TestReader,
+        body: Option<&'static [u8]>,
+        received: Rc<RefCell<Vec<u8>>>,
+    }
+
+    impl KimojioAsyncRead for ContinueClient {
+        async fn try_read(&mut self, buf: &mut [u8]) -> Result<usize, ParseError> {
+            let n = self.head.try_read(buf).await?;
+            if n > 0 {
+                return Ok(n);
+            }
+            if !self.received.borrow().starts_with(CONTINUE_RESPONSE) {
+                // A real client would sit here until its expect timeout
+                return Err(ParseError::Io("client waiting for 100 Continue".into()));
+            }
+            let Some(body) = self.body.take() else {
+                return Ok(0);
+            };
+            buf[..body.len()].copy_from_slice(body);
+            Ok(body.len())
+        }
+    }
+
+    /// Writer whose output the [`ContinueClient`] can see.
+    struct SharedWriter(Rc<RefCell<Vec<u8>>>);
+
+    impl KimojioAsyncWrite for SharedWriter {
+        async fn write_all(&mut self, buf: &[u8]) -> Result<(), ParseError> {
+            self.0.borrow_mut().extend_from_slice(buf);
+            Ok(())
+        }
+
+        async fn shutdown(&mut self) -> Result<(), ParseError> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_expect_continue() {
+        let request_data = b"POST /upload HTTP/1.1\r\nHost: localhost\r\nExpect: 100-continue\r\nContent-Length: 5\r\nConnection: close\r\n\r\n";
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let mut reader = ContinueClient {
+            head: TestReader::new(request_data),
+            body: Some(b"Hello"),
+            received: received.clone(),
+        };
+        let mut writer = SharedWriter(received.clone());
+
+        let result = handle_http_connection(&mut reader, &mut writer, simple_echo_handler).await;
+        assert!(result.is_ok(), "{:?}", result);
+
+        let response = received.borrow();
+        let response_text = String::from_utf8_lossy(&response);
+        assert!(response_text.starts_with("HTTP/1.1 100 Continue\r\n\r\nHTTP/1.1 200 OK\r\n"));
+        assert!(response_text.ends_with("\r\n\r\nHello"));
+    }
+
+    #[tokio::test]
+    async fn test_expect_continue_rejected() {
+        let request_data = b"POST /upload HTTP/1.1\r\nHost: localhost\r\nExpect: 100-continue\r\nContent-Length: 2048\r\n\r\n";
+        let mut reader = TestReader::new(request_data);
+        let mut writer = TestWriter::new();
+        let mut parser = KimojioHttpParser::new_with_limits(1024);
+
+        let result = parser
+            .parse_request_with_continue(&mut reader, &mut writer)
+            .await;
+        assert!(matches!(result, Err(ParseError::BodyTooLarge)));
+        assert!(
+            writer.data.is_empty(),
+            "100 Continue sent for a rejected body"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_handle_connection_with_state() {
+        let request_data = b"GET /a HTTP/1.1\r\nHost: localhost\r\n\r\nGET /b HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n";
+        let mut reader = TestReader::new(request_data);
+        let mut writer = TestWriter::new();
+        let count = Rc::new(std::cell::Cell::new(0u32));
+
+        let result = handle_http_connection_with_state(
+            &mut reader,
+            &mut writer,
+            count.clone(),
+            |count, _req| {
+                count.set(count.get() + 1);
+                let body = Bytes::from(format!("request {}", count.get()));
+                async move { Response::new(body) }
+            },
+        )
+        .await;
+        assert!(result.is_ok());
+        assert_eq!(count.get(), 2);
+
+        let response_text = String::from_utf8_lossy(&writer.data);
+        assert!(response_text.contains("\r\n\r\nrequest 1"));
+        assert!(response_text.ends_with("\r\n\r\nrequest 2"));
+    }
+
+    #[tokio::test]
+    async fn test_stream_large_bodies() {
+        const BODY_LEN: usize = 1024 * 1024;
+
+        // A fixed-length upload and a chunked one, then a final request
+        let mut request_data = format!(
+            "POST /fixed HTTP/1.1\r\nHost: localhost\r\nContent-Length: {BODY_LEN}\r\n\r\n"
+        )
+        .into_bytes();
+        request_data.extend(std::iter::repeat_n(b'a', BODY_LEN));
+        request_data.extend_from_slice(
+            b"POST /chunked HTTP/1.1\r\nHost: localhost\r\nTransfer-Encoding: chunked\r\n\r\n",
+        );
+        for _ in 0..(BODY_LEN / 0x10000) {
+            request_data.extend_from_slice(b"10000\r\n");
+            request_data.extend(std::iter::repeat_n(b'b', 0x10000));
+            request_data.extend_from_slice(b"\r\n");
+        }
+        request_data.extend_from_slice(b"0\r\nX-Checksum: 1\r\n\r\n");
+        request_data.extend_from_slice(
+            b"GET /done HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n",
+        );
+
+        let mut reader = TestReader::new(&request_data);
+        let mut writer = TestWriter::new();
+        let largest = std::cell::Cell::new(0usize);
+
+        // Count the body piece by piece; nothing keeps more than one piece
+        let result = handle_http_connection_streaming(
+            &mut reader,
+            &mut writer,
+            async |req: Request<()>, body: &mut BodyReader<'_, TestReader>| {
+                let mut total = 0;
+                while let Some(chunk) = body.chunk().await.unwrap() {
+                    largest.set(largest.get().max(chunk.len()));
+                    total += chunk.len();
+                }
+                assert!(body.is_finished());
+                assert_eq!(total, body.bytes_read());
+                if req.uri().path() == "/chunked" {
+                    assert_eq!(body.trailers()["x-checksum"], "1");
+                }
+                Response::new(Bytes::from(format!("{} {total}", req.uri().path())))
+            },
+        )
+        .await;
+        assert!(result.is_ok(), "{:?}", result);
+        assert!(largest.get() <= STREAM_CHUNK_SIZE);
+
+        let response_text = String::from_utf8_lossy(&writer.data);
+        assert!(response_text.contains(&format!("\r\n\r\n/fixed {BODY_LEN}")));
+        assert!(response_text.contains(&format!("\r\n\r\n/chunked {BODY_LEN}")));
+        assert!(response_text.ends_with("\r\n\r\n/done 0"));
+    }
+
+    #[tokio::test]
+    async fn test_stream_unread_body_is_drained() {
+        let request_data = b"POST /a HTTP/1.1\r\nHost: localhost\r\nContent-Length: 5\r\n\r\nHelloGET /b HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n";
+        let mut reader = TestReader::new(request_data);
+        let mut writer = TestWriter::new();
+
+        // The handler ignores the body entirely
+        let result = handle_http_connection_streaming(
+            &mut reader,
+            &mut writer,
+            async |req: Request<()>, _body: &mut BodyReader<'_, TestReader>| {
+                Response::new(Bytes::from(req.uri().path().to_string()))
+            },
+        )
+        .await;
+        assert!(result.is_ok(), "{:?}", result);
+
+        let response_text = String::from_utf8_lossy(&writer.data);
+        assert_eq!(response_text.matches("HTTP/1.1 200 OK\r\n").count(), 2);
+        assert!(response_text.ends_with("\r\n\r\n/b"));
+    }
+
     #[test]
     fn test_keep_alive() {
         let mut headers = HeaderMap::new();
@@ -1038,6 +1830,41 @@ mod kimojio_integration_tests {
         assert!(response_text.contains("Hello, World!"));
     }
 
+    #[kimojio::test]
+    async fn test_kimojio_accept_loop_shutdown() {
+        use std::rc::Rc;
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::time::{Duration, Instant};
+
+        let port: u16 = 19878;
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        // Ask for shutdown while no client ever connects
+        let trigger = shutdown.clone();
+        spawn_task(async move {
+            let _ = operations::sleep(Duration::from_millis(20)).await;
+            trigger.store(true, Ordering::SeqCst);
+        });
+
+        let start = Instant::now();
+        let result = run_kimojio_accept_loop(
+            0,
+            port,
+            Rc::new(()),
+            |_: &Rc<()>, req| simple_echo_handler(req),
+            shutdown,
+        )
+        .await;
+        let elapsed = start.elapsed();
+
+        assert!(result.is_ok());
+        assert!(
+            elapsed < Duration::from_secs(1),
+            "accept loop took {elapsed:?} to notice shutdown"
+        );
+    }
+
     #[kimojio::test]
     async fn test_kimojio_http_echo_post() {
         // Use a different port for this test
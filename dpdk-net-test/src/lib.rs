@@ -12,128 +12,7 @@ pub mod util {
     pub const TEST_MBUF_COUNT: u32 = 8192;
     pub const TEST_MBUF_CACHE_SIZE: u32 = 256;
 
-    /// Channel information from ethtool
-    #[derive(Debug, Clone, Copy, Default)]
-    pub struct EthtoolChannels {
-        /// Maximum supported RX-only channels
-        pub max_rx: u32,
-        /// Maximum supported TX-only channels
-        pub max_tx: u32,
-        /// Maximum supported other channels
-        pub max_other: u32,
-        /// Maximum supported combined channels (RX+TX on same queue)
-        pub max_combined: u32,
-        /// Current RX-only channels
-        pub rx_count: u32,
-        /// Current TX-only channels
-        pub tx_count: u32,
-        /// Current other channels
-        pub other_count: u32,
-        /// Current combined channels
-        pub combined_count: u32,
-    }
-
-    /// Get ethtool channel information for a network interface.
-    ///
-    /// This uses the SIOCETHTOOL ioctl to query channel counts,
-    /// equivalent to running `ethtool -l <interface>`.
-    ///
-    /// # Example
-    /// ```no_run
-    /// use dpdk_net_test::util::get_ethtool_channels;
-    ///
-    /// let channels = get_ethtool_channels("eth1").unwrap();
-    /// println!("Max combined queues: {}", channels.max_combined);
-    /// println!("Current combined queues: {}", channels.combined_count);
-    /// ```
-    pub fn get_ethtool_channels(interface: &str) -> Result<EthtoolChannels, String> {
-        use nix::libc;
-        use std::ffi::CString;
-
-        // ethtool command constants
-        const ETHTOOL_GCHANNELS: u32 = 0x0000003c;
-        const SIOCETHTOOL: libc::c_ulong = 0x8946;
-
-        // struct ethtool_channels from linux/ethtool.h
-        #[repr(C)]
-        struct EthtoolChannelsRaw {
-            cmd: u32,
-            max_rx: u32,
-            max_tx: u32,
-            max_other: u32,
-            max_combined: u32,
-            rx_count: u32,
-            tx_count: u32,
-            other_count: u32,
-            combined_count: u32,
-        }
-
-        // Create a socket for the ioctl
-        let sock = unsafe { libc::socket(libc::AF_INET, libc::SOCK_DGRAM, 0) };
-        if sock < 0 {
-            return Err("Failed to create socket".to_string());
-        }
-
-        // Ensure socket is closed when we're done
-        struct SocketGuard(i32);
-        impl Drop for SocketGuard {
-            fn drop(&mut self) {
-                unsafe { libc::close(self.0) };
-            }
-        }
-        let _guard = SocketGuard(sock);
-
-        // Prepare ethtool_channels struct
-        let mut channels = EthtoolChannelsRaw {
-            cmd: ETHTOOL_GCHANNELS,
-            max_rx: 0,
-            max_tx: 0,
-            max_other: 0,
-            max_combined: 0,
-            rx_count: 0,
-            tx_count: 0,
-            other_count: 0,
-            combined_count: 0,
-        };
-
-        // Prepare ifreq struct
-        let mut ifr: libc::ifreq = unsafe { std::mem::zeroed() };
-
-        // Copy interface name
-        let ifname = CString::new(interface).map_err(|_| "Invalid interface name")?;
-        let ifname_bytes = ifname.as_bytes_with_nul();
-        if ifname_bytes.len() > libc::IFNAMSIZ {
-            return Err("Interface name too long".to_string());
-        }
-        unsafe {
-            std::ptr::copy_nonoverlapping(
-                ifname_bytes.as_ptr(),
-                ifr.ifr_name.as_mut_ptr().cast::<u8>(),
-                ifname_bytes.len(),
-            );
-        }
-
-        // Set ifr_data to point to our ethtool_channels struct
-        ifr.ifr_ifru.ifru_data = &mut channels as *mut _ as *mut libc::c_char;
-
-        // Make the ioctl call
-        let ret = unsafe { libc::ioctl(sock, SIOCETHTOOL, &mut ifr) };
-        if ret < 0 {
-            let errno = std::io::Error::last_os_error();
-            return Err(format!("ioctl SIOCETHTOOL failed: {}", errno));
-        }
-
-        Ok(EthtoolChannels {
-            max_rx: channels.max_rx,
-            max_tx: channels.max_tx,
-            max_other: channels.max_other,
-            max_combined: channels.max_combined,
-            rx_count: channels.rx_count,
-            tx_count: channels.tx_count,
-            other_count: channels.other_count,
-            combined_count: channels.combined_count,
-        })
-    }
+    pub use dpdk_net::topology::{EthtoolChannels, get_ethtool_channels};
 
     /// Ensure that hugepages are set up correctly
     /// nr_hugepages: number of hugepages to allocate
@@ -336,16 +215,49 @@ pub mod util {
 pub mod send {
     use std::sync::{Arc, atomic::AtomicBool, atomic::Ordering};
 
-    use arrayvec::ArrayVec;
     use ctrlc;
     use dpdk_net::api::rte::eal::EalBuilder;
     use dpdk_net::api::rte::eth::{EthConf, EthDevBuilder, RxQueueConf, TxQueueConf};
     use dpdk_net::api::rte::pktmbuf::{MemPool, MemPoolConfig};
-    use dpdk_net::api::rte::queue::TxQueue;
+    use dpdk_net::generator::PacketGenerator;
     use smoltcp::wire;
 
     use crate::dpdk_test::DEFAULT_MBUF_DATA_ROOM_SIZE;
 
+    const TOTAL_HEADER_LEN: usize = 42;
+    const PAYLOAD_LEN: usize = 18;
+
+    /// Write a fixed UDP datagram frame into `buf`, returning its length.
+    fn build_udp_frame(buf: &mut [u8]) -> usize {
+        let len = TOTAL_HEADER_LEN + PAYLOAD_LEN;
+        let mut frame = wire::EthernetFrame::new_unchecked(&mut buf[..len]);
+        frame.set_src_addr(wire::EthernetAddress([0x00, 0x50, 0x56, 0xae, 0x76, 0xf5]));
+        frame.set_dst_addr(wire::EthernetAddress([0x00, 0x0b, 0x86, 0x64, 0x8b, 0xa0]));
+        frame.set_ethertype(wire::EthernetProtocol::Ipv4);
+
+        let mut ipv4_pkt = wire::Ipv4Packet::new_unchecked(frame.payload_mut());
+        ipv4_pkt.set_version(4);
+        ipv4_pkt.set_header_len(20);
+        ipv4_pkt.set_dscp(0);
+        ipv4_pkt.set_ecn(0);
+        ipv4_pkt.set_total_len((28 + PAYLOAD_LEN) as u16);
+        ipv4_pkt.set_ident(0x5c65);
+        ipv4_pkt.clear_flags();
+        ipv4_pkt.set_frag_offset(0);
+        ipv4_pkt.set_hop_limit(128);
+        ipv4_pkt.set_next_header(wire::IpProtocol::Udp);
+        ipv4_pkt.set_src_addr(wire::Ipv4Address::new(192, 168, 29, 58));
+        ipv4_pkt.set_dst_addr(wire::Ipv4Address::new(192, 168, 29, 160));
+        ipv4_pkt.set_checksum(0);
+
+        let mut udp_pkt = wire::UdpPacket::new_unchecked(ipv4_pkt.payload_mut());
+        udp_pkt.set_src_port(60376);
+        udp_pkt.set_dst_port(161);
+        udp_pkt.set_len((8 + PAYLOAD_LEN) as u16);
+        udp_pkt.set_checksum(0xbc86);
+        len
+    }
+
     /// port_id is the device port id to send packets
     /// VM might have only port 0.
     pub fn udp_gen(mem_pool_name: &str, port_id: u16) {
@@ -376,94 +288,30 @@ pub mod send {
             .build(&mempool)
             .expect("Failed to configure eth device");
 
-        let run = Arc::new(AtomicBool::new(true));
-        let run_curr = run.clone();
-        let run_clone = run.clone();
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_clone = stop.clone();
         ctrlc::set_handler(move || {
-            run_clone.store(false, Ordering::Release);
+            stop_clone.store(true, Ordering::Release);
         })
         .unwrap();
 
-        let total_header_len = 42;
-        let payload_len = 18;
-
-        let mut jhs = Vec::new();
-        for i in 0..nb_qs {
-            let run = run.clone();
-            let mem_pool_name = mem_pool_name.to_string();
-            let jh = std::thread::spawn(move || {
-                // Note: thread_bind_to is not available in our wrapper yet
-                // For now, we proceed without CPU pinning
-                let txq = TxQueue::new(port_id, i);
-                // Re-get mempool reference in thread via lookup
-                let mp = MemPool::lookup(mem_pool_name.clone()).expect("Failed to lookup mempool");
-                let mut batch = ArrayVec::<_, 64>::new();
-
-                while run.load(Ordering::Acquire) {
-                    mp.fill_batch(&mut batch);
-                    for mbuf in batch.iter_mut() {
-                        unsafe { mbuf.extend(total_header_len + payload_len) };
-
-                        let mut frame = wire::EthernetFrame::new_unchecked(mbuf.data_mut());
-                        frame.set_src_addr(wire::EthernetAddress([
-                            0x00, 0x50, 0x56, 0xae, 0x76, 0xf5,
-                        ]));
-                        frame.set_dst_addr(wire::EthernetAddress([
-                            0x00, 0x0b, 0x86, 0x64, 0x8b, 0xa0,
-                        ]));
-                        frame.set_ethertype(wire::EthernetProtocol::Ipv4);
-
-                        let mut ipv4_pkt = wire::Ipv4Packet::new_unchecked(frame.payload_mut());
-                        ipv4_pkt.set_version(4);
-                        ipv4_pkt.set_header_len(20);
-                        ipv4_pkt.set_dscp(0);
-                        ipv4_pkt.set_ecn(0);
-                        ipv4_pkt.set_total_len((28 + payload_len) as u16);
-                        ipv4_pkt.set_ident(0x5c65);
-                        ipv4_pkt.clear_flags();
-                        ipv4_pkt.set_frag_offset(0);
-                        ipv4_pkt.set_hop_limit(128);
-                        ipv4_pkt.set_next_header(wire::IpProtocol::Udp);
-                        ipv4_pkt.set_src_addr(wire::Ipv4Address::new(192, 168, 29, 58));
-                        ipv4_pkt.set_dst_addr(wire::Ipv4Address::new(192, 168, 29, 160));
-                        ipv4_pkt.set_checksum(0);
-
-                        let mut udp_pkt = wire::UdpPacket::new_unchecked(ipv4_pkt.payload_mut());
-                        udp_pkt.set_src_port(60376);
-                        udp_pkt.set_dst_port(161);
-                        udp_pkt.set_len((8 + payload_len) as u16);
-                        udp_pkt.set_checksum(0xbc86);
-                    }
-
-                    while !batch.is_empty() {
-                        let _sent = txq.tx(&mut batch);
-                        // net_null0 might not accept all packets, just continue
-                        if _sent == 0 {
-                            // Drain remaining to avoid infinite loop
-                            batch.clear();
-                            break;
-                        }
-                    }
-                }
-            });
-            jhs.push(jh);
-        }
-
-        // stop the loop after 2 seconds
-        let run_stop = run_curr.clone();
+        // stop the generator after 2 seconds
+        let stop_timer = stop.clone();
         std::thread::spawn(move || {
             std::thread::sleep(std::time::Duration::from_secs(2));
-            run_stop.store(false, Ordering::Release);
+            stop_timer.store(true, Ordering::Release);
         });
 
-        // Wait for stop signal
-        while run_curr.load(Ordering::Acquire) {
-            std::thread::sleep(std::time::Duration::from_millis(100));
-        }
-
-        for jh in jhs {
-            jh.join().unwrap();
-        }
+        let report =
+            PacketGenerator::new(port_id)
+                .queues(nb_qs)
+                .run(&mempool, &stop, |_, _, buf| build_udp_frame(buf));
+        println!(
+            "sent {} packets ({:.0} pps) on {} queues",
+            report.packets(),
+            report.pps(),
+            report.queues.len()
+        );
 
         let _ = eth_dev.stop();
         let _ = eth_dev.close();
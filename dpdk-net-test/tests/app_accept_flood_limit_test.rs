@@ -0,0 +1,135 @@
+//! DpdkApp Accept Flood Socket Limit Test
+//!
+//! Validates that `accept` keeps the reactor within its socket cap under a
+//! connection flood:
+//! - clients connect in rounds until `connect` is refused at the cap
+//! - `accept` resets what it cannot refill the backlog for, and the backlog
+//!   shrinks instead of allocating past the cap
+//! - `socket_count` never exceeds the cap at any point of the flood
+//! - once the clients are gone, the backlog refills to its bound size
+//!
+//! Uses `net_ring0` for loopback: transmitted frames re-enter the RX path.
+
+use std::cell::Cell;
+use std::time::Duration;
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::socket::{TcpConnectError, TcpListenError, TcpListener, TcpStream};
+use dpdk_net_util::{DpdkApp, WorkerContext};
+
+use smoltcp::wire::{IpAddress, Ipv4Address};
+
+use serial_test::serial;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+const SERVER_PORT: u16 = 8080;
+const MAX_SOCKETS: usize = 8;
+const BACKLOG: usize = 2;
+const ROUNDS: u16 = 5;
+
+async fn accept_flood_main(ctx: WorkerContext) {
+    let reactor = &ctx.reactor;
+    reactor.set_max_sockets(Some(MAX_SOCKETS));
+
+    let mut listener = TcpListener::bind_with_backlog(reactor, SERVER_PORT, 4096, 4096, BACKLOG)
+        .expect("Failed to bind listener");
+
+    let flooding = Cell::new(true);
+    let peak = Cell::new(reactor.socket_count());
+    let accepted = Cell::new(0usize);
+    let rejected = Cell::new(0usize);
+
+    let watch = async {
+        while flooding.get() {
+            peak.set(peak.get().max(reactor.socket_count()));
+            tokio::task::yield_now().await;
+        }
+    };
+
+    let server = async {
+        loop {
+            match reactor
+                .timeout(Duration::from_millis(200), listener.accept())
+                .await
+            {
+                Ok(Ok(stream)) => {
+                    stream.abort();
+                    accepted.set(accepted.get() + 1);
+                }
+                Ok(Err(TcpListenError::TooManySockets)) => rejected.set(rejected.get() + 1),
+                Ok(Err(e)) => panic!("accept failed: {e:?}"),
+                Err(_) if !flooding.get() => break,
+                Err(_) => {}
+            }
+        }
+    };
+
+    let flood = async {
+        let mut local_port = 49152;
+        for round in 0..ROUNDS {
+            let mut clients = Vec::new();
+            loop {
+                match TcpStream::connect(
+                    reactor,
+                    IpAddress::Ipv4(SERVER_IP),
+                    SERVER_PORT,
+                    local_port,
+                    4096,
+                    4096,
+                ) {
+                    Ok(client) => clients.push(client),
+                    Err(TcpConnectError::TooManySockets) => break,
+                    Err(e) => panic!("connect failed: {e:?}"),
+                }
+                local_port += 1;
+            }
+            println!("Round {round}: {} clients connecting", clients.len());
+            reactor.sleep(Duration::from_millis(50)).await;
+            drop(clients);
+        }
+        flooding.set(false);
+    };
+
+    tokio::join!(watch, server, flood);
+
+    println!(
+        "Accepted {}, rejected {}, peak {} sockets",
+        accepted.get(),
+        rejected.get(),
+        peak.get()
+    );
+    assert!(peak.get() <= MAX_SOCKETS, "socket cap exceeded");
+    assert!(rejected.get() > 0, "the flood never hit the cap");
+    assert!(reactor.socket_count() <= MAX_SOCKETS);
+    assert_eq!(listener.backlog(), BACKLOG, "backlog was not refilled");
+
+    drop(listener);
+
+    println!("\n✓ Accept flood socket limit test PASSED!");
+}
+
+#[test]
+#[serial]
+fn test_dpdk_app_accept_flood_limit() {
+    println!("\n=== DpdkApp Accept Flood Socket Limit Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    DpdkApp::new()
+        .eth_dev(0)
+        .ip(SERVER_IP)
+        .gateway(GATEWAY_IP)
+        .mbufs_per_queue(1024)
+        .descriptors(128, 128)
+        .run(accept_flood_main);
+
+    println!("\n=== DpdkApp Accept Flood Socket Limit Test Complete ===\n");
+}
@@ -0,0 +1,82 @@
+//! DpdkApp Checksum Offload Test
+//!
+//! Validates `DpdkApp::checksum_offload`. The app enables only the offload
+//! directions the driver advertises, so a TCP exchange must work exactly as
+//! with software checksums, and no received frame may be dropped for a bad
+//! checksum.
+//!
+//! Uses `net_ring0` for loopback: transmitted frames re-enter the RX path.
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::api::rte::eth::EthDev;
+use dpdk_net::socket::{TcpListener, TcpStream};
+use dpdk_net_util::{DpdkApp, WorkerContext};
+
+use smoltcp::wire::{IpAddress, Ipv4Address};
+
+use serial_test::serial;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+const SERVER_PORT: u16 = 8080;
+
+async fn checksum_main(ctx: WorkerContext) {
+    let capa = EthDev::new(0)
+        .checksum_offload_capa()
+        .expect("Failed to get device info");
+    println!("Driver checksum offload: rx={} tx={}", capa.rx, capa.tx);
+
+    let mut listener =
+        TcpListener::bind(&ctx.reactor, SERVER_PORT, 4096, 4096).expect("Failed to bind listener");
+    let client = TcpStream::connect(
+        &ctx.reactor,
+        IpAddress::Ipv4(SERVER_IP),
+        SERVER_PORT,
+        49152,
+        4096,
+        4096,
+    )
+    .expect("connect failed");
+    let server = listener.accept().await.expect("accept failed");
+
+    let mut buf = [0u8; 16];
+    client.send(b"ping").await.expect("client send failed");
+    let n = server.recv(&mut buf).await.expect("server recv failed");
+    assert_eq!(&buf[..n], b"ping");
+    server.send(b"pong").await.expect("server send failed");
+    let n = client.recv(&mut buf).await.expect("client recv failed");
+    assert_eq!(&buf[..n], b"pong");
+
+    assert_eq!(ctx.reactor.device_stats().rx_checksum_drops, 0);
+
+    client.close().await.ok();
+    server.close().await.ok();
+
+    println!("\n✓ Checksum offload test PASSED!");
+}
+
+#[test]
+#[serial]
+fn test_dpdk_app_checksum_offload() {
+    println!("\n=== DpdkApp Checksum Offload Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    DpdkApp::new()
+        .eth_dev(0)
+        .ip(SERVER_IP)
+        .gateway(GATEWAY_IP)
+        .mbufs_per_queue(1024)
+        .descriptors(128, 128)
+        .checksum_offload(true)
+        .run(checksum_main);
+
+    println!("\n=== DpdkApp Checksum Offload Test Complete ===\n");
+}
@@ -0,0 +1,89 @@
+//! DpdkApp Ephemeral Connect Test
+//!
+//! Validates `TcpStream::connect_ephemeral`. Concurrent connections get
+//! distinct local ports from the worker's ephemeral range, and a range whose
+//! only port is taken fails with `PortsExhausted` instead of colliding.
+//!
+//! Uses `net_ring0` for loopback: transmitted frames re-enter the RX path.
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::socket::{TcpConnectError, TcpListener, TcpStream};
+use dpdk_net_util::{DpdkApp, WorkerContext};
+
+use smoltcp::wire::{IpAddress, Ipv4Address};
+
+use serial_test::serial;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+const SERVER_PORT: u16 = 8080;
+
+fn connect(ctx: &WorkerContext) -> Result<TcpStream, TcpConnectError> {
+    TcpStream::connect_ephemeral(
+        &ctx.reactor,
+        IpAddress::Ipv4(SERVER_IP),
+        SERVER_PORT,
+        4096,
+        4096,
+    )
+}
+
+async fn ephemeral_main(ctx: WorkerContext) {
+    let reactor = &ctx.reactor;
+    let mut listener = TcpListener::bind_with_backlog(reactor, SERVER_PORT, 4096, 4096, 4)
+        .expect("Failed to bind listener");
+
+    let range = reactor.ephemeral_ports();
+    let mut clients = Vec::new();
+    let mut servers = Vec::new();
+    for _ in 0..3 {
+        clients.push(connect(&ctx).expect("connect_ephemeral failed"));
+        servers.push(listener.accept().await.expect("accept failed"));
+    }
+    let mut ports: Vec<u16> = clients
+        .iter()
+        .map(|c| c.local_addr().expect("no local address").1)
+        .collect();
+    println!("Local ports: {ports:?}");
+    assert!(ports.iter().all(|p| range.contains(p)));
+    ports.sort_unstable();
+    ports.dedup();
+    assert_eq!(ports.len(), 3, "local ports collided");
+
+    // The listener holds the only port in this range
+    reactor.set_ephemeral_ports(SERVER_PORT..=SERVER_PORT);
+    assert_eq!(connect(&ctx).err(), Some(TcpConnectError::PortsExhausted));
+    reactor.set_ephemeral_ports(range);
+    println!("Exhausted range refused");
+
+    for stream in clients.iter().chain(&servers) {
+        stream.abort();
+    }
+
+    println!("\n✓ Ephemeral connect test PASSED!");
+}
+
+#[test]
+#[serial]
+fn test_dpdk_app_connect_ephemeral() {
+    println!("\n=== DpdkApp Ephemeral Connect Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    DpdkApp::new()
+        .eth_dev(0)
+        .ip(SERVER_IP)
+        .gateway(GATEWAY_IP)
+        .mbufs_per_queue(1024)
+        .descriptors(128, 128)
+        .run(ephemeral_main);
+
+    println!("\n=== DpdkApp Ephemeral Connect Test Complete ===\n");
+}
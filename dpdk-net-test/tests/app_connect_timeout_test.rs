@@ -0,0 +1,116 @@
+//! DpdkApp Connect Timeout Test
+//!
+//! Validates `TcpStream::connect_timeout`. Validates that:
+//! - a connect to a listening port completes within the timeout
+//! - a connect to a port nobody listens on fails with `Refused`
+//! - a connect to an address that never answers fails with `TimedOut`,
+//!   no earlier than the timeout, and its half-open socket is removed from
+//!   the reactor right away
+//!
+//! Uses `net_ring0` for loopback: transmitted frames re-enter the RX path.
+
+use std::time::{Duration, Instant};
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::socket::{TcpConnectError, TcpListener, TcpStream};
+use dpdk_net_util::{DpdkApp, WorkerContext};
+
+use smoltcp::wire::{IpAddress, Ipv4Address};
+
+use serial_test::serial;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+/// On the interface's subnet, but nothing answers ARP for it.
+const SILENT_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 50);
+const SERVER_PORT: u16 = 8080;
+const CLOSED_PORT: u16 = 8081;
+const TIMEOUT: Duration = Duration::from_millis(200);
+
+async fn connect_timeout_main(ctx: WorkerContext) {
+    let reactor = &ctx.reactor;
+    let mut listener =
+        TcpListener::bind(reactor, SERVER_PORT, 4096, 4096).expect("Failed to bind listener");
+
+    let (client, server) = tokio::join!(
+        TcpStream::connect_timeout(
+            reactor,
+            IpAddress::Ipv4(SERVER_IP),
+            SERVER_PORT,
+            49152,
+            4096,
+            4096,
+            TIMEOUT,
+        ),
+        listener.accept()
+    );
+    let client = client.expect("connect_timeout failed");
+    let server = server.expect("accept failed");
+    assert!(client.is_connected());
+    println!("Connected within the timeout");
+    client.abort();
+    server.abort();
+    drop((client, server));
+
+    let refused = TcpStream::connect_timeout(
+        reactor,
+        IpAddress::Ipv4(SERVER_IP),
+        CLOSED_PORT,
+        49153,
+        4096,
+        4096,
+        TIMEOUT,
+    )
+    .await
+    .err();
+    assert_eq!(refused, Some(TcpConnectError::Refused));
+    println!("Connect to a closed port refused");
+
+    // Let the aborted streams above finish closing
+    reactor.sleep(Duration::from_millis(20)).await;
+    let sockets = reactor.socket_count();
+    let start = Instant::now();
+    let timed_out = TcpStream::connect_timeout(
+        reactor,
+        IpAddress::Ipv4(SILENT_IP),
+        SERVER_PORT,
+        49154,
+        4096,
+        4096,
+        TIMEOUT,
+    )
+    .await
+    .err();
+    let elapsed = start.elapsed();
+    println!("Connect to a silent address gave up after {elapsed:?}");
+    assert_eq!(timed_out, Some(TcpConnectError::TimedOut));
+    assert!(elapsed >= TIMEOUT);
+    assert_eq!(reactor.socket_count(), sockets, "half-open socket leaked");
+
+    println!("\n✓ Connect timeout test PASSED!");
+}
+
+#[test]
+#[serial]
+fn test_dpdk_app_connect_timeout() {
+    println!("\n=== DpdkApp Connect Timeout Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    DpdkApp::new()
+        .eth_dev(0)
+        .ip(SERVER_IP)
+        .gateway(GATEWAY_IP)
+        .mbufs_per_queue(1024)
+        .descriptors(128, 128)
+        .run(connect_timeout_main);
+
+    println!("\n=== DpdkApp Connect Timeout Test Complete ===\n");
+}
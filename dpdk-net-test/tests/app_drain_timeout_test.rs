@@ -0,0 +1,91 @@
+//! DpdkApp Drain Timeout Test
+//!
+//! Validates `DpdkApp::drain_timeout`. The worker half-closes both ends of
+//! a connection and returns right away, dropping the streams mid-close and
+//! leaving their FIN exchange to the reactor. With a drain timeout (and no
+//! shutdown timeout) the reactor keeps polling until those closes finish,
+//! so the run report shows a clean shutdown.
+//!
+//! Uses `net_ring0` for loopback: transmitted frames re-enter the RX path.
+
+use std::net::Shutdown;
+use std::time::Duration;
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::socket::{TcpListener, TcpStream};
+use dpdk_net_util::{DpdkApp, ShutdownStatus, WorkerContext};
+
+use smoltcp::wire::{IpAddress, Ipv4Address};
+
+use serial_test::serial;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+const SERVER_PORT: u16 = 8080;
+
+async fn drain_main(ctx: WorkerContext) {
+    let mut listener =
+        TcpListener::bind(&ctx.reactor, SERVER_PORT, 4096, 4096).expect("Failed to bind listener");
+    let client = TcpStream::connect(
+        &ctx.reactor,
+        IpAddress::Ipv4(SERVER_IP),
+        SERVER_PORT,
+        49152,
+        4096,
+        4096,
+    )
+    .expect("connect failed");
+    let server = listener.accept().await.expect("accept failed");
+
+    client.send(b"ping").await.expect("send failed");
+    let mut buf = [0u8; 4];
+    let n = server.recv(&mut buf).await.expect("recv failed");
+    assert_eq!(&buf[..n], b"ping");
+
+    // Start both closes and walk away
+    client
+        .shutdown(Shutdown::Write)
+        .expect("client shutdown failed");
+    server
+        .shutdown(Shutdown::Write)
+        .expect("server shutdown failed");
+    drop(client);
+    drop(server);
+    drop(listener);
+    assert!(ctx.reactor.closing_count() > 0);
+    println!(
+        "Returning with {} streams closing",
+        ctx.reactor.closing_count()
+    );
+}
+
+#[test]
+#[serial]
+fn test_dpdk_app_drain_timeout() {
+    println!("\n=== DpdkApp Drain Timeout Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    let report = DpdkApp::new()
+        .eth_dev(0)
+        .ip(SERVER_IP)
+        .gateway(GATEWAY_IP)
+        .mbufs_per_queue(1024)
+        .descriptors(128, 128)
+        .drain_timeout(Duration::from_secs(1))
+        .run_reporting(drain_main);
+
+    println!("{report:#?}");
+    assert_eq!(report.queues.len(), 1);
+    assert_eq!(report.queues[0].shutdown, ShutdownStatus::Clean);
+
+    println!("\n✓ Drain timeout test PASSED!");
+    println!("\n=== DpdkApp Drain Timeout Test Complete ===\n");
+}
@@ -0,0 +1,153 @@
+//! DpdkApp Eager Egress Test
+//!
+//! Validates that `TcpStream::connect` puts its SYN on the wire before the
+//! calling task yields when eager egress is enabled, and leaves it to the
+//! reactor loop when disabled. Also measures connect latency in both modes
+//! with a few busy tasks competing for the executor.
+//!
+//! Uses `net_ring0` for loopback: transmitted frames re-enter the RX path.
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::api::rte::eth::EthDev;
+use dpdk_net::runtime::ReactorHandle;
+use dpdk_net::socket::{TcpListener, TcpStream};
+use dpdk_net_util::{DpdkApp, WorkerContext};
+
+use smoltcp::wire::{IpAddress, Ipv4Address};
+
+use serial_test::serial;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+const SERVER_PORT: u16 = 8080;
+
+const SPINNERS: usize = 8;
+const ROUNDS: usize = 50;
+
+fn tx_packets() -> u64 {
+    EthDev::new(0)
+        .stats_typed()
+        .expect("Failed to read port stats")
+        .tx_packets
+}
+
+fn connect(reactor: &ReactorHandle, local_port: u16) -> TcpStream {
+    TcpStream::connect(
+        reactor,
+        IpAddress::Ipv4(SERVER_IP),
+        SERVER_PORT,
+        local_port,
+        4096,
+        4096,
+    )
+    .expect("connect failed")
+}
+
+/// Connect, accept and close once; returns the time until `wait_connected`.
+async fn round_trip(
+    reactor: &ReactorHandle,
+    listener: &mut TcpListener,
+    local_port: u16,
+) -> Duration {
+    let start = Instant::now();
+    let client = connect(reactor, local_port);
+    let (connected, server) = tokio::join!(client.wait_connected(), listener.accept());
+    let elapsed = start.elapsed();
+    connected.expect("not connected");
+    let server = server.expect("accept failed");
+    let (a, b) = tokio::join!(client.close(), server.close());
+    a.ok();
+    b.ok();
+    elapsed
+}
+
+async fn mean_connect_latency(
+    reactor: &ReactorHandle,
+    listener: &mut TcpListener,
+    port_base: u16,
+) -> Duration {
+    let mut total = Duration::ZERO;
+    for i in 0..ROUNDS {
+        total += round_trip(reactor, listener, port_base + i as u16).await;
+    }
+    total / ROUNDS as u32
+}
+
+async fn eager_egress_main(ctx: WorkerContext) {
+    let reactor = ctx.reactor.clone();
+    assert!(reactor.eager_egress());
+    let mut listener =
+        TcpListener::bind(&reactor, SERVER_PORT, 4096, 4096).expect("Failed to bind listener");
+
+    // Warm up so the neighbor entry exists and connect emits a SYN, not ARP
+    round_trip(&reactor, &mut listener, 49000).await;
+
+    reactor.set_eager_egress(false);
+    let before = tx_packets();
+    let lazy = connect(&reactor, 49001);
+    assert_eq!(tx_packets(), before, "SYN sent without eager egress");
+    lazy.abort();
+
+    reactor.set_eager_egress(true);
+    let before = tx_packets();
+    let eager = connect(&reactor, 49002);
+    // Frames left over from the warm-up close may go out with it
+    assert!(tx_packets() > before, "SYN not sent by connect");
+    eager.abort();
+    println!("SYN transmitted inside connect");
+
+    // Busy tasks that keep the run queue full
+    let done = Rc::new(Cell::new(false));
+    let spinners: Vec<_> = (0..SPINNERS)
+        .map(|_| {
+            let done = done.clone();
+            tokio::task::spawn_local(async move {
+                while !done.get() {
+                    tokio::task::yield_now().await;
+                }
+            })
+        })
+        .collect();
+
+    reactor.set_eager_egress(false);
+    let lazy = mean_connect_latency(&reactor, &mut listener, 50000).await;
+    reactor.set_eager_egress(true);
+    let eager = mean_connect_latency(&reactor, &mut listener, 51000).await;
+    println!("Mean connect latency: lazy {lazy:?}, eager {eager:?}");
+
+    done.set(true);
+    for spinner in spinners {
+        spinner.await.expect("spinner task failed");
+    }
+
+    println!("\n✓ Eager egress test PASSED!");
+}
+
+#[test]
+#[serial]
+fn test_dpdk_app_eager_egress() {
+    println!("\n=== DpdkApp Eager Egress Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    DpdkApp::new()
+        .eth_dev(0)
+        .ip(SERVER_IP)
+        .gateway(GATEWAY_IP)
+        .mbufs_per_queue(1024)
+        .descriptors(128, 128)
+        .run(eager_egress_main);
+
+    println!("\n=== DpdkApp Eager Egress Test Complete ===\n");
+}
@@ -0,0 +1,55 @@
+//! DpdkApp EAL Options Test
+//!
+//! Validates `DpdkApp::file_prefix` and `in_memory`: they are added to the
+//! EAL the app initializes from `eal()`, the run succeeds with them, and EAL
+//! is cleaned up afterwards.
+//!
+//! Uses `net_ring0` for loopback: transmitted frames re-enter the RX path.
+
+use dpdk_net::api::rte::eal::{Eal, EalBuilder};
+use dpdk_net::socket::TcpListener;
+use dpdk_net_util::{DpdkApp, WorkerContext};
+
+use smoltcp::wire::Ipv4Address;
+
+use serial_test::serial;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+
+async fn eal_options_main(ctx: WorkerContext) {
+    assert!(Eal::is_initialized(), "app should have initialized EAL");
+    let _listener =
+        TcpListener::bind(&ctx.reactor, 8080, 4096, 4096).expect("Failed to bind listener");
+    ctx.mark_ready();
+}
+
+#[test]
+#[serial]
+fn test_dpdk_app_eal_options() {
+    println!("\n=== DpdkApp EAL Options Test ===\n");
+
+    assert!(!Eal::is_initialized());
+
+    // in_memory comes from the app, not the builder
+    DpdkApp::new()
+        .eal(
+            EalBuilder::new()
+                .no_huge()
+                .no_pci()
+                .core_list("0")
+                .vdev("net_ring0"),
+        )
+        .file_prefix("app_eal_options_test")
+        .in_memory(true)
+        .eth_dev(0)
+        .ip(SERVER_IP)
+        .gateway(GATEWAY_IP)
+        .mbufs_per_queue(1024)
+        .descriptors(128, 128)
+        .run(eal_options_main);
+
+    assert!(!Eal::is_initialized());
+
+    println!("\n=== DpdkApp EAL Options Test Complete ===\n");
+}
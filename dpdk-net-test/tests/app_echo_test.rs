@@ -148,8 +148,9 @@ async fn server_main(ctx: WorkerContext) {
     // Spawn server task
     let server_task = tokio::task::spawn_local(run_echo_server(listener, done_tx));
 
-    // Give server a moment to start listening
-    tokio::task::yield_now().await;
+    // Wait until every queue is listening before connecting
+    ctx.mark_ready();
+    ctx.wait_ready().await;
 
     // Run client
     let client_result = run_echo_client(&ctx, done_rx).await;
@@ -0,0 +1,143 @@
+//! DpdkApp Half-Open Eviction Rebuild Test
+//!
+//! Floods a listener bound to one address, with socket options set, past
+//! the reactor's half-open limit while an `accept` is pending. The evicted
+//! backlog socket is rebuilt in place. Validates that:
+//! - the rebuilt socket keeps the listener's address, so a connection to
+//!   another local address is refused instead of accepted
+//! - the pending `accept` wakes and accepts the evicted client once its SYN
+//!   is retransmitted
+//! - every accepted stream, including the one on the rebuilt socket, has
+//!   the listener's keep-alive, timeout and Nagle settings
+//!
+//! Uses `net_ring0` for loopback: transmitted frames re-enter the RX path.
+
+use std::time::Duration;
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::socket::{TcpListener, TcpSocketOptions, TcpStream};
+use dpdk_net_util::{DpdkApp, WorkerContext};
+
+use smoltcp::wire::{IpAddress, IpCidr, Ipv4Address};
+
+use serial_test::serial;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const ALIAS_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 2);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+const SERVER_PORT: u16 = 8080;
+/// Fewer than the default backlog of 2, so one of two SYNs is evicted
+const MAX_HALF_OPEN: usize = 1;
+const CLIENTS: usize = 2;
+
+async fn rebuild_main(ctx: WorkerContext) {
+    let reactor = ctx.reactor.clone();
+    reactor.set_max_half_open(Some(MAX_HALF_OPEN));
+
+    let options = TcpSocketOptions::new(4096, 4096)
+        .keep_alive(Some(Duration::from_secs(10)))
+        .timeout(Some(Duration::from_secs(30)))
+        .nagle(false);
+    let mut listener = TcpListener::bind_addr_with_options(
+        &reactor,
+        IpAddress::Ipv4(SERVER_IP),
+        SERVER_PORT,
+        options,
+    )
+    .expect("Failed to bind listener");
+
+    // Accept is pending when the eviction happens
+    let server = tokio::task::spawn_local(async move {
+        let mut accepted = Vec::with_capacity(CLIENTS);
+        while accepted.len() < CLIENTS {
+            accepted.push(listener.accept().await.expect("accept failed"));
+        }
+        accepted
+    });
+    tokio::task::yield_now().await;
+
+    // Both SYNs leave in the same egress pass
+    let clients: Vec<TcpStream> = (0..CLIENTS)
+        .map(|i| {
+            TcpStream::connect(
+                &reactor,
+                IpAddress::Ipv4(SERVER_IP),
+                SERVER_PORT,
+                49152 + i as u16,
+                4096,
+                4096,
+            )
+            .expect("connect failed")
+        })
+        .collect();
+    while reactor.syn_dropped() == 0 {
+        reactor.sleep(Duration::from_millis(1)).await;
+    }
+    println!("SYN evicted, backlog socket rebuilt");
+
+    // The rebuilt socket listens on SERVER_IP only
+    let alias = TcpStream::connect(
+        &reactor,
+        IpAddress::Ipv4(ALIAS_IP),
+        SERVER_PORT,
+        49160,
+        4096,
+        4096,
+    )
+    .expect("connect failed");
+    assert!(
+        alias.wait_connected().await.is_err(),
+        "rebuilt socket accepted a connection to {ALIAS_IP}"
+    );
+    println!("Connection to {ALIAS_IP} refused");
+
+    let accepted = reactor
+        .timeout(Duration::from_secs(5), server)
+        .await
+        .expect("evicted client never accepted")
+        .expect("server task failed");
+    for client in &clients {
+        client.wait_connected().await.expect("client not connected");
+    }
+    for stream in &accepted {
+        assert_eq!(stream.keep_alive(), Some(Duration::from_secs(10)));
+        assert_eq!(stream.timeout(), Some(Duration::from_secs(30)));
+        assert!(stream.nodelay());
+        assert_eq!(
+            stream.local_addr(),
+            Some((IpAddress::Ipv4(SERVER_IP), SERVER_PORT))
+        );
+    }
+    println!(
+        "Accepted {} streams with the listener's options",
+        accepted.len()
+    );
+
+    println!("\n✓ Half-open rebuild test PASSED!");
+}
+
+#[test]
+#[serial]
+fn test_dpdk_app_half_open_rebuild() {
+    println!("\n=== DpdkApp Half-Open Eviction Rebuild Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    DpdkApp::new()
+        .eth_dev(0)
+        .ip(SERVER_IP)
+        .add_ip(IpCidr::new(IpAddress::Ipv4(ALIAS_IP), 24))
+        .gateway(GATEWAY_IP)
+        .mbufs_per_queue(1024)
+        .descriptors(128, 128)
+        .run(rebuild_main);
+
+    println!("\n=== DpdkApp Half-Open Eviction Rebuild Test Complete ===\n");
+}
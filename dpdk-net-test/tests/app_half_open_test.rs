@@ -0,0 +1,121 @@
+//! DpdkApp Half-Open Limit Test
+//!
+//! Floods a listener with more simultaneous SYNs than the reactor's
+//! half-open limit allows. Validates that:
+//! - `half_open_count()` never exceeds the limit
+//! - the excess SYNs are counted in `syn_dropped()`
+//! - the dropped clients get in on their SYN retransmit, so every
+//!   connection is eventually accepted
+//!
+//! Uses `net_ring0` for loopback: transmitted frames re-enter the RX path.
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::socket::{TcpListener, TcpStream};
+use dpdk_net_util::{DpdkApp, WorkerContext};
+
+use smoltcp::wire::{IpAddress, Ipv4Address};
+
+use serial_test::serial;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+const SERVER_PORT: u16 = 8080;
+
+const MAX_HALF_OPEN: usize = 4;
+const CLIENTS: usize = 12;
+
+async fn half_open_main(ctx: WorkerContext) {
+    let reactor = ctx.reactor.clone();
+    reactor.set_max_half_open(Some(MAX_HALF_OPEN));
+    assert_eq!(reactor.max_half_open(), Some(MAX_HALF_OPEN));
+
+    // Backlog larger than the limit, so the limit is what gets enforced
+    let mut listener = TcpListener::bind_with_backlog(&reactor, SERVER_PORT, 1024, 1024, CLIENTS)
+        .expect("Failed to bind listener");
+
+    // Watch the half-open count for the whole run
+    let done = Rc::new(Cell::new(false));
+    let peak = Rc::new(Cell::new(0usize));
+    let monitor = {
+        let reactor = reactor.clone();
+        let done = done.clone();
+        let peak = peak.clone();
+        tokio::task::spawn_local(async move {
+            while !done.get() {
+                peak.set(peak.get().max(reactor.half_open_count()));
+                tokio::task::yield_now().await;
+            }
+        })
+    };
+
+    // All SYNs leave in the same egress pass
+    let clients: Vec<TcpStream> = (0..CLIENTS)
+        .map(|i| {
+            TcpStream::connect(
+                &reactor,
+                IpAddress::Ipv4(SERVER_IP),
+                SERVER_PORT,
+                49152 + i as u16,
+                1024,
+                1024,
+            )
+            .expect("connect failed")
+        })
+        .collect();
+
+    let mut accepted = Vec::with_capacity(CLIENTS);
+    while accepted.len() < CLIENTS {
+        accepted.push(listener.accept().await.expect("accept failed"));
+    }
+    for client in &clients {
+        client.wait_connected().await.expect("client not connected");
+    }
+
+    done.set(true);
+    monitor.await.expect("monitor task failed");
+
+    let dropped = reactor.syn_dropped();
+    println!(
+        "Accepted {} connections; peak half-open {}, SYNs dropped {}",
+        accepted.len(),
+        peak.get(),
+        dropped
+    );
+    assert!(
+        peak.get() <= MAX_HALF_OPEN,
+        "half-open peak {} exceeded limit {MAX_HALF_OPEN}",
+        peak.get()
+    );
+    assert!(dropped > 0, "no SYNs were dropped");
+    assert_eq!(reactor.half_open_count(), 0);
+
+    println!("\n✓ Half-open limit test PASSED!");
+}
+
+#[test]
+#[serial]
+fn test_dpdk_app_half_open_limit() {
+    println!("\n=== DpdkApp Half-Open Limit Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    DpdkApp::new()
+        .eth_dev(0)
+        .ip(SERVER_IP)
+        .gateway(GATEWAY_IP)
+        .mbufs_per_queue(1024)
+        .descriptors(128, 128)
+        .run(half_open_main);
+
+    println!("\n=== DpdkApp Half-Open Limit Test Complete ===\n");
+}
@@ -0,0 +1,86 @@
+//! DpdkApp ICMP Ping Test
+//!
+//! Validates `IcmpSocket::ping`. The interface answers echo requests to its
+//! own address, so pinging it exercises a full round trip. Validates that:
+//! - a ping to the interface address returns a round-trip time
+//! - consecutive sequence numbers are each matched to their own reply
+//! - a ping to an address that never answers stays pending until
+//!   `ReactorHandle::timeout` gives up
+//! - an IPv6 destination is rejected with `Unaddressable`
+//!
+//! Uses `net_ring0` for loopback: transmitted frames re-enter the RX path.
+
+use std::time::Duration;
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::socket::{IcmpSendError, IcmpSocket, PingError};
+use dpdk_net_util::{DpdkApp, WorkerContext};
+
+use smoltcp::wire::{IpAddress, Ipv4Address, Ipv6Address};
+
+use serial_test::serial;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+/// On the interface's subnet, but nothing answers ARP for it.
+const SILENT_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 50);
+const IDENT: u16 = 0x1234;
+
+async fn ping_main(ctx: WorkerContext) {
+    let reactor = &ctx.reactor;
+    let socket = IcmpSocket::bind(reactor, IDENT).expect("Failed to bind ICMP socket");
+    assert_eq!(socket.ident(), IDENT);
+
+    for seq in 0..4 {
+        let rtt = reactor
+            .timeout(
+                Duration::from_secs(1),
+                socket.ping(IpAddress::Ipv4(SERVER_IP), seq, b"dpdk-net ping"),
+            )
+            .await
+            .expect("ping timed out")
+            .expect("ping failed");
+        println!("Reply from {SERVER_IP}: seq={seq} time={rtt:?}");
+    }
+
+    let silent = reactor
+        .timeout(
+            Duration::from_millis(200),
+            socket.ping(IpAddress::Ipv4(SILENT_IP), 4, b"anyone there?"),
+        )
+        .await;
+    assert!(silent.is_err(), "got a reply from {SILENT_IP}: {silent:?}");
+    println!("No reply from {SILENT_IP}, timed out");
+
+    let v6 = socket
+        .ping(IpAddress::Ipv6(Ipv6Address::LOCALHOST), 5, b"v6")
+        .await;
+    assert_eq!(v6, Err(PingError::Send(IcmpSendError::Unaddressable)));
+
+    println!("\n✓ ICMP ping test PASSED!");
+}
+
+#[test]
+#[serial]
+fn test_dpdk_app_icmp_ping() {
+    println!("\n=== DpdkApp ICMP Ping Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    DpdkApp::new()
+        .eth_dev(0)
+        .ip(SERVER_IP)
+        .gateway(GATEWAY_IP)
+        .mbufs_per_queue(1024)
+        .descriptors(128, 128)
+        .run(ping_main);
+
+    println!("\n=== DpdkApp ICMP Ping Test Complete ===\n");
+}
@@ -0,0 +1,84 @@
+//! DpdkApp IPv6 Test
+//!
+//! Validates `DpdkApp::ipv6` and `gateway_v6`: with both address families
+//! configured, the interface holds the IPv6 address after the IPv4 one, and
+//! a TCP connection to the IPv6 address is accepted and carries data in
+//! both directions.
+//!
+//! Uses `net_ring0` for loopback: transmitted frames re-enter the RX path,
+//! so the neighbor solicitation for our own address is answered by us.
+
+use std::net::SocketAddr;
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::socket::{TcpListener, TcpStream};
+use dpdk_net_util::{DpdkApp, WorkerContext};
+
+use smoltcp::wire::{IpAddress, Ipv4Address, Ipv6Address};
+
+use serial_test::serial;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+const SERVER_IP6: Ipv6Address = Ipv6Address::new(0xfd00, 0, 0, 0, 0, 0, 0, 1);
+const GATEWAY_IP6: Ipv6Address = Ipv6Address::new(0xfd00, 0, 0, 0, 0, 0, 0, 0xfe);
+const SERVER_PORT: u16 = 8080;
+
+async fn ipv6_main(ctx: WorkerContext) {
+    assert_eq!(ctx.reactor.ip_addr(), Some(IpAddress::Ipv4(SERVER_IP)));
+
+    let mut listener =
+        TcpListener::bind(&ctx.reactor, SERVER_PORT, 4096, 4096).expect("Failed to bind listener");
+    let remote = SocketAddr::new(SERVER_IP6.into(), SERVER_PORT);
+    let client = TcpStream::connect_std(&ctx.reactor, remote, 49152, 4096, 4096)
+        .expect("IPv6 connect failed");
+    let (connected, server) = tokio::join!(client.wait_connected(), listener.accept());
+    connected.expect("not connected");
+    let server = server.expect("accept failed");
+    assert_eq!(
+        server.local_addr(),
+        Some((IpAddress::Ipv6(SERVER_IP6), SERVER_PORT))
+    );
+    println!("Connected over IPv6");
+
+    let mut buf = [0u8; 16];
+    client.send(b"ping").await.expect("client send failed");
+    let n = server.recv(&mut buf).await.expect("server recv failed");
+    assert_eq!(&buf[..n], b"ping");
+    server.send(b"pong").await.expect("server send failed");
+    let n = client.recv(&mut buf).await.expect("client recv failed");
+    assert_eq!(&buf[..n], b"pong");
+    println!("Exchange over IPv6 OK");
+
+    client.close().await.ok();
+    server.close().await.ok();
+
+    println!("\n✓ IPv6 test PASSED!");
+}
+
+#[test]
+#[serial]
+fn test_dpdk_app_ipv6() {
+    println!("\n=== DpdkApp IPv6 Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    DpdkApp::new()
+        .eth_dev(0)
+        .ip(SERVER_IP)
+        .gateway(GATEWAY_IP)
+        .ipv6(SERVER_IP6, 64)
+        .gateway_v6(GATEWAY_IP6)
+        .mbufs_per_queue(1024)
+        .descriptors(128, 128)
+        .run(ipv6_main);
+
+    println!("\n=== DpdkApp IPv6 Test Complete ===\n");
+}
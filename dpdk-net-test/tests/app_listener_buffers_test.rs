@@ -0,0 +1,123 @@
+//! DpdkApp Listener Buffer Resize Test
+//!
+//! Validates `TcpListener::set_buffer_sizes`. A first connection is accepted
+//! with the original sizes, the buffers are grown, and a second connection
+//! is accepted with the larger sizes. Both connections carry a patterned
+//! transfer larger than the original buffers, checked byte for byte.
+//!
+//! Uses `net_ring0` for loopback: transmitted frames re-enter the RX path.
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::socket::{TcpListener, TcpStream};
+use dpdk_net_util::{DpdkApp, WorkerContext};
+
+use smoltcp::wire::{IpAddress, Ipv4Address};
+
+use serial_test::serial;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+const SERVER_PORT: u16 = 8080;
+
+const SMALL: usize = 4096;
+const LARGE: usize = 65536;
+const TRANSFER: usize = 256 * 1024;
+
+fn pattern(i: usize) -> u8 {
+    (i % 251) as u8
+}
+
+/// Send `TRANSFER` patterned bytes from `client` and verify them on `server`.
+async fn transfer(client: TcpStream, server: TcpStream) {
+    let sender = tokio::task::spawn_local(async move {
+        let data: Vec<u8> = (0..TRANSFER).map(pattern).collect();
+        client.send(&data).await.expect("send failed");
+        client.close().await.ok();
+    });
+
+    let mut buf = vec![0u8; 8192];
+    let mut received = 0;
+    while received < TRANSFER {
+        let n = server.recv(&mut buf).await.expect("recv failed");
+        assert!(n > 0, "unexpected EOF after {received} bytes");
+        for (i, b) in buf[..n].iter().enumerate() {
+            assert_eq!(
+                *b,
+                pattern(received + i),
+                "corrupt byte at {}",
+                received + i
+            );
+        }
+        received += n;
+    }
+    assert_eq!(received, TRANSFER);
+    sender.await.expect("sender task failed");
+    server.close().await.ok();
+}
+
+async fn connect(ctx: &WorkerContext, local_port: u16) -> TcpStream {
+    let stream = TcpStream::connect(
+        &ctx.reactor,
+        IpAddress::Ipv4(SERVER_IP),
+        SERVER_PORT,
+        local_port,
+        LARGE,
+        LARGE,
+    )
+    .expect("connect failed");
+    stream.wait_connected().await.expect("not connected");
+    stream
+}
+
+async fn listener_buffers_main(ctx: WorkerContext) {
+    let mut listener = TcpListener::bind(&ctx.reactor, SERVER_PORT, SMALL, SMALL)
+        .expect("Failed to bind listener");
+    assert_eq!(listener.buffer_sizes(), (SMALL, SMALL));
+
+    let client = connect(&ctx, 49152).await;
+    let server = listener.accept().await.expect("accept failed");
+    assert_eq!(server.recv_buffer_size(), SMALL);
+    assert_eq!(server.send_buffer_size(), SMALL);
+    transfer(client, server).await;
+    println!("Transfer with {SMALL}-byte buffers OK");
+
+    listener
+        .set_buffer_sizes(LARGE, LARGE)
+        .expect("set_buffer_sizes failed");
+    assert_eq!(listener.buffer_sizes(), (LARGE, LARGE));
+    assert_eq!(listener.backlog(), 2);
+
+    let client = connect(&ctx, 49153).await;
+    let server = listener.accept().await.expect("accept failed");
+    assert_eq!(server.recv_buffer_size(), LARGE);
+    assert_eq!(server.send_buffer_size(), LARGE);
+    transfer(client, server).await;
+    println!("Transfer with {LARGE}-byte buffers OK");
+
+    println!("\n✓ Listener buffer resize test PASSED!");
+}
+
+#[test]
+#[serial]
+fn test_dpdk_app_listener_buffers() {
+    println!("\n=== DpdkApp Listener Buffer Resize Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    DpdkApp::new()
+        .eth_dev(0)
+        .ip(SERVER_IP)
+        .gateway(GATEWAY_IP)
+        .mbufs_per_queue(4096)
+        .descriptors(512, 512)
+        .run(listener_buffers_main);
+
+    println!("\n=== DpdkApp Listener Buffer Resize Test Complete ===\n");
+}
@@ -0,0 +1,106 @@
+//! DpdkApp Listener In-Flight Cap Test
+//!
+//! Validates `TcpListener::set_max_inflight` with a cap of one stream:
+//! - the first connection is accepted and counted as in flight
+//! - a second connection completes its handshake on a backlog socket, but
+//!   `accept` holds it back while the first stream is alive
+//! - dropping the first stream wakes the pending accept, which hands out
+//!   the queued connection
+//!
+//! Uses `net_ring0` for loopback: transmitted frames re-enter the RX path.
+
+use std::time::Duration;
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::socket::{TcpListener, TcpStream};
+use dpdk_net_util::{DpdkApp, WorkerContext};
+
+use smoltcp::wire::{IpAddress, Ipv4Address};
+
+use serial_test::serial;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+const SERVER_PORT: u16 = 8080;
+
+async fn connect(ctx: &WorkerContext, local_port: u16) -> TcpStream {
+    let client = TcpStream::connect(
+        &ctx.reactor,
+        IpAddress::Ipv4(SERVER_IP),
+        SERVER_PORT,
+        local_port,
+        4096,
+        4096,
+    )
+    .expect("connect failed");
+    client.wait_connected().await.expect("not connected");
+    client
+}
+
+async fn max_inflight_main(ctx: WorkerContext) {
+    let mut listener = TcpListener::bind_with_backlog(&ctx.reactor, SERVER_PORT, 4096, 4096, 2)
+        .expect("Failed to bind listener");
+    listener.set_max_inflight(Some(1));
+    assert_eq!(listener.max_inflight(), Some(1));
+
+    let first_client = connect(&ctx, 49152).await;
+    let first = listener.accept().await.expect("first accept failed");
+    assert_eq!(listener.inflight(), 1);
+    println!("First connection accepted");
+
+    // The handshake completes on a backlog socket, but accept waits
+    let second_client = connect(&ctx, 49153).await;
+    assert!(listener.is_pending());
+    tokio::select! {
+        _ = listener.accept() => panic!("accept went past the in-flight cap"),
+        _ = ctx.reactor.sleep(Duration::from_millis(50)) => {}
+    }
+    assert_eq!(ctx.reactor.connections_accepted(), 1);
+    println!("Second connection held back at the cap");
+
+    let (second, _) = tokio::join!(listener.accept(), async {
+        ctx.reactor.sleep(Duration::from_millis(10)).await;
+        drop(first);
+    });
+    let second = second.expect("second accept failed");
+    assert_eq!(listener.inflight(), 1);
+    assert_eq!(ctx.reactor.connections_accepted(), 2);
+    println!("Dropping the first stream released the second");
+
+    second_client.send(b"hello").await.expect("send failed");
+    let mut buf = [0u8; 16];
+    let n = second.recv(&mut buf).await.expect("recv failed");
+    assert_eq!(&buf[..n], b"hello");
+
+    drop(second);
+    assert_eq!(listener.inflight(), 0);
+    drop(first_client);
+    drop(second_client);
+
+    println!("\n✓ Listener max in-flight test PASSED!");
+}
+
+#[test]
+#[serial]
+fn test_dpdk_app_listener_max_inflight() {
+    println!("\n=== DpdkApp Listener In-Flight Cap Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    DpdkApp::new()
+        .eth_dev(0)
+        .ip(SERVER_IP)
+        .gateway(GATEWAY_IP)
+        .mbufs_per_queue(1024)
+        .descriptors(128, 128)
+        .run(max_inflight_main);
+
+    println!("\n=== DpdkApp Listener In-Flight Cap Test Complete ===\n");
+}
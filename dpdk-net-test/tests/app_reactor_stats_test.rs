@@ -0,0 +1,91 @@
+//! DpdkApp Reactor Stats Test
+//!
+//! Validates `ReactorHandle::stats`. After a short exchange, and after
+//! dropping a stream without closing it (which leaves an orphaned socket
+//! for the reactor to reap), the counters must show packets processed,
+//! egress polls, idle passes and the cleaned-up orphan.
+//!
+//! Uses `net_ring0` for loopback: transmitted frames re-enter the RX path.
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::socket::{TcpListener, TcpStream};
+use dpdk_net_util::{DpdkApp, WorkerContext};
+
+use smoltcp::wire::{IpAddress, Ipv4Address};
+
+use serial_test::serial;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+const SERVER_PORT: u16 = 8080;
+
+async fn reactor_stats_main(ctx: WorkerContext) {
+    let start = ctx.reactor.stats();
+
+    let mut listener =
+        TcpListener::bind(&ctx.reactor, SERVER_PORT, 4096, 4096).expect("Failed to bind listener");
+    let client = TcpStream::connect(
+        &ctx.reactor,
+        IpAddress::Ipv4(SERVER_IP),
+        SERVER_PORT,
+        49152,
+        4096,
+        4096,
+    )
+    .expect("connect failed");
+    client.wait_connected().await.expect("not connected");
+    let server = listener.accept().await.expect("accept failed");
+
+    client.send(b"ping").await.expect("client send failed");
+    let mut buf = [0u8; 16];
+    let n = server.recv(&mut buf).await.expect("server recv failed");
+    assert_eq!(&buf[..n], b"ping");
+
+    // Dropped while established: reset, then reaped by the reactor
+    drop(server);
+    while ctx.reactor.stats().orphans_cleaned == start.orphans_cleaned {
+        tokio::task::yield_now().await;
+    }
+    drop(client);
+
+    // Give the reactor some passes with nothing to do
+    for _ in 0..100 {
+        tokio::task::yield_now().await;
+    }
+
+    let stats = ctx.reactor.stats();
+    println!("reactor stats: {stats:?}");
+    assert!(stats.packets_processed > start.packets_processed);
+    assert!(stats.egress_polls > start.egress_polls);
+    assert!(stats.idle_passes > start.idle_passes);
+    assert!(stats.orphans_cleaned > start.orphans_cleaned);
+    assert!(stats.passes >= stats.idle_passes);
+    assert_eq!(stats.passes, ctx.reactor.generation());
+
+    println!("\n✓ Reactor stats test PASSED!");
+}
+
+#[test]
+#[serial]
+fn test_dpdk_app_reactor_stats() {
+    println!("\n=== DpdkApp Reactor Stats Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    DpdkApp::new()
+        .eth_dev(0)
+        .ip(SERVER_IP)
+        .gateway(GATEWAY_IP)
+        .mbufs_per_queue(1024)
+        .descriptors(128, 128)
+        .run(reactor_stats_main);
+
+    println!("\n=== DpdkApp Reactor Stats Test Complete ===\n");
+}
@@ -0,0 +1,94 @@
+//! DpdkApp RX Burst Size Test
+//!
+//! Validates `DpdkApp::rx_burst_size`. With a burst size of 4, a flushed
+//! burst of 16 datagrams is received. Validates that:
+//! - no RX burst returns more frames than the configured size
+//! - frames of one burst reach smoltcp in arrival order, so the datagrams
+//!   are received in the order they were sent
+//!
+//! Uses `net_ring0` for loopback: transmitted frames re-enter the RX path.
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::socket::UdpSocket;
+use dpdk_net_util::{DpdkApp, WorkerContext};
+
+use smoltcp::wire::{IpAddress, Ipv4Address};
+
+use serial_test::serial;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+const SERVER_PORT: u16 = 7777;
+const CLIENT_PORT: u16 = 8888;
+
+const RX_BURST: usize = 4;
+const DATAGRAMS: usize = 16;
+
+async fn rx_burst_main(ctx: WorkerContext) {
+    let server = UdpSocket::bind(&ctx.reactor, SERVER_PORT, 32, 32, 1500)
+        .expect("Failed to bind server socket");
+    let client = UdpSocket::bind(&ctx.reactor, CLIENT_PORT, 32, 32, 1500)
+        .expect("Failed to bind client socket");
+    let server_ip = IpAddress::Ipv4(SERVER_IP);
+    let mut buf = [0u8; 1500];
+
+    // Warm up so the neighbor entry exists and the burst is all datagrams
+    client
+        .send_to(b"warm-up", server_ip, SERVER_PORT)
+        .await
+        .expect("send failed");
+    server.recv_from(&mut buf).await.expect("recv_from failed");
+
+    let before = ctx.reactor.device_stats();
+    for i in 0..DATAGRAMS {
+        client
+            .send_to(&[i as u8; 64], server_ip, SERVER_PORT)
+            .await
+            .expect("send failed");
+    }
+    client.flush().await;
+
+    for i in 0..DATAGRAMS {
+        let (len, _, _) = server.recv_from(&mut buf).await.expect("recv_from failed");
+        assert_eq!(&buf[..len], &[i as u8; 64], "datagram {i} out of order");
+    }
+    println!("{DATAGRAMS} datagrams received in order");
+
+    let after = ctx.reactor.device_stats();
+    let frames = after.rx_frames - before.rx_frames;
+    let bursts = after.rx_bursts - before.rx_bursts;
+    println!("{frames} frames in {bursts} bursts");
+    assert!(frames >= DATAGRAMS as u64);
+    assert!(
+        bursts * RX_BURST as u64 >= frames,
+        "a burst exceeded {RX_BURST} frames"
+    );
+
+    println!("\n✓ RX burst size test PASSED!");
+}
+
+#[test]
+#[serial]
+fn test_dpdk_app_rx_burst_size() {
+    println!("\n=== DpdkApp RX Burst Size Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    DpdkApp::new()
+        .eth_dev(0)
+        .ip(SERVER_IP)
+        .gateway(GATEWAY_IP)
+        .mbufs_per_queue(1024)
+        .descriptors(128, 128)
+        .rx_burst_size(RX_BURST)
+        .run(rx_burst_main);
+
+    println!("\n=== DpdkApp RX Burst Size Test Complete ===\n");
+}
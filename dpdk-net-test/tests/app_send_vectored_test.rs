@@ -0,0 +1,98 @@
+//! DpdkApp Vectored Send Test
+//!
+//! Validates `TcpStream::send_vectored`. The client sends a header slice, an
+//! empty slice and a body slice larger than its 1 KB transmit buffer, so
+//! writes stop and resume mid-slice. The server must receive the exact
+//! concatenation, and the future must report the total length.
+//!
+//! Uses `net_ring0` for loopback: transmitted frames re-enter the RX path.
+
+use std::io::IoSlice;
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::socket::{TcpListener, TcpStream};
+use dpdk_net_util::{DpdkApp, WorkerContext};
+
+use smoltcp::wire::{IpAddress, Ipv4Address};
+
+use serial_test::serial;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+const SERVER_PORT: u16 = 8080;
+
+const HEADER: &[u8] = b"HTTP/1.1 200 OK\r\ncontent-length: 5000\r\n\r\n";
+const BODY_LEN: usize = 5000;
+
+async fn send_vectored_main(ctx: WorkerContext) {
+    let mut listener =
+        TcpListener::bind(&ctx.reactor, SERVER_PORT, 8192, 8192).expect("Failed to bind listener");
+
+    let client = TcpStream::connect(
+        &ctx.reactor,
+        IpAddress::Ipv4(SERVER_IP),
+        SERVER_PORT,
+        49152,
+        1024,
+        1024,
+    )
+    .expect("connect failed");
+    client.wait_connected().await.expect("not connected");
+    let server = listener.accept().await.expect("accept failed");
+
+    let body: Vec<u8> = (0..BODY_LEN).map(|i| (i % 251) as u8).collect();
+    let mut expected = HEADER.to_vec();
+    expected.extend_from_slice(&body);
+    let total = expected.len();
+
+    let reader = tokio::task::spawn_local(async move {
+        let mut received = Vec::with_capacity(total);
+        let mut buf = [0u8; 2048];
+        while received.len() < total {
+            let n = server.recv(&mut buf).await.expect("server recv failed");
+            assert!(n > 0, "unexpected EOF after {} bytes", received.len());
+            received.extend_from_slice(&buf[..n]);
+        }
+        server.abort();
+        received
+    });
+
+    let bufs = [IoSlice::new(HEADER), IoSlice::new(&[]), IoSlice::new(&body)];
+    let sent = client.send_vectored(&bufs).await.expect("send failed");
+    assert_eq!(sent, total);
+    assert_eq!(client.stats().bytes_sent, total as u64);
+
+    let received = reader.await.expect("reader task failed");
+    assert_eq!(received, expected);
+
+    // Nothing to send completes at once
+    assert_eq!(client.send_vectored(&[]).await.expect("send failed"), 0);
+
+    client.abort();
+    println!("\n✓ Vectored send test PASSED!");
+}
+
+#[test]
+#[serial]
+fn test_dpdk_app_send_vectored() {
+    println!("\n=== DpdkApp Vectored Send Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    DpdkApp::new()
+        .eth_dev(0)
+        .ip(SERVER_IP)
+        .gateway(GATEWAY_IP)
+        .mbufs_per_queue(1024)
+        .descriptors(128, 128)
+        .run(send_vectored_main);
+
+    println!("\n=== DpdkApp Vectored Send Test Complete ===\n");
+}
@@ -0,0 +1,124 @@
+//! DpdkApp Stream Shutdown Test
+//!
+//! Validates `TcpStream::shutdown`:
+//! - after `shutdown(Write)` the client still reads the whole reply, while
+//!   the server sees EOF and further client sends fail
+//! - after `shutdown(Read)` reads return EOF even with data arriving
+//! - shutting down a stream mid-handshake fails with `NotConnected`
+//!
+//! Uses `net_ring0` for loopback: transmitted frames re-enter the RX path.
+
+use std::io::ErrorKind;
+use std::net::Shutdown;
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::socket::{TcpListener, TcpStream};
+use dpdk_net_util::{DpdkApp, WorkerContext};
+
+use smoltcp::wire::{IpAddress, Ipv4Address};
+
+use serial_test::serial;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+const SERVER_PORT: u16 = 8080;
+
+const REQUEST: &[u8] = b"request, then end of input";
+const REPLY_LEN: usize = 3000;
+
+/// Read until EOF.
+async fn read_to_end(stream: &TcpStream) -> Vec<u8> {
+    let mut data = Vec::new();
+    let mut buf = [0u8; 1024];
+    loop {
+        let n = stream.recv(&mut buf).await.expect("recv failed");
+        if n == 0 {
+            return data;
+        }
+        data.extend_from_slice(&buf[..n]);
+    }
+}
+
+async fn shutdown_main(ctx: WorkerContext) {
+    let mut listener =
+        TcpListener::bind(&ctx.reactor, SERVER_PORT, 4096, 4096).expect("Failed to bind listener");
+    let connect = |local_port| {
+        TcpStream::connect(
+            &ctx.reactor,
+            IpAddress::Ipv4(SERVER_IP),
+            SERVER_PORT,
+            local_port,
+            4096,
+            4096,
+        )
+        .expect("connect failed")
+    };
+
+    // Write shutdown: request, FIN, then the reply still comes back
+    let client = connect(49152);
+    let err = client.shutdown(Shutdown::Write).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotConnected);
+    client.wait_connected().await.expect("not connected");
+    let server = listener.accept().await.expect("accept failed");
+
+    client.send(REQUEST).await.expect("client send failed");
+    client.shutdown(Shutdown::Write).expect("shutdown failed");
+    let err = client.send(b"late").await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::BrokenPipe);
+
+    assert_eq!(read_to_end(&server).await, REQUEST);
+    server
+        .send(&[7u8; REPLY_LEN])
+        .await
+        .expect("server send failed");
+    server.close().await.expect("server close failed");
+    assert_eq!(read_to_end(&client).await, vec![7u8; REPLY_LEN]);
+    println!("client state after exchange: {:?}", client.state());
+    drop(client);
+    drop(server);
+
+    // Read shutdown: data sent afterwards is never delivered
+    let client = connect(49153);
+    client.wait_connected().await.expect("not connected");
+    let server = listener.accept().await.expect("accept failed");
+    server.shutdown(Shutdown::Read).expect("shutdown failed");
+    client.send(b"ignored").await.expect("client send failed");
+    let mut buf = [0u8; 64];
+    assert_eq!(server.recv(&mut buf).await.expect("recv failed"), 0);
+    // Writing still works
+    server
+        .send(b"still here")
+        .await
+        .expect("server send failed");
+    let n = client.recv(&mut buf).await.expect("client recv failed");
+    assert_eq!(&buf[..n], b"still here");
+
+    client.abort();
+    server.abort();
+    println!("\n✓ Stream shutdown test PASSED!");
+}
+
+#[test]
+#[serial]
+fn test_dpdk_app_shutdown() {
+    println!("\n=== DpdkApp Stream Shutdown Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    DpdkApp::new()
+        .eth_dev(0)
+        .ip(SERVER_IP)
+        .gateway(GATEWAY_IP)
+        .mbufs_per_queue(1024)
+        .descriptors(128, 128)
+        .run(shutdown_main);
+
+    println!("\n=== DpdkApp Stream Shutdown Test Complete ===\n");
+}
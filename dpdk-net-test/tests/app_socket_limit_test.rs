@@ -0,0 +1,122 @@
+//! DpdkApp Socket Limit Test
+//!
+//! Validates the reactor socket cap. With a cap set, `connect` and `bind`
+//! fail with `TooManySockets` instead of allocating, and `accept` resets
+//! the peer when it cannot refill the listener backlog, leaving the backlog
+//! short until the cap allows the socket again.
+//!
+//! Uses `net_ring0` for loopback: transmitted frames re-enter the RX path.
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::socket::{TcpConnectError, TcpListenError, TcpListener, TcpStream};
+use dpdk_net_util::{DpdkApp, WorkerContext};
+
+use smoltcp::socket::tcp::State;
+use smoltcp::wire::{IpAddress, Ipv4Address};
+
+use serial_test::serial;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+const SERVER_PORT: u16 = 8080;
+
+fn connect(ctx: &WorkerContext, local_port: u16) -> Result<TcpStream, TcpConnectError> {
+    TcpStream::connect(
+        &ctx.reactor,
+        IpAddress::Ipv4(SERVER_IP),
+        SERVER_PORT,
+        local_port,
+        4096,
+        4096,
+    )
+}
+
+async fn socket_limit_main(ctx: WorkerContext) {
+    let reactor = &ctx.reactor;
+    assert_eq!(reactor.max_sockets(), None);
+    reactor.set_max_sockets(Some(4));
+
+    let mut listener = TcpListener::bind_with_backlog(reactor, SERVER_PORT, 4096, 4096, 2)
+        .expect("Failed to bind listener");
+    assert_eq!(reactor.socket_count(), 2);
+
+    // Under the cap: connect and accept both allocate
+    let client1 = connect(&ctx, 49152).expect("connect under cap failed");
+    let server1 = listener.accept().await.expect("accept under cap failed");
+    client1
+        .wait_connected()
+        .await
+        .expect("client1 not connected");
+    assert_eq!(reactor.socket_count(), 4);
+
+    // At the cap: new sockets are refused
+    assert_eq!(
+        connect(&ctx, 49153).err(),
+        Some(TcpConnectError::TooManySockets)
+    );
+    assert_eq!(
+        TcpListener::bind(reactor, SERVER_PORT + 1, 4096, 4096).err(),
+        Some(TcpListenError::TooManySockets)
+    );
+    println!("connect/bind at cap refused");
+
+    // Room for one client but not for its backlog replacement
+    reactor.set_max_sockets(Some(5));
+    let client2 = connect(&ctx, 49154).expect("connect under raised cap failed");
+    let err = listener.accept().await.err();
+    assert_eq!(err, Some(TcpListenError::TooManySockets));
+    assert_eq!(listener.backlog(), 1);
+    assert_eq!(reactor.socket_count(), 5);
+
+    // The rejected peer is reset
+    for _ in 0..10_000 {
+        if client2.state() == State::Closed {
+            break;
+        }
+        tokio::task::yield_now().await;
+    }
+    assert_eq!(client2.state(), State::Closed, "client2 was not reset");
+    println!("accept at cap reset the peer");
+
+    // The reset socket is reaped, and the freed room refills the backlog
+    let pending = reactor
+        .timeout(std::time::Duration::from_millis(200), listener.accept())
+        .await;
+    assert!(pending.is_err(), "accept did not wait for a connection");
+    assert_eq!(listener.backlog(), 2);
+    assert!(reactor.socket_count() <= 5);
+
+    client1.abort();
+    server1.abort();
+    drop(client2);
+    drop(client1);
+    drop(server1);
+    drop(listener);
+
+    println!("\n✓ Socket limit test PASSED!");
+}
+
+#[test]
+#[serial]
+fn test_dpdk_app_socket_limit() {
+    println!("\n=== DpdkApp Socket Limit Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    DpdkApp::new()
+        .eth_dev(0)
+        .ip(SERVER_IP)
+        .gateway(GATEWAY_IP)
+        .mbufs_per_queue(1024)
+        .descriptors(128, 128)
+        .run(socket_limit_main);
+
+    println!("\n=== DpdkApp Socket Limit Test Complete ===\n");
+}
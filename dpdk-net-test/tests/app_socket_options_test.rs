@@ -0,0 +1,106 @@
+//! DpdkApp TCP Socket Options Test
+//!
+//! Validates `TcpSocketOptions` on both connection paths. Validates that:
+//! - `TcpListener::bind_with_options` applies the options to its backlog,
+//!   and accepted streams inherit keep-alive, timeout and Nagle settings
+//! - `TcpStream::connect_with_options` applies them to the client socket
+//! - streams so configured exchange data normally
+//! - `TcpStream::set_keep_alive`/`set_timeout` change them afterwards
+//!
+//! Uses `net_ring0` for loopback: transmitted frames re-enter the RX path.
+
+use std::time::Duration;
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::socket::{TcpListener, TcpSocketOptions, TcpStream};
+use dpdk_net_util::{DpdkApp, WorkerContext};
+
+use smoltcp::wire::{IpAddress, Ipv4Address};
+
+use serial_test::serial;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+const SERVER_PORT: u16 = 8080;
+
+async fn socket_options_main(ctx: WorkerContext) {
+    let server_options = TcpSocketOptions::new(8192, 4096)
+        .keep_alive(Some(Duration::from_secs(10)))
+        .timeout(Some(Duration::from_secs(30)))
+        .nagle(false);
+    let mut listener = TcpListener::bind_with_options(&ctx.reactor, SERVER_PORT, server_options)
+        .expect("Failed to bind listener");
+    assert_eq!(listener.options(), server_options);
+    assert_eq!(listener.buffer_sizes(), (8192, 4096));
+    assert!(listener.nodelay());
+
+    let client_options = TcpSocketOptions::new(4096, 4096)
+        .timeout(Some(Duration::from_secs(5)))
+        .ack_delay(None);
+    let client = TcpStream::connect_with_options(
+        &ctx.reactor,
+        IpAddress::Ipv4(SERVER_IP),
+        SERVER_PORT,
+        49152,
+        client_options,
+    )
+    .expect("connect failed");
+    let (connected, server) = tokio::join!(client.wait_connected(), listener.accept());
+    connected.expect("not connected");
+    let server = server.expect("accept failed");
+
+    assert_eq!(server.keep_alive(), Some(Duration::from_secs(10)));
+    assert_eq!(server.timeout(), Some(Duration::from_secs(30)));
+    assert!(server.nodelay());
+    assert_eq!(server.recv_buffer_size(), 8192);
+    println!("Accepted stream inherited the listener's options");
+
+    assert_eq!(client.keep_alive(), None);
+    assert_eq!(client.timeout(), Some(Duration::from_secs(5)));
+    assert!(!client.nodelay());
+    println!("Client stream got its own options");
+
+    client.send(b"ping").await.expect("send failed");
+    let mut buf = [0u8; 16];
+    let n = server.recv(&mut buf).await.expect("recv failed");
+    assert_eq!(&buf[..n], b"ping");
+    server.send(b"pong").await.expect("send failed");
+    let n = client.recv(&mut buf).await.expect("recv failed");
+    assert_eq!(&buf[..n], b"pong");
+    println!("Data exchanged");
+
+    client.set_keep_alive(Some(Duration::from_secs(1)));
+    client.set_timeout(None);
+    assert_eq!(client.keep_alive(), Some(Duration::from_secs(1)));
+    assert_eq!(client.timeout(), None);
+
+    client.close().await.ok();
+    server.close().await.ok();
+
+    println!("\n✓ Socket options test PASSED!");
+}
+
+#[test]
+#[serial]
+fn test_dpdk_app_socket_options() {
+    println!("\n=== DpdkApp TCP Socket Options Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    DpdkApp::new()
+        .eth_dev(0)
+        .ip(SERVER_IP)
+        .gateway(GATEWAY_IP)
+        .mbufs_per_queue(1024)
+        .descriptors(128, 128)
+        .run(socket_options_main);
+
+    println!("\n=== DpdkApp TCP Socket Options Test Complete ===\n");
+}
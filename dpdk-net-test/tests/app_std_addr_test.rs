@@ -0,0 +1,86 @@
+//! DpdkApp std SocketAddr Constructors Test
+//!
+//! Validates `TcpListener::bind_std` and `TcpStream::connect_std`: a listener
+//! bound from a parsed `0.0.0.0:port` accepts a client connected from a
+//! parsed `SocketAddrV4`, and both directions carry data. An IPv6 connect
+//! is unaddressable without an IPv6 address on the interface, while an IPv6
+//! bind only takes the port.
+//!
+//! Uses `net_ring0` for loopback: transmitted frames re-enter the RX path.
+
+use std::net::{SocketAddr, SocketAddrV4};
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::socket::{ConnectError, TcpConnectError, TcpListener, TcpStream};
+use dpdk_net_util::{DpdkApp, WorkerContext};
+
+use smoltcp::wire::Ipv4Address;
+
+use serial_test::serial;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+
+async fn std_addr_main(ctx: WorkerContext) {
+    let bind_addr: SocketAddr = "0.0.0.0:8080".parse().unwrap();
+    let mut listener =
+        TcpListener::bind_std(&ctx.reactor, bind_addr, 4096, 4096).expect("bind_std failed");
+    assert_eq!(listener.local_port(), 8080);
+
+    let remote = SocketAddrV4::new(SERVER_IP, 8080);
+    let client = TcpStream::connect_std(&ctx.reactor, remote.into(), 49152, 4096, 4096)
+        .expect("connect_std failed");
+    let (connected, server) = tokio::join!(client.wait_connected(), listener.accept());
+    connected.expect("not connected");
+    let server = server.expect("accept failed");
+
+    let mut buf = [0u8; 16];
+    client.send(b"ping").await.expect("client send failed");
+    let n = server.recv(&mut buf).await.expect("server recv failed");
+    assert_eq!(&buf[..n], b"ping");
+    server.send(b"pong").await.expect("server send failed");
+    let n = client.recv(&mut buf).await.expect("client recv failed");
+    assert_eq!(&buf[..n], b"pong");
+    println!("Exchange over std addresses OK");
+
+    let v6: SocketAddr = "[::1]:8080".parse().unwrap();
+    let err = TcpStream::connect_std(&ctx.reactor, v6, 49153, 4096, 4096)
+        .err()
+        .expect("IPv6 connect should fail");
+    assert_eq!(err, TcpConnectError::Connect(ConnectError::Unaddressable));
+    let any_v6: SocketAddr = "[::]:8081".parse().unwrap();
+    let v6_listener =
+        TcpListener::bind_std(&ctx.reactor, any_v6, 4096, 4096).expect("IPv6 bind failed");
+    assert_eq!(v6_listener.local_port(), 8081);
+    println!("IPv6 connect rejected, bind OK");
+
+    client.close().await.ok();
+    server.close().await.ok();
+
+    println!("\n✓ std SocketAddr constructors test PASSED!");
+}
+
+#[test]
+#[serial]
+fn test_dpdk_app_std_addr() {
+    println!("\n=== DpdkApp std SocketAddr Constructors Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    DpdkApp::new()
+        .eth_dev(0)
+        .ip(SERVER_IP)
+        .gateway(GATEWAY_IP)
+        .mbufs_per_queue(1024)
+        .descriptors(128, 128)
+        .run(std_addr_main);
+
+    println!("\n=== DpdkApp std SocketAddr Constructors Test Complete ===\n");
+}
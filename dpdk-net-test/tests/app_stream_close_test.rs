@@ -0,0 +1,138 @@
+//! DpdkApp Stream Close Semantics Test
+//!
+//! Validates the `TcpStreamError` contract for recv/send across closing
+//! states: writes issued before the handshake wait for it, a graceful close
+//! yields EOF on recv and `BrokenPipe` on send, and a reset yields
+//! `ConnectionReset` on both.
+//!
+//! Uses `net_ring0` for loopback: transmitted frames re-enter the RX path.
+
+use std::io;
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::socket::{TcpListener, TcpStream, TcpStreamError};
+use dpdk_net_util::{DpdkApp, WorkerContext};
+
+use smoltcp::socket::tcp::State;
+use smoltcp::wire::{IpAddress, Ipv4Address};
+
+use serial_test::serial;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+const SERVER_PORT: u16 = 8080;
+
+fn stream_error(err: &io::Error) -> Option<TcpStreamError> {
+    err.get_ref()?.downcast_ref::<TcpStreamError>().copied()
+}
+
+/// Connect without waiting, write immediately, and accept.
+async fn pair(
+    ctx: &WorkerContext,
+    listener: &mut TcpListener,
+    local_port: u16,
+) -> (TcpStream, TcpStream) {
+    let client = TcpStream::connect(
+        &ctx.reactor,
+        IpAddress::Ipv4(SERVER_IP),
+        SERVER_PORT,
+        local_port,
+        4096,
+        4096,
+    )
+    .expect("connect failed");
+
+    // The handshake has not completed yet; send must wait rather than fail
+    let (sent, server) = tokio::join!(client.send(b"hello"), listener.accept());
+    assert_eq!(sent.expect("send before handshake failed"), 5);
+    let server = server.expect("accept failed");
+
+    let mut buf = [0u8; 16];
+    let n = server.recv(&mut buf).await.expect("recv failed");
+    assert_eq!(&buf[..n], b"hello");
+    (client, server)
+}
+
+async fn graceful_close(ctx: &WorkerContext, listener: &mut TcpListener) {
+    let (client, server) = pair(ctx, listener, 49152).await;
+    let mut buf = [0u8; 16];
+
+    // Client closes first; server sees EOF but may still write (CloseWait)
+    let (closed, ()) = tokio::join!(client.close(), async {
+        assert_eq!(server.recv(&mut buf).await.expect("recv in CloseWait"), 0);
+        assert_eq!(server.state(), State::CloseWait);
+        server.send(b"bye").await.expect("send in CloseWait failed");
+        server.close().await.expect("server close failed");
+    });
+    closed.expect("client close failed");
+
+    // Active closer: buffered data, then EOF; writes are refused
+    let n = client.recv(&mut buf).await.expect("recv after close");
+    assert_eq!(&buf[..n], b"bye");
+    assert_eq!(client.recv(&mut buf).await.expect("EOF after close"), 0);
+    let err = client.send(b"x").await.expect_err("send after close");
+    assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+    assert_eq!(stream_error(&err), Some(TcpStreamError::Shutdown));
+
+    // Passive closer ends Closed after the FIN exchange: still EOF, not reset
+    assert_eq!(server.state(), State::Closed);
+    assert_eq!(server.recv(&mut buf).await.expect("EOF when closed"), 0);
+    let err = server.send(b"x").await.expect_err("send when closed");
+    assert_eq!(stream_error(&err), Some(TcpStreamError::Shutdown));
+    println!("Graceful close semantics OK");
+}
+
+async fn reset(ctx: &WorkerContext, listener: &mut TcpListener) {
+    let (client, server) = pair(ctx, listener, 49153).await;
+    let mut buf = [0u8; 16];
+
+    client.abort();
+
+    let err = server
+        .recv(&mut buf)
+        .await
+        .expect_err("recv after peer RST");
+    assert_eq!(err.kind(), io::ErrorKind::ConnectionReset);
+    assert_eq!(stream_error(&err), Some(TcpStreamError::ConnectionReset));
+    let err = server.send(b"x").await.expect_err("send after peer RST");
+    assert_eq!(stream_error(&err), Some(TcpStreamError::ConnectionReset));
+
+    let err = client.recv(&mut buf).await.expect_err("recv after abort");
+    assert_eq!(stream_error(&err), Some(TcpStreamError::ConnectionReset));
+    println!("Reset semantics OK");
+}
+
+async fn stream_close_main(ctx: WorkerContext) {
+    let mut listener =
+        TcpListener::bind(&ctx.reactor, SERVER_PORT, 4096, 4096).expect("Failed to bind listener");
+
+    graceful_close(&ctx, &mut listener).await;
+    reset(&ctx, &mut listener).await;
+
+    println!("\n✓ Stream close semantics test PASSED!");
+}
+
+#[test]
+#[serial]
+fn test_dpdk_app_stream_close() {
+    println!("\n=== DpdkApp Stream Close Semantics Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    DpdkApp::new()
+        .eth_dev(0)
+        .ip(SERVER_IP)
+        .gateway(GATEWAY_IP)
+        .mbufs_per_queue(1024)
+        .descriptors(128, 128)
+        .run(stream_close_main);
+
+    println!("\n=== DpdkApp Stream Close Semantics Test Complete ===\n");
+}
@@ -0,0 +1,95 @@
+//! DpdkApp Stream Peek Test
+//!
+//! Validates `TcpStream::peek`, the way a protocol sniffer uses it: the
+//! server peeks at the first bytes of a connection, twice, and then reads
+//! them again with `recv`. Peeking must not consume data or count toward
+//! `stats`, and must return `Ok(0)` once the peer has closed.
+//!
+//! Uses `net_ring0` for loopback: transmitted frames re-enter the RX path.
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::socket::{TcpListener, TcpStream};
+use dpdk_net_util::{DpdkApp, WorkerContext};
+
+use smoltcp::wire::{IpAddress, Ipv4Address};
+
+use serial_test::serial;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+const SERVER_PORT: u16 = 8080;
+
+const PREFACE: &[u8] = b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n";
+
+async fn stream_peek_main(ctx: WorkerContext) {
+    let mut listener =
+        TcpListener::bind(&ctx.reactor, SERVER_PORT, 4096, 4096).expect("Failed to bind listener");
+
+    let client = TcpStream::connect(
+        &ctx.reactor,
+        IpAddress::Ipv4(SERVER_IP),
+        SERVER_PORT,
+        49152,
+        4096,
+        4096,
+    )
+    .expect("connect failed");
+    client.wait_connected().await.expect("not connected");
+    let server = listener.accept().await.expect("accept failed");
+
+    // The peek waits for data to arrive
+    let peeker = tokio::task::spawn_local(async move {
+        let mut buf = [0u8; 3];
+        let n = server.peek(&mut buf).await.expect("peek failed");
+        assert_eq!(&buf[..n], &PREFACE[..n]);
+        server
+    });
+    client.send(PREFACE).await.expect("client send failed");
+    let server = peeker.await.expect("peek task failed");
+
+    // Wait for the whole preface, then peek at it again
+    while server.recv_queue_len() < PREFACE.len() {
+        tokio::task::yield_now().await;
+    }
+    let mut buf = [0u8; 64];
+    let n = server.peek(&mut buf).await.expect("peek failed");
+    assert_eq!(&buf[..n], PREFACE);
+    assert_eq!(server.recv_queue_len(), PREFACE.len());
+    assert_eq!(server.stats().bytes_received, 0);
+
+    // recv still sees every byte
+    let n = server.recv(&mut buf).await.expect("recv failed");
+    assert_eq!(&buf[..n], PREFACE);
+    assert_eq!(server.stats().bytes_received, PREFACE.len() as u64);
+
+    client.close().await.expect("client close failed");
+    assert_eq!(server.peek(&mut buf).await.expect("peek failed"), 0);
+
+    server.abort();
+    println!("\n✓ Stream peek test PASSED!");
+}
+
+#[test]
+#[serial]
+fn test_dpdk_app_stream_peek() {
+    println!("\n=== DpdkApp Stream Peek Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    DpdkApp::new()
+        .eth_dev(0)
+        .ip(SERVER_IP)
+        .gateway(GATEWAY_IP)
+        .mbufs_per_queue(1024)
+        .descriptors(128, 128)
+        .run(stream_peek_main);
+
+    println!("\n=== DpdkApp Stream Peek Test Complete ===\n");
+}
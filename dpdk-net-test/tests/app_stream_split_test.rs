@@ -0,0 +1,133 @@
+//! DpdkApp Stream Split Test
+//!
+//! Validates `TcpStream::into_split`. The server splits an accepted stream
+//! and moves the halves into separate tasks: one reads, the other echoes
+//! what it is handed over a channel. Then:
+//! - halves of one stream reunite into a working stream
+//! - halves of different streams refuse to reunite
+//!
+//! Uses `net_ring0` for loopback: transmitted frames re-enter the RX path.
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::socket::{TcpListener, TcpStream};
+use dpdk_net_util::{DpdkApp, WorkerContext};
+
+use smoltcp::socket::tcp::State;
+use smoltcp::wire::{IpAddress, Ipv4Address};
+use tokio::sync::mpsc;
+
+use serial_test::serial;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+const SERVER_PORT: u16 = 8080;
+const MESSAGES: [&[u8]; 3] = [b"one", b"two", b"three"];
+
+async fn recv_exact(stream: &TcpStream, len: usize) -> Vec<u8> {
+    let mut buf = vec![0u8; len];
+    let mut received = 0;
+    while received < len {
+        let n = stream
+            .recv(&mut buf[received..])
+            .await
+            .expect("recv failed");
+        assert!(n > 0, "unexpected EOF after {received} bytes");
+        received += n;
+    }
+    buf
+}
+
+async fn stream_split_main(ctx: WorkerContext) {
+    let mut listener =
+        TcpListener::bind(&ctx.reactor, SERVER_PORT, 4096, 4096).expect("Failed to bind listener");
+
+    let connect = |local_port| {
+        TcpStream::connect(
+            &ctx.reactor,
+            IpAddress::Ipv4(SERVER_IP),
+            SERVER_PORT,
+            local_port,
+            4096,
+            4096,
+        )
+        .expect("connect failed")
+    };
+    let client = connect(49152);
+    client.wait_connected().await.expect("not connected");
+    let server = listener.accept().await.expect("accept failed");
+
+    // Reader and writer halves in their own tasks, joined by a channel
+    let (read_half, write_half) = server.into_split();
+    let (tx, mut rx) = mpsc::unbounded_channel::<Vec<u8>>();
+    let reader = tokio::task::spawn_local(async move {
+        let mut buf = [0u8; 64];
+        for _ in MESSAGES {
+            let n = read_half.recv(&mut buf).await.expect("server recv failed");
+            tx.send(buf[..n].to_vec()).unwrap();
+        }
+        read_half
+    });
+    let writer = tokio::task::spawn_local(async move {
+        while let Some(msg) = rx.recv().await {
+            write_half.send(&msg).await.expect("server send failed");
+        }
+        write_half
+    });
+
+    for msg in MESSAGES {
+        client.send(msg).await.expect("client send failed");
+        assert_eq!(recv_exact(&client, msg.len()).await, msg);
+    }
+    let read_half = reader.await.expect("reader task failed");
+    let write_half = writer.await.expect("writer task failed");
+    assert_eq!(read_half.as_ref().state(), State::Established);
+
+    // Halves of different streams stay apart
+    let other_client = connect(49153);
+    other_client.wait_connected().await.expect("not connected");
+    let (other_read, other_write) = listener.accept().await.expect("accept failed").into_split();
+    let Err(err) = read_half.reunite(other_write) else {
+        panic!("reunited halves of different streams");
+    };
+    let (read_half, other_write) = (err.0, err.1);
+    other_read
+        .reunite(other_write)
+        .expect("reunite failed")
+        .abort();
+
+    // Reunited, the original stream still works
+    let server = write_half.reunite(read_half).expect("reunite failed");
+    client.send(b"again").await.expect("client send failed");
+    assert_eq!(recv_exact(&server, 5).await, b"again");
+    assert_eq!(server.stats().bytes_received, 16);
+
+    client.abort();
+    other_client.abort();
+    server.abort();
+    println!("\n✓ Stream split test PASSED!");
+}
+
+#[test]
+#[serial]
+fn test_dpdk_app_stream_split() {
+    println!("\n=== DpdkApp Stream Split Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    DpdkApp::new()
+        .eth_dev(0)
+        .ip(SERVER_IP)
+        .gateway(GATEWAY_IP)
+        .mbufs_per_queue(1024)
+        .descriptors(128, 128)
+        .run(stream_split_main);
+
+    println!("\n=== DpdkApp Stream Split Test Complete ===\n");
+}
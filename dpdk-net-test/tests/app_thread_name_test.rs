@@ -0,0 +1,78 @@
+//! DpdkApp Thread Name Test
+//!
+//! Validates worker thread naming. Validates that:
+//! - a `DpdkApp` worker thread is named `dpdk-q{queue_id}`
+//! - `ThreadRegistration::with_name` registers a plain std thread with
+//!   DPDK and names it
+//! - `set_thread_name` cuts names longer than the kernel keeps
+//!
+//! Uses `net_ring0` so the app has a port to run on.
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::api::rte::lcore::Lcore;
+use dpdk_net::api::rte::thread::{THREAD_NAME_MAX, ThreadRegistration, set_thread_name};
+use dpdk_net_util::{DpdkApp, WorkerContext};
+
+use smoltcp::wire::Ipv4Address;
+
+use serial_test::serial;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+
+/// The calling thread's name, as the kernel reports it.
+fn current_thread_name() -> String {
+    std::fs::read_to_string("/proc/thread-self/comm")
+        .expect("Failed to read thread name")
+        .trim_end()
+        .to_string()
+}
+
+async fn thread_name_main(ctx: WorkerContext) {
+    let name = current_thread_name();
+    println!("Queue {} runs on thread {name:?}", ctx.queue_id);
+    assert_eq!(name, format!("dpdk-q{}", ctx.queue_id));
+
+    let helper = std::thread::spawn(|| {
+        let _registration =
+            ThreadRegistration::with_name("dpdk-helper").expect("Failed to register thread");
+        assert!(Lcore::current().is_some(), "thread not registered");
+        let name = current_thread_name();
+        println!("Registered helper runs on thread {name:?}");
+        assert_eq!(name, "dpdk-helper");
+
+        set_thread_name("dpdk-helper-with-a-long-name").expect("Failed to rename thread");
+        let name = current_thread_name();
+        println!("Long name cut to {name:?}");
+        assert_eq!(name.len(), THREAD_NAME_MAX);
+        assert_eq!(name, "dpdk-helper-wit");
+    });
+    helper.join().expect("helper thread panicked");
+
+    println!("\n✓ Thread name test PASSED!");
+}
+
+#[test]
+#[serial]
+fn test_dpdk_app_thread_name() {
+    println!("\n=== DpdkApp Thread Name Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    DpdkApp::new()
+        .eth_dev(0)
+        .ip(SERVER_IP)
+        .gateway(GATEWAY_IP)
+        .mbufs_per_queue(1024)
+        .descriptors(128, 128)
+        .run(thread_name_main);
+
+    println!("\n=== DpdkApp Thread Name Test Complete ===\n");
+}
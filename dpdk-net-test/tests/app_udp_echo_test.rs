@@ -40,32 +40,32 @@ async fn udp_echo_main(ctx: WorkerContext) {
 
     // Client sends to server
     let sent = client
-        .send_to(message, server_endpoint)
+        .send_with_meta(message, server_endpoint)
         .await
-        .expect("Client: send_to failed");
+        .expect("Client: send_with_meta failed");
     println!("Client: sent {sent} bytes");
 
     // Server receives the datagram
     let mut buf = [0u8; 1500];
     let (len, meta) = server
-        .recv_from(&mut buf)
+        .recv_with_meta(&mut buf)
         .await
-        .expect("Server: recv_from failed");
+        .expect("Server: recv_with_meta failed");
     println!("Server: received {} bytes from {:?}", len, meta.endpoint);
     assert_eq!(&buf[..len], message, "Server: payload mismatch");
 
     // Server echoes back to the client's endpoint
     server
-        .send_to(&buf[..len], meta.endpoint)
+        .send_with_meta(&buf[..len], meta.endpoint)
         .await
-        .expect("Server: echo send_to failed");
+        .expect("Server: echo send_with_meta failed");
     println!("Server: echoed back");
 
     // Client receives the echo
     let (len, meta) = client
-        .recv_from(&mut buf)
+        .recv_with_meta(&mut buf)
         .await
-        .expect("Client: recv_from failed");
+        .expect("Client: recv_with_meta failed");
     println!("Client: received {} bytes from {:?}", len, meta.endpoint);
     assert_eq!(&buf[..len], message, "Client: echo payload mismatch");
     assert_eq!(meta.endpoint.port, SERVER_PORT);
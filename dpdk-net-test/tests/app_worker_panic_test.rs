@@ -0,0 +1,78 @@
+//! DpdkApp Worker Panic Test
+//!
+//! Validates that a panicking worker closure does not hang or abort the app.
+//! Two lcores run: the worker on queue 1 panics after binding a listener,
+//! queue 0 waits for every worker to be ready and then returns normally.
+//! `run` must join both, stop the device, and re-raise the panic, which the
+//! test catches.
+//!
+//! Uses `net_ring0` for loopback: transmitted frames re-enter the RX path.
+
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::socket::TcpListener;
+use dpdk_net_util::DpdkApp;
+
+use smoltcp::wire::Ipv4Address;
+
+use serial_test::serial;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+const SERVER_PORT: u16 = 8080;
+
+#[test]
+#[serial]
+fn test_dpdk_app_worker_panic() {
+    println!("\n=== DpdkApp Worker Panic Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0-1")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    let survivor_done = Arc::new(AtomicBool::new(false));
+    let done = survivor_done.clone();
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        DpdkApp::new()
+            .eth_dev(0)
+            .ip(SERVER_IP)
+            .gateway(GATEWAY_IP)
+            .mbufs_per_queue(1024)
+            .descriptors(128, 128)
+            .run(move |ctx| {
+                let done = done.clone();
+                async move {
+                    if ctx.queue_id == 1 {
+                        // Panic with a socket open and without marking ready
+                        let _listener = TcpListener::bind(&ctx.reactor, SERVER_PORT, 4096, 4096)
+                            .expect("Failed to bind listener");
+                        panic!("worker {} giving up", ctx.queue_id);
+                    }
+                    // Would hang if the panicking worker never counted as ready
+                    ctx.mark_ready();
+                    ctx.wait_ready().await;
+                    done.store(true, Ordering::SeqCst);
+                }
+            });
+    }));
+
+    let payload = result.expect_err("run returned although a worker panicked");
+    let message = payload
+        .downcast_ref::<String>()
+        .cloned()
+        .unwrap_or_default();
+    println!("run panicked with: {message}");
+    assert!(message.contains("[1]"), "unexpected panic: {message}");
+    assert!(survivor_done.load(Ordering::SeqCst));
+
+    println!("\n=== DpdkApp Worker Panic Test Complete ===\n");
+}
@@ -0,0 +1,174 @@
+//! DpdkApp Yield Budget Test
+//!
+//! One connection streams data as fast as the reactor can move it, with a
+//! receiver that loops on `recv` and never yields by itself. Alongside it,
+//! several connections run 1-byte ping-pongs. The yield budget must keep
+//! the ping-pongs moving while the bulk stream is saturated.
+//!
+//! Uses `net_ring0` for loopback: transmitted frames re-enter the RX path.
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::runtime::{DEFAULT_YIELD_BUDGET, ReactorHandle};
+use dpdk_net::socket::{TcpListener, TcpStream};
+use dpdk_net_util::{DpdkApp, WorkerContext};
+
+use smoltcp::wire::{IpAddress, Ipv4Address};
+
+use serial_test::serial;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+const BULK_PORT: u16 = 8080;
+const PING_PORT: u16 = 8081;
+
+const BULK_BUFFER: usize = 256 * 1024;
+const PINGERS: usize = 4;
+const PINGS: usize = 200;
+/// Generous bound; a starved ping would wait for the whole bulk transfer.
+const MAX_PING_RTT: Duration = Duration::from_millis(500);
+
+async fn connect(reactor: &ReactorHandle, port: u16, local_port: u16, buf: usize) -> TcpStream {
+    let stream = TcpStream::connect(
+        reactor,
+        IpAddress::Ipv4(SERVER_IP),
+        port,
+        local_port,
+        buf,
+        buf,
+    )
+    .expect("connect failed");
+    stream.wait_connected().await.expect("not connected");
+    stream
+}
+
+async fn yield_budget_main(ctx: WorkerContext) {
+    let reactor = ctx.reactor.clone();
+    assert_eq!(reactor.yield_budget(), Some(DEFAULT_YIELD_BUDGET));
+
+    let mut bulk_listener = TcpListener::bind(&reactor, BULK_PORT, BULK_BUFFER, BULK_BUFFER)
+        .expect("Failed to bind bulk listener");
+    let mut ping_listener =
+        TcpListener::bind_with_backlog(&reactor, PING_PORT, 1024, 1024, PINGERS)
+            .expect("Failed to bind ping listener");
+
+    let done = Rc::new(Cell::new(false));
+    let bulk_bytes = Rc::new(Cell::new(0usize));
+
+    // Saturating pair: the sender never stops and the receiver always has
+    // data waiting, so neither returns Pending on its own
+    let bulk_client = connect(&reactor, BULK_PORT, 49152, BULK_BUFFER).await;
+    let bulk_server = bulk_listener.accept().await.expect("bulk accept failed");
+    let sender = {
+        let done = done.clone();
+        tokio::task::spawn_local(async move {
+            let chunk = vec![0xABu8; 16 * 1024];
+            while !done.get() {
+                if bulk_client.send(&chunk).await.is_err() {
+                    break;
+                }
+            }
+            bulk_client.abort();
+        })
+    };
+    let receiver = {
+        let bulk_bytes = bulk_bytes.clone();
+        tokio::task::spawn_local(async move {
+            let mut buf = vec![0u8; 1024];
+            while let Ok(n) = bulk_server.recv(&mut buf).await {
+                if n == 0 {
+                    break;
+                }
+                bulk_bytes.set(bulk_bytes.get() + n);
+            }
+        })
+    };
+
+    // Echo side of the ping-pongs
+    let echo = tokio::task::spawn_local(async move {
+        let mut tasks = Vec::new();
+        for _ in 0..PINGERS {
+            let stream = ping_listener.accept().await.expect("ping accept failed");
+            tasks.push(tokio::task::spawn_local(async move {
+                let mut buf = [0u8; 1];
+                while let Ok(1) = stream.recv(&mut buf).await {
+                    if stream.send(&buf).await.is_err() {
+                        break;
+                    }
+                }
+            }));
+        }
+        for task in tasks {
+            task.await.ok();
+        }
+    });
+
+    let pingers: Vec<_> = (0..PINGERS)
+        .map(|i| {
+            let reactor = reactor.clone();
+            tokio::task::spawn_local(async move {
+                let stream = connect(&reactor, PING_PORT, 50000 + i as u16, 1024).await;
+                let mut worst = Duration::ZERO;
+                let mut buf = [0u8; 1];
+                for _ in 0..PINGS {
+                    let start = Instant::now();
+                    stream.send(&[i as u8]).await.expect("ping send failed");
+                    let n = stream.recv(&mut buf).await.expect("ping recv failed");
+                    assert_eq!(n, 1);
+                    assert_eq!(buf[0], i as u8);
+                    worst = worst.max(start.elapsed());
+                }
+                stream.close().await.ok();
+                worst
+            })
+        })
+        .collect();
+
+    let mut worst = Duration::ZERO;
+    for pinger in pingers {
+        worst = worst.max(pinger.await.expect("pinger task failed"));
+    }
+    let moved = bulk_bytes.get();
+    println!("Bulk stream moved {moved} bytes; worst ping RTT {worst:?}");
+
+    done.set(true);
+    sender.await.expect("sender task failed");
+    receiver.await.expect("receiver task failed");
+    echo.await.expect("echo task failed");
+
+    assert!(moved > 0, "bulk stream made no progress");
+    assert!(
+        worst < MAX_PING_RTT,
+        "ping RTT {worst:?} exceeded {MAX_PING_RTT:?} under load"
+    );
+
+    println!("\n✓ Yield budget test PASSED!");
+}
+
+#[test]
+#[serial]
+fn test_dpdk_app_yield_budget() {
+    println!("\n=== DpdkApp Yield Budget Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    DpdkApp::new()
+        .eth_dev(0)
+        .ip(SERVER_IP)
+        .gateway(GATEWAY_IP)
+        .mbufs_per_queue(8192)
+        .descriptors(1024, 1024)
+        .run(yield_budget_main);
+
+    println!("\n=== DpdkApp Yield Budget Test Complete ===\n");
+}
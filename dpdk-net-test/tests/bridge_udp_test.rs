@@ -26,8 +26,8 @@ async fn udp_echo_loop(socket: UdpSocket, cancel: CancellationToken) {
         tokio::select! {
             _ = cancel.cancelled() => break,
             result = socket.recv_from(&mut buf) => {
-                if let Ok((len, meta)) = result {
-                    let _ = socket.send_to(&buf[..len], meta.endpoint).await;
+                if let Ok((len, addr, port)) = result {
+                    let _ = socket.send_to(&buf[..len], addr, port).await;
                 }
             }
         }
@@ -0,0 +1,55 @@
+//! Ethernet Flow Rule Test
+//!
+//! Validates `FlowBuilder` against a started `net_ring0` port. The ring PMD
+//! implements no flow API, so validating and creating a dst-port rule must
+//! fail with an error instead of installing anything, and a rule matching a
+//! port without a protocol must be refused before the driver is asked.
+
+use dpdk_net::api::Errno;
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::api::rte::eth::{EthConf, EthDevBuilder, RxQueueConf, TxQueueConf};
+use dpdk_net::api::rte::flow::{FlowBuilder, FlowProto};
+use dpdk_net::api::rte::pktmbuf::{MemPool, MemPoolConfig};
+use dpdk_net_test::dpdk_test::DEFAULT_MBUF_DATA_ROOM_SIZE;
+
+use serial_test::serial;
+
+#[test]
+#[serial]
+fn test_eth_flow_rules() {
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    let mempool_config = MemPoolConfig::new()
+        .num_mbufs(1024)
+        .data_room_size(DEFAULT_MBUF_DATA_ROOM_SIZE as u16);
+    let mempool = MemPool::create("flow_pool", &mempool_config).expect("Failed to create mempool");
+
+    let eth_dev = EthDevBuilder::new(0)
+        .eth_conf(EthConf::new())
+        .rx_queue_conf(RxQueueConf::new().nb_desc(128))
+        .tx_queue_conf(TxQueueConf::new().nb_desc(128))
+        .build(&mempool)
+        .expect("Failed to configure eth device");
+
+    let rule = FlowBuilder::new(0)
+        .proto(FlowProto::Tcp)
+        .dst_port(8080)
+        .queue(0);
+    let err = rule.validate().expect_err("ring PMD has no flow support");
+    println!("validate: {err}");
+    let err = rule.create().expect_err("ring PMD has no flow support");
+    println!("create: {err}");
+
+    let no_proto = FlowBuilder::new(0).dst_port(8080);
+    assert_eq!(no_proto.validate(), Err(Errno::EINVAL));
+
+    eth_dev.stop().expect("Failed to stop device");
+    println!("\n✓ Flow rule test PASSED!");
+}
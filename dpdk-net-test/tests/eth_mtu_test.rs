@@ -0,0 +1,54 @@
+//! Ethernet Runtime MTU Test
+//!
+//! Validates `EthDev::set_mtu` and `EthDev::mtu` on a started `net_ring0`
+//! port, and `DpdkDevice::set_mtu`. The ring PMD has no MTU operation, so
+//! a change must come back as the driver's error with the MTU unchanged.
+//! The device side must report the new MTU to smoltcp, and refuse one whose
+//! frames would not fit its mbufs.
+
+use dpdk_net::api::Errno;
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net_test::eth_dev_config::EthDevConfig;
+
+use smoltcp::phy::Device;
+
+use serial_test::serial;
+
+#[test]
+#[serial]
+fn test_eth_runtime_mtu() {
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    let config = EthDevConfig::new()
+        .mempool_name("mtu_pool")
+        .num_mbufs(1024)
+        .nb_desc(128);
+    let (mempool, eth_dev) = config.clone().build().expect("Failed to build device");
+
+    assert_eq!(eth_dev.mtu(), Ok(1500));
+    let result = eth_dev.set_mtu(9000);
+    println!("set_mtu(9000) on net_ring: {result:?}");
+    match result {
+        Ok(()) => assert_eq!(eth_dev.mtu(), Ok(9000)),
+        Err(_) => assert_eq!(eth_dev.mtu(), Ok(1500)),
+    }
+    assert_eq!(eth_dev.set_mtu(70_000), Err(Errno::EINVAL));
+
+    let mut device = config.create_device(mempool, 0);
+    assert!(device.set_mtu(1400));
+    assert_eq!(device.capabilities().max_transmission_unit, 1400);
+    // 2048-byte mbufs cannot hold jumbo frames
+    assert!(!device.set_mtu(9000));
+    assert_eq!(device.mtu(), 1400);
+
+    drop(device);
+    eth_dev.stop().expect("Failed to stop device");
+    println!("\n✓ Runtime MTU test PASSED!");
+}
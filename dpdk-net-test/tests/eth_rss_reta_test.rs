@@ -0,0 +1,47 @@
+//! Ethernet RSS RETA Test
+//!
+//! Validates `EthDev::set_rss_reta` length checking on a started `net_ring0`
+//! port. A table whose length differs from the device's `reta_size` must be
+//! refused with `EINVAL` before reaching the driver, and
+//! `configure_rss_reta` must still succeed on a device without a RETA.
+
+use dpdk_net::api::Errno;
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net_test::eth_dev_config::EthDevConfig;
+
+use serial_test::serial;
+
+#[test]
+#[serial]
+fn test_eth_rss_reta_length() {
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    let config = EthDevConfig::new()
+        .mempool_name("reta_pool")
+        .num_mbufs(1024)
+        .nb_desc(128);
+    let (_mempool, eth_dev) = config.build().expect("Failed to build device");
+
+    let reta_size = eth_dev.info().expect("Failed to get device info").reta_size;
+    println!("net_ring reta_size: {reta_size}");
+
+    let too_long = vec![0u16; reta_size as usize + 1];
+    assert_eq!(eth_dev.set_rss_reta(&too_long), Err(Errno::EINVAL));
+    if reta_size > 0 {
+        let too_short = vec![0u16; reta_size as usize - 1];
+        assert_eq!(eth_dev.set_rss_reta(&too_short), Err(Errno::EINVAL));
+    }
+    eth_dev
+        .configure_rss_reta(1)
+        .expect("round-robin RETA failed");
+
+    eth_dev.stop().expect("Failed to stop device");
+    println!("\n✓ RSS RETA test PASSED!");
+}
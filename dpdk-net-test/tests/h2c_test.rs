@@ -0,0 +1,137 @@
+//! HTTP/2 Prior-Knowledge (h2c) Test
+//!
+//! Serves connections with `dpdk_net_util::h2c_serve_connection` and talks to
+//! them with `http2_connect`:
+//! - several requests multiplexed on one connection are echoed back
+//! - an HTTP/1.1 client is refused, since there is no upgrade or sniffing
+//!
+//! Uses `net_ring0` for loopback: transmitted frames re-enter the RX path.
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::socket::TcpListener;
+use dpdk_net_util::{DpdkApp, WorkerContext, h2c_serve_connection, http1_connect, http2_connect};
+
+use http_body_util::{BodyExt, Full};
+use hyper::body::{Bytes, Incoming};
+use hyper::service::service_fn;
+use hyper::{Request, Response, Version};
+
+use smoltcp::wire::{IpAddress, Ipv4Address};
+
+use serial_test::serial;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+const SERVER_PORT: u16 = 8080;
+const BUFFER: usize = 65536;
+const REQUESTS: usize = 4;
+
+async fn echo(req: Request<Incoming>) -> Result<Response<Full<Bytes>>, hyper::Error> {
+    assert_eq!(req.version(), Version::HTTP_2);
+    let body = req.into_body().collect().await?.to_bytes();
+    Ok(Response::new(Full::new(body)))
+}
+
+fn request(body: String) -> Request<Full<Bytes>> {
+    Request::builder()
+        .method("POST")
+        .uri(format!("http://{SERVER_IP}:{SERVER_PORT}/echo"))
+        .body(Full::new(Bytes::from(body)))
+        .expect("request build failed")
+}
+
+async fn h2c_main(ctx: WorkerContext) {
+    let reactor = ctx.reactor.clone();
+    let mut listener =
+        TcpListener::bind(&reactor, SERVER_PORT, BUFFER, BUFFER).expect("Failed to bind listener");
+
+    // One h2c connection per accepted stream; the second one is expected to fail
+    let server = tokio::task::spawn_local(async move {
+        let mut results = Vec::new();
+        for _ in 0..2 {
+            let stream = listener.accept().await.expect("accept failed");
+            results.push(h2c_serve_connection(stream, service_fn(echo)).await);
+        }
+        results
+    });
+
+    // Multiplexed requests over one prior-knowledge connection
+    let mut conn = http2_connect(
+        &reactor,
+        IpAddress::Ipv4(SERVER_IP),
+        SERVER_PORT,
+        49152,
+        BUFFER,
+        BUFFER,
+    )
+    .await
+    .expect("http2_connect failed");
+    let pending: Vec<_> = (0..REQUESTS)
+        .map(|i| {
+            let body = format!("h2c request {i}");
+            (body.clone(), conn.send_request(request(body)))
+        })
+        .collect();
+    for (expected, response) in pending {
+        let response = response.await.expect("request failed");
+        assert_eq!(response.version(), Version::HTTP_2);
+        let body = response
+            .into_body()
+            .collect()
+            .await
+            .expect("body read failed")
+            .to_bytes();
+        assert_eq!(body, expected.as_bytes());
+    }
+    drop(conn);
+    println!("{REQUESTS} multiplexed h2c requests echoed");
+
+    // HTTP/1.1 gets no upgrade path on a prior-knowledge server
+    let mut conn = http1_connect(
+        &reactor,
+        IpAddress::Ipv4(SERVER_IP),
+        SERVER_PORT,
+        49153,
+        BUFFER,
+        BUFFER,
+    )
+    .await
+    .expect("http1_connect failed");
+    let result = conn.send_request(request("hello".into())).await;
+    assert!(result.is_err(), "HTTP/1.1 request unexpectedly succeeded");
+    drop(conn);
+
+    let results = server.await.expect("server task failed");
+    // The first connection ends however the client went away; the second
+    // must be rejected for lacking the HTTP/2 preface
+    println!("h2c connection ended: {:?}", results[0]);
+    assert!(results[1].is_err(), "HTTP/1.1 connection was served");
+    println!("HTTP/1.1 client refused");
+
+    println!("\n✓ h2c test PASSED!");
+}
+
+#[test]
+#[serial]
+fn test_h2c_prior_knowledge() {
+    println!("\n=== HTTP/2 Prior-Knowledge (h2c) Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    DpdkApp::new()
+        .eth_dev(0)
+        .ip(SERVER_IP)
+        .gateway(GATEWAY_IP)
+        .mbufs_per_queue(2048)
+        .descriptors(256, 256)
+        .run(h2c_main);
+
+    println!("\n=== HTTP/2 Prior-Knowledge (h2c) Test Complete ===\n");
+}
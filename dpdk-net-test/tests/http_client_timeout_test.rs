@@ -0,0 +1,98 @@
+//! HTTP Client Timeout Test
+//!
+//! Validates `ClientConfig::connect_timeout` and `request_timeout`:
+//! - connecting to an address that never answers ARP fails with
+//!   `Error::Timeout` instead of hanging
+//! - a server that accepts the connection but never responds fails the
+//!   request with `Error::Timeout`
+//!
+//! Uses `net_ring0` for loopback: transmitted frames re-enter the RX path.
+
+use std::time::{Duration, Instant};
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::socket::TcpListener;
+use dpdk_net_util::{
+    ClientConfig, DpdkApp, DpdkHttpClient, DpdkRequestBuilder, Error, WorkerContext,
+};
+
+use smoltcp::wire::{IpAddress, Ipv4Address};
+
+use serial_test::serial;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+/// On-link address nobody answers for.
+const UNREACHABLE_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 2);
+const SERVER_PORT: u16 = 8080;
+
+const TIMEOUT: Duration = Duration::from_millis(200);
+
+async fn timeout_main(ctx: WorkerContext) {
+    let config = ClientConfig::default()
+        .connect_timeout(TIMEOUT)
+        .request_timeout(TIMEOUT);
+    let client = DpdkHttpClient::with_config(ctx.reactor.clone(), config);
+
+    let start = Instant::now();
+    let err = client
+        .connect(IpAddress::Ipv4(UNREACHABLE_IP), SERVER_PORT, 49152)
+        .await
+        .err()
+        .expect("connect to unreachable address succeeded");
+    assert!(matches!(err, Error::Timeout), "got {err:?}");
+    assert!(start.elapsed() >= TIMEOUT);
+    println!("Connect timed out after {:?}", start.elapsed());
+
+    // Accepts connections but never reads or answers
+    let mut listener =
+        TcpListener::bind(&ctx.reactor, SERVER_PORT, 4096, 4096).expect("Failed to bind listener");
+    let server_task = tokio::task::spawn_local(async move {
+        let stream = listener.accept().await.expect("accept failed");
+        std::future::pending::<()>().await;
+        drop(stream);
+    });
+
+    let addr = IpAddress::Ipv4(SERVER_IP);
+    let mut conn = client
+        .connect(addr, SERVER_PORT, 49153)
+        .await
+        .expect("connect failed");
+    let request = DpdkRequestBuilder::get(addr, SERVER_PORT, "/")
+        .empty()
+        .expect("request build failed");
+    let start = Instant::now();
+    let result = conn.send_request(request).await;
+    assert!(matches!(result, Err(Error::Timeout)), "got {result:?}");
+    assert!(start.elapsed() >= TIMEOUT);
+    println!("Request timed out after {:?}", start.elapsed());
+
+    server_task.abort();
+
+    println!("\n✓ HTTP client timeout test PASSED!");
+}
+
+#[test]
+#[serial]
+fn test_http_client_timeouts() {
+    println!("\n=== HTTP Client Timeout Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    DpdkApp::new()
+        .eth_dev(0)
+        .ip(SERVER_IP)
+        .gateway(GATEWAY_IP)
+        .mbufs_per_queue(1024)
+        .descriptors(128, 128)
+        .run(timeout_main);
+
+    println!("\n=== HTTP Client Timeout Test Complete ===\n");
+}
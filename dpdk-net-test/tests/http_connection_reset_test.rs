@@ -0,0 +1,83 @@
+//! HTTP Connection Reset Test
+//!
+//! Validates that a peer RST in the middle of a request can be told apart
+//! from other client errors. A server reads the request and aborts the
+//! connection instead of answering. Validates that:
+//! - the request fails with an `Error::Request` from hyper
+//! - `Error::is_connection_reset` sees the reset through hyper's error
+//! - `Error::stream_error` recovers the stream's `TcpStreamError`
+//!
+//! Uses `net_ring0` for loopback: transmitted frames re-enter the RX path.
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::socket::{TcpListener, TcpStreamError};
+use dpdk_net_util::{DpdkApp, DpdkHttpClient, DpdkRequestBuilder, Error, WorkerContext};
+
+use smoltcp::wire::{IpAddress, Ipv4Address};
+
+use serial_test::serial;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+const SERVER_PORT: u16 = 8080;
+
+async fn reset_main(ctx: WorkerContext) {
+    let mut listener =
+        TcpListener::bind(&ctx.reactor, SERVER_PORT, 4096, 4096).expect("Failed to bind listener");
+    let server_task = tokio::task::spawn_local(async move {
+        let stream = listener.accept().await.expect("accept failed");
+        let mut buf = [0u8; 1024];
+        let n = stream.recv(&mut buf).await.expect("recv failed");
+        assert!(buf[..n].starts_with(b"GET / HTTP/1.1"));
+        println!("Server read the request, resetting");
+        stream.abort();
+    });
+
+    let client = DpdkHttpClient::new(ctx.reactor.clone());
+    let addr = IpAddress::Ipv4(SERVER_IP);
+    let mut conn = client
+        .connect(addr, SERVER_PORT, 49152)
+        .await
+        .expect("connect failed");
+    let request = DpdkRequestBuilder::get(addr, SERVER_PORT, "/")
+        .empty()
+        .expect("request build failed");
+    let err = conn
+        .send_request(request)
+        .await
+        .err()
+        .expect("request succeeded despite the reset");
+    println!("Request failed: {err}");
+
+    assert!(matches!(err, Error::Request(_)), "got {err:?}");
+    assert!(err.is_connection_reset(), "reset not recognized: {err:?}");
+    assert_eq!(err.stream_error(), Some(TcpStreamError::ConnectionReset));
+    server_task.await.expect("server task failed");
+
+    println!("\n✓ HTTP connection reset test PASSED!");
+}
+
+#[test]
+#[serial]
+fn test_http_connection_reset() {
+    println!("\n=== HTTP Connection Reset Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    DpdkApp::new()
+        .eth_dev(0)
+        .ip(SERVER_IP)
+        .gateway(GATEWAY_IP)
+        .mbufs_per_queue(1024)
+        .descriptors(128, 128)
+        .run(reset_main);
+
+    println!("\n=== HTTP Connection Reset Test Complete ===\n");
+}
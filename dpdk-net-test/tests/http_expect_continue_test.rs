@@ -0,0 +1,102 @@
+//! HTTP/1.1 Expect: 100-continue Test
+//!
+//! Plays a large-upload client against `Http1Server`: it sends the request
+//! head with `Expect: 100-continue`, waits for the `100 Continue` interim
+//! response, and only then sends the body, which is echoed back.
+//!
+//! Uses `net_ring0` for loopback: transmitted frames re-enter the RX path.
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::socket::{TcpListener, TcpStream};
+use dpdk_net_util::bench::http::{Http1Server, echo_service};
+use dpdk_net_util::{DpdkApp, WorkerContext};
+
+use smoltcp::wire::{IpAddress, Ipv4Address};
+
+use serial_test::serial;
+use tokio_util::sync::CancellationToken;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+const SERVER_PORT: u16 = 8080;
+
+const HEAD: &[u8] = b"POST /upload HTTP/1.1\r\nhost: 192.168.1.1:8080\r\n\
+    expect: 100-continue\r\ncontent-length: 11\r\n\r\n";
+const BODY: &[u8] = b"hello world";
+
+/// Read until `response` contains `needle`.
+async fn recv_until(stream: &TcpStream, response: &mut Vec<u8>, needle: &[u8]) {
+    let mut buf = [0u8; 1024];
+    while !response.windows(needle.len()).any(|w| w == needle) {
+        let n = stream.recv(&mut buf).await.expect("recv failed");
+        assert!(n > 0, "connection closed while waiting for {needle:?}");
+        response.extend_from_slice(&buf[..n]);
+    }
+}
+
+async fn expect_continue_main(ctx: WorkerContext) {
+    let reactor = ctx.reactor.clone();
+    let listener = TcpListener::bind_with_backlog(&reactor, SERVER_PORT, 4096, 4096, 4)
+        .expect("Failed to bind listener");
+
+    let cancel = CancellationToken::new();
+    let server = Http1Server::new(listener, cancel.clone(), echo_service, 0, SERVER_PORT);
+    let server_task = tokio::task::spawn_local(server.run());
+
+    let stream = TcpStream::connect(
+        &reactor,
+        IpAddress::Ipv4(SERVER_IP),
+        SERVER_PORT,
+        49152,
+        4096,
+        4096,
+    )
+    .expect("connect failed");
+    stream.wait_connected().await.expect("not connected");
+
+    stream.send(HEAD).await.expect("send head failed");
+    let mut response = Vec::new();
+    recv_until(&stream, &mut response, b"\r\n\r\n").await;
+    assert!(
+        response.starts_with(b"HTTP/1.1 100 Continue\r\n"),
+        "expected 100 Continue, got {:?}",
+        String::from_utf8_lossy(&response)
+    );
+    println!("Got 100 Continue");
+
+    stream.send(BODY).await.expect("send body failed");
+    recv_until(&stream, &mut response, BODY).await;
+    let text = String::from_utf8_lossy(&response);
+    assert!(text.contains("\r\n\r\nHTTP/1.1 200 OK\r\n"), "{text}");
+
+    stream.close().await.ok();
+    cancel.cancel();
+    server_task.await.expect("server task failed");
+
+    println!("\n✓ HTTP/1.1 Expect: 100-continue test PASSED!");
+}
+
+#[test]
+#[serial]
+fn test_http1_expect_continue() {
+    println!("\n=== HTTP/1.1 Expect: 100-continue Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    DpdkApp::new()
+        .eth_dev(0)
+        .ip(SERVER_IP)
+        .gateway(GATEWAY_IP)
+        .mbufs_per_queue(1024)
+        .descriptors(128, 128)
+        .run(expect_continue_main);
+
+    println!("\n=== HTTP/1.1 Expect: 100-continue Test Complete ===\n");
+}
@@ -0,0 +1,110 @@
+//! HTTP Connection Pool Reuse and Idle Eviction Test
+//!
+//! Runs `Http1Server` and checks `ConnectionPool::get_or_connect`:
+//! - a connection dropped after a request goes back to the pool and is
+//!   handed out again for the same host:port
+//! - two connections checked out at once are distinct, and both are pooled
+//! - the reaper closes connections idle longer than `idle_timeout`
+//!
+//! Uses `net_ring0` for loopback: transmitted frames re-enter the RX path.
+
+use std::time::Duration;
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::socket::TcpListener;
+use dpdk_net_util::bench::http::{Http1Server, echo_service};
+use dpdk_net_util::{ConnectionPool, DpdkApp, DpdkRequestBuilder, WorkerContext};
+
+use http_body_util::BodyExt;
+
+use smoltcp::wire::{IpAddress, Ipv4Address};
+
+use serial_test::serial;
+use tokio_util::sync::CancellationToken;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+const SERVER_PORT: u16 = 8080;
+
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_millis(200);
+
+async fn pool_main(ctx: WorkerContext) {
+    let reactor = ctx.reactor.clone();
+    let listener = TcpListener::bind_with_backlog(&reactor, SERVER_PORT, 16384, 16384, 4)
+        .expect("Failed to bind listener");
+
+    let cancel = CancellationToken::new();
+    let server = Http1Server::new(listener, cancel.clone(), echo_service, 0, SERVER_PORT);
+    let server_task = tokio::task::spawn_local(server.run());
+
+    let addr = IpAddress::Ipv4(SERVER_IP);
+    let pool = ConnectionPool::new(reactor.clone()).idle_timeout(POOL_IDLE_TIMEOUT);
+
+    let mut conn = pool
+        .get_or_connect(addr, SERVER_PORT)
+        .await
+        .expect("pool connect failed");
+    let request = DpdkRequestBuilder::get(addr, SERVER_PORT, "/")
+        .empty()
+        .expect("request build failed");
+    conn.send_request(request)
+        .await
+        .expect("request failed")
+        .into_body()
+        .collect()
+        .await
+        .expect("body read failed");
+    drop(conn);
+    assert_eq!(pool.idle_count(addr, SERVER_PORT), 1);
+    // Let hyper's dispatcher mark the connection ready for the next request
+    reactor.sleep(Duration::from_millis(10)).await;
+
+    // Reused: checking it out empties the pool again
+    let first = pool
+        .get_or_connect(addr, SERVER_PORT)
+        .await
+        .expect("pool reuse failed");
+    assert_eq!(pool.idle_count(addr, SERVER_PORT), 0);
+    let second = pool
+        .get_or_connect(addr, SERVER_PORT)
+        .await
+        .expect("second connect failed");
+    drop(first);
+    drop(second);
+    assert_eq!(pool.idle_count(addr, SERVER_PORT), 2);
+    println!("Pooled 2 idle connections");
+
+    reactor.sleep(POOL_IDLE_TIMEOUT * 3).await;
+    assert_eq!(pool.idle_count(addr, SERVER_PORT), 0);
+    println!("Reaper closed idle connections");
+
+    cancel.cancel();
+    server_task.await.expect("server task failed");
+
+    println!("\n✓ Connection pool idle test PASSED!");
+}
+
+#[test]
+#[serial]
+fn test_http_pool_idle_eviction() {
+    println!("\n=== HTTP Connection Pool Idle Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    DpdkApp::new()
+        .eth_dev(0)
+        .ip(SERVER_IP)
+        .gateway(GATEWAY_IP)
+        .mbufs_per_queue(1024)
+        .descriptors(128, 128)
+        .run(pool_main);
+
+    println!("\n=== HTTP Connection Pool Idle Test Complete ===\n");
+}
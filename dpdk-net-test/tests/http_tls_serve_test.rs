@@ -0,0 +1,169 @@
+//! HTTPS serve_worker Test
+//!
+//! Validates `DpdkApp::tls`. `serve_worker` terminates TLS with a
+//! self-signed certificate for the server IP, and HTTPS clients on the same
+//! worker check that:
+//! - the worker context reports TLS as active
+//! - the default ALPN list lets an HTTP/2 client negotiate `h2` and an
+//!   HTTP/1.1 client negotiate `http/1.1`, both reaching the handler
+//! - a cleartext client gets no response
+//!
+//! Uses `net_ring0` for loopback: transmitted frames re-enter the RX path.
+
+use std::time::Duration;
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net_util::serve::{ServeConfig, serve_worker};
+use dpdk_net_util::{
+    ClientConfig, DpdkApp, DpdkHttpClient, DpdkRequestBuilder, HttpVersion, WorkerContext,
+    http1_connect,
+};
+
+use http_body_util::{BodyExt, Full};
+use hyper::body::{Bytes, Incoming};
+use hyper::{Request, Response};
+use rcgen::{CertifiedKey, generate_simple_self_signed};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use smoltcp::wire::{IpAddress, Ipv4Address};
+
+use serial_test::serial;
+use tokio_util::sync::CancellationToken;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+const SERVER_PORT: u16 = 8443;
+
+/// Answers with the HTTP version the request came in on.
+async fn handler(req: Request<Incoming>) -> Result<Response<Full<Bytes>>, hyper::Error> {
+    let version = format!("{:?}", req.version());
+    Ok(Response::new(Full::new(Bytes::from(version))))
+}
+
+/// Connect over TLS preferring `version`, and return the negotiated version
+/// and the handler's answer.
+async fn https_get(
+    ctx: &WorkerContext,
+    client_tls: rustls::ClientConfig,
+    version: HttpVersion,
+    local_port: u16,
+) -> (HttpVersion, Bytes) {
+    let addr = IpAddress::Ipv4(SERVER_IP);
+    let mut config = ClientConfig::default().tls(client_tls);
+    config.http_version = version;
+    let client = DpdkHttpClient::with_config(ctx.reactor.clone(), config);
+    let mut conn = client
+        .connect(addr, SERVER_PORT, local_port)
+        .await
+        .expect("TLS connect failed");
+    assert!(conn.is_tls());
+
+    let request = DpdkRequestBuilder::get(addr, SERVER_PORT, "/")
+        .version(conn.version())
+        .secure(true)
+        .empty()
+        .expect("request build failed");
+    let response = conn.send_request(request).await.expect("request failed");
+    assert_eq!(response.status(), 200);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("body read failed")
+        .to_bytes();
+    (conn.version(), body)
+}
+
+async fn serve_main(ctx: WorkerContext, cert_der: CertificateDer<'static>) {
+    assert!(ctx.is_tls());
+
+    let shutdown = CancellationToken::new();
+    let config = ServeConfig::new(SERVER_PORT)
+        .backlog(4)
+        .shutdown(shutdown.clone())
+        .grace_period(Duration::from_secs(2));
+
+    let mut roots = rustls::RootCertStore::empty();
+    roots.add(cert_der).unwrap();
+    let client_tls = rustls::ClientConfig::builder()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+
+    let client = async {
+        let (version, body) = https_get(&ctx, client_tls.clone(), HttpVersion::Http2, 49152).await;
+        assert_eq!(version, HttpVersion::Http2);
+        assert_eq!(&body[..], b"HTTP/2.0");
+        println!("Client: HTTP/2 over TLS served");
+
+        let (version, body) = https_get(&ctx, client_tls, HttpVersion::Http1, 49153).await;
+        assert_eq!(version, HttpVersion::Http1);
+        assert_eq!(&body[..], b"HTTP/1.1");
+        println!("Client: HTTP/1.1 over TLS served");
+
+        // A cleartext request is not a ClientHello, so the handshake fails
+        let mut plain = http1_connect(
+            &ctx.reactor,
+            IpAddress::Ipv4(SERVER_IP),
+            SERVER_PORT,
+            49154,
+            16384,
+            16384,
+        )
+        .await
+        .expect("TCP connect failed");
+        let request = DpdkRequestBuilder::get(IpAddress::Ipv4(SERVER_IP), SERVER_PORT, "/")
+            .empty()
+            .expect("request build failed");
+        tokio::select! {
+            result = plain.send_request(request) => {
+                assert!(result.is_err(), "cleartext request got a response");
+            }
+            _ = ctx.reactor.sleep(Duration::from_secs(2)) => panic!("cleartext request hung"),
+        }
+        println!("Client: cleartext request rejected");
+
+        ctx.reactor.sleep(Duration::from_millis(20)).await;
+        shutdown.cancel();
+    };
+
+    tokio::join!(serve_worker(&ctx, &config, handler), client);
+    println!("Server drained and stopped");
+
+    assert_eq!(ctx.reactor.connections_accepted(), 3);
+    println!("\n✓ HTTPS serve test PASSED!");
+}
+
+#[test]
+#[serial]
+fn test_http_tls_serve_worker() {
+    println!("\n=== HTTPS serve_worker Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    let CertifiedKey { cert, signing_key } =
+        generate_simple_self_signed(vec![SERVER_IP.to_string()]).unwrap();
+    let cert_der = cert.der().clone();
+    let key_der = PrivateKeyDer::Pkcs8(signing_key.serialize_der().into());
+    // No ALPN here: the app offers h2 and http/1.1 itself
+    let server_tls = rustls::ServerConfig::builder()
+        .with_no_client_auth()
+        .with_single_cert(vec![cert_der.clone()], key_der)
+        .expect("server config");
+
+    DpdkApp::new()
+        .eth_dev(0)
+        .ip(SERVER_IP)
+        .gateway(GATEWAY_IP)
+        .mbufs_per_queue(1024)
+        .descriptors(128, 128)
+        .tls(server_tls)
+        .run(move |ctx| serve_main(ctx, cert_der.clone()));
+
+    println!("\n=== HTTPS serve_worker Test Complete ===\n");
+}
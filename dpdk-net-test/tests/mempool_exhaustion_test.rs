@@ -0,0 +1,73 @@
+//! Mempool Exhaustion Test
+//!
+//! Drains the device's mempool, then has smoltcp transmit. Validates that:
+//! - `MemPool::in_use_count` and `ReactorHandle::mbufs_in_use` track the
+//!   mbufs held outside the pool
+//! - a frame with no mbuf to carry it is counted in
+//!   `DeviceStats::tx_alloc_failures` instead of vanishing silently
+//! - `DeviceStats::mbufs_in_use_peak` records the exhaustion
+//!
+//! Note: This is a separate test file because DPDK has global state that persists
+//! across tests within the same process.
+
+use dpdk_net::runtime::{Reactor, ReactorConfig};
+use dpdk_net::socket::UdpSocket;
+use dpdk_net_test::dpdk_test::create_test_context;
+
+use smoltcp::time::Instant;
+use smoltcp::wire::{EthernetAddress, IpAddress, IpCidr, Ipv4Address};
+use tokio::runtime::Builder;
+use tokio::task::LocalSet;
+
+const LOCAL_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const PEER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 2);
+
+#[test]
+fn test_mempool_exhaustion() {
+    println!("\n=== Mempool Exhaustion Test ===\n");
+
+    let (ctx, device) = create_test_context().expect("Failed to create DPDK test context");
+    let mac = ctx.eth_dev().mac_addr().expect("Failed to get MAC address");
+    let pool = device.mempool().clone();
+
+    let config = ReactorConfig::new(EthernetAddress(mac.addr_bytes))
+        .ip_addr(IpCidr::new(IpAddress::Ipv4(LOCAL_IP), 24));
+    // The reactor is never run; poll_once drives it
+    let reactor = Reactor::new_with_config(device, config).expect("Failed to create reactor");
+    let handle = reactor.handle();
+
+    let rt = Builder::new_current_thread().build().unwrap();
+    let local = LocalSet::new();
+    local.block_on(&rt, async {
+        let socket = UdpSocket::bind(&handle, 9000, 4, 4, 1500).expect("Failed to bind socket");
+        let baseline = pool.in_use_count();
+
+        let held: Vec<_> = std::iter::from_fn(|| pool.try_alloc()).collect();
+        let exhausted = baseline + held.len() as u32;
+        assert_eq!(handle.mbufs_in_use(), exhausted);
+        assert_eq!(handle.mbufs_available(), 0);
+        println!("Holding {} mbufs, pool exhausted", held.len());
+
+        // Whatever goes out first (the datagram or an ARP request for the
+        // peer) finds no mbuf
+        socket
+            .send_to(b"no room", IpAddress::Ipv4(PEER_IP), 9000)
+            .await
+            .expect("send_to failed");
+        handle.poll_once(Instant::now());
+
+        let stats = handle.device_stats();
+        assert!(stats.tx_alloc_failures >= 1, "TX drop was not counted");
+        assert_eq!(stats.mbufs_in_use_peak, exhausted);
+        println!(
+            "{} TX allocation failures, peak {} mbufs in use",
+            stats.tx_alloc_failures, stats.mbufs_in_use_peak
+        );
+
+        drop(held);
+        assert_eq!(handle.mbufs_in_use(), baseline);
+        println!("Mbufs returned to the pool");
+    });
+
+    println!("\n=== Mempool Exhaustion Test Complete ===\n");
+}
@@ -0,0 +1,126 @@
+//! Multi-Reactor Test
+//!
+//! Validates `MultiReactor` driving two devices from one thread. Each
+//! `net_ring` vdev is its own interface with its own subnet. A task forwards
+//! datagrams from a socket on the first interface to a socket on the second.
+//! Validates that:
+//! - both interfaces are polled by the one loop
+//! - a datagram received on one interface can be sent out the other
+//! - each socket only sees traffic of the interface it was bound on
+//!
+//! Note: This is a separate test file because DPDK has global state that persists
+//! across tests within the same process.
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::runtime::{MultiReactor, Reactor, ReactorConfig};
+use dpdk_net::socket::UdpSocket;
+use dpdk_net_test::eth_dev_config::EthDevConfig;
+
+use smoltcp::wire::{EthernetAddress, IpAddress, IpCidr, Ipv4Address};
+use tokio::runtime::Builder;
+use tokio::task::LocalSet;
+
+const LAN_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const WAN_IP: Ipv4Address = Ipv4Address::new(192, 168, 2, 1);
+const SENDER_PORT: u16 = 7000;
+const FORWARD_PORT: u16 = 7001;
+const RECEIVER_PORT: u16 = 7002;
+const DATAGRAMS: usize = 8;
+
+#[test]
+fn test_multi_reactor_forwarding() {
+    println!("\n=== Multi-Reactor Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .vdev("net_ring0")
+        .vdev("net_ring1")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    let mut reactors = MultiReactor::new();
+    let mut eth_devs = Vec::new();
+    for (port_id, ip) in [(0u16, LAN_IP), (1u16, WAN_IP)] {
+        let eth_dev_config = EthDevConfig::new()
+            .port_id(port_id)
+            .mempool_name(format!("multi_pool_{port_id}"));
+        let (mempool, eth_dev) = eth_dev_config
+            .clone()
+            .build()
+            .expect("Failed to build EthDev");
+        let mac = eth_dev.mac_addr().expect("Failed to get MAC address");
+        let device = eth_dev_config.create_device(mempool, 0);
+
+        let config = ReactorConfig::new(EthernetAddress(mac.addr_bytes))
+            .ip_addr(IpCidr::new(IpAddress::Ipv4(ip), 24));
+        let reactor = Reactor::new_with_config(device, config).expect("Failed to create reactor");
+        let index = reactors.add(reactor);
+        assert_eq!(index, port_id as usize);
+        eth_devs.push(eth_dev);
+    }
+    assert_eq!(reactors.len(), 2);
+    let lan = reactors.handle(0).unwrap();
+    let wan = reactors.handle(1).unwrap();
+    assert_eq!(lan.ip_addr(), Some(IpAddress::Ipv4(LAN_IP)));
+    assert_eq!(wan.ip_addr(), Some(IpAddress::Ipv4(WAN_IP)));
+
+    let rt = Builder::new_current_thread().build().unwrap();
+    let local = LocalSet::new();
+    local.block_on(&rt, async {
+        let cancel = Rc::new(Cell::new(false));
+        let reactor_task = tokio::task::spawn_local(reactors.run(cancel.clone()));
+
+        let sender = UdpSocket::bind(&lan, SENDER_PORT, 32, 32, 1500).expect("bind sender");
+        let inside = UdpSocket::bind(&lan, FORWARD_PORT, 32, 32, 1500).expect("bind inside");
+        let outside = UdpSocket::bind(&wan, FORWARD_PORT, 32, 32, 1500).expect("bind outside");
+        let receiver = UdpSocket::bind(&wan, RECEIVER_PORT, 32, 32, 1500).expect("bind receiver");
+
+        // Forward everything arriving on the LAN side out of the WAN side
+        let forwarder = tokio::task::spawn_local(async move {
+            let mut buf = [0u8; 1500];
+            for _ in 0..DATAGRAMS {
+                let (n, _, _) = inside.recv_from(&mut buf).await.expect("recv inside");
+                outside
+                    .send_to(&buf[..n], IpAddress::Ipv4(WAN_IP), RECEIVER_PORT)
+                    .await
+                    .expect("send outside");
+            }
+        });
+
+        let mut buf = [0u8; 1500];
+        for i in 0..DATAGRAMS {
+            let msg = format!("datagram {i}");
+            sender
+                .send_to(msg.as_bytes(), IpAddress::Ipv4(LAN_IP), FORWARD_PORT)
+                .await
+                .expect("send sender");
+            let (n, from, port) = receiver.recv_from(&mut buf).await.expect("recv receiver");
+            assert_eq!(&buf[..n], msg.as_bytes());
+            // Sent by the forwarder from the WAN interface
+            assert_eq!((from, port), (IpAddress::Ipv4(WAN_IP), FORWARD_PORT));
+        }
+        forwarder.await.expect("forwarder failed");
+        println!("Forwarded {DATAGRAMS} datagrams from the LAN to the WAN interface");
+
+        let (lan_stats, wan_stats) = (lan.stats(), wan.stats());
+        println!("LAN: {lan_stats:?}");
+        println!("WAN: {wan_stats:?}");
+        assert_eq!(lan_stats.passes, wan_stats.passes);
+        assert!(lan_stats.packets_processed >= DATAGRAMS as u64);
+        assert!(wan_stats.packets_processed >= DATAGRAMS as u64);
+
+        cancel.set(true);
+        reactor_task.await.expect("reactor task failed");
+    });
+
+    for eth_dev in eth_devs {
+        eth_dev.stop().ok();
+        eth_dev.close().ok();
+    }
+
+    println!("\n=== Multi-Reactor Test Complete ===\n");
+}
@@ -0,0 +1,106 @@
+//! Reactor Egress Batch Test
+//!
+//! Runs a reactor with `Reactor::run_with_config` and a tiny egress cap, so
+//! each pass transmits only a couple of segments. Validates that an echo
+//! much larger than the cap still completes, with the leftover segments
+//! going out on later passes.
+//!
+//! Note: This is a separate test file because DPDK has global state that persists
+//! across tests within the same process.
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use dpdk_net::runtime::{PollConfig, Reactor, ReactorConfig, SpinRuntime};
+use dpdk_net::socket::{TcpListener, TcpStream};
+use dpdk_net_test::dpdk_test::create_test_context;
+
+use smoltcp::wire::{EthernetAddress, IpAddress, IpCidr, Ipv4Address};
+use tokio::runtime::Builder;
+use tokio::task::LocalSet;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const SERVER_PORT: u16 = 8080;
+const PAYLOAD: usize = 32 * 1024;
+const EGRESS_BATCH: usize = 2;
+
+async fn recv_exact(stream: &TcpStream, len: usize) -> Vec<u8> {
+    let mut data = vec![0u8; len];
+    let mut received = 0;
+    while received < len {
+        let n = stream
+            .recv(&mut data[received..])
+            .await
+            .expect("recv failed");
+        assert!(n > 0, "unexpected EOF after {received} bytes");
+        received += n;
+    }
+    data
+}
+
+#[test]
+fn test_reactor_egress_batch() {
+    println!("\n=== Reactor Egress Batch Test ===\n");
+
+    let (ctx, device) = create_test_context().expect("Failed to create DPDK test context");
+    let mac = ctx.eth_dev().mac_addr().expect("Failed to get MAC address");
+
+    let config = ReactorConfig::new(EthernetAddress(mac.addr_bytes))
+        .ip_addr(IpCidr::new(IpAddress::Ipv4(SERVER_IP), 24));
+    let reactor = Reactor::new_with_config(device, config).expect("Failed to create reactor");
+    let handle = reactor.handle();
+
+    let rt = Builder::new_current_thread().build().unwrap();
+    let local = LocalSet::new();
+    local.block_on(&rt, async {
+        let cancel = Rc::new(Cell::new(false));
+        let reactor_cancel = cancel.clone();
+        let poll_config = PollConfig::new()
+            .ingress_batch(8)
+            .egress_batch(EGRESS_BATCH);
+        assert_eq!(poll_config.egress_batch, Some(EGRESS_BATCH));
+        let reactor_task = tokio::task::spawn_local(async move {
+            reactor
+                .run_with_config::<SpinRuntime>(poll_config, reactor_cancel)
+                .await;
+        });
+
+        let mut listener =
+            TcpListener::bind(&handle, SERVER_PORT, 65536, 65536).expect("Failed to bind listener");
+        let server = tokio::task::spawn_local(async move {
+            let stream = listener.accept().await.expect("accept failed");
+            let data = recv_exact(&stream, PAYLOAD).await;
+            stream.send(&data).await.expect("server send failed");
+            stream.close().await.ok();
+        });
+
+        let client = TcpStream::connect(
+            &handle,
+            IpAddress::Ipv4(SERVER_IP),
+            SERVER_PORT,
+            49152,
+            65536,
+            65536,
+        )
+        .expect("connect failed");
+        client.wait_connected().await.expect("not connected");
+
+        let payload: Vec<u8> = (0..PAYLOAD).map(|i| (i % 253) as u8).collect();
+        let start = handle.generation();
+        client.send(&payload).await.expect("client send failed");
+        assert_eq!(recv_exact(&client, PAYLOAD).await, payload);
+        let passes = handle.generation() - start;
+        server.await.expect("server task failed");
+
+        // Each direction needs at least PAYLOAD / MSS segments
+        let min_passes = (2 * PAYLOAD / 1460 / EGRESS_BATCH) as u64;
+        println!("Echoed {PAYLOAD} bytes in {passes} passes (at least {min_passes})");
+        assert!(passes >= min_passes, "egress cap was not applied");
+
+        client.close().await.ok();
+        cancel.set(true);
+        reactor_task.await.expect("reactor task failed");
+    });
+
+    println!("\n=== Reactor Egress Batch Test Complete ===\n");
+}
@@ -0,0 +1,108 @@
+//! Reactor Idle Sleep Test
+//!
+//! Runs a reactor with `Reactor::run_with` and a tokio-timer `Runtime`, so
+//! idle passes sleep instead of spinning. Validates that:
+//! - a TCP echo still completes
+//! - an idle reactor makes few passes (each one waits at least
+//!   `DEFAULT_IDLE_POLL_INTERVAL`)
+//!
+//! Note: This is a separate test file because DPDK has global state that persists
+//! across tests within the same process.
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use dpdk_net::runtime::{DEFAULT_IDLE_POLL_INTERVAL, Reactor, ReactorConfig, Runtime};
+use dpdk_net::socket::{TcpListener, TcpStream};
+use dpdk_net_test::dpdk_test::create_test_context;
+
+use smoltcp::wire::{EthernetAddress, IpAddress, IpCidr, Ipv4Address};
+use tokio::runtime::Builder;
+use tokio::task::LocalSet;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const SERVER_PORT: u16 = 8080;
+const MESSAGE: &[u8] = b"sleep when idle";
+
+const IDLE_PERIOD: Duration = Duration::from_millis(200);
+
+/// Runtime hooks backed by tokio's timer.
+struct TokioTimers;
+
+impl Runtime for TokioTimers {
+    fn yield_now() -> impl Future<Output = ()> {
+        tokio::task::yield_now()
+    }
+
+    fn poll_delay(delay: Duration) -> impl Future<Output = ()> {
+        tokio::time::sleep(delay)
+    }
+}
+
+#[test]
+fn test_reactor_idle_sleep() {
+    println!("\n=== Reactor Idle Sleep Test ===\n");
+
+    let (ctx, device) = create_test_context().expect("Failed to create DPDK test context");
+    let mac = ctx.eth_dev().mac_addr().expect("Failed to get MAC address");
+
+    let config = ReactorConfig::new(EthernetAddress(mac.addr_bytes))
+        .ip_addr(IpCidr::new(IpAddress::Ipv4(SERVER_IP), 24));
+    let reactor = Reactor::new_with_config(device, config).expect("Failed to create reactor");
+    let handle = reactor.handle();
+
+    let rt = Builder::new_current_thread().enable_time().build().unwrap();
+    let local = LocalSet::new();
+    local.block_on(&rt, async {
+        let cancel = Rc::new(Cell::new(false));
+        let reactor_cancel = cancel.clone();
+        let reactor_task = tokio::task::spawn_local(async move {
+            reactor.run_with::<TokioTimers>(32, reactor_cancel).await;
+        });
+
+        let mut listener =
+            TcpListener::bind(&handle, SERVER_PORT, 4096, 4096).expect("Failed to bind listener");
+        let server = tokio::task::spawn_local(async move {
+            let stream = listener.accept().await.expect("accept failed");
+            let mut buf = [0u8; 64];
+            let n = stream.recv(&mut buf).await.expect("server recv failed");
+            stream.send(&buf[..n]).await.expect("server send failed");
+            stream.close().await.ok();
+        });
+
+        let client = TcpStream::connect(
+            &handle,
+            IpAddress::Ipv4(SERVER_IP),
+            SERVER_PORT,
+            49152,
+            4096,
+            4096,
+        )
+        .expect("connect failed");
+        client.wait_connected().await.expect("not connected");
+        client.send(MESSAGE).await.expect("client send failed");
+        let mut buf = [0u8; 64];
+        let n = client.recv(&mut buf).await.expect("client recv failed");
+        assert_eq!(&buf[..n], MESSAGE);
+        client.close().await.ok();
+        server.await.expect("server task failed");
+        drop(client);
+
+        // Let closing timers run out, then count passes while nothing happens
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let before = handle.generation();
+        tokio::time::sleep(IDLE_PERIOD).await;
+        let passes = handle.generation() - before;
+
+        let max_passes = (IDLE_PERIOD.as_micros() / DEFAULT_IDLE_POLL_INTERVAL.as_micros()) as u64;
+        println!("{passes} passes in {IDLE_PERIOD:?} idle (at most {max_passes} expected)");
+        assert!(passes > 0, "reactor stopped polling");
+        assert!(passes <= max_passes, "idle reactor did not sleep");
+
+        cancel.set(true);
+        reactor_task.await.expect("reactor task failed");
+    });
+
+    println!("\n=== Reactor Idle Sleep Test Complete ===\n");
+}
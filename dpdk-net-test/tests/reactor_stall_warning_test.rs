@@ -0,0 +1,96 @@
+//! Reactor Stall Warning Test
+//!
+//! Uses sockets on a reactor that nothing drives. Validates that:
+//! - a socket future that keeps getting polled logs the stall warning
+//!   (debug builds, where the check is on)
+//! - the reactor generation only moves when a pass runs
+//!
+//! Note: This is a separate test file because DPDK has global state that persists
+//! across tests within the same process.
+
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use dpdk_net::runtime::{DEFAULT_STALL_WARN_POLLS, Reactor, ReactorConfig};
+use dpdk_net::socket::UdpSocket;
+use dpdk_net_test::dpdk_test::create_test_context;
+
+use smoltcp::wire::{EthernetAddress, IpAddress, IpCidr, Ipv4Address};
+use tokio::runtime::Builder;
+use tokio::task::LocalSet;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+
+/// Collects formatted log output.
+#[derive(Clone, Default)]
+struct Captured(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Captured {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_reactor_stall_warning() {
+    println!("\n=== Reactor Stall Warning Test ===\n");
+
+    let (ctx, device) = create_test_context().expect("Failed to create DPDK test context");
+    let mac = ctx.eth_dev().mac_addr().expect("Failed to get MAC address");
+
+    let config = ReactorConfig::new(EthernetAddress(mac.addr_bytes))
+        .ip_addr(IpCidr::new(IpAddress::Ipv4(SERVER_IP), 24));
+    // Deliberately never run
+    let reactor = Reactor::new_with_config(device, config).expect("Failed to create reactor");
+    let handle = reactor.handle();
+
+    let expected = cfg!(debug_assertions).then_some(DEFAULT_STALL_WARN_POLLS);
+    assert_eq!(handle.stall_warning(), expected);
+    handle.set_stall_warning(Some(16));
+
+    let captured = Captured::default();
+    let writer = captured.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_writer(move || writer.clone())
+        .with_ansi(false)
+        .finish();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let rt = Builder::new_current_thread().build().unwrap();
+        let local = LocalSet::new();
+        local.block_on(&rt, async {
+            let socket = UdpSocket::bind(&handle, 9000, 4, 4, 512).expect("bind failed");
+            let mut buf = [0u8; 512];
+
+            // A recv raced against a yield gets polled on every iteration
+            let start = Instant::now();
+            while start.elapsed() < Duration::from_millis(1500) {
+                tokio::select! {
+                    _ = socket.recv_from(&mut buf) => panic!("nothing was sent"),
+                    _ = tokio::task::yield_now() => {}
+                }
+            }
+        });
+    });
+
+    let logs = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+    let warnings = logs.matches("reactor has not polled").count();
+    if cfg!(debug_assertions) {
+        assert_eq!(warnings, 1, "expected one stall warning, got:\n{logs}");
+    } else {
+        assert_eq!(warnings, 0);
+    }
+    println!("Stall warnings logged: {warnings}");
+
+    assert_eq!(handle.generation(), 0);
+    handle.poll_once(smoltcp::time::Instant::now());
+    assert_eq!(handle.generation(), 1);
+
+    println!("\n=== Reactor Stall Warning Test Complete ===\n");
+}
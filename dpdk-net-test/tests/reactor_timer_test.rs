@@ -0,0 +1,73 @@
+//! Reactor Timer Test
+//!
+//! Validates `ReactorHandle::sleep` and `ReactorHandle::timeout`, whose
+//! wakers live in the reactor's timer heap:
+//! - a sleep completes no earlier than its deadline, woken by the reactor
+//! - a timeout gives up on a future that never completes
+//! - a timeout passes through the output of a future that does, and its
+//!   cancelled timer wakes nobody
+//!
+//! Note: This is a separate test file because DPDK has global state that persists
+//! across tests within the same process.
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use dpdk_net::runtime::{Reactor, ReactorConfig};
+use dpdk_net_test::dpdk_test::create_test_context;
+
+use smoltcp::wire::{EthernetAddress, IpAddress, IpCidr, Ipv4Address};
+use tokio::runtime::Builder;
+use tokio::task::LocalSet;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+
+#[test]
+fn test_reactor_timer() {
+    println!("\n=== Reactor Timer Test ===\n");
+
+    let (ctx, device) = create_test_context().expect("Failed to create DPDK test context");
+    let mac = ctx.eth_dev().mac_addr().expect("Failed to get MAC address");
+
+    let config = ReactorConfig::new(EthernetAddress(mac.addr_bytes))
+        .ip_addr(IpCidr::new(IpAddress::Ipv4(SERVER_IP), 24));
+    let reactor = Reactor::new_with_config(device, config).expect("Failed to create reactor");
+    let handle = reactor.handle();
+
+    let rt = Builder::new_current_thread().build().unwrap();
+    let local = LocalSet::new();
+    local.block_on(&rt, async {
+        let cancel = Rc::new(Cell::new(false));
+        let reactor_cancel = cancel.clone();
+        let reactor_task = tokio::task::spawn_local(async move {
+            reactor.run(reactor_cancel).await;
+        });
+
+        let start = Instant::now();
+        handle.sleep(Duration::from_millis(50)).await;
+        let elapsed = start.elapsed();
+        println!("sleep(50ms) took {elapsed:?}");
+        assert!(elapsed >= Duration::from_millis(50));
+        assert_eq!(handle.stats().timers_fired, 1);
+
+        let result = handle
+            .timeout(Duration::from_millis(20), std::future::pending::<()>())
+            .await;
+        assert!(result.is_err(), "pending future did not time out");
+        assert_eq!(handle.stats().timers_fired, 2);
+        println!("timeout gave up on a pending future");
+
+        let result = handle.timeout(Duration::from_millis(20), async { 7 }).await;
+        assert_eq!(result, Ok(7));
+        // Outlive the cancelled timer's deadline
+        handle.sleep(Duration::from_millis(40)).await;
+        assert_eq!(handle.stats().timers_fired, 3);
+        println!("timeout passed a ready output through");
+
+        cancel.set(true);
+        reactor_task.await.expect("reactor task failed");
+    });
+
+    println!("\n=== Reactor Timer Test Complete ===\n");
+}
@@ -0,0 +1,81 @@
+//! Reactor Tokio Timer Test
+//!
+//! Runs a reactor with `Reactor::run_with::<TokioRuntime>` on a runtime with
+//! tokio's time driver. Validates that the handle's timers then wait on
+//! `tokio::time` rather than the reactor's timer heap:
+//! - a sleep completes no earlier than its deadline
+//! - a timeout gives up on a future that never completes
+//! - an interval ticks once per period
+//! - the reactor fires none of them (`timers_fired` stays zero)
+//!
+//! Note: This is a separate test file because DPDK has global state that persists
+//! across tests within the same process.
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use dpdk_net::runtime::{Reactor, ReactorConfig};
+use dpdk_net_test::dpdk_test::create_test_context;
+use dpdk_net_util::TokioRuntime;
+
+use smoltcp::wire::{EthernetAddress, IpAddress, IpCidr, Ipv4Address};
+use tokio::runtime::Builder;
+use tokio::task::LocalSet;
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const PERIOD: Duration = Duration::from_millis(20);
+
+#[test]
+fn test_reactor_tokio_timer() {
+    println!("\n=== Reactor Tokio Timer Test ===\n");
+
+    let (ctx, device) = create_test_context().expect("Failed to create DPDK test context");
+    let mac = ctx.eth_dev().mac_addr().expect("Failed to get MAC address");
+
+    let config = ReactorConfig::new(EthernetAddress(mac.addr_bytes))
+        .ip_addr(IpCidr::new(IpAddress::Ipv4(SERVER_IP), 24));
+    let reactor = Reactor::new_with_config(device, config).expect("Failed to create reactor");
+    let handle = reactor.handle();
+
+    let rt = Builder::new_current_thread().enable_time().build().unwrap();
+    let local = LocalSet::new();
+    local.block_on(&rt, async {
+        let cancel = Rc::new(Cell::new(false));
+        let reactor_cancel = cancel.clone();
+        let reactor_task = tokio::task::spawn_local(async move {
+            reactor.run_with::<TokioRuntime>(32, reactor_cancel).await;
+        });
+        tokio::task::yield_now().await;
+
+        let start = Instant::now();
+        handle.sleep(Duration::from_millis(50)).await;
+        let elapsed = start.elapsed();
+        println!("sleep(50ms) took {elapsed:?}");
+        assert!(elapsed >= Duration::from_millis(50));
+
+        let result = handle.timeout(PERIOD, std::future::pending::<()>()).await;
+        assert!(result.is_err(), "pending future did not time out");
+        println!("timeout gave up on a pending future");
+
+        let start = Instant::now();
+        let mut interval = handle.interval(PERIOD);
+        for _ in 0..3 {
+            interval.tick().await;
+        }
+        let elapsed = start.elapsed();
+        println!("3 ticks took {elapsed:?}");
+        assert!(elapsed >= PERIOD * 2);
+
+        assert_eq!(
+            handle.stats().timers_fired,
+            0,
+            "timer went through the heap"
+        );
+
+        cancel.set(true);
+        reactor_task.await.expect("reactor task failed");
+    });
+
+    println!("\n=== Reactor Tokio Timer Test Complete ===\n");
+}
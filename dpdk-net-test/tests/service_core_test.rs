@@ -0,0 +1,96 @@
+//! Service Core Test
+//!
+//! Validates the service core API with one main lcore and one worker:
+//! - a registered service runs on the calling thread with `run_once`
+//! - adding the worker as a service core takes it out of the worker pool
+//! - a started service mapped to the started core runs repeatedly there,
+//!   and stops running once stopped
+//! - dropping the service core hands the lcore back as a launchable worker
+//!
+//! Note: This is a separate test file because DPDK has global state that persists
+//! across tests within the same process.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::api::rte::lcore::{Lcore, Role};
+use dpdk_net::api::rte::service::ServiceCore;
+
+/// Wait up to a second for `cond`.
+fn wait_for(mut cond: impl FnMut() -> bool) -> bool {
+    let deadline = Instant::now() + Duration::from_secs(1);
+    while Instant::now() < deadline {
+        if cond() {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    cond()
+}
+
+#[test]
+fn test_service_core() {
+    println!("\n=== Service Core Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0-1")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    let calls = Arc::new(AtomicU64::new(0));
+    let service = Lcore::register_service("test_counter", {
+        let calls = calls.clone();
+        move || {
+            calls.fetch_add(1, Ordering::Relaxed);
+        }
+    })
+    .expect("Failed to register service");
+
+    service.start().expect("Failed to start service");
+    assert!(service.is_running());
+    service.run_once().expect("run_once failed");
+    assert_eq!(calls.load(Ordering::Relaxed), 1);
+    service.stop().expect("Failed to stop service");
+    println!("Service ran once on the main lcore");
+
+    let worker = Lcore::workers().next().expect("No worker lcore");
+    let core = ServiceCore::add(worker).expect("Failed to add service core");
+    assert_eq!(worker.role(), Role::Service);
+    assert!(worker.is_service_core());
+    assert_eq!(ServiceCore::count(), 1);
+    assert_eq!(Lcore::workers().count(), 0);
+    println!("Lcore {} is now a service core", worker.id());
+
+    core.map(&service).expect("Failed to map service");
+    service.start().expect("Failed to start service");
+    core.start().expect("Failed to start service core");
+    assert!(
+        wait_for(|| calls.load(Ordering::Relaxed) > 100),
+        "service did not run on the service core"
+    );
+    println!(
+        "Service ran {} times on the service core",
+        calls.load(Ordering::Relaxed)
+    );
+
+    service.stop().expect("Failed to stop service");
+    assert!(wait_for(|| !service.may_be_active()));
+    let stopped_at = calls.load(Ordering::Relaxed);
+    std::thread::sleep(Duration::from_millis(20));
+    assert_eq!(calls.load(Ordering::Relaxed), stopped_at);
+    println!("Stopped service is no longer scheduled");
+
+    core.stop().expect("Failed to stop service core");
+    drop(core);
+    assert_eq!(worker.role(), Role::Rte);
+    assert_eq!(worker.run(|| 7).expect("launch failed"), 7);
+    println!("Lcore {} is a worker again", worker.id());
+
+    drop(service);
+    println!("\n=== Service Core Test Complete ===\n");
+}
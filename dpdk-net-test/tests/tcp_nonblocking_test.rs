@@ -0,0 +1,136 @@
+//! Nonblocking TCP Test
+//!
+//! Drives a TCP echo from a plain synchronous loop: no executor, only
+//! `ReactorHandle::poll_once` plus `TcpStream::send_nonblocking` and
+//! `recv_nonblocking`. Validates that:
+//! - `recv_nonblocking` reports `WouldBlock` before data arrives
+//! - data sent with `send_nonblocking` is echoed back once the reactor runs
+//! - EOF shows up as `Ok(0)` after the peer closes
+//!
+//! Note: This is a separate test file because DPDK has global state that persists
+//! across tests within the same process.
+
+use std::future::Future;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+use dpdk_net::runtime::{Reactor, ReactorConfig, ReactorHandle};
+use dpdk_net::socket::{NonblockingError, TcpListener, TcpStream};
+use dpdk_net_test::dpdk_test::create_test_context;
+
+use smoltcp::time::Instant;
+use smoltcp::wire::{EthernetAddress, IpAddress, IpCidr, Ipv4Address};
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const SERVER_PORT: u16 = 8080;
+const MESSAGE: &[u8] = b"no futures here";
+const MAX_PASSES: usize = 100_000;
+
+/// Run reactor passes until `done` returns true.
+fn drive(handle: &ReactorHandle, what: &str, mut done: impl FnMut() -> bool) {
+    for _ in 0..MAX_PASSES {
+        if done() {
+            return;
+        }
+        handle.poll_once(Instant::now());
+    }
+    panic!("{what} did not complete");
+}
+
+#[test]
+fn test_tcp_nonblocking() {
+    println!("\n=== Nonblocking TCP Test ===\n");
+
+    let (ctx, device) = create_test_context().expect("Failed to create DPDK test context");
+    let mac = ctx.eth_dev().mac_addr().expect("Failed to get MAC address");
+
+    let config = ReactorConfig::new(EthernetAddress(mac.addr_bytes))
+        .ip_addr(IpCidr::new(IpAddress::Ipv4(SERVER_IP), 24));
+    let reactor = Reactor::new_with_config(device, config).expect("Failed to create reactor");
+    let handle = reactor.handle();
+
+    let mut listener =
+        TcpListener::bind(&handle, SERVER_PORT, 4096, 4096).expect("Failed to bind listener");
+    let client = TcpStream::connect(
+        &handle,
+        IpAddress::Ipv4(SERVER_IP),
+        SERVER_PORT,
+        49152,
+        4096,
+        4096,
+    )
+    .expect("connect failed");
+
+    // Accept is the one future here; poll it by hand
+    let mut server = None;
+    {
+        let mut accept = pin!(listener.accept());
+        let mut cx = Context::from_waker(Waker::noop());
+        drive(&handle, "accept", || match accept.as_mut().poll(&mut cx) {
+            Poll::Ready(stream) => {
+                server = Some(stream.expect("accept failed"));
+                true
+            }
+            Poll::Pending => false,
+        });
+    }
+    let server = server.unwrap();
+    drive(&handle, "connect", || client.is_connected());
+
+    let mut buf = [0u8; 64];
+    assert_eq!(
+        server.recv_nonblocking(&mut buf),
+        Err(NonblockingError::WouldBlock)
+    );
+
+    let mut sent = 0;
+    drive(&handle, "client send", || {
+        match client.send_nonblocking(&MESSAGE[sent..]) {
+            Ok(n) => sent += n,
+            Err(NonblockingError::WouldBlock) => {}
+            Err(e) => panic!("client send failed: {e}"),
+        }
+        sent == MESSAGE.len()
+    });
+
+    // Echo on the server side
+    let mut received = Vec::new();
+    drive(&handle, "server recv", || {
+        match server.recv_nonblocking(&mut buf) {
+            Ok(n) => received.extend_from_slice(&buf[..n]),
+            Err(NonblockingError::WouldBlock) => {}
+            Err(e) => panic!("server recv failed: {e}"),
+        }
+        received.len() == MESSAGE.len()
+    });
+    assert_eq!(server.send_nonblocking(&received), Ok(MESSAGE.len()));
+
+    let mut echoed = Vec::new();
+    drive(&handle, "client recv", || {
+        match client.recv_nonblocking(&mut buf) {
+            Ok(n) => echoed.extend_from_slice(&buf[..n]),
+            Err(NonblockingError::WouldBlock) => {}
+            Err(e) => panic!("client recv failed: {e}"),
+        }
+        echoed.len() == MESSAGE.len()
+    });
+    assert_eq!(echoed, MESSAGE);
+
+    // Closing the server side surfaces as EOF on the client
+    {
+        let mut close = pin!(server.close());
+        let mut cx = Context::from_waker(Waker::noop());
+        let _ = close.as_mut().poll(&mut cx);
+    }
+    drive(&handle, "client EOF", || {
+        matches!(client.recv_nonblocking(&mut buf), Ok(0))
+    });
+    println!("Echoed {} bytes without an executor", echoed.len());
+
+    client.abort();
+    drop(client);
+    drop(server);
+    drop(listener);
+    drop(reactor);
+    println!("\n=== Nonblocking TCP Test Complete ===\n");
+}
@@ -0,0 +1,141 @@
+//! Tonic gRPC Channel Pool Test
+//!
+//! Validates `DpdkGrpcChannel::with_pool_size`. A request sent while the
+//! only connection is waiting on a response makes the channel open a
+//! second one; once the pool is at its limit, further concurrent requests
+//! share the existing connections.
+//!
+//! Requests go to a method the Greeter does not implement, so the server
+//! answers at once with `grpc-status: 12` and no protobuf is needed.
+
+use std::time::Duration;
+
+use dpdk_net::api::rte::eal::EalBuilder;
+use dpdk_net::socket::TcpListener;
+use dpdk_net_tonic::tonic::{ChannelResponseFuture, DpdkGrpcChannel, serve};
+use dpdk_net_util::{DpdkApp, WorkerContext};
+
+use smoltcp::wire::Ipv4Address;
+use tonic::client::GrpcService;
+
+use serial_test::serial;
+
+/// Generated protobuf/gRPC code from `proto/greeter.proto`.
+mod greeter {
+    tonic::include_proto!("greeter");
+}
+
+use greeter::greeter_server::{Greeter, GreeterServer};
+use greeter::{HelloReply, HelloRequest};
+
+const SERVER_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+const GATEWAY_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+const SERVER_PORT: u16 = 50051;
+const POOL_SIZE: usize = 2;
+
+#[derive(Debug, Default)]
+struct MyGreeter;
+
+#[tonic::async_trait]
+impl Greeter for MyGreeter {
+    async fn say_hello(
+        &self,
+        request: tonic::Request<HelloRequest>,
+    ) -> Result<tonic::Response<HelloReply>, tonic::Status> {
+        let name = request.into_inner().name;
+        Ok(tonic::Response::new(HelloReply {
+            message: format!("Hello, {}!", name),
+        }))
+    }
+}
+
+/// Wait for the channel to be ready, then send one request.
+async fn send(channel: &mut DpdkGrpcChannel) -> ChannelResponseFuture {
+    std::future::poll_fn(|cx| channel.poll_ready(cx))
+        .await
+        .expect("channel not ready");
+    let request = http::Request::builder()
+        .uri("/greeter.Greeter/Unimplemented")
+        .header("content-type", "application/grpc")
+        .body(tonic::body::Body::empty())
+        .expect("request build failed");
+    channel.call(request)
+}
+
+async fn pool_main(ctx: WorkerContext) {
+    let listener =
+        TcpListener::bind(&ctx.reactor, SERVER_PORT, 4096, 4096).expect("Failed to bind listener");
+    let routes = tonic::service::Routes::new(GreeterServer::new(MyGreeter));
+    let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+    let server_task = tokio::task::spawn_local(serve(listener, routes, async {
+        let _ = shutdown_rx.await;
+    }));
+
+    let uri: http::Uri = format!("http://{}:{}", SERVER_IP, SERVER_PORT)
+        .parse()
+        .unwrap();
+    let mut channel = DpdkGrpcChannel::connect(&ctx.reactor, uri)
+        .await
+        .expect("Client: connect failed")
+        .with_pool_size(POOL_SIZE);
+    assert_eq!(channel.connection_count(), 1);
+
+    // The second request finds the only connection busy
+    let first = send(&mut channel).await;
+    let second = send(&mut channel).await;
+    for response in [first, second] {
+        assert_eq!(response.await.expect("request failed").status(), 200);
+    }
+    for _ in 0..100 {
+        std::future::poll_fn(|cx| channel.poll_ready(cx))
+            .await
+            .expect("channel not ready");
+        if channel.connection_count() == POOL_SIZE {
+            break;
+        }
+        ctx.reactor.sleep(Duration::from_millis(10)).await;
+    }
+    assert_eq!(channel.connection_count(), POOL_SIZE);
+    println!("Pool grew to {POOL_SIZE} connections");
+
+    // At the limit, requests are spread over the existing connections
+    let mut responses = Vec::new();
+    for _ in 0..4 {
+        responses.push(send(&mut channel).await);
+    }
+    for response in responses {
+        assert_eq!(response.await.expect("request failed").status(), 200);
+    }
+    assert_eq!(channel.connection_count(), POOL_SIZE);
+
+    println!("\n✓ Tonic gRPC pool test PASSED!");
+
+    drop(channel);
+    let _ = shutdown_tx.send(());
+    let _ = server_task.await;
+}
+
+#[test]
+#[serial]
+fn test_tonic_grpc_pool() {
+    println!("\n=== Tonic gRPC Pool Test ===\n");
+
+    let _eal = EalBuilder::new()
+        .no_huge()
+        .no_pci()
+        .in_memory()
+        .core_list("0")
+        .vdev("net_ring0")
+        .init()
+        .expect("Failed to initialize EAL");
+
+    DpdkApp::new()
+        .eth_dev(0)
+        .ip(SERVER_IP)
+        .gateway(GATEWAY_IP)
+        .mbufs_per_queue(1024)
+        .descriptors(128, 128)
+        .run(pool_main);
+
+    println!("\n=== Tonic gRPC Pool Test Complete ===\n");
+}
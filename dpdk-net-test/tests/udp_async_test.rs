@@ -173,7 +173,7 @@ fn test_udp_send_recv_loopback() {
         let server_endpoint = IpEndpoint::new(IpAddress::Ipv4(SERVER_IP), SERVER_PORT);
 
         let sent = client
-            .send_to(message, server_endpoint)
+            .send_with_meta(message, server_endpoint)
             .await
             .expect("Failed to send");
         println!("Client sent {} bytes to {:?}", sent, server_endpoint);
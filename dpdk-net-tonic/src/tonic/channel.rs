@@ -1,4 +1,4 @@
-//! `!Send` gRPC channel backed by a persistent HTTP/2 connection.
+//! `!Send` gRPC channel backed by persistent HTTP/2 connections.
 //!
 //! [`DpdkGrpcChannel`] wraps [`dpdk_net_util::Connection`] (HTTP/2 only) and
 //! implements `tower::Service<Request<tonic::body::Body>>`, satisfying tonic's
@@ -6,24 +6,67 @@
 //!
 //! Use this instead of `tonic::transport::Channel`, which requires `Send`.
 
+use std::cell::Cell;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
 use std::task::{Context, Poll};
+use std::time::Instant;
 
 use dpdk_net::runtime::ReactorHandle;
-use dpdk_net_util::{Connection, Error, ResponseFuture};
+use dpdk_net_util::{Backoff, BackoffConfig, Connection, Error, ResponseFuture};
 use http::Uri;
 use http::uri::{Authority, Scheme};
+use smoltcp::wire::IpAddress;
+use tokio::task::JoinHandle;
 
-/// A `!Send` gRPC channel backed by a persistent HTTP/2 connection
+/// A `!Send` gRPC channel backed by persistent HTTP/2 connections
 /// over dpdk-net transport.
 ///
 /// Implements `tower::Service<Request<tonic::body::Body>>`, which satisfies
 /// `tonic::client::GrpcService` via blanket impl.
 ///
 /// Not `Clone` — create one channel per tonic client instance.
+///
+/// By default the channel holds one connection. Every stream on it shares
+/// one TCP flow, so a busy client is limited to that flow's window.
+/// [`with_pool_size`](Self::with_pool_size) lets it open more: requests go
+/// round-robin to connections with no response outstanding, and when every
+/// connection is busy another is opened in the background, up to the limit.
+/// Connections the server closes (e.g. with `GOAWAY`) leave the pool.
+///
+/// By default `poll_ready` fails once no connection is left. With
+/// [`reconnect_backoff`](Self::reconnect_backoff), `poll_ready` instead
+/// reconnects, waiting out a jittered backoff after each failed attempt.
 pub struct DpdkGrpcChannel {
-    conn: Connection,
+    conns: Vec<PooledConnection>,
+    /// Where the round-robin search for the next connection starts.
+    next: usize,
+    /// Connection picked by the last successful `poll_ready`.
+    selected: Option<usize>,
+    max_connections: usize,
     scheme: Scheme,
     authority: Authority,
+    endpoint: Endpoint,
+    backoff: Option<Backoff>,
+    opening: Option<JoinHandle<Result<Connection, Error>>>,
+}
+
+struct PooledConnection {
+    conn: Connection,
+    /// Requests sent on `conn` whose response head has not arrived.
+    in_flight: Rc<Cell<usize>>,
+}
+
+/// Parameters needed to re-establish the connection.
+#[derive(Clone)]
+struct Endpoint {
+    reactor: ReactorHandle,
+    addr: IpAddress,
+    port: u16,
+    local_port: u16,
+    rx_buffer: usize,
+    tx_buffer: usize,
 }
 
 impl DpdkGrpcChannel {
@@ -35,7 +78,7 @@ impl DpdkGrpcChannel {
     /// Establishes a TCP connection and completes the HTTP/2 handshake
     /// using [`LocalExecutor`](dpdk_net_util::LocalExecutor) (no `Send` required).
     ///
-    /// Uses an ephemeral local port (`0`) and default buffer sizes
+    /// Uses an ephemeral local port and default buffer sizes
     /// (4096 bytes rx/tx).
     pub async fn connect(reactor: &ReactorHandle, uri: Uri) -> Result<Self, Error> {
         Self::connect_with(reactor, uri, 0, 4096, 4096).await
@@ -43,6 +86,11 @@ impl DpdkGrpcChannel {
 
     /// Connect with explicit local port and buffer sizes.
     ///
+    /// A `local_port` of `0` takes one from
+    /// [`ReactorHandle::alloc_ephemeral_port`]. A non-zero `local_port` is
+    /// only used while the channel holds no other connection; extra pooled
+    /// connections take ephemeral ports.
+    ///
     /// See [`connect`](Self::connect) for URI format requirements.
     pub async fn connect_with(
         reactor: &ReactorHandle,
@@ -58,31 +106,209 @@ impl DpdkGrpcChannel {
             .parse()
             .expect("URI host must be an IP address");
         let port = uri.port_u16().expect("URI must have a port");
-        let conn = Connection::http2(reactor, addr, port, local_port, rx_buffer, tx_buffer).await?;
+        let endpoint = Endpoint {
+            reactor: reactor.clone(),
+            addr,
+            port,
+            local_port,
+            rx_buffer,
+            tx_buffer,
+        };
+        let conn = endpoint.connect(true).await?;
         Ok(Self {
-            conn,
+            conns: vec![PooledConnection::new(conn)],
+            next: 0,
+            selected: None,
+            max_connections: 1,
             scheme,
             authority,
+            endpoint,
+            backoff: None,
+            opening: None,
         })
     }
 
-    /// Check if the underlying HTTP/2 connection is still usable.
+    /// Reconnect automatically from `poll_ready` when the connection breaks.
+    ///
+    /// Attempts after a failure are delayed with jittered exponential backoff
+    /// so that many channels failing at once do not reconnect in lockstep.
+    /// The same delay applies to opening extra pooled connections.
+    pub fn reconnect_backoff(mut self, config: BackoffConfig) -> Self {
+        self.backoff = Some(Backoff::new(config));
+        self
+    }
+
+    /// Keep up to `max_connections` HTTP/2 connections to the server
+    /// (default: 1).
+    ///
+    /// Connections beyond the first are opened only while all existing
+    /// ones are waiting on a response, so an idle channel stays at one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_connections` is zero.
+    pub fn with_pool_size(mut self, max_connections: usize) -> Self {
+        assert!(max_connections > 0, "pool size must be at least 1");
+        self.max_connections = max_connections;
+        self
+    }
+
+    /// Check if any underlying HTTP/2 connection is still usable.
     pub fn is_ready(&self) -> bool {
-        self.conn.is_ready()
+        self.conns.iter().any(|c| c.conn.is_ready())
+    }
+
+    /// Number of open connections in the pool.
+    pub fn connection_count(&self) -> usize {
+        self.conns.len()
+    }
+
+    /// The least loaded ready connection, taking the first in round-robin
+    /// order on ties.
+    fn pick(&self) -> Option<usize> {
+        let n = self.conns.len();
+        (0..n)
+            .map(|k| (self.next + k) % n)
+            .filter(|&i| self.conns[i].conn.is_ready())
+            .min_by_key(|&i| self.conns[i].in_flight.get())
+    }
+
+    /// Start opening a connection on a local task.
+    fn open(&mut self) {
+        let delay = self
+            .backoff
+            .as_ref()
+            .and_then(|b| b.remaining(Instant::now()));
+        let endpoint = self.endpoint.clone();
+        let first = self.conns.is_empty();
+        self.opening = Some(tokio::task::spawn_local(async move {
+            if let Some(delay) = delay {
+                endpoint.reactor.sleep(delay).await;
+            }
+            endpoint.connect(first).await
+        }));
+    }
+}
+
+impl Endpoint {
+    /// Open an HTTP/2 connection. Only the pool's sole connection may use
+    /// the configured local port; others would collide on it.
+    async fn connect(&self, sole: bool) -> Result<Connection, Error> {
+        let local_port = match self.local_port {
+            0 => None,
+            port => sole.then_some(port),
+        };
+        let local_port = match local_port {
+            Some(port) => port,
+            None => self
+                .reactor
+                .alloc_ephemeral_port()
+                .ok_or(Error::PortsExhausted)?,
+        };
+        Connection::http2(
+            &self.reactor,
+            self.addr,
+            self.port,
+            local_port,
+            self.rx_buffer,
+            self.tx_buffer,
+        )
+        .await
+    }
+}
+
+impl PooledConnection {
+    fn new(conn: Connection) -> Self {
+        Self {
+            conn,
+            in_flight: Rc::new(Cell::new(0)),
+        }
+    }
+}
+
+/// Future returned by [`DpdkGrpcChannel`]'s `call`.
+///
+/// Resolves like [`ResponseFuture`]; while pending, it counts toward its
+/// connection's load.
+pub struct ChannelResponseFuture {
+    response: ResponseFuture,
+    _in_flight: InFlight,
+}
+
+impl Future for ChannelResponseFuture {
+    type Output = Result<http::Response<hyper::body::Incoming>, Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.response).poll(cx)
+    }
+}
+
+struct InFlight(Rc<Cell<usize>>);
+
+impl InFlight {
+    fn new(count: &Rc<Cell<usize>>) -> Self {
+        count.set(count.get() + 1);
+        Self(count.clone())
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() - 1);
     }
 }
 
 impl tower::Service<http::Request<tonic::body::Body>> for DpdkGrpcChannel {
     type Response = http::Response<hyper::body::Incoming>;
     type Error = Error;
-    type Future = ResponseFuture;
+    type Future = ChannelResponseFuture;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.selected = None;
+        self.conns.retain(|c| !c.conn.is_closed());
 
-    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-        if self.conn.is_ready() {
-            Poll::Ready(Ok(()))
-        } else {
-            Poll::Ready(Err(Error::ConnectionNotReady))
+        if let Some(opening) = self.opening.as_mut()
+            && let Poll::Ready(result) = Pin::new(opening).poll(cx)
+        {
+            self.opening = None;
+            match result.unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic())) {
+                Ok(conn) => {
+                    if let Some(backoff) = self.backoff.as_mut() {
+                        backoff.reset();
+                    }
+                    self.conns.push(PooledConnection::new(conn));
+                }
+                Err(e) => {
+                    let delay = self
+                        .backoff
+                        .as_mut()
+                        .map(|b| b.record_failure(Instant::now()));
+                    tracing::debug!(error = %e, ?delay, "gRPC connect failed");
+                    if self.conns.is_empty() {
+                        return Poll::Ready(Err(e));
+                    }
+                }
+            }
         }
+
+        if let Some(i) = self.pick() {
+            let saturated = self.conns[i].in_flight.get() > 0;
+            if saturated && self.opening.is_none() && self.conns.len() < self.max_connections {
+                self.open();
+            }
+            self.selected = Some(i);
+            return Poll::Ready(Ok(()));
+        }
+
+        if self.opening.is_some() {
+            return Poll::Pending;
+        }
+        if self.backoff.is_none() {
+            return Poll::Ready(Err(Error::ConnectionNotReady));
+        }
+        // Poll the new attempt once so it wakes this task
+        self.open();
+        self.poll_ready(cx)
     }
 
     fn call(&mut self, mut req: http::Request<tonic::body::Body>) -> Self::Future {
@@ -102,6 +328,15 @@ impl tower::Service<http::Request<tonic::body::Body>> for DpdkGrpcChannel {
                 .expect("valid URI");
             *req.uri_mut() = uri;
         }
-        self.conn.send_request(req)
+        let i = self
+            .selected
+            .take()
+            .expect("poll_ready must succeed before call");
+        self.next = i + 1;
+        let pooled = &mut self.conns[i];
+        ChannelResponseFuture {
+            _in_flight: InFlight::new(&pooled.in_flight),
+            response: pooled.conn.send_request(req),
+        }
     }
 }
@@ -2,12 +2,13 @@
 //!
 //! Provides:
 //! - [`serve`] — gRPC server wrapper that accepts tonic `Routes`
-//! - [`DpdkGrpcChannel`] — `!Send` gRPC client channel over HTTP/2
+//! - [`DpdkGrpcChannel`] — `!Send` gRPC client channel over pooled HTTP/2
+//!   connections
 //! - [`bridge`] — OS thread adapters for tonic's native transport APIs
 
 pub mod bridge;
 mod channel;
 mod serve;
 
-pub use channel::DpdkGrpcChannel;
+pub use channel::{ChannelResponseFuture, DpdkGrpcChannel};
 pub use serve::serve;
@@ -1,26 +1,34 @@
 //! DpdkApp builder and runner.
 
 use crate::context::WorkerContext;
+use crate::ready::{OnReady, ReadyBarrier};
+use crate::report::{QueueReport, RssReport, RunReport, ShutdownStatus};
 
-use dpdk_net::api::rte::eth::{EthConf, EthDev, EthDevBuilder, RxQueueConf, TxQueueConf, rss_hf};
+use dpdk_net::api::rte::eal::{Eal, EalBuilder};
+use dpdk_net::api::rte::eth::{
+    ChecksumOffload, EthConf, EthDev, EthDevBuilder, RxQueueConf, TxQueueConf, rss_hf,
+};
 use dpdk_net::api::rte::lcore::Lcore;
 use dpdk_net::api::rte::pktmbuf::{MemPool, MemPoolConfig};
 use dpdk_net::api::rte::queue::{RxQueue, TxQueue};
-use dpdk_net::device::{DpdkDevice, SharedArpCache};
-use dpdk_net::runtime::Reactor;
+use dpdk_net::api::rte::stats::{QueueStats, StatsSampler};
+use dpdk_net::api::rte::thread::set_thread_name;
+use dpdk_net::device::{DEFAULT_ARP_TTL, DEFAULT_RX_BURST_SIZE, DpdkDevice, SharedArpCache};
+use dpdk_net::runtime::{Reactor, ReactorConfig, ReactorHandle, check_routes, queue_port_range};
+use dpdk_net::topology::verify_isolation;
 
-use smoltcp::iface::{Config, Interface};
-use smoltcp::time::Instant;
-use smoltcp::wire::{EthernetAddress, IpAddress, IpCidr, Ipv4Address};
+use smoltcp::wire::{EthernetAddress, IpAddress, IpCidr, Ipv4Address, Ipv6Address};
 
 use std::cell::Cell;
 use std::future::Future;
 use std::net::Ipv4Addr;
+use std::panic::{self, AssertUnwindSafe};
 use std::rc::Rc;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use tokio::runtime::Builder;
-use tracing::{debug, info, warn};
+use tracing::{debug, error, info, warn};
 
 /// Default headroom reserved at the front of each mbuf
 const DEFAULT_MBUF_HEADROOM: usize = 128;
@@ -31,12 +39,61 @@ const DEFAULT_MBUF_DATA_ROOM_SIZE: u16 = 2048 + DEFAULT_MBUF_HEADROOM as u16;
 /// Default MTU
 const DEFAULT_MTU: usize = 1500;
 
+/// Maximum number of IP addresses per interface.
+///
+/// Matches the `iface-max-addr-count-8` smoltcp feature enabled for the workspace.
+pub const MAX_IP_ADDRS: usize = 8;
+
+/// Per-app settings shared by every worker.
+#[derive(Clone)]
+struct WorkerSetup {
+    port_id: u16,
+    mempool: Arc<MemPool>,
+    mac_addr: EthernetAddress,
+    ip_addr: Ipv4Address,
+    ip_cidrs: Vec<IpCidr>,
+    gateway: Option<Ipv4Address>,
+    routes: Vec<(IpCidr, IpAddress)>,
+    num_queues: u16,
+    shared_arp_cache: Option<SharedArpCache>,
+    ready: ReadyBarrier,
+    stats_interval: Option<Duration>,
+    shutdown_timeout: Duration,
+    drain_timeout: Duration,
+    checksum_offload: ChecksumOffload,
+    rx_burst_size: usize,
+    #[cfg(feature = "tls")]
+    tls: Option<Arc<rustls::ServerConfig>>,
+    reports: Arc<Mutex<Vec<QueueReport>>>,
+}
+
 /// Builder for configuring and running a DPDK application.
 ///
 /// `DpdkApp` uses DPDK's native lcore threading model, where:
 /// - EAL creates lcore threads during `rte_eal_init()`
 /// - Each lcore gets its own RX/TX queue
 /// - Queue count equals lcore count
+/// - Each worker thread is named `dpdk-q{queue_id}` for `top -H` and `perf`;
+///   on the main lcore that renames the process as well
+///
+/// # EAL lifecycle
+///
+/// EAL must be up before `run` and must stay up until the device is closed
+/// and the mempool freed. There are three ways to arrange that:
+///
+/// - Initialize EAL yourself and keep the guard alive past `run`, as in the
+///   example below. Dropping it early tears DPDK down under the app.
+/// - Hand the guard to the app with [`with_eal`](Self::with_eal); it is
+///   dropped after everything else the app created.
+/// - Pass an [`EalBuilder`] to [`eal`](Self::eal); the app initializes EAL
+///   at the start of `run` and cleans it up at the end.
+///
+/// In the last two cases EAL is cleaned up when `run` returns, and it
+/// cannot be initialized again in the same process.
+///
+/// [`file_prefix`](Self::file_prefix) and [`in_memory`](Self::in_memory) add
+/// their flags to the EAL the app initializes. Setting either without
+/// [`eal`](Self::eal) has the app initialize EAL from a default builder.
 ///
 /// # Example
 ///
@@ -59,17 +116,49 @@ const DEFAULT_MTU: usize = 1500;
 ///         .gateway(Ipv4Address::new(10, 0, 0, 1))
 ///         .run(|ctx| async move {
 ///             let listener = TcpListener::bind(&ctx.reactor, 8080, 4096, 4096).unwrap();
+///             ctx.mark_ready();
 ///             // ... serve until done, then return
 ///         });
 /// }
 /// ```
 pub struct DpdkApp {
+    eal: EalSource,
+    /// `--file-prefix` for EAL initialized by the app.
+    file_prefix: Option<String>,
+    /// Add `--in-memory` to EAL initialized by the app.
+    in_memory: bool,
     port_id: u16,
     ip_addr: Option<Ipv4Address>,
+    ipv6_addr: Option<IpCidr>,
+    extra_ips: Vec<IpCidr>,
     gateway: Option<Ipv4Address>,
+    gateway_v6: Option<Ipv6Address>,
+    routes: Vec<(IpCidr, IpAddress)>,
     mbufs_per_queue: u32,
+    mempool_cache_size: u32,
+    warm_mempool: bool,
     rx_desc: u16,
     tx_desc: u16,
+    rx_burst_size: usize,
+    on_all_ready: Option<OnReady>,
+    stats_interval: Option<Duration>,
+    shutdown_timeout: Duration,
+    drain_timeout: Duration,
+    link_timeout: Option<Duration>,
+    checksum_offload: bool,
+    arp_ttl: Duration,
+    #[cfg(feature = "tls")]
+    tls: Option<Arc<rustls::ServerConfig>>,
+}
+
+/// Who brings up EAL for a [`DpdkApp`].
+enum EalSource {
+    /// The caller did, and holds the guard.
+    External,
+    /// The app initializes EAL at the start of `run`.
+    Builder(EalBuilder),
+    /// The caller handed over its guard.
+    Guard(Eal),
 }
 
 impl Default for DpdkApp {
@@ -82,15 +171,84 @@ impl DpdkApp {
     /// Create a new DpdkApp builder.
     pub fn new() -> Self {
         Self {
+            eal: EalSource::External,
+            file_prefix: None,
+            in_memory: false,
             port_id: 0,
             ip_addr: None,
+            ipv6_addr: None,
+            extra_ips: Vec::new(),
             gateway: None,
+            gateway_v6: None,
+            routes: Vec::new(),
             mbufs_per_queue: 8192,
+            mempool_cache_size: 256,
+            warm_mempool: false,
             rx_desc: 1024,
             tx_desc: 1024,
+            rx_burst_size: DEFAULT_RX_BURST_SIZE,
+            on_all_ready: None,
+            stats_interval: None,
+            shutdown_timeout: Duration::ZERO,
+            drain_timeout: Duration::ZERO,
+            link_timeout: None,
+            checksum_offload: true,
+            arp_ttl: DEFAULT_ARP_TTL,
+            #[cfg(feature = "tls")]
+            tls: None,
         }
     }
 
+    /// Initialize EAL from `builder` when the app runs, and clean it up
+    /// after the device and mempool are gone.
+    ///
+    /// See [EAL lifecycle](Self#eal-lifecycle).
+    pub fn eal(mut self, builder: EalBuilder) -> Self {
+        self.eal = EalSource::Builder(builder);
+        self
+    }
+
+    /// Keep an already initialized EAL alive for the duration of the run.
+    ///
+    /// The guard is dropped, cleaning up EAL, only after the device has been
+    /// closed and the mempool freed. See [EAL lifecycle](Self#eal-lifecycle).
+    pub fn with_eal(mut self, eal: Eal) -> Self {
+        self.eal = EalSource::Guard(eal);
+        self
+    }
+
+    /// Pass `--file-prefix=<prefix>` to EAL, so several DPDK processes can run
+    /// on one host without sharing runtime files.
+    ///
+    /// Applies only to EAL initialized by the app: the builder given to
+    /// [`eal`](Self::eal), or a default [`EalBuilder`] if none was given and
+    /// EAL is not up yet. With [`with_eal`](Self::with_eal), or when EAL was
+    /// initialized beforehand, it is ignored with a warning.
+    pub fn file_prefix(mut self, prefix: &str) -> Self {
+        self.file_prefix = Some(prefix.to_string());
+        self
+    }
+
+    /// Pass `--in-memory` to EAL, so no hugepage or runtime files are left
+    /// behind (default: off).
+    ///
+    /// Applies under the same conditions as [`file_prefix`](Self::file_prefix).
+    pub fn in_memory(mut self, enabled: bool) -> Self {
+        self.in_memory = enabled;
+        self
+    }
+
+    /// Add the app's EAL options to `builder`.
+    fn with_eal_options(&self, mut builder: EalBuilder) -> EalBuilder {
+        if let Some(prefix) = &self.file_prefix {
+            builder = builder.file_prefix(prefix.clone());
+        }
+        if self.in_memory {
+            builder = builder.in_memory();
+        }
+        builder
+    }
+
     /// Set the DPDK port ID (default: 0).
     pub fn eth_dev(mut self, port_id: u16) -> Self {
         self.port_id = port_id;
@@ -103,18 +261,95 @@ impl DpdkApp {
         self
     }
 
-    /// Set the gateway address.
+    /// Set an IPv6 address with its prefix length, for dual-stack serving.
+    ///
+    /// It is configured right after the IPv4 address from [`ip`](Self::ip),
+    /// which is still required. Listeners accept on both families; outbound
+    /// IPv6 connections need this address to send from.
+    ///
+    /// Neighbor discovery is per queue: unlike ARP replies, which queue 0
+    /// shares with the others, each queue resolves IPv6 neighbors itself,
+    /// and RSS may steer a neighbor advertisement to another queue than the
+    /// one that solicited it.
+    pub fn ipv6(mut self, addr: Ipv6Address, prefix_len: u8) -> Self {
+        self.ipv6_addr = Some(IpCidr::new(IpAddress::Ipv6(addr), prefix_len));
+        self
+    }
+
+    /// Add an extra IP address (alias) to the interface.
+    ///
+    /// Can be called repeatedly. The primary address from [`ip`](Self::ip)
+    /// is always configured first as a /24, then the one from
+    /// [`ipv6`](Self::ipv6). Listeners bound via
+    /// `TcpListener::bind` accept connections to any configured address;
+    /// `TcpListener::bind_addr` restricts one to a single address.
+    ///
+    /// At most [`MAX_IP_ADDRS`] addresses (including the primary) are supported.
+    pub fn add_ip(mut self, cidr: IpCidr) -> Self {
+        self.extra_ips.push(cidr);
+        self
+    }
+
+    /// Set the gateway address, used as the default IPv4 route.
+    ///
+    /// Without a gateway only the on-link subnets of the configured
+    /// addresses are routed, which suits point-to-point links and
+    /// back-to-back NICs with no router. Connections to any other
+    /// destination then fail to leave the host.
     pub fn gateway(mut self, addr: Ipv4Address) -> Self {
         self.gateway = Some(addr);
         self
     }
 
+    /// Set the IPv6 gateway, installed as a `::/0` route.
+    ///
+    /// As with [`gateway`](Self::gateway), without one only on-link IPv6
+    /// destinations are reachable. It counts as a route, so `run` panics if
+    /// [`add_route`](Self::add_route) also adds a `::/0` route.
+    pub fn gateway_v6(mut self, addr: Ipv6Address) -> Self {
+        self.gateway_v6 = Some(addr);
+        self
+    }
+
+    /// Route `cidr` via `next_hop`, in addition to the gateway (repeatable).
+    ///
+    /// Use it for networks reached through a different router than the
+    /// default one. The longest matching prefix wins, so a more specific
+    /// route overrides a broader one, including the default.
+    ///
+    /// `run` panics if a destination is listed twice or a `0.0.0.0/0` route
+    /// conflicts with [`gateway`](Self::gateway); see
+    /// [`check_routes`](dpdk_net::runtime::check_routes). At most 8 routes
+    /// fit, the gateways included.
+    pub fn add_route(mut self, cidr: IpCidr, next_hop: IpAddress) -> Self {
+        self.routes.push((cidr, next_hop));
+        self
+    }
+
     /// Set mbufs per queue (default: 8192).
     pub fn mbufs_per_queue(mut self, count: u32) -> Self {
         self.mbufs_per_queue = count;
         self
     }
 
+    /// Set the mempool's per-lcore cache size (default: 256).
+    ///
+    /// See [`MemPoolConfig::cache_size`] for sizing. The pool holds
+    /// `mbufs_per_queue` times the queue count, so the cache must fit in that.
+    pub fn mempool_cache_size(mut self, size: u32) -> Self {
+        self.mempool_cache_size = size;
+        self
+    }
+
+    /// Touch every mbuf before the device starts (default: false).
+    ///
+    /// Runs [`MemPool::warm`] on the new pool so the first packets do not
+    /// take page faults, at the cost of a slower startup.
+    pub fn warm_mempool(mut self, enable: bool) -> Self {
+        self.warm_mempool = enable;
+        self
+    }
+
     /// Set RX/TX descriptors (default: 1024).
     pub fn descriptors(mut self, rx: u16, tx: u16) -> Self {
         self.rx_desc = rx;
@@ -122,10 +357,126 @@ impl DpdkApp {
         self
     }
 
+    /// Set how many frames each worker's device asks its RX queue for per
+    /// burst (default: [`DEFAULT_RX_BURST_SIZE`]).
+    ///
+    /// See [`DpdkDevice::with_rx_burst_size`].
+    pub fn rx_burst_size(mut self, size: usize) -> Self {
+        self.rx_burst_size = size;
+        self
+    }
+
+    /// Set a callback invoked once every worker has reported ready.
+    ///
+    /// Workers report ready via [`WorkerContext::mark_ready`], or implicitly
+    /// when their closure returns. The callback runs on the lcore thread of
+    /// the last worker to arrive, so it should not block.
+    pub fn on_all_ready<C>(mut self, callback: C) -> Self
+    where
+        C: FnOnce() + Send + 'static,
+    {
+        self.on_all_ready = Some(Box::new(callback));
+        self
+    }
+
+    /// Log port throughput every `interval` (default: off).
+    ///
+    /// Queue 0 samples the port counters with a [`StatsSampler`] and logs
+    /// RX/TX packet and bit rates plus drop rates at `info` level.
+    pub fn stats_interval(mut self, interval: Duration) -> Self {
+        self.stats_interval = Some(interval);
+        self
+    }
+
+    /// Let sockets finish closing for up to `timeout` after a worker's
+    /// closure returns (default: zero).
+    ///
+    /// Streams dropped or closed at the end of a worker need a few more
+    /// reactor polls to get their FIN or RST out. With the default the
+    /// reactor stops right away; the [`RunReport`] records any sockets that
+    /// were still open as [`ShutdownStatus::TimedOut`].
+    pub fn shutdown_timeout(mut self, timeout: Duration) -> Self {
+        self.shutdown_timeout = timeout;
+        self
+    }
+
+    /// Keep polling for up to `timeout` after a worker's closure returns,
+    /// until every stream it dropped has finished closing (default: zero).
+    ///
+    /// A server that stops on shutdown typically drops its listener and
+    /// connections on the way out. Their FIN exchanges need the reactor,
+    /// and stopping it early leaves clients with a reset instead of a clean
+    /// close. Unlike [`shutdown_timeout`](Self::shutdown_timeout), which
+    /// waits for every socket to be gone, this only waits for the closes
+    /// already under way (see `ReactorHandle::closing_count`), so sockets a
+    /// leftover task still holds do not hold up the exit. When both are
+    /// set, the worker waits as long as either still applies.
+    pub fn drain_timeout(mut self, timeout: Duration) -> Self {
+        self.drain_timeout = timeout;
+        self
+    }
+
+    /// Hand IPv4/TCP/UDP checksums to the NIC where it supports them
+    /// (default: true).
+    ///
+    /// Each direction (RX verification, TX computation) is offloaded only if
+    /// the driver advertises all three checksums for it; the rest stays in
+    /// software. Turn it off to rule the NIC out when chasing corrupted
+    /// traffic.
+    pub fn checksum_offload(mut self, enabled: bool) -> Self {
+        self.checksum_offload = enabled;
+        self
+    }
+
+    /// How long a MAC learned into the multi-queue ARP cache is trusted
+    /// without a fresh reply (default: [`DEFAULT_ARP_TTL`]).
+    ///
+    /// With several queues, queue 0 shares the ARP replies it receives with
+    /// the others. Once an entry is older than `ttl`, queue 0 drops it and
+    /// ARPs for the peer again, so a gateway whose MAC moved (failover,
+    /// VRRP) is picked up. Single-queue apps rely on smoltcp's own cache.
+    pub fn arp_ttl(mut self, ttl: Duration) -> Self {
+        self.arp_ttl = ttl;
+        self
+    }
+
+    /// Terminate TLS on accepted connections with `config` (feature: `tls`).
+    ///
+    /// Workers see it through [`WorkerContext::is_tls`];
+    /// [`serve_worker`](crate::serve::serve_worker) then runs the handshake
+    /// on every connection before hyper, and closures that accept streams
+    /// themselves wrap them with [`WorkerContext::accept_tls`]. Unless
+    /// `config` already lists ALPN protocols, `h2` and `http/1.1` are
+    /// offered, in that order, so HTTP/2 clients get HTTP/2 over TLS.
+    #[cfg(feature = "tls")]
+    pub fn tls(mut self, mut config: rustls::ServerConfig) -> Self {
+        if config.alpn_protocols.is_empty() {
+            config.alpn_protocols = vec![
+                crate::tls::ALPN_H2.to_vec(),
+                crate::tls::ALPN_HTTP1.to_vec(),
+            ];
+        }
+        self.tls = Some(Arc::new(config));
+        self
+    }
+
+    /// Wait up to `timeout` for the link to come up before starting the
+    /// workers (default: don't wait).
+    ///
+    /// A freshly started port may take a few seconds to negotiate its link,
+    /// and whatever the workers send or accept before then is lost. If the
+    /// link is still down when `timeout` runs out, a warning is logged and
+    /// the workers start anyway.
+    pub fn wait_for_link(mut self, timeout: Duration) -> Self {
+        self.link_timeout = Some(timeout);
+        self
+    }
+
     /// Run the application.
     ///
     /// Launches work on all worker lcores and runs queue 0 on the main lcore.
-    /// Blocks until all worker closures return.
+    /// Blocks until all worker closures return. Use
+    /// [`run_reporting`](Self::run_reporting) to get a [`RunReport`] back.
     ///
     /// # Arguments
     ///
@@ -135,21 +486,104 @@ impl DpdkApp {
     /// # Panics
     ///
     /// Panics if:
+    /// - EAL initialization from [`eal`](Self::eal) fails
     /// - IP address is not set
-    /// - Gateway is not set
+    /// - Routes from [`add_route`](Self::add_route) conflict
     /// - No lcores are available
     /// - Ethernet device configuration fails
+    /// - A worker's closure panics. The panic is re-raised on the calling
+    ///   thread once every other worker has returned and the device is
+    ///   stopped; the panicking worker's reactor is shut down regardless.
     pub fn run<F, Fut>(self, server: F)
     where
         F: Fn(WorkerContext) -> Fut + Send + Sync + 'static,
         Fut: Future<Output = ()> + 'static,
     {
+        self.run_reporting(server);
+    }
+
+    /// Run the application and return a summary of the run.
+    ///
+    /// Behaves like [`run`](Self::run). Once every worker has finished, the
+    /// port and per-queue counters are read and combined with each worker's
+    /// connection count and shutdown status.
+    ///
+    /// # Panics
+    ///
+    /// Same as [`run`](Self::run).
+    pub fn run_reporting<F, Fut>(mut self, server: F) -> RunReport
+    where
+        F: Fn(WorkerContext) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + 'static,
+    {
+        let has_eal_options = self.file_prefix.is_some() || self.in_memory;
+        let source = match std::mem::replace(&mut self.eal, EalSource::External) {
+            EalSource::External if has_eal_options && !Eal::is_initialized() => {
+                EalSource::Builder(EalBuilder::new())
+            }
+            source => source,
+        };
+        if has_eal_options && !matches!(source, EalSource::Builder(_)) {
+            warn!("EAL already initialized; ignoring file_prefix/in_memory");
+        }
+        // Declared before anything else so it drops last, after the device
+        // and mempool
+        let _eal = match source {
+            EalSource::External => None,
+            EalSource::Builder(builder) => Some(
+                self.with_eal_options(builder)
+                    .init()
+                    .expect("Failed to initialize EAL"),
+            ),
+            EalSource::Guard(eal) => Some(eal),
+        };
+
+        let started = Instant::now();
         let ip_addr = self
             .ip_addr
             .expect("IP address not set. Call ip() before run()");
-        let gateway = self
-            .gateway
-            .expect("Gateway not set. Call gateway() before run()");
+        let gateway = self.gateway;
+
+        let mut ip_cidrs = vec![IpCidr::new(IpAddress::Ipv4(ip_addr), 24)];
+        ip_cidrs.extend(self.ipv6_addr);
+        ip_cidrs.extend(self.extra_ips.iter().copied());
+        if ip_cidrs.len() > MAX_IP_ADDRS {
+            panic!(
+                "Too many IP addresses ({}); at most {} are supported per interface",
+                ip_cidrs.len(),
+                MAX_IP_ADDRS
+            );
+        }
+        match gateway {
+            Some(gateway) if !is_on_link(gateway, &ip_cidrs) => warn!(
+                %gateway,
+                "Gateway is outside every configured subnet; neighbor resolution for it will fail"
+            ),
+            Some(_) => {}
+            None => info!(
+                subnets = ?ip_cidrs,
+                "No gateway: only on-link destinations are routed"
+            ),
+        }
+        if let Some(gateway_v6) = self.gateway_v6 {
+            if !is_on_link(gateway_v6, &ip_cidrs) {
+                warn!(
+                    %gateway_v6,
+                    "IPv6 gateway is outside every configured subnet; neighbor resolution for it will fail"
+                );
+            }
+            let default = IpCidr::new(IpAddress::Ipv6(Ipv6Address::UNSPECIFIED), 0);
+            self.routes
+                .insert(0, (default, IpAddress::Ipv6(gateway_v6)));
+        }
+        if let Err(e) = check_routes(gateway, &self.routes) {
+            panic!("Invalid routes: {e}");
+        }
+        for &(cidr, next_hop) in &self.routes {
+            if !is_on_link(next_hop, &ip_cidrs) {
+                warn!(%cidr, %next_hop, "Route next hop is outside every configured subnet");
+            }
+        }
 
         // Collect lcores
         let lcores: Vec<Lcore> = Lcore::all().collect();
@@ -163,10 +597,23 @@ impl DpdkApp {
             num_lcores = num_queues,
             port_id = self.port_id,
             ip = %ip_addr,
-            %gateway,
+            ipv6 = ?self.ipv6_addr,
+            gateway = ?gateway,
             "DpdkApp starting"
         );
 
+        // Polling lcores sharing a CPU with other tasks get preempted
+        let cpus: Vec<usize> = lcores
+            .iter()
+            .filter_map(|l| l.cpu_id())
+            .map(|cpu| cpu as usize)
+            .collect();
+        match verify_isolation(&cpus) {
+            Ok(report) if report.is_isolated() => info!(?cpus, "Lcores run on isolated cores"),
+            Ok(_) => {}
+            Err(e) => debug!(error = %e, "Could not check core isolation"),
+        }
+
         // Query device info
         let dev_info = EthDev::new(self.port_id)
             .info()
@@ -177,11 +624,21 @@ impl DpdkApp {
         let total_mbufs = self.mbufs_per_queue * num_queues as u32;
         let mempool_config = MemPoolConfig::new()
             .num_mbufs(total_mbufs)
+            .cache_size(self.mempool_cache_size)
             .data_room_size(DEFAULT_MBUF_DATA_ROOM_SIZE);
 
+        // A fixed name would collide with a pool left behind by an earlier run
+        let pool_name = MemPool::unique_name("dpdk_app_pool");
         let mempool = Arc::new(
-            MemPool::create("dpdk_app_pool", &mempool_config).expect("Failed to create mempool"),
+            MemPool::create(pool_name.as_str(), &mempool_config)
+                .unwrap_or_else(|e| panic!("Failed to create mempool {pool_name}: {e}")),
         );
+        debug!(name = %pool_name, mbufs = total_mbufs, "Mempool created");
+        if self.warm_mempool {
+            let start = Instant::now();
+            let warmed = mempool.warm();
+            info!(warmed, elapsed = ?start.elapsed(), "Mempool warmed");
+        }
 
         // Configure ethernet device with RSS if supported
         let eth_conf = if reta_size > 0 && num_queues > 1 {
@@ -195,6 +652,19 @@ impl DpdkApp {
             }
             EthConf::new()
         };
+        let checksum_offload = if self.checksum_offload {
+            ChecksumOffload::from_offloads(dev_info.rx_offload_capa, dev_info.tx_offload_capa)
+        } else {
+            ChecksumOffload::default()
+        };
+        if checksum_offload.any() {
+            info!(
+                rx = checksum_offload.rx,
+                tx = checksum_offload.tx,
+                "Enabling checksum offload"
+            );
+        }
+        let eth_conf = eth_conf.checksum_offload_for(checksum_offload);
 
         let eth_dev = EthDevBuilder::new(self.port_id)
             .eth_conf(eth_conf)
@@ -212,15 +682,29 @@ impl DpdkApp {
         info!(
             mac = ?mac_addr,
             ip = %ip_addr,
-            %gateway,
+            gateway = ?gateway,
             queues = num_queues,
             "Ethernet device configured"
         );
 
+        if let Some(timeout) = self.link_timeout {
+            match eth_dev.wait_for_link(timeout) {
+                Ok(link) => info!(
+                    speed_mbps = link.speed_mbps,
+                    full_duplex = link.full_duplex,
+                    "Link up"
+                ),
+                Err(e) => warn!(?timeout, error = %e, "Link not up; starting workers anyway"),
+            }
+        }
+
+        // What the driver actually enabled decides whether TCP works across queues
+        let rss = (num_queues > 1).then(|| check_rss(&eth_dev)).flatten();
+
         // Create shared ARP cache for multi-queue setups
         let shared_arp_cache = if num_queues > 1 {
             info!("Multi-queue mode: using shared ARP cache");
-            Some(SharedArpCache::new())
+            Some(SharedArpCache::with_ttl(self.arp_ttl))
         } else {
             None
         };
@@ -228,6 +712,31 @@ impl DpdkApp {
         // Wrap server in Arc for sharing
         let server = Arc::new(server);
 
+        // Startup barrier: released once every queue has reported ready
+        let ready = ReadyBarrier::new(num_queues, self.on_all_ready.take());
+
+        let setup = WorkerSetup {
+            port_id: self.port_id,
+            mempool: mempool.clone(),
+            mac_addr,
+            ip_addr,
+            ip_cidrs,
+            gateway,
+            routes: self.routes.clone(),
+            num_queues: num_queues as u16,
+            shared_arp_cache,
+            ready,
+            stats_interval: self.stats_interval,
+            shutdown_timeout: self.shutdown_timeout,
+            drain_timeout: self.drain_timeout,
+            checksum_offload,
+            rx_burst_size: self.rx_burst_size,
+            #[cfg(feature = "tls")]
+            tls: self.tls.clone(),
+            reports: Arc::new(Mutex::new(Vec::with_capacity(num_queues))),
+        };
+        let reports = setup.reports.clone();
+
         // Launch on worker lcores (all except main)
         let _main_lcore = Lcore::main();
         let mut main_queue_id = 0u16;
@@ -238,70 +747,93 @@ impl DpdkApp {
                 continue; // Run on main thread after launching workers
             }
 
-            let mempool = mempool.clone();
-            let shared_arp_cache = shared_arp_cache.clone();
+            let setup = setup.clone();
             let server = server.clone();
             let queue_id = queue_id as u16;
-            let port_id = self.port_id;
 
+            // A panic must not unwind into the EAL's C launch trampoline
             lcore
                 .launch(move || {
-                    Self::run_worker(
-                        queue_id,
-                        port_id,
-                        mempool,
-                        mac_addr,
-                        ip_addr,
-                        gateway,
-                        shared_arp_cache,
-                        server,
-                    );
-                    0
+                    let worker = || Self::run_worker(queue_id, setup, server);
+                    match panic::catch_unwind(AssertUnwindSafe(worker)) {
+                        Ok(()) => 0,
+                        Err(_) => WORKER_PANICKED,
+                    }
                 })
                 .expect("Failed to launch on worker lcore");
         }
 
         // Run main queue on main lcore
-        Self::run_worker(
-            main_queue_id,
-            self.port_id,
-            mempool.clone(),
-            mac_addr,
-            ip_addr,
-            gateway,
-            shared_arp_cache,
-            server,
-        );
+        let main_result = panic::catch_unwind(AssertUnwindSafe(|| {
+            Self::run_worker(main_queue_id, setup, server)
+        }));
 
         // Wait for all workers to finish
-        Lcore::wait_all_workers();
+        let panicked: Vec<u16> = lcores
+            .iter()
+            .enumerate()
+            .filter(|(_, lcore)| !lcore.is_main() && lcore.wait() == WORKER_PANICKED)
+            .map(|(queue_id, _)| queue_id as u16)
+            .collect();
 
         info!("All workers finished, cleaning up");
 
+        // Read counters before stopping the device
+        let raw_stats = eth_dev.stats().ok();
+        match eth_dev.xstats() {
+            Ok(xstats) => {
+                for (name, value) in xstats.iter().filter(|(_, value)| *value > 0) {
+                    debug!(name = %name, value, "Extended port counter");
+                }
+            }
+            Err(e) => debug!(error = %e, "Extended port counters unavailable"),
+        }
+        let mut queues = std::mem::take(&mut *reports.lock().unwrap());
+        queues.sort_by_key(|q| q.queue_id);
+        for queue in &mut queues {
+            queue.stats = raw_stats
+                .as_ref()
+                .and_then(|raw| QueueStats::from_raw(raw, queue.queue_id));
+        }
+        let report = RunReport {
+            port: raw_stats.as_ref().map(Into::into).unwrap_or_default(),
+            queues,
+            elapsed: started.elapsed(),
+            rss,
+        };
+        info!(
+            mbufs_in_use_peak = report.mbufs_in_use_peak(),
+            mbufs = total_mbufs,
+            tx_alloc_failures = report.tx_alloc_failures(),
+            "Mempool high-water mark"
+        );
+
         // Cleanup
         let _ = eth_dev.stop();
         let _ = eth_dev.close();
         drop(mempool);
 
+        if let Err(payload) = main_result {
+            panic::resume_unwind(payload);
+        }
+        if !panicked.is_empty() {
+            panic!("DpdkApp worker panicked on queues {panicked:?}");
+        }
+
         info!("DpdkApp shutdown complete");
+        report
     }
 
     /// Run a single worker on the current lcore.
-    #[allow(clippy::too_many_arguments)]
-    fn run_worker<F, Fut>(
-        queue_id: u16,
-        port_id: u16,
-        mempool: Arc<MemPool>,
-        mac_addr: EthernetAddress,
-        ip_addr: Ipv4Address,
-        gateway: Ipv4Address,
-        shared_arp_cache: Option<SharedArpCache>,
-        server: Arc<F>,
-    ) where
+    fn run_worker<F, Fut>(queue_id: u16, setup: WorkerSetup, server: Arc<F>)
+    where
         F: Fn(WorkerContext) -> Fut + Send + Sync + 'static,
         Fut: Future<Output = ()> + 'static,
     {
         let lcore = Lcore::current().expect("Not running on an lcore");
+        if let Err(e) = set_thread_name(&format!("dpdk-q{queue_id}")) {
+            warn!(queue_id, error = %e, "Failed to name worker thread");
+        }
         debug!(
             queue_id,
             lcore_id = lcore.id(),
@@ -310,10 +842,33 @@ impl DpdkApp {
         );
 
         // Create DPDK device for this queue
+        let WorkerSetup {
+            port_id,
+            mempool,
+            mac_addr,
+            ip_addr,
+            ip_cidrs,
+            gateway,
+            routes,
+            num_queues,
+            shared_arp_cache,
+            ready,
+            stats_interval,
+            shutdown_timeout,
+            drain_timeout,
+            checksum_offload,
+            rx_burst_size,
+            #[cfg(feature = "tls")]
+            tls,
+            reports,
+        } = setup;
+
         let rxq = RxQueue::new(port_id, queue_id);
         let txq = TxQueue::new(port_id, queue_id);
         let mbuf_capacity = DEFAULT_MBUF_DATA_ROOM_SIZE as usize - DEFAULT_MBUF_HEADROOM;
-        let mut device = DpdkDevice::new(rxq, txq, mempool, DEFAULT_MTU, mbuf_capacity);
+        let mut device = DpdkDevice::new(rxq, txq, mempool, DEFAULT_MTU, mbuf_capacity)
+            .with_checksum_offload(checksum_offload)
+            .with_rx_burst_size(rx_burst_size);
 
         // Configure shared ARP cache if multi-queue
         if let Some(cache) = shared_arp_cache {
@@ -330,15 +885,12 @@ impl DpdkApp {
         }
 
         // Configure smoltcp interface
-        let config = Config::new(mac_addr.into());
-        let mut iface = Interface::new(config, &mut device, Instant::now());
-
-        iface.update_ip_addrs(|ip_addrs| {
-            ip_addrs
-                .push(IpCidr::new(IpAddress::Ipv4(ip_addr), 24))
-                .unwrap();
-        });
-        iface.routes_mut().add_default_ipv4_route(gateway).unwrap();
+        let mut config = ReactorConfig::new(mac_addr);
+        config.ipv4_gateway = gateway;
+        config.routes = routes;
+        config.ip_addrs = ip_cidrs;
+        // Keep outbound connections of different queues from sharing a 4-tuple
+        config.ephemeral_ports = queue_port_range(queue_id, num_queues);
 
         // Create tokio runtime
         let rt = Builder::new_current_thread().build().unwrap();
@@ -346,10 +898,12 @@ impl DpdkApp {
 
         local.block_on(&rt, async {
             // Create reactor
-            let reactor = Reactor::new(device, iface);
+            let reactor =
+                Reactor::new_with_config(device, config).expect("Failed to configure interface");
             let handle = reactor.handle();
+            let report_handle = handle.clone();
 
-            // Reactor cancel flag
+            // Reactor cancel flag, set by the guard however this block exits
             let reactor_cancel = Rc::new(Cell::new(false));
             let reactor_cancel_clone = reactor_cancel.clone();
 
@@ -358,22 +912,197 @@ impl DpdkApp {
                 reactor.run(reactor_cancel_clone).await;
             });
 
+            // Periodic port stats, logged once per port from queue 0
+            let stats_task = stats_interval.filter(|_| queue_id == 0).map(|interval| {
+                let cancel = reactor_cancel.clone();
+                tokio::task::spawn_local(log_port_stats(handle.clone(), port_id, interval, cancel))
+            });
+
             // Create worker context
+            let marked = Rc::new(Cell::new(false));
+            let guard = WorkerGuard {
+                reactor_cancel: reactor_cancel.clone(),
+                ready: ready.clone(),
+                marked: marked.clone(),
+            };
             let ctx = WorkerContext {
                 lcore,
                 queue_id,
                 socket_id: lcore.socket_id(),
                 reactor: handle,
+                ready: ready.clone(),
+                marked: marked.clone(),
+                #[cfg(feature = "tls")]
+                tls,
             };
 
             // Run user's server/client
             server(ctx).await;
 
+            // Count a worker that never marked itself ready, so waiters don't hang
+            guard.arrive();
+
+            // Give closing sockets a chance to finish before stopping
+            let stopping = Instant::now();
+            let (deadline, drain_deadline) =
+                (stopping + shutdown_timeout, stopping + drain_timeout);
+            loop {
+                let now = Instant::now();
+                let waiting = (report_handle.socket_count() > 0 && now < deadline)
+                    || (report_handle.closing_count() > 0 && now < drain_deadline);
+                if !waiting {
+                    break;
+                }
+                report_handle.sleep(Duration::from_millis(1)).await;
+            }
+            let unclosed = report_handle.closing_count();
+            if unclosed > 0 && !drain_timeout.is_zero() {
+                warn!(
+                    queue_id,
+                    unclosed, "Drain timeout elapsed with streams still closing"
+                );
+            }
+            let shutdown = match report_handle.socket_count() {
+                0 => ShutdownStatus::Clean,
+                open_sockets => ShutdownStatus::TimedOut { open_sockets },
+            };
+            reports.lock().unwrap().push(QueueReport {
+                queue_id,
+                lcore_id: lcore.id(),
+                stats: None,
+                connections_accepted: report_handle.connections_accepted(),
+                device: report_handle.device_stats(),
+                shutdown,
+            });
+
             // Signal reactor to stop
-            reactor_cancel.set(true);
+            drop(guard);
             let _ = reactor_task.await;
+            if let Some(task) = stats_task {
+                task.abort();
+            }
         });
 
         debug!(queue_id, "Worker finished");
     }
 }
+
+/// Returns true if `addr` falls inside one of the interface's subnets.
+fn is_on_link(addr: impl Into<IpAddress>, cidrs: &[IpCidr]) -> bool {
+    let addr = addr.into();
+    cidrs.iter().any(|cidr| cidr.contains_addr(&addr))
+}
+
+/// Read back the device's RSS configuration and warn if TCP is not hashed.
+fn check_rss(eth_dev: &EthDev) -> Option<RssReport> {
+    let (rss_hf, key) = match eth_dev.rss_hash_conf() {
+        Ok(conf) => conf,
+        Err(e) => {
+            warn!(
+                error = %e,
+                "Failed to read RSS configuration; TCP flows may not stay on one queue"
+            );
+            return None;
+        }
+    };
+    let rss = RssReport { rss_hf, key };
+    if rss.hashes_tcp() {
+        info!(rss_hf = format!("{:#x}", rss_hf), "RSS hashing TCP ports");
+    } else {
+        error!(
+            rss_hf = format!("{:#x}", rss_hf),
+            "RSS is not hashing TCP ports: packets of a connection can arrive on a \
+             queue that does not own it, and multi-queue TCP will fail"
+        );
+    }
+    Some(rss)
+}
+
+/// Return code of a worker lcore whose closure panicked.
+const WORKER_PANICKED: i32 = -1;
+
+/// Stops a worker's reactor and counts it as ready when dropped.
+///
+/// Dropped at the end of a normal shutdown, or during unwinding if the
+/// worker's closure panics, so neither the reactor nor workers waiting on
+/// the ready barrier are left running.
+struct WorkerGuard {
+    reactor_cancel: Rc<Cell<bool>>,
+    ready: ReadyBarrier,
+    marked: Rc<Cell<bool>>,
+}
+
+impl WorkerGuard {
+    fn arrive(&self) {
+        if !self.marked.replace(true) {
+            self.ready.arrive();
+        }
+    }
+}
+
+impl Drop for WorkerGuard {
+    fn drop(&mut self) {
+        self.arrive();
+        self.reactor_cancel.set(true);
+    }
+}
+
+/// Sample the port counters every `interval` and log the rates.
+async fn log_port_stats(
+    reactor: ReactorHandle,
+    port_id: u16,
+    interval: Duration,
+    cancel: Rc<Cell<bool>>,
+) {
+    let mut sampler = StatsSampler::new(port_id);
+    while !cancel.get() {
+        match sampler.sample() {
+            Ok(Some(rates)) => info!(
+                port_id,
+                rx_pps = rates.rx_pps as u64,
+                tx_pps = rates.tx_pps as u64,
+                rx_mbps = rates.rx_bps / 1e6,
+                tx_mbps = rates.tx_bps / 1e6,
+                rx_missed_pps = rates.rx_missed_pps as u64,
+                rx_nombuf_pps = rates.rx_nombuf_pps as u64,
+                rx_errors_pps = rates.rx_errors_pps as u64,
+                tx_errors_pps = rates.tx_errors_pps as u64,
+                "Port stats"
+            ),
+            Ok(None) => {}
+            Err(e) => {
+                warn!(port_id, error = %e, "Failed to read port stats");
+                return;
+            }
+        }
+        reactor.sleep(interval).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gateway_on_link() {
+        let cidrs = [
+            IpCidr::new(IpAddress::v4(192, 168, 1, 1), 24),
+            IpCidr::new(IpAddress::v4(10, 0, 0, 1), 30),
+        ];
+        assert!(is_on_link(Ipv4Address::new(192, 168, 1, 254), &cidrs));
+        assert!(is_on_link(Ipv4Address::new(10, 0, 0, 2), &cidrs));
+        assert!(!is_on_link(Ipv4Address::new(10, 0, 0, 5), &cidrs));
+        assert!(!is_on_link(Ipv4Address::new(192, 168, 1, 1), &[]));
+
+        let v6 = [IpCidr::new(IpAddress::v6(0xfd00, 0, 0, 0, 0, 0, 0, 1), 64)];
+        assert!(is_on_link(
+            Ipv6Address::new(0xfd00, 0, 0, 0, 0, 0, 0, 0xfe),
+            &v6
+        ));
+        assert!(!is_on_link(
+            Ipv6Address::new(0xfd01, 0, 0, 0, 0, 0, 0, 1),
+            &v6
+        ));
+        assert!(!is_on_link(Ipv4Address::new(192, 168, 1, 254), &v6));
+    }
+}
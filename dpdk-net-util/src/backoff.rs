@@ -0,0 +1,208 @@
+//! Jittered exponential backoff for reconnects.
+//!
+//! When a backend restarts, every pooled connection fails at once. Without
+//! backoff all clients reconnect in lockstep and hammer the recovering
+//! server. [`Backoff`] tracks consecutive failures per endpoint and spreads
+//! retry times with random jitter.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, Instant};
+
+use smoltcp::wire::IpAddress;
+
+/// Backoff settings, set via [`ClientConfig::reconnect_backoff`](crate::ClientConfig::reconnect_backoff).
+#[derive(Debug, Clone)]
+pub struct BackoffConfig {
+    /// Delay after the first failure.
+    pub initial: Duration,
+    /// Upper bound on the delay.
+    pub max: Duration,
+    /// Growth factor per consecutive failure.
+    pub multiplier: u32,
+    /// Fraction of the delay that is randomized, in `0.0..=1.0`.
+    ///
+    /// With jitter `j`, the delay is drawn uniformly from
+    /// `[base * (1 - j), base]`. `1.0` is "full jitter".
+    pub jitter: f64,
+}
+
+impl Default for BackoffConfig {
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_millis(100),
+            max: Duration::from_secs(10),
+            multiplier: 2,
+            jitter: 0.5,
+        }
+    }
+}
+
+/// Backoff state for one endpoint.
+#[derive(Debug)]
+pub struct Backoff {
+    config: BackoffConfig,
+    failures: u32,
+    retry_at: Option<Instant>,
+    rng: u64,
+}
+
+impl Backoff {
+    /// Create a backoff with no recorded failures.
+    pub fn new(config: BackoffConfig) -> Self {
+        // RandomState is seeded per thread and per instance, so lcores and
+        // endpoints draw different jitter sequences.
+        let seed = RandomState::new().build_hasher().finish() | 1;
+        Self {
+            config,
+            failures: 0,
+            retry_at: None,
+            rng: seed,
+        }
+    }
+
+    /// Number of consecutive failures recorded.
+    pub fn failures(&self) -> u32 {
+        self.failures
+    }
+
+    /// Record a failed attempt and return the delay before the next one.
+    pub fn record_failure(&mut self, now: Instant) -> Duration {
+        let delay = self.next_delay();
+        self.failures = self.failures.saturating_add(1);
+        self.retry_at = Some(now + delay);
+        delay
+    }
+
+    /// Record a successful attempt, clearing the failure count.
+    pub fn reset(&mut self) {
+        self.failures = 0;
+        self.retry_at = None;
+    }
+
+    /// Time left before the next attempt is allowed, if any.
+    pub fn remaining(&self, now: Instant) -> Option<Duration> {
+        self.retry_at
+            .and_then(|at| at.checked_duration_since(now))
+            .filter(|d| !d.is_zero())
+    }
+
+    fn next_delay(&mut self) -> Duration {
+        let factor = self
+            .config
+            .multiplier
+            .max(1)
+            .saturating_pow(self.failures.min(32));
+        let base = self
+            .config
+            .initial
+            .saturating_mul(factor)
+            .min(self.config.max);
+        let jitter = self.config.jitter.clamp(0.0, 1.0);
+        base.mul_f64(1.0 - jitter * self.next_unit())
+    }
+
+    /// Uniform sample in `[0, 1)` (xorshift64*).
+    fn next_unit(&mut self) -> f64 {
+        self.rng ^= self.rng >> 12;
+        self.rng ^= self.rng << 25;
+        self.rng ^= self.rng >> 27;
+        let x = self.rng.wrapping_mul(0x2545_F491_4F6C_DD1D);
+        (x >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Per-endpoint backoff table shared by a client or pool.
+pub(crate) struct EndpointBackoff {
+    config: Option<BackoffConfig>,
+    states: RefCell<HashMap<(IpAddress, u16), Backoff>>,
+}
+
+impl EndpointBackoff {
+    pub(crate) fn new(config: Option<BackoffConfig>) -> Self {
+        Self {
+            config,
+            states: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Delay to wait before connecting to `key`, if it recently failed.
+    pub(crate) fn delay_for(&self, key: (IpAddress, u16)) -> Option<Duration> {
+        if self.config.is_none() {
+            return None;
+        }
+        let states = self.states.borrow();
+        states.get(&key)?.remaining(Instant::now())
+    }
+
+    /// Record the outcome of a connection attempt to `key`.
+    pub(crate) fn record(&self, key: (IpAddress, u16), success: bool) {
+        let Some(config) = &self.config else {
+            return;
+        };
+        let mut states = self.states.borrow_mut();
+        if success {
+            states.remove(&key);
+        } else {
+            let delay = states
+                .entry(key)
+                .or_insert_with(|| Backoff::new(config.clone()))
+                .record_failure(Instant::now());
+            tracing::debug!(addr = %key.0, port = key.1, ?delay, "Reconnect backoff");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay_grows_and_caps() {
+        let config = BackoffConfig {
+            initial: Duration::from_millis(100),
+            max: Duration::from_millis(800),
+            multiplier: 2,
+            jitter: 0.0,
+        };
+        let mut backoff = Backoff::new(config);
+        let now = Instant::now();
+        let delays: Vec<_> = (0..6).map(|_| backoff.record_failure(now)).collect();
+        assert_eq!(
+            delays,
+            [100, 200, 400, 800, 800, 800].map(Duration::from_millis)
+        );
+
+        backoff.reset();
+        assert_eq!(backoff.failures(), 0);
+        assert!(backoff.remaining(now).is_none());
+        assert_eq!(backoff.record_failure(now), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_simultaneous_failures_are_spread() {
+        // Many clients fail at the same instant, as when a backend restarts.
+        let config = BackoffConfig {
+            initial: Duration::from_millis(1000),
+            jitter: 1.0,
+            ..BackoffConfig::default()
+        };
+        let now = Instant::now();
+        let mut delays: Vec<Duration> = (0..200)
+            .map(|_| Backoff::new(config.clone()).record_failure(now))
+            .collect();
+        delays.sort();
+
+        for d in &delays {
+            assert!(*d <= config.initial);
+        }
+        let spread = delays[delays.len() - 1] - delays[0];
+        assert!(spread > Duration::from_millis(500), "spread {spread:?}");
+
+        // No large cluster at a single retry time
+        delays.dedup();
+        assert!(delays.len() > 190, "only {} distinct delays", delays.len());
+    }
+}
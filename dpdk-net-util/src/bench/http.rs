@@ -0,0 +1,643 @@
+//! Reusable async HTTP server components for DPDK + smoltcp + hyper.
+//!
+//! This module provides generic HTTP servers that can run with custom handlers:
+//! - `Http1Server` - HTTP/1.1 only
+//! - `Http2Server` - HTTP/2 only (cleartext h2c)
+//! - `HttpAutoServer` - Auto-detects HTTP/1.1 or HTTP/2
+//!
+//! Also provides a default `echo_service` handler for testing.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use dpdk_net_util::bench::http::{HttpAutoServer, Http1Server, echo_service};
+//! use dpdk_net::socket::TcpListener;
+//! use tokio_util::sync::CancellationToken;
+//!
+//! // Using the default echo handler
+//! async fn run_echo(listener: TcpListener, cancel: CancellationToken) {
+//!     let server = Http1Server::new(listener, cancel, echo_service, 0, 8080);
+//!     server.run().await;
+//! }
+//!
+//! // Using a custom handler
+//! use http_body_util::Full;
+//! use hyper::body::Bytes;
+//! use hyper::{Request, Response, StatusCode};
+//!
+//! async fn my_handler(req: Request<Bytes>) -> Result<Response<Full<Bytes>>, hyper::Error> {
+//!     Ok(Response::builder()
+//!         .status(StatusCode::OK)
+//!         .body(Full::new(Bytes::from("Hello!")))
+//!         .unwrap())
+//! }
+//!
+//! async fn run_custom(listener: TcpListener, cancel: CancellationToken) {
+//!     let server = Http1Server::new(listener, cancel, my_handler, 0, 8080);
+//!     server.run().await;
+//! }
+//! ```
+
+use std::cell::Cell;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use dpdk_net::runtime::ReactorHandle;
+use dpdk_net::socket::{TcpListener, TcpStream};
+use futures_io::{AsyncRead, AsyncWrite};
+use tokio_util::compat::FuturesAsyncReadCompatExt;
+use tracing::{debug, error, info};
+
+use http_body_util::BodyExt;
+use http_body_util::Full;
+use hyper::body::{Bytes, Incoming};
+use hyper::server::conn::http1 as server_http1;
+use hyper::server::conn::http2 as server_http2;
+use hyper::service::service_fn;
+use hyper::{Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use hyper_util::server::conn::auto::Builder as AutoBuilder;
+
+use tokio_util::sync::CancellationToken;
+
+use crate::overload::{self, ServerLoad, ShedPolicy};
+use crate::semaphore::LocalSemaphore;
+use crate::serve::ActiveGuard;
+
+pub use crate::executor::LocalExecutor;
+
+/// HTTP echo service handler - echoes the request body back.
+///
+/// This function handles HTTP requests by echoing the request body
+/// back in the response. Works with both HTTP/1.1 and HTTP/2.
+pub async fn echo_service(req: Request<Bytes>) -> Result<Response<Full<Bytes>>, hyper::Error> {
+    let method = req.method().clone();
+    let uri = req.uri().clone();
+    let version = req.version();
+    debug!(version = ?version, %method, %uri, "HTTP request received");
+
+    // Get the request body (already collected)
+    let body_bytes = req.into_body();
+    debug!(bytes = body_bytes.len(), "HTTP body received");
+
+    // Echo it back
+    let response = Response::builder()
+        .status(StatusCode::OK)
+        .header("Content-Type", "text/plain")
+        .body(Full::new(body_bytes))
+        .unwrap();
+
+    Ok(response)
+}
+
+/// Wrap a handler that takes `Request<Bytes>` to work with hyper's `Request<Incoming>`.
+///
+/// This adapter collects the streaming body into `Bytes` before calling the handler,
+/// allowing handlers to be written with non-streaming body types.
+///
+/// Collecting is also what answers `Expect: 100-continue`: hyper writes the
+/// `100 Continue` when the body is first polled.
+#[allow(clippy::type_complexity)]
+fn with_collected_body<F, Fut>(
+    handler: F,
+) -> impl Fn(
+    Request<Incoming>,
+) -> std::pin::Pin<
+    Box<dyn std::future::Future<Output = Result<Response<Full<Bytes>>, hyper::Error>>>,
+> + Clone
++ 'static
+where
+    F: Fn(Request<Bytes>) -> Fut + Clone + 'static,
+    Fut: std::future::Future<Output = Result<Response<Full<Bytes>>, hyper::Error>> + 'static,
+{
+    move |req: Request<Incoming>| {
+        let handler = handler.clone();
+        Box::pin(async move {
+            // Split request into parts and body
+            let (parts, body) = req.into_parts();
+            // Collect the body
+            let body_bytes = body.collect().await?.to_bytes();
+            // Reconstruct with Bytes body
+            let req = Request::from_parts(parts, body_bytes);
+            handler(req).await
+        })
+    }
+}
+
+/// HTTP/1+2 Auto Server with custom handler.
+///
+/// Accepts TCP connections and serves both HTTP/1.1 and HTTP/2 (cleartext h2c)
+/// using hyper-util's auto builder.
+pub struct HttpAutoServer<F> {
+    listener: TcpListener,
+    cancel: CancellationToken,
+    handler: F,
+    queue_id: usize,
+    port: u16,
+}
+
+impl<F, Fut> HttpAutoServer<F>
+where
+    F: Fn(Request<Bytes>) -> Fut + Clone + 'static,
+    Fut: Future<Output = Result<Response<Full<Bytes>>, hyper::Error>> + 'static,
+{
+    /// Create a new HTTP auto server with a custom handler.
+    ///
+    /// # Arguments
+    /// * `listener` - The TCP listener to accept connections on
+    /// * `cancel` - Cancellation token for graceful shutdown
+    /// * `handler` - The request handler function (receives collected body as Bytes)
+    /// * `queue_id` - Queue identifier for logging
+    /// * `port` - Port number for logging
+    pub fn new(
+        listener: TcpListener,
+        cancel: CancellationToken,
+        handler: F,
+        queue_id: usize,
+        port: u16,
+    ) -> Self {
+        Self {
+            listener,
+            cancel,
+            handler,
+            queue_id,
+            port,
+        }
+    }
+
+    /// Run the server until cancellation.
+    ///
+    /// This accepts TCP connections in a loop and spawns an HTTP handler
+    /// for each connection. The handler automatically detects whether
+    /// the client is using HTTP/1.1 or HTTP/2 and responds accordingly.
+    pub async fn run(mut self) {
+        info!(
+            queue_id = self.queue_id,
+            port = self.port,
+            "HTTP/1+2 Auto Server listening"
+        );
+
+        let wrapped_handler = with_collected_body(self.handler);
+        let mut conn_id = 0u64;
+
+        loop {
+            tokio::select! {
+                _ = self.cancel.cancelled() => {
+                    break;
+                }
+                result = self.listener.accept() => {
+                    match result {
+                        Ok(stream) => {
+                            let id = conn_id;
+                            conn_id += 1;
+                            let queue_id = self.queue_id;
+                            debug!(queue_id, conn_id = id, "HTTP connection accepted");
+
+                            let io = TokioIo::new(stream.compat());
+                            let handler = wrapped_handler.clone();
+
+                            tokio::task::spawn_local(async move {
+                                let result = AutoBuilder::new(LocalExecutor)
+                                    .serve_connection(io, service_fn(handler))
+                                    .await;
+
+                                match result {
+                                    Ok(()) => debug!(queue_id, conn_id = id, "HTTP connection closed"),
+                                    Err(e) => debug!(queue_id, conn_id = id, error = %e, "HTTP connection error"),
+                                }
+                            });
+                        }
+                        Err(e) => {
+                            error!(queue_id = self.queue_id, error = ?e, "HTTP accept failed");
+                        }
+                    }
+                }
+            }
+        }
+
+        info!(queue_id = self.queue_id, "HTTP server shutting down");
+    }
+}
+
+/// Tracks request activity on one connection for the idle reaper.
+struct Activity {
+    in_flight: Cell<usize>,
+    last_active: Cell<Instant>,
+}
+
+impl Activity {
+    fn new() -> Self {
+        Self {
+            in_flight: Cell::new(0),
+            last_active: Cell::new(Instant::now()),
+        }
+    }
+
+    fn begin(&self) {
+        self.in_flight.set(self.in_flight.get() + 1);
+        self.last_active.set(Instant::now());
+    }
+
+    fn end(&self) {
+        self.in_flight.set(self.in_flight.get() - 1);
+        self.last_active.set(Instant::now());
+    }
+
+    /// When the connection becomes idle for `timeout`, or `None` while a
+    /// request is being handled.
+    fn idle_deadline(&self, timeout: Duration) -> Option<Instant> {
+        (self.in_flight.get() == 0).then(|| self.last_active.get() + timeout)
+    }
+}
+
+/// A stream that logs its [`TcpStream::stats`] when the connection ends.
+struct StatsLogged {
+    stream: TcpStream,
+    queue_id: usize,
+    conn_id: u64,
+}
+
+impl AsyncRead for StatsLogged {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.stream).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for StatsLogged {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.stream).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_close(cx)
+    }
+}
+
+impl Drop for StatsLogged {
+    fn drop(&mut self) {
+        let stats = self.stream.stats();
+        debug!(
+            queue_id = self.queue_id,
+            conn_id = self.conn_id,
+            bytes_sent = stats.bytes_sent,
+            bytes_received = stats.bytes_received,
+            state = ?stats.state,
+            "HTTP/1.1 connection stats"
+        );
+    }
+}
+
+/// Serve one HTTP/1.1 connection of an [`Http1Server`] until it closes.
+///
+/// With `idle_timeout` set, the connection is shut down gracefully once it
+/// has gone that long without a request in flight.
+async fn serve_http1_conn<H, HFut>(
+    stream: StatsLogged,
+    handler: H,
+    limit: Option<LocalSemaphore>,
+    idle_timeout: Option<Duration>,
+    idle_closed: Arc<AtomicU64>,
+    reactor: ReactorHandle,
+    _guard: ActiveGuard,
+) where
+    H: Fn(Request<Incoming>) -> HFut + 'static,
+    HFut: Future<Output = Result<Response<Full<Bytes>>, hyper::Error>> + 'static,
+{
+    let (queue_id, conn_id) = (stream.queue_id, stream.conn_id);
+    let io = TokioIo::new(stream.compat());
+    let activity = Rc::new(Activity::new());
+    let service = {
+        let activity = activity.clone();
+        service_fn(move |req| {
+            activity.begin();
+            let activity = activity.clone();
+            let permit = limit.as_ref().map(LocalSemaphore::acquire);
+            let response = handler(req);
+            async move {
+                let _permit = match permit {
+                    Some(acquire) => Some(acquire.await),
+                    None => None,
+                };
+                let result = response.await;
+                activity.end();
+                result
+            }
+        })
+    };
+    let conn = server_http1::Builder::new().serve_connection(io, service);
+    tokio::pin!(conn);
+
+    let result = match idle_timeout {
+        None => conn.await,
+        Some(timeout) => loop {
+            // While a request is in flight, check back a full period later
+            let deadline = activity
+                .idle_deadline(timeout)
+                .unwrap_or_else(|| Instant::now() + timeout);
+            tokio::select! {
+                result = conn.as_mut() => break result,
+                _ = reactor.sleep_until(deadline) => {
+                    let idle = activity
+                        .idle_deadline(timeout)
+                        .is_some_and(|d| d <= Instant::now());
+                    if idle {
+                        debug!(queue_id, conn_id, "HTTP/1.1 connection idle, closing");
+                        idle_closed.fetch_add(1, Ordering::Relaxed);
+                        conn.as_mut().graceful_shutdown();
+                        break conn.await;
+                    }
+                }
+            }
+        },
+    };
+
+    match result {
+        Ok(()) => debug!(queue_id, conn_id, "HTTP/1.1 connection closed"),
+        Err(e) => debug!(queue_id, conn_id, error = %e, "HTTP/1.1 connection error"),
+    }
+}
+
+/// HTTP/1.1 Server with custom handler.
+///
+/// Accepts TCP connections and serves HTTP/1.1 only.
+///
+/// With [`idle_timeout`](Self::idle_timeout) set, a keep-alive connection
+/// that goes that long without a request is shut down, releasing its socket
+/// and buffers.
+///
+/// With [`concurrency_limit`](Self::concurrency_limit) set, requests beyond
+/// the limit wait for a permit before their handler runs.
+///
+/// With [`shed_load`](Self::shed_load) set, connections arriving while the
+/// policy reports overload get an immediate 503 instead.
+///
+/// Each connection's byte counts are logged at debug level when it ends.
+pub struct Http1Server<F> {
+    listener: TcpListener,
+    cancel: CancellationToken,
+    handler: F,
+    queue_id: usize,
+    port: u16,
+    idle_timeout: Option<Duration>,
+    idle_closed: Arc<AtomicU64>,
+    limit: Option<LocalSemaphore>,
+    shed: Option<ShedPolicy>,
+    shed_count: Arc<AtomicU64>,
+}
+
+impl<F, Fut> Http1Server<F>
+where
+    F: Fn(Request<Bytes>) -> Fut + Clone + 'static,
+    Fut: Future<Output = Result<Response<Full<Bytes>>, hyper::Error>> + 'static,
+{
+    /// Create a new HTTP/1.1 server with a custom handler.
+    pub fn new(
+        listener: TcpListener,
+        cancel: CancellationToken,
+        handler: F,
+        queue_id: usize,
+        port: u16,
+    ) -> Self {
+        Self {
+            listener,
+            cancel,
+            handler,
+            queue_id,
+            port,
+            idle_timeout: None,
+            idle_closed: Arc::new(AtomicU64::new(0)),
+            limit: None,
+            shed: None,
+            shed_count: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Close connections that go `timeout` without a request (default: never).
+    ///
+    /// The clock starts when a connection is accepted and restarts each time
+    /// a response completes; a request still being handled is never cut off.
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Cap the requests handled at once across all of this server's
+    /// connections (default: unlimited).
+    ///
+    /// Each request holds a permit from `limit` while its body is read and
+    /// its handler runs. Pass a clone of a semaphore that other servers or
+    /// tasks on the same worker also use to share one budget between them.
+    ///
+    /// ```ignore
+    /// let limit = LocalSemaphore::new(128);
+    /// Http1Server::new(listener, cancel, echo_service, queue_id, 8080)
+    ///     .concurrency_limit(limit.clone())
+    ///     .run()
+    ///     .await;
+    /// ```
+    pub fn concurrency_limit(mut self, limit: LocalSemaphore) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Turn connections away with `503 Service Unavailable` while `policy`
+    /// reports overload (default: never).
+    ///
+    /// Checked once per accepted connection, against the number of
+    /// connections this server is serving and the free mbufs. The 503 is
+    /// written without reading the request, and the connection is closed.
+    /// Unlike [`concurrency_limit`](Self::concurrency_limit), which queues
+    /// requests, this bounds how much work is admitted at all.
+    pub fn shed_load(mut self, policy: ShedPolicy) -> Self {
+        self.shed = Some(policy);
+        self
+    }
+
+    /// Counter of connections turned away by [`shed_load`](Self::shed_load).
+    ///
+    /// Like [`idle_closed`](Self::idle_closed), take it before calling
+    /// [`run`](Self::run).
+    pub fn shed_count(&self) -> Arc<AtomicU64> {
+        self.shed_count.clone()
+    }
+
+    /// Counter of connections closed by the idle timeout.
+    ///
+    /// Take it before calling [`run`](Self::run); it keeps counting while the
+    /// server runs.
+    pub fn idle_closed(&self) -> Arc<AtomicU64> {
+        self.idle_closed.clone()
+    }
+
+    /// Run the server until cancellation.
+    pub async fn run(mut self) {
+        info!(
+            queue_id = self.queue_id,
+            port = self.port,
+            "HTTP/1.1 Server listening"
+        );
+
+        let wrapped_handler = with_collected_body(self.handler);
+        let mut conn_id = 0u64;
+        let active = Rc::new(Cell::new(0usize));
+        let reactor = self.listener.reactor();
+
+        loop {
+            tokio::select! {
+                _ = self.cancel.cancelled() => {
+                    break;
+                }
+                result = self.listener.accept() => {
+                    match result {
+                        Ok(stream) => {
+                            let id = conn_id;
+                            conn_id += 1;
+                            let queue_id = self.queue_id;
+                            debug!(queue_id, conn_id = id, "HTTP/1.1 connection accepted");
+
+                            if let Some(policy) = &self.shed {
+                                let load = ServerLoad {
+                                    active_connections: active.get(),
+                                    mbufs_available: reactor.mbufs_available(),
+                                };
+                                if policy.should_shed(&load) {
+                                    debug!(
+                                        queue_id,
+                                        conn_id = id,
+                                        ?load,
+                                        "HTTP/1.1 overloaded, shedding connection"
+                                    );
+                                    self.shed_count.fetch_add(1, Ordering::Relaxed);
+                                    tokio::task::spawn_local(overload::reject(stream));
+                                    continue;
+                                }
+                            }
+
+                            let guard = ActiveGuard::new(&active);
+                            let stream = StatsLogged { stream, queue_id, conn_id: id };
+                            tokio::task::spawn_local(serve_http1_conn(
+                                stream,
+                                wrapped_handler.clone(),
+                                self.limit.clone(),
+                                self.idle_timeout,
+                                self.idle_closed.clone(),
+                                reactor.clone(),
+                                guard,
+                            ));
+                        }
+                        Err(e) => {
+                            error!(queue_id = self.queue_id, error = ?e, "HTTP/1.1 accept failed");
+                        }
+                    }
+                }
+            }
+        }
+
+        info!(
+            queue_id = self.queue_id,
+            last_conn = conn_id,
+            "HTTP/1.1 server shutting down"
+        );
+    }
+}
+
+/// HTTP/2 Server with custom handler (cleartext h2c).
+///
+/// Accepts TCP connections and serves HTTP/2 only.
+pub struct Http2Server<F> {
+    listener: TcpListener,
+    cancel: CancellationToken,
+    handler: F,
+    queue_id: usize,
+    port: u16,
+}
+
+impl<F, Fut> Http2Server<F>
+where
+    F: Fn(Request<Bytes>) -> Fut + Clone + 'static,
+    Fut: Future<Output = Result<Response<Full<Bytes>>, hyper::Error>> + 'static,
+{
+    /// Create a new HTTP/2 server with a custom handler.
+    pub fn new(
+        listener: TcpListener,
+        cancel: CancellationToken,
+        handler: F,
+        queue_id: usize,
+        port: u16,
+    ) -> Self {
+        Self {
+            listener,
+            cancel,
+            handler,
+            queue_id,
+            port,
+        }
+    }
+
+    /// Run the server until cancellation.
+    pub async fn run(mut self) {
+        info!(
+            queue_id = self.queue_id,
+            port = self.port,
+            "HTTP/2 Server listening"
+        );
+
+        let wrapped_handler = with_collected_body(self.handler);
+        let mut conn_id = 0u64;
+
+        loop {
+            tokio::select! {
+                _ = self.cancel.cancelled() => {
+                    break;
+                }
+                result = self.listener.accept() => {
+                    match result {
+                        Ok(stream) => {
+                            let id = conn_id;
+                            conn_id += 1;
+                            let queue_id = self.queue_id;
+                            debug!(queue_id, conn_id = id, "HTTP/2 connection accepted");
+
+                            let io = TokioIo::new(stream.compat());
+                            let handler = wrapped_handler.clone();
+
+                            tokio::task::spawn_local(async move {
+                                let result = server_http2::Builder::new(LocalExecutor)
+                                    .serve_connection(io, service_fn(handler))
+                                    .await;
+
+                                match result {
+                                    Ok(()) => debug!(queue_id, conn_id = id, "HTTP/2 connection closed"),
+                                    Err(e) => debug!(queue_id, conn_id = id, error = %e, "HTTP/2 connection error"),
+                                }
+                            });
+                        }
+                        Err(e) => {
+                            error!(queue_id = self.queue_id, error = ?e, "HTTP/2 accept failed");
+                        }
+                    }
+                }
+            }
+        }
+
+        info!(queue_id = self.queue_id, "HTTP/2 server shutting down");
+    }
+}
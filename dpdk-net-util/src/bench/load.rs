@@ -0,0 +1,353 @@
+//! Closed-loop load generator for the bench servers.
+//!
+//! Each connection runs as its own local task and sends the next request as
+//! soon as the previous response arrives, for a fixed duration. Per-request
+//! latencies are recorded and merged into one [`LoadReport`].
+//!
+//! # Example
+//!
+//! ```ignore
+//! use dpdk_net_util::bench::load::{LoadConfig, run_echo_load};
+//! use smoltcp::wire::IpAddress;
+//! use std::time::Duration;
+//!
+//! let config = LoadConfig::new(IpAddress::v4(10, 0, 0, 1), 8080)
+//!     .connections(16)
+//!     .duration(Duration::from_secs(10));
+//! let report = run_echo_load(&ctx.reactor, &config, &[0u8; 64]).await;
+//! println!("{:.0} req/s, p99 {:?}", report.throughput(), report.latency_percentile(99.0));
+//! ```
+
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use bytes::Bytes;
+use dpdk_net::runtime::ReactorHandle;
+use dpdk_net::socket::TcpStream;
+use http_body_util::{BodyExt, Full};
+use hyper::Request;
+use smoltcp::wire::IpAddress;
+use tracing::debug;
+
+use crate::connection::Connection;
+
+/// Load generator settings.
+#[derive(Debug, Clone)]
+pub struct LoadConfig {
+    /// Server address.
+    pub addr: IpAddress,
+    /// Server port.
+    pub port: u16,
+    /// Number of concurrent connections.
+    pub connections: usize,
+    /// How long to generate load.
+    pub duration: Duration,
+    /// Local port of the first connection; connection `i` uses `base + i`.
+    pub local_port_base: u16,
+    /// TCP receive and transmit buffer size per connection.
+    pub buffer_size: usize,
+}
+
+impl LoadConfig {
+    /// Create a config targeting `addr:port` with one connection for 5 seconds.
+    pub fn new(addr: IpAddress, port: u16) -> Self {
+        Self {
+            addr,
+            port,
+            connections: 1,
+            duration: Duration::from_secs(5),
+            local_port_base: 49152,
+            buffer_size: 16384,
+        }
+    }
+
+    /// Set the number of concurrent connections.
+    pub fn connections(mut self, n: usize) -> Self {
+        self.connections = n.max(1);
+        self
+    }
+
+    /// Set how long to generate load.
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Set the first local port.
+    pub fn local_port_base(mut self, port: u16) -> Self {
+        self.local_port_base = port;
+        self
+    }
+
+    /// Set the per-connection TCP buffer size.
+    pub fn buffer_size(mut self, size: usize) -> Self {
+        self.buffer_size = size;
+        self
+    }
+
+    fn local_port(&self, conn: usize) -> u16 {
+        self.local_port_base.wrapping_add(conn as u16)
+    }
+}
+
+/// Results of a load run.
+#[derive(Debug, Clone, Default)]
+pub struct LoadReport {
+    /// Completed requests (echo round trips or HTTP responses).
+    pub requests: u64,
+    /// Failed connects, requests or short reads.
+    pub errors: u64,
+    /// Response bytes received.
+    pub bytes: u64,
+    /// Wall-clock duration of the run.
+    pub elapsed: Duration,
+    /// Sorted per-request latencies.
+    latencies: Vec<Duration>,
+}
+
+impl LoadReport {
+    /// Completed requests per second.
+    pub fn throughput(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs == 0.0 {
+            0.0
+        } else {
+            self.requests as f64 / secs
+        }
+    }
+
+    /// Latency at percentile `p` (0-100), or `None` if nothing completed.
+    pub fn latency_percentile(&self, p: f64) -> Option<Duration> {
+        if self.latencies.is_empty() {
+            return None;
+        }
+        let rank = (p.clamp(0.0, 100.0) / 100.0 * (self.latencies.len() - 1) as f64).round();
+        Some(self.latencies[rank as usize])
+    }
+
+    /// All recorded latencies, sorted ascending.
+    pub fn latencies(&self) -> &[Duration] {
+        &self.latencies
+    }
+
+    fn merge(&mut self, other: LoadReport) {
+        self.requests += other.requests;
+        self.errors += other.errors;
+        self.bytes += other.bytes;
+        self.latencies.extend(other.latencies);
+    }
+
+    fn finish(mut self, elapsed: Duration) -> Self {
+        self.elapsed = elapsed;
+        self.latencies.sort_unstable();
+        self
+    }
+}
+
+/// Drive an [`EchoServer`](super::echo::EchoServer)-style TCP server.
+///
+/// Each round trip sends `payload` and waits until the same number of bytes
+/// has come back.
+pub async fn run_echo_load(
+    reactor: &ReactorHandle,
+    config: &LoadConfig,
+    payload: &[u8],
+) -> LoadReport {
+    let payload: Rc<[u8]> = payload.into();
+    run_connections(config, |conn, deadline| {
+        let reactor = reactor.clone();
+        let config = config.clone();
+        let payload = payload.clone();
+        async move { echo_connection(&reactor, &config, conn, deadline, &payload).await }
+    })
+    .await
+}
+
+/// Drive an HTTP/1.1 server such as [`Http1Server`](super::http::Http1Server).
+///
+/// `make_request` builds each request; the response body is read in full
+/// before the next request is sent.
+pub async fn run_http1_load<F>(
+    reactor: &ReactorHandle,
+    config: &LoadConfig,
+    make_request: F,
+) -> LoadReport
+where
+    F: Fn() -> Request<Full<Bytes>> + 'static,
+{
+    let make_request = Rc::new(make_request);
+    run_connections(config, |conn, deadline| {
+        let reactor = reactor.clone();
+        let config = config.clone();
+        let make_request = make_request.clone();
+        async move { http1_connection(&reactor, &config, conn, deadline, &*make_request).await }
+    })
+    .await
+}
+
+async fn run_connections<F, Fut>(config: &LoadConfig, spawn_one: F) -> LoadReport
+where
+    F: Fn(usize, Instant) -> Fut,
+    Fut: Future<Output = LoadReport> + 'static,
+{
+    let start = Instant::now();
+    let deadline = start + config.duration;
+    let tasks: Vec<_> = (0..config.connections)
+        .map(|conn| tokio::task::spawn_local(spawn_one(conn, deadline)))
+        .collect();
+
+    let mut report = LoadReport::default();
+    for task in tasks {
+        match task.await {
+            Ok(partial) => report.merge(partial),
+            Err(_) => report.errors += 1,
+        }
+    }
+    report.finish(start.elapsed())
+}
+
+async fn echo_connection(
+    reactor: &ReactorHandle,
+    config: &LoadConfig,
+    conn: usize,
+    deadline: Instant,
+    payload: &[u8],
+) -> LoadReport {
+    let mut report = LoadReport::default();
+    let stream = match TcpStream::connect(
+        reactor,
+        config.addr,
+        config.port,
+        config.local_port(conn),
+        config.buffer_size,
+        config.buffer_size,
+    ) {
+        Ok(s) => s,
+        Err(e) => {
+            debug!(conn, error = %e, "Load connect failed");
+            report.errors += 1;
+            return report;
+        }
+    };
+    if stream.wait_connected().await.is_err() {
+        report.errors += 1;
+        return report;
+    }
+
+    let mut buf = vec![0u8; payload.len().max(1)];
+    'outer: while Instant::now() < deadline {
+        let sent_at = Instant::now();
+        let mut sent = 0;
+        while sent < payload.len() {
+            match stream.send(&payload[sent..]).await {
+                Ok(n) => sent += n,
+                Err(_) => {
+                    report.errors += 1;
+                    break 'outer;
+                }
+            }
+        }
+        let mut received = 0;
+        while received < payload.len() {
+            match stream.recv(&mut buf).await {
+                Ok(0) | Err(_) => {
+                    report.errors += 1;
+                    break 'outer;
+                }
+                Ok(n) => received += n,
+            }
+        }
+        report.requests += 1;
+        report.bytes += received as u64;
+        report.latencies.push(sent_at.elapsed());
+    }
+
+    stream.close().await.ok();
+    report
+}
+
+async fn http1_connection(
+    reactor: &ReactorHandle,
+    config: &LoadConfig,
+    conn: usize,
+    deadline: Instant,
+    make_request: &dyn Fn() -> Request<Full<Bytes>>,
+) -> LoadReport {
+    let mut report = LoadReport::default();
+    let mut connection = match Connection::http1(
+        reactor,
+        config.addr,
+        config.port,
+        config.local_port(conn),
+        config.buffer_size,
+        config.buffer_size,
+    )
+    .await
+    {
+        Ok(c) => c,
+        Err(e) => {
+            debug!(conn, error = %e, "Load connect failed");
+            report.errors += 1;
+            return report;
+        }
+    };
+
+    while Instant::now() < deadline {
+        let sent_at = Instant::now();
+        let body = match connection.send_request(make_request()).await {
+            Ok(resp) => resp.into_body().collect().await,
+            Err(_) => {
+                report.errors += 1;
+                break;
+            }
+        };
+        match body {
+            Ok(body) => {
+                report.requests += 1;
+                report.bytes += body.to_bytes().len() as u64;
+                report.latencies.push(sent_at.elapsed());
+            }
+            Err(_) => {
+                report.errors += 1;
+                break;
+            }
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_merge_and_percentiles() {
+        let mut report = LoadReport::default();
+        for chunk in [[5u64, 1, 3], [4, 2, 6]] {
+            report.merge(LoadReport {
+                requests: chunk.len() as u64,
+                bytes: 10,
+                latencies: chunk.iter().map(|&ms| Duration::from_millis(ms)).collect(),
+                ..LoadReport::default()
+            });
+        }
+        let report = report.finish(Duration::from_secs(2));
+
+        assert_eq!(report.requests, 6);
+        assert_eq!(report.bytes, 20);
+        assert_eq!(report.throughput(), 3.0);
+        assert_eq!(
+            report.latency_percentile(0.0),
+            Some(Duration::from_millis(1))
+        );
+        assert_eq!(
+            report.latency_percentile(50.0),
+            Some(Duration::from_millis(4))
+        );
+        assert_eq!(
+            report.latency_percentile(100.0),
+            Some(Duration::from_millis(6))
+        );
+        assert_eq!(LoadReport::default().latency_percentile(99.0), None);
+    }
+}
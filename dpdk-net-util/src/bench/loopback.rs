@@ -0,0 +1,183 @@
+//! Loopback harness for in-process benchmarks.
+//!
+//! [`Loopback`] initializes EAL with a `net_ring0` vdev, whose TX ring feeds
+//! its own RX ring, and runs one reactor on the calling thread. Server and
+//! client sockets on that reactor talk to each other through the full
+//! DPDK + smoltcp path without a NIC or hugepages.
+//!
+//! The harness is driven synchronously, which fits criterion's
+//! `iter_custom`:
+//!
+//! ```ignore
+//! use criterion::{Criterion, criterion_group, criterion_main};
+//! use dpdk_net_util::bench::loopback::Loopback;
+//!
+//! fn bench_echo(c: &mut Criterion) {
+//!     let lo = Loopback::new().expect("loopback setup failed");
+//!     // Start a server on the loopback reactor once
+//!     lo.spawn(|reactor| async move { /* EchoServer::new(...).run().await */ });
+//!     c.bench_function("echo_64b", |b| {
+//!         b.iter_custom(|iters| {
+//!             lo.iter_custom(iters, |reactor| async move {
+//!                 // one echo round trip
+//!             })
+//!         })
+//!     });
+//! }
+//! ```
+//!
+//! EAL can only be initialized once per process, so create one `Loopback`
+//! and reuse it for every benchmark in the binary.
+
+use std::cell::Cell;
+use std::future::Future;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use dpdk_net::api::rte::eal::{Eal, EalBuilder};
+use dpdk_net::api::rte::eth::{EthConf, EthDev, EthDevBuilder, RxQueueConf, TxQueueConf};
+use dpdk_net::api::rte::pktmbuf::{MemPool, MemPoolConfig};
+use dpdk_net::api::rte::queue::{RxQueue, TxQueue};
+use dpdk_net::device::DpdkDevice;
+use dpdk_net::runtime::{Reactor, ReactorConfig, ReactorHandle};
+use smoltcp::wire::{EthernetAddress, IpAddress, IpCidr, Ipv4Address};
+use tokio::runtime::{Builder, Runtime};
+use tokio::task::LocalSet;
+
+/// IP address of the loopback interface.
+pub const LOOPBACK_IP: Ipv4Address = Ipv4Address::new(192, 168, 1, 1);
+
+/// Default gateway of the loopback interface (never contacted).
+const LOOPBACK_GATEWAY: Ipv4Address = Ipv4Address::new(192, 168, 1, 254);
+
+const MBUF_HEADROOM: usize = 128;
+const MBUF_DATA_ROOM_SIZE: u16 = 2048 + MBUF_HEADROOM as u16;
+const MTU: usize = 1500;
+
+/// A single-queue loopback device with a running reactor.
+///
+/// Not `Send`: use it from the thread that created it.
+pub struct Loopback {
+    local: LocalSet,
+    rt: Runtime,
+    reactor: ReactorHandle,
+    cancel: Rc<Cell<bool>>,
+    eth_dev: EthDev,
+    _mempool: Arc<MemPool>,
+    _eal: Eal,
+}
+
+impl Loopback {
+    /// Initialize EAL and the loopback device with default sizes.
+    pub fn new() -> dpdk_net::Result<Self> {
+        Self::with_mbufs(8192)
+    }
+
+    /// Initialize EAL and the loopback device with `num_mbufs` mbufs.
+    pub fn with_mbufs(num_mbufs: u32) -> dpdk_net::Result<Self> {
+        let eal = EalBuilder::new()
+            .no_huge()
+            .no_pci()
+            .in_memory()
+            .core_list("0")
+            .vdev("net_ring0")
+            .init()?;
+
+        let mempool_config = MemPoolConfig::new()
+            .num_mbufs(num_mbufs)
+            .data_room_size(MBUF_DATA_ROOM_SIZE);
+        let mempool = Arc::new(MemPool::create(
+            MemPool::unique_name("bench_loopback_pool"),
+            &mempool_config,
+        )?);
+
+        let eth_dev = EthDevBuilder::new(0)
+            .eth_conf(EthConf::new())
+            .nb_rx_queues(1)
+            .nb_tx_queues(1)
+            .rx_queue_conf(RxQueueConf::new().nb_desc(1024))
+            .tx_queue_conf(TxQueueConf::new().nb_desc(1024))
+            .build(&mempool)?;
+        let mac = eth_dev.mac_addr()?;
+
+        let device = DpdkDevice::new(
+            RxQueue::new(0, 0),
+            TxQueue::new(0, 0),
+            mempool.clone(),
+            MTU,
+            MBUF_DATA_ROOM_SIZE as usize - MBUF_HEADROOM,
+        );
+        let config = ReactorConfig::new(EthernetAddress(mac.addr_bytes))
+            .ip_addr(IpCidr::new(IpAddress::Ipv4(LOOPBACK_IP), 24))
+            .ipv4_gateway(LOOPBACK_GATEWAY);
+        let reactor = Reactor::new_with_config(device, config)?;
+        let handle = reactor.handle();
+
+        let rt = Builder::new_current_thread().build()?;
+        let local = LocalSet::new();
+        let cancel = Rc::new(Cell::new(false));
+        let reactor_cancel = cancel.clone();
+        local.spawn_local(async move { reactor.run(reactor_cancel).await });
+
+        Ok(Self {
+            local,
+            rt,
+            reactor: handle,
+            cancel,
+            eth_dev,
+            _mempool: mempool,
+            _eal: eal,
+        })
+    }
+
+    /// Handle to the loopback reactor.
+    pub fn reactor(&self) -> &ReactorHandle {
+        &self.reactor
+    }
+
+    /// Spawn a background task, such as a server, on the loopback reactor.
+    ///
+    /// The task makes progress whenever [`block_on`](Self::block_on) or
+    /// [`iter_custom`](Self::iter_custom) runs.
+    pub fn spawn<F, Fut>(&self, f: F)
+    where
+        F: FnOnce(ReactorHandle) -> Fut,
+        Fut: Future<Output = ()> + 'static,
+    {
+        self.local.spawn_local(f(self.reactor.clone()));
+    }
+
+    /// Run `future` to completion, driving the reactor and spawned tasks.
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.local.block_on(&self.rt, future)
+    }
+
+    /// Run `routine` `iters` times and return the total time taken.
+    ///
+    /// Matches the contract of criterion's `Bencher::iter_custom`. Only the
+    /// routine itself is timed.
+    pub fn iter_custom<F, Fut>(&self, iters: u64, mut routine: F) -> Duration
+    where
+        F: FnMut(ReactorHandle) -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        self.block_on(async {
+            let start = Instant::now();
+            for _ in 0..iters {
+                routine(self.reactor.clone()).await;
+            }
+            start.elapsed()
+        })
+    }
+}
+
+impl Drop for Loopback {
+    fn drop(&mut self) {
+        self.cancel.set(true);
+        // Let the reactor observe the flag and exit
+        self.block_on(tokio::task::yield_now());
+        let _ = self.eth_dev.stop();
+        let _ = self.eth_dev.close();
+    }
+}
@@ -0,0 +1,43 @@
+//! Benchmarking fixtures for the DPDK stack.
+//!
+//! These are the servers and clients the project's own tests and
+//! `dpdk-bench-server` use, packaged so downstream users can measure their
+//! own handlers with a few lines:
+//!
+//! - [`echo`]: TCP echo server ([`EchoServer`](echo::EchoServer)) with
+//!   shared counters.
+//! - [`http`]: HTTP/1.1, HTTP/2 (h2c) and auto-detecting servers generic
+//!   over a `Request<Bytes>` handler, plus [`echo_service`](http::echo_service).
+//! - [`load`]: closed-loop load generator reporting throughput and latency
+//!   percentiles.
+//! - [`loopback`]: single-queue `net_ring0` setup with an
+//!   `iter_custom`-style timing helper for criterion.
+//!
+//! The kernel-socket baselines (tokio and kimojio servers) stay in
+//! `dpdk-net-test`, since they do not use the DPDK stack.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use dpdk_net::socket::TcpListener;
+//! use dpdk_net_util::DpdkApp;
+//! use dpdk_net_util::bench::http::Http1Server;
+//! use tokio_util::sync::CancellationToken;
+//!
+//! DpdkApp::new()
+//!     .eth_dev(0)
+//!     .ip(ip)
+//!     .gateway(gateway)
+//!     .run(|ctx| async move {
+//!         let listener = TcpListener::bind_with_backlog(&ctx.reactor, 8080, 16384, 16384, 64)
+//!             .expect("bind failed");
+//!         Http1Server::new(listener, CancellationToken::new(), my_handler, ctx.queue_id as usize, 8080)
+//!             .run()
+//!             .await;
+//!     });
+//! ```
+
+pub mod echo;
+pub mod http;
+pub mod load;
+pub mod loopback;
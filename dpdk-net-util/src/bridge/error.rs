@@ -1,6 +1,8 @@
 use std::fmt;
 use std::io;
 
+use dpdk_net::socket::{TcpConnectError, TcpListenError};
+
 /// Error type for bridge operations.
 #[derive(Debug)]
 pub enum BridgeError {
@@ -10,10 +12,10 @@ pub enum BridgeError {
     ConnectionFailed,
     /// IO error from the underlying stream.
     Io(io::Error),
-    /// TCP connect error from smoltcp.
-    Connect(smoltcp::socket::tcp::ConnectError),
-    /// TCP listen error from smoltcp.
-    Listen(smoltcp::socket::tcp::ListenError),
+    /// TCP connect error from the lcore's reactor.
+    Connect(TcpConnectError),
+    /// TCP listen or accept error from the lcore's reactor.
+    Listen(TcpListenError),
     /// UDP bind error from smoltcp.
     UdpBind(smoltcp::socket::udp::BindError),
 }
@@ -49,14 +51,14 @@ impl From<io::Error> for BridgeError {
     }
 }
 
-impl From<smoltcp::socket::tcp::ConnectError> for BridgeError {
-    fn from(e: smoltcp::socket::tcp::ConnectError) -> Self {
+impl From<TcpConnectError> for BridgeError {
+    fn from(e: TcpConnectError) -> Self {
         BridgeError::Connect(e)
     }
 }
 
-impl From<smoltcp::socket::tcp::ListenError> for BridgeError {
-    fn from(e: smoltcp::socket::tcp::ListenError) -> Self {
+impl From<TcpListenError> for BridgeError {
+    fn from(e: TcpListenError) -> Self {
         BridgeError::Listen(e)
     }
 }
@@ -77,6 +79,22 @@ impl From<BridgeError> for io::Error {
             BridgeError::ConnectionFailed => {
                 io::Error::new(io::ErrorKind::ConnectionRefused, "TCP connection failed")
             }
+            BridgeError::Connect(TcpConnectError::TooManySockets)
+            | BridgeError::Listen(TcpListenError::TooManySockets) => {
+                io::Error::new(io::ErrorKind::OutOfMemory, "reactor socket limit reached")
+            }
+            BridgeError::Connect(TcpConnectError::MemoryLimit)
+            | BridgeError::Listen(TcpListenError::MemoryLimit) => io::Error::new(
+                io::ErrorKind::OutOfMemory,
+                "reactor buffer memory limit reached",
+            ),
+            BridgeError::Connect(TcpConnectError::PortsExhausted) => io::Error::new(
+                io::ErrorKind::AddrNotAvailable,
+                "no free local port in the ephemeral range",
+            ),
+            BridgeError::Connect(TcpConnectError::TimedOut) => {
+                io::Error::new(io::ErrorKind::TimedOut, "connection attempt timed out")
+            }
             BridgeError::Connect(e) => {
                 io::Error::new(io::ErrorKind::ConnectionRefused, e.to_string())
             }
@@ -301,7 +301,7 @@ impl std::fmt::Debug for BridgeUdpSocket {
 pub(crate) fn to_smoltcp_endpoint(addr: SocketAddr) -> smoltcp::wire::IpEndpoint {
     let ip = match addr.ip() {
         IpAddr::V4(v4) => smoltcp::wire::IpAddress::Ipv4(v4),
-        IpAddr::V6(_) => unimplemented!("IPv6 not supported by smoltcp config"),
+        IpAddr::V6(v6) => smoltcp::wire::IpAddress::Ipv6(v6),
     };
     smoltcp::wire::IpEndpoint::new(ip, addr.port())
 }
@@ -310,6 +310,7 @@ pub(crate) fn to_smoltcp_endpoint(addr: SocketAddr) -> smoltcp::wire::IpEndpoint
 pub(crate) fn from_smoltcp_endpoint(ep: smoltcp::wire::IpEndpoint) -> SocketAddr {
     let ip = match ep.addr {
         smoltcp::wire::IpAddress::Ipv4(v4) => IpAddr::V4(v4),
+        smoltcp::wire::IpAddress::Ipv6(v6) => IpAddr::V6(v6),
     };
     SocketAddr::new(ip, ep.port)
 }
@@ -206,6 +206,7 @@ async fn bridge_worker(reactor: ReactorHandle, mut cmd_rx: mpsc::Receiver<Bridge
                 let local_addr = SocketAddr::new(
                     match local_ip {
                         smoltcp::wire::IpAddress::Ipv4(v4) => IpAddr::V4(v4),
+                        smoltcp::wire::IpAddress::Ipv6(v6) => IpAddr::V6(v6),
                     },
                     port,
                 );
@@ -326,10 +327,10 @@ async fn udp_relay_task(
                 let endpoint = to_smoltcp_endpoint(dg.addr);
                 // send_slice errors (Unaddressable, BufferFull) are silently dropped.
                 // The OS side already got Ok(len) when the datagram entered the channel.
-                let _ = socket.send_to(&dg.payload, endpoint).await;
+                let _ = socket.send_with_meta(&dg.payload, endpoint).await;
             }
             // Ingress: NIC → OS thread
-            result = socket.recv_from(&mut recv_buf) => {
+            result = socket.recv_with_meta(&mut recv_buf) => {
                 match result {
                     Ok((len, metadata)) => {
                         let dg = UdpDatagram {
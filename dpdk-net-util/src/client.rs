@@ -1,3 +1,5 @@
+#[cfg(feature = "tls")]
+use std::sync::Arc;
 use std::time::Duration;
 
 use bytes::Bytes;
@@ -5,10 +7,16 @@ use hyper::body::Incoming;
 use hyper::{Request, Response};
 
 use dpdk_net::runtime::ReactorHandle;
+#[cfg(feature = "tls")]
+use rustls::pki_types::ServerName;
 use smoltcp::wire::IpAddress;
 
+use crate::backoff::{BackoffConfig, EndpointBackoff};
 use crate::connection::{Connection, HttpVersion};
 use crate::error::Error;
+use crate::interceptor::Interceptors;
+use crate::proxy::{self, ProxyConfig};
+use crate::request::DpdkRequestBuilder;
 
 /// Configuration for [`DpdkHttpClient`].
 pub struct ClientConfig {
@@ -18,8 +26,102 @@ pub struct ClientConfig {
     pub tx_buffer_size: usize,
     /// HTTP version preference.
     pub http_version: HttpVersion,
-    /// Connection timeout (not yet enforced — reserved for future use).
+    /// Limit on opening a connection: TCP connect, proxy tunnel and HTTP
+    /// handshake together (default: 5s). Exceeding it fails with
+    /// [`Error::Timeout`].
     pub connect_timeout: Duration,
+    /// Limit on waiting for a response head after a request is sent
+    /// (default: none). Exceeding it fails with [`Error::Timeout`].
+    pub request_timeout: Option<Duration>,
+    /// Egress proxy to tunnel connections through (default: none).
+    pub proxy: Option<ProxyConfig>,
+    /// Request/response hooks attached to every connection.
+    pub interceptors: Interceptors,
+    /// Backoff applied to reconnects after a failed attempt (default: none).
+    ///
+    /// State is tracked per `(addr, port)` within one client or pool.
+    pub reconnect_backoff: Option<BackoffConfig>,
+    /// TLS settings; connections are cleartext if unset (feature: `tls`).
+    #[cfg(feature = "tls")]
+    pub tls: Option<Arc<rustls::ClientConfig>>,
+    /// Name the server certificate is verified against (default: the IP
+    /// address connected to).
+    #[cfg(feature = "tls")]
+    pub tls_server_name: Option<ServerName<'static>>,
+}
+
+impl ClientConfig {
+    /// Give up on opening a connection after `timeout`.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Give up on a response whose head has not arrived within `timeout` of
+    /// sending the request.
+    ///
+    /// Applies to every [`Connection`] the client or pool opens. Reading
+    /// the body is not covered.
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = Some(timeout);
+        self
+    }
+
+    /// Route connections through an HTTP `CONNECT` or SOCKS5 proxy.
+    pub fn proxy(mut self, proxy: ProxyConfig) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Delay reconnects to an endpoint after failures, with jittered
+    /// exponential backoff.
+    pub fn reconnect_backoff(mut self, backoff: BackoffConfig) -> Self {
+        self.reconnect_backoff = Some(backoff);
+        self
+    }
+
+    /// Speak HTTP over TLS, with certificates verified by `config`.
+    ///
+    /// The TLS handshake runs after the TCP connect (and proxy tunnel, if
+    /// any) and before hyper's handshake. If `config` has no ALPN protocols,
+    /// `h2` and `http/1.1` are offered with
+    /// [`http_version`](Self::http_version) first, and the connection
+    /// speaks whichever the server picks; build requests for
+    /// [`Connection::version`].
+    #[cfg(feature = "tls")]
+    pub fn tls(mut self, config: rustls::ClientConfig) -> Self {
+        self.tls = Some(Arc::new(config));
+        self
+    }
+
+    /// Verify the server certificate against `name` instead of the IP
+    /// address, and send it as SNI.
+    #[cfg(feature = "tls")]
+    pub fn tls_server_name(mut self, name: ServerName<'static>) -> Self {
+        self.tls_server_name = Some(name);
+        self
+    }
+
+    /// Invoke `f` with the head of every outgoing request.
+    ///
+    /// The hook may modify headers, e.g. to inject trace context.
+    /// See [`interceptor`](crate::interceptor) for ordering guarantees.
+    pub fn on_request<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut hyper::http::request::Parts) + 'static,
+    {
+        self.interceptors = self.interceptors.on_request(f);
+        self
+    }
+
+    /// Invoke `f` with every response once its head arrives.
+    pub fn on_response<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&Response<Incoming>) + 'static,
+    {
+        self.interceptors = self.interceptors.on_response(f);
+        self
+    }
 }
 
 impl Default for ClientConfig {
@@ -29,6 +131,14 @@ impl Default for ClientConfig {
             tx_buffer_size: 16384,
             http_version: HttpVersion::Http1,
             connect_timeout: Duration::from_secs(5),
+            request_timeout: None,
+            proxy: None,
+            interceptors: Interceptors::default(),
+            reconnect_backoff: None,
+            #[cfg(feature = "tls")]
+            tls: None,
+            #[cfg(feature = "tls")]
+            tls_server_name: None,
         }
     }
 }
@@ -45,7 +155,7 @@ impl Default for ClientConfig {
 /// # Examples
 ///
 /// ```ignore
-/// use dpdk_net_util::{DpdkHttpClient, ClientConfig};
+/// use dpdk_net_util::{DpdkHttpClient, DpdkRequestBuilder};
 /// use dpdk_net::runtime::ReactorHandle;
 /// use smoltcp::wire::IpAddress;
 ///
@@ -56,9 +166,8 @@ impl Default for ClientConfig {
 ///         .await
 ///         .unwrap();
 ///
-///     let req = hyper::Request::get("/")
-///         .header("Host", "10.0.0.1:8080")
-///         .body(http_body_util::Empty::<bytes::Bytes>::new())
+///     let req = DpdkRequestBuilder::get(IpAddress::v4(10, 0, 0, 1), 8080, "/")
+///         .empty()
 ///         .unwrap();
 ///     let resp = conn.send_request(req).await.unwrap();
 /// }
@@ -66,6 +175,7 @@ impl Default for ClientConfig {
 pub struct DpdkHttpClient {
     reactor: ReactorHandle,
     config: ClientConfig,
+    backoff: EndpointBackoff,
 }
 
 impl DpdkHttpClient {
@@ -76,43 +186,38 @@ impl DpdkHttpClient {
 
     /// Create a new HTTP client with custom configuration.
     pub fn with_config(reactor: ReactorHandle, config: ClientConfig) -> Self {
-        Self { reactor, config }
+        let backoff = EndpointBackoff::new(config.reconnect_backoff.clone());
+        Self {
+            reactor,
+            config,
+            backoff,
+        }
     }
 
     /// Open an HTTP connection to the given address and port.
     ///
     /// `local_port` is the ephemeral source port for the TCP connection.
     /// The HTTP version is determined by [`ClientConfig::http_version`].
+    /// If [`ClientConfig::proxy`] is set, the TCP connection goes to the
+    /// proxy and `addr:port` is reached through the tunnel.
+    ///
+    /// If [`ClientConfig::reconnect_backoff`] is set and the previous attempt
+    /// to this endpoint failed, waits out the backoff delay first.
     pub async fn connect(
         &self,
         addr: IpAddress,
         port: u16,
         local_port: u16,
     ) -> Result<Connection, Error> {
-        match self.config.http_version {
-            HttpVersion::Http1 => {
-                Connection::http1(
-                    &self.reactor,
-                    addr,
-                    port,
-                    local_port,
-                    self.config.rx_buffer_size,
-                    self.config.tx_buffer_size,
-                )
-                .await
-            }
-            HttpVersion::Http2 => {
-                Connection::http2(
-                    &self.reactor,
-                    addr,
-                    port,
-                    local_port,
-                    self.config.rx_buffer_size,
-                    self.config.tx_buffer_size,
-                )
-                .await
-            }
-        }
+        connect_with_backoff(
+            &self.reactor,
+            &self.config,
+            &self.backoff,
+            addr,
+            port,
+            local_port,
+        )
+        .await
     }
 
     /// Send a one-shot HTTP request, creating a new connection.
@@ -134,8 +239,127 @@ impl DpdkHttpClient {
         conn.send_request(request).await
     }
 
+    /// Send a one-shot `GET` for `path` to `addr:port`.
+    ///
+    /// The request is built with [`DpdkRequestBuilder`] for the connection's
+    /// HTTP version, so `Host` and the URI authority match the target.
+    pub async fn get(
+        &self,
+        addr: IpAddress,
+        port: u16,
+        local_port: u16,
+        path: &str,
+    ) -> Result<Response<Incoming>, Error> {
+        let mut conn = self.connect(addr, port, local_port).await?;
+        let request = DpdkRequestBuilder::get(addr, port, path)
+            .version(conn.version())
+            .secure(conn.is_tls())
+            .empty()
+            .map_err(Error::InvalidRequest)?;
+        conn.send_request(request).await
+    }
+
+    /// Send a one-shot `POST` of `body` to `path` on `addr:port`.
+    ///
+    /// Built the same way as [`get`](Self::get).
+    pub async fn post(
+        &self,
+        addr: IpAddress,
+        port: u16,
+        local_port: u16,
+        path: &str,
+        body: Bytes,
+    ) -> Result<Response<Incoming>, Error> {
+        let mut conn = self.connect(addr, port, local_port).await?;
+        let request = DpdkRequestBuilder::post(addr, port, path)
+            .version(conn.version())
+            .secure(conn.is_tls())
+            .body(http_body_util::Full::new(body))
+            .map_err(Error::InvalidRequest)?;
+        conn.send_request(request).await
+    }
+
     /// Returns a reference to the client configuration.
     pub fn config(&self) -> &ClientConfig {
         &self.config
     }
 }
+
+/// Open a connection, honoring per-endpoint reconnect backoff.
+pub(crate) async fn connect_with_backoff(
+    reactor: &ReactorHandle,
+    config: &ClientConfig,
+    backoff: &EndpointBackoff,
+    addr: IpAddress,
+    port: u16,
+    local_port: u16,
+) -> Result<Connection, Error> {
+    if let Some(delay) = backoff.delay_for((addr, port)) {
+        reactor.sleep(delay).await;
+    }
+    let result = open_connection(reactor, config, addr, port, local_port).await;
+    backoff.record((addr, port), result.is_ok());
+    result
+}
+
+/// Open a connection according to `config`, tunneling through the proxy if
+/// one is configured, within [`ClientConfig::connect_timeout`].
+pub(crate) async fn open_connection(
+    reactor: &ReactorHandle,
+    config: &ClientConfig,
+    addr: IpAddress,
+    port: u16,
+    local_port: u16,
+) -> Result<Connection, Error> {
+    reactor
+        .timeout(
+            config.connect_timeout,
+            establish(reactor, config, addr, port, local_port),
+        )
+        .await
+        .unwrap_or(Err(Error::Timeout))
+}
+
+async fn establish(
+    reactor: &ReactorHandle,
+    config: &ClientConfig,
+    addr: IpAddress,
+    port: u16,
+    local_port: u16,
+) -> Result<Connection, Error> {
+    let (tcp_addr, tcp_port) = match &config.proxy {
+        Some(proxy) => (proxy.addr, proxy.port),
+        None => (addr, port),
+    };
+    let stream = Connection::connect_tcp(
+        reactor,
+        tcp_addr,
+        tcp_port,
+        local_port,
+        config.rx_buffer_size,
+        config.tx_buffer_size,
+    )
+    .await?;
+    if let Some(proxy) = &config.proxy {
+        proxy::establish_tunnel(&stream, proxy, &addr.to_string(), port).await?;
+    }
+    #[cfg(feature = "tls")]
+    let mut conn = match &config.tls {
+        Some(tls) => {
+            let server_name = config
+                .tls_server_name
+                .clone()
+                .unwrap_or_else(|| ServerName::from(std::net::IpAddr::from(addr)));
+            let tls = crate::tls::with_default_alpn(tls, config.http_version);
+            Connection::handshake_tls(stream, tls, server_name, config.http_version).await?
+        }
+        None => Connection::handshake(stream, config.http_version).await?,
+    };
+    #[cfg(not(feature = "tls"))]
+    let mut conn = Connection::handshake(stream, config.http_version).await?;
+    if !config.interceptors.is_empty() {
+        conn.set_interceptors(config.interceptors.clone());
+    }
+    conn.set_request_timeout(config.request_timeout);
+    Ok(conn)
+}
@@ -32,7 +32,9 @@ pub async fn http1_connect(
 
 /// Create an HTTP/2 connection to the given address.
 ///
-/// Convenience wrapper around [`Connection::http2`].
+/// Convenience wrapper around [`Connection::http2`]. The connection uses
+/// prior knowledge (no HTTP/1.1 upgrade), so the server must speak HTTP/2
+/// from the start, as [`h2c_serve_connection`](crate::h2c::h2c_serve_connection) does.
 ///
 /// # Arguments
 /// * `reactor`    – reactor handle for this lcore
@@ -1,6 +1,9 @@
 use std::future::Future;
+use std::io;
 use std::pin::Pin;
+use std::rc::Rc;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
 use bytes::Bytes;
 use http_body_util::BodyExt;
@@ -11,11 +14,14 @@ use hyper_util::rt::TokioIo;
 
 use dpdk_net::runtime::ReactorHandle;
 use dpdk_net::socket::TcpStream;
+use futures_io::{AsyncRead, AsyncWrite};
+use smoltcp::socket::tcp::State;
 use smoltcp::wire::IpAddress;
-use tokio_util::compat::{Compat, FuturesAsyncReadCompatExt};
+use tokio_util::compat::FuturesAsyncReadCompatExt;
 
 use crate::error::Error;
 use crate::executor::LocalExecutor;
+use crate::interceptor::Interceptors;
 
 /// HTTP version to use for a connection.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -34,6 +40,43 @@ pub enum HttpVersion {
 /// All usage must be on a single lcore via `spawn_local`.
 pub struct Connection {
     sender: ConnectionSender,
+    interceptors: Interceptors,
+    request_timeout: Option<Duration>,
+    /// The stream hyper does I/O on, kept for [`Connection::ping`].
+    stream: Rc<TcpStream>,
+    /// HTTP runs inside a TLS session on `stream`.
+    tls: bool,
+}
+
+/// Gives hyper I/O on a stream the [`Connection`] also holds.
+struct SharedStream(Rc<TcpStream>);
+
+impl AsyncRead for SharedStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut &*self.0).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for SharedStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut &*self.0).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut &*self.0).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut &*self.0).poll_close(cx)
+    }
 }
 
 type BoxError = Box<dyn std::error::Error + Send + Sync>;
@@ -88,16 +131,9 @@ impl Connection {
         rx_buffer: usize,
         tx_buffer: usize,
     ) -> Result<Self, Error> {
-        let io = Self::connect_tcp(reactor, addr, port, local_port, rx_buffer, tx_buffer).await?;
-        let (sender, conn) = http1::handshake(io).await.map_err(Error::Handshake)?;
-        tokio::task::spawn_local(async move {
-            if let Err(e) = conn.await {
-                tracing::error!(error = ?e, "HTTP/1.1 connection error");
-            }
-        });
-        Ok(Self {
-            sender: ConnectionSender::Http1(sender),
-        })
+        let stream =
+            Self::connect_tcp(reactor, addr, port, local_port, rx_buffer, tx_buffer).await?;
+        Self::handshake(stream, HttpVersion::Http1).await
     }
 
     /// Create a new HTTP/2 connection.
@@ -112,18 +148,91 @@ impl Connection {
         rx_buffer: usize,
         tx_buffer: usize,
     ) -> Result<Self, Error> {
-        let io = Self::connect_tcp(reactor, addr, port, local_port, rx_buffer, tx_buffer).await?;
-        let (sender, conn) = http2::handshake(LocalExecutor, io)
+        let stream =
+            Self::connect_tcp(reactor, addr, port, local_port, rx_buffer, tx_buffer).await?;
+        Self::handshake(stream, HttpVersion::Http2).await
+    }
+
+    /// Run the HTTP handshake over an already connected TCP stream.
+    ///
+    /// Useful when the stream needs preparation before HTTP starts, such as
+    /// a proxy tunnel. The connection driver is spawned via `spawn_local`.
+    pub async fn handshake(stream: TcpStream, version: HttpVersion) -> Result<Self, Error> {
+        let stream = Rc::new(stream);
+        Self::handshake_io(SharedStream(stream.clone()), stream, version, false).await
+    }
+
+    /// Run a TLS handshake with `server_name` over an already connected TCP
+    /// stream, then the HTTP handshake inside the session.
+    ///
+    /// The HTTP version is the one agreed through ALPN, or `version` if the
+    /// server did not pick one; offer protocols with
+    /// [`alpn_protocols`](rustls::ClientConfig::alpn_protocols).
+    #[cfg(feature = "tls")]
+    pub async fn handshake_tls(
+        stream: TcpStream,
+        config: std::sync::Arc<rustls::ClientConfig>,
+        server_name: rustls::pki_types::ServerName<'static>,
+        version: HttpVersion,
+    ) -> Result<Self, Error> {
+        use crate::tls::{ALPN_H2, ALPN_HTTP1, TlsStream};
+
+        let stream = Rc::new(stream);
+        let tls = TlsStream::connect(stream.clone(), config, server_name)
             .await
-            .map_err(Error::Handshake)?;
-        tokio::task::spawn_local(async move {
-            if let Err(e) = conn.await {
-                tracing::error!(error = ?e, "HTTP/2 connection error");
+            .map_err(Error::Tls)?;
+        let version = match tls.alpn_protocol() {
+            Some(p) if p == ALPN_H2 => HttpVersion::Http2,
+            Some(p) if p == ALPN_HTTP1 => HttpVersion::Http1,
+            _ => version,
+        };
+        Self::handshake_io(tls, stream, version, true).await
+    }
+
+    async fn handshake_io<I>(
+        io: I,
+        stream: Rc<TcpStream>,
+        version: HttpVersion,
+        tls: bool,
+    ) -> Result<Self, Error>
+    where
+        I: AsyncRead + AsyncWrite + Unpin + 'static,
+    {
+        let io = TokioIo::new(io.compat());
+        match version {
+            HttpVersion::Http1 => {
+                let (sender, conn) = http1::handshake(io).await.map_err(Error::Handshake)?;
+                tokio::task::spawn_local(async move {
+                    if let Err(e) = conn.await {
+                        tracing::error!(error = ?e, "HTTP/1.1 connection error");
+                    }
+                });
+                Ok(Self {
+                    sender: ConnectionSender::Http1(sender),
+                    interceptors: Interceptors::default(),
+                    request_timeout: None,
+                    stream,
+                    tls,
+                })
             }
-        });
-        Ok(Self {
-            sender: ConnectionSender::Http2(sender),
-        })
+            HttpVersion::Http2 => {
+                let (sender, conn) = http2::handshake(LocalExecutor, io)
+                    .await
+                    .map_err(Error::Handshake)?;
+                tokio::task::spawn_local(async move {
+                    if let Err(e) = conn.await {
+                        tracing::error!(error = ?e, "HTTP/2 connection error");
+                    }
+                });
+                Ok(Self {
+                    sender: ConnectionSender::Http2(sender),
+                    interceptors: Interceptors::default(),
+                    request_timeout: None,
+                    stream,
+                    tls,
+                })
+            }
+        }
     }
 
     /// Send a request over this connection.
@@ -132,26 +241,65 @@ impl Connection {
     /// is an owned (`'static`) future that does not borrow the connection.
     /// This means the connection can be reused for another request while the
     /// response is still being awaited (HTTP/2 multiplexing).
+    ///
+    /// Configured [`Interceptors`] run here: `on_request` before dispatch and
+    /// `on_response` when the response head arrives.
+    ///
+    /// With a [request timeout](Self::set_request_timeout), the future fails
+    /// with [`Error::Timeout`] if the head does not arrive in time.
     pub fn send_request<B>(&mut self, request: Request<B>) -> ResponseFuture
     where
         B: hyper::body::Body<Data = Bytes> + 'static,
         B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
     {
+        let request = match &self.interceptors.on_request {
+            Some(on_request) => {
+                let (mut parts, body) = request.into_parts();
+                on_request(&mut parts);
+                Request::from_parts(parts, body)
+            }
+            None => request,
+        };
         let request = request.map(into_box_body);
-        let inner: Pin<Box<dyn Future<Output = Result<Response<Incoming>, Error>>>> =
+        let on_response = self.interceptors.on_response.clone();
+        let response: Pin<Box<dyn Future<Output = hyper::Result<Response<Incoming>>>>> =
             match &mut self.sender {
-                ConnectionSender::Http1(sender) => {
-                    let fut = sender.send_request(request);
-                    Box::pin(async move { fut.await.map_err(Error::Request) })
-                }
-                ConnectionSender::Http2(sender) => {
-                    let fut = sender.send_request(request);
-                    Box::pin(async move { fut.await.map_err(Error::Request) })
-                }
+                ConnectionSender::Http1(sender) => Box::pin(sender.send_request(request)),
+                ConnectionSender::Http2(sender) => Box::pin(sender.send_request(request)),
             };
+        let timeout = self.request_timeout;
+        let reactor = self.stream.reactor();
+        let inner = Box::pin(async move {
+            let resp = match timeout {
+                Some(timeout) => match reactor.timeout(timeout, response).await {
+                    Ok(resp) => resp,
+                    Err(_) => return Err(Error::Timeout),
+                },
+                None => response.await,
+            }
+            .map_err(Error::Request)?;
+            if let Some(on_response) = on_response {
+                on_response(&resp);
+            }
+            Ok(resp)
+        });
         ResponseFuture { inner }
     }
 
+    /// Attach interceptors to this connection, replacing any existing ones.
+    pub fn set_interceptors(&mut self, interceptors: Interceptors) {
+        self.interceptors = interceptors;
+    }
+
+    /// Fail requests sent from now on with [`Error::Timeout`] if their
+    /// response head takes longer than `timeout`; `None` waits forever.
+    ///
+    /// Connections opened by [`DpdkHttpClient`](crate::DpdkHttpClient) get
+    /// [`ClientConfig::request_timeout`](crate::ClientConfig::request_timeout).
+    pub fn set_request_timeout(&mut self, timeout: Option<Duration>) {
+        self.request_timeout = timeout;
+    }
+
     /// Check if the connection is still usable for sending requests.
     pub fn is_ready(&self) -> bool {
         match &self.sender {
@@ -160,6 +308,84 @@ impl Connection {
         }
     }
 
+    /// Check if hyper has given up on the connection for good.
+    pub fn is_closed(&self) -> bool {
+        match &self.sender {
+            ConnectionSender::Http1(s) => s.is_closed(),
+            ConnectionSender::Http2(s) => s.is_closed(),
+        }
+    }
+
+    /// Check if an HTTP/1.1 request is still in flight: the connection is
+    /// open but takes no other request until that response has been read.
+    ///
+    /// Always false for HTTP/2, which multiplexes.
+    pub fn is_busy(&self) -> bool {
+        self.version() == HttpVersion::Http1
+            && !self.is_ready()
+            && !self.is_closed()
+            && self.stream.state() == State::Established
+    }
+
+    /// Check that the connection is still worth sending a request on,
+    /// without waiting.
+    ///
+    /// Fails if hyper has given up on the connection, or if the TCP stream
+    /// has left `Established` (the server closed or reset it). For HTTP/1.1
+    /// it also fails when the stream holds unread bytes: an idle HTTP/1.1
+    /// connection receives nothing, so those are a stray response or an
+    /// error the server sent before closing (e.g. a `408 Request Timeout`).
+    /// HTTP/2 peers may send frames at any time, and a TLS session may get
+    /// records such as session tickets, so unread bytes are not checked
+    /// there.
+    pub fn check(&self) -> Result<(), Error> {
+        if !self.is_ready() {
+            return Err(Error::ConnectionNotReady);
+        }
+        if self.stream.state() != State::Established {
+            return Err(Error::Unhealthy("TCP connection is not established"));
+        }
+        if self.version() == HttpVersion::Http1 && !self.tls && self.stream.recv_queue_len() > 0 {
+            return Err(Error::Unhealthy("unexpected data on idle connection"));
+        }
+        Ok(())
+    }
+
+    /// Check that the connection is alive, waiting up to `timeout` for it
+    /// to accept a request.
+    ///
+    /// Runs [`check`](Self::check), then waits until hyper can dispatch a
+    /// request: for HTTP/1.1 until any in-flight request has finished, for
+    /// HTTP/2 until the connection has room for another stream.
+    ///
+    /// # HTTP/2
+    ///
+    /// hyper does not expose PING frames on client connections, so this is
+    /// not a PING round trip: a peer that vanished without closing the TCP
+    /// connection passes until the stream's own timeouts notice. It does
+    /// catch a connection that was closed, reset or ended with `GOAWAY`.
+    pub async fn ping(&mut self, timeout: Duration) -> Result<(), Error> {
+        self.check()?;
+        let reactor = self.stream.reactor();
+        let ready = async {
+            match &mut self.sender {
+                ConnectionSender::Http1(s) => s.ready().await,
+                ConnectionSender::Http2(s) => s.ready().await,
+            }
+        };
+        reactor
+            .timeout(timeout, ready)
+            .await
+            .map_err(|_| Error::Unhealthy("ping timed out"))?
+            .map_err(Error::Request)?;
+        self.check()
+    }
+
+    /// Check if HTTP runs over TLS on this connection.
+    pub fn is_tls(&self) -> bool {
+        self.tls
+    }
+
     /// Returns the HTTP version of this connection.
     pub fn version(&self) -> HttpVersion {
         match &self.sender {
@@ -168,20 +394,20 @@ impl Connection {
         }
     }
 
-    /// Establish a DPDK TCP connection and wrap it for hyper.
-    async fn connect_tcp(
+    /// Establish a DPDK TCP connection.
+    pub(crate) async fn connect_tcp(
         reactor: &ReactorHandle,
         addr: IpAddress,
         port: u16,
         local_port: u16,
         rx_buffer: usize,
         tx_buffer: usize,
-    ) -> Result<TokioIo<Compat<TcpStream>>, Error> {
+    ) -> Result<TcpStream, Error> {
         let stream = TcpStream::connect(reactor, addr, port, local_port, rx_buffer, tx_buffer)?;
         stream
             .wait_connected()
             .await
             .map_err(|()| Error::ConnectionFailed)?;
-        Ok(TokioIo::new(stream.compat()))
+        Ok(stream)
     }
 }
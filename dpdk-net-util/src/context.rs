@@ -1,8 +1,22 @@
 //! Worker context passed to each lcore.
 
+use crate::ready::ReadyBarrier;
+
 use dpdk_net::api::rte::lcore::Lcore;
 use dpdk_net::runtime::ReactorHandle;
 
+use std::cell::Cell;
+use std::rc::Rc;
+#[cfg(feature = "tls")]
+use std::sync::Arc;
+
+#[cfg(feature = "tls")]
+use crate::tls::TlsStream;
+#[cfg(feature = "tls")]
+use dpdk_net::socket::TcpStream;
+#[cfg(feature = "tls")]
+use rustls::ServerConnection;
+
 /// Context passed to each worker lcore.
 ///
 /// This provides everything needed to run a server or client on a specific lcore:
@@ -18,6 +32,7 @@ use dpdk_net::runtime::ReactorHandle;
 /// async fn my_server(ctx: WorkerContext) {
 ///     // Create a server listener
 ///     let listener = TcpListener::bind(&ctx.reactor, 8080, 4096, 4096).unwrap();
+///     ctx.mark_ready();
 ///     // ... serve requests
 /// }
 /// ```
@@ -39,4 +54,87 @@ pub struct WorkerContext {
     ///
     /// Use this to create `TcpListener` (server) or `TcpStream` (client).
     pub reactor: ReactorHandle,
+
+    /// Startup barrier shared by all workers of the app.
+    pub(crate) ready: ReadyBarrier,
+
+    /// Whether this worker has already arrived at the barrier.
+    pub(crate) marked: Rc<Cell<bool>>,
+
+    /// TLS settings from [`DpdkApp::tls`](crate::DpdkApp::tls).
+    #[cfg(feature = "tls")]
+    pub(crate) tls: Option<Arc<rustls::ServerConfig>>,
+}
+
+impl WorkerContext {
+    /// Report this worker as ready (e.g. after binding its listeners).
+    ///
+    /// Calling this more than once has no further effect. Workers that never
+    /// call it are counted as ready when their closure returns or panics, so waiters
+    /// cannot hang on a worker that exited early.
+    pub fn mark_ready(&self) {
+        if !self.marked.replace(true) {
+            self.ready.arrive();
+        }
+    }
+
+    /// Wait until every worker of the app has reported ready.
+    ///
+    /// Call [`mark_ready`](Self::mark_ready) first, otherwise this worker
+    /// waits for itself.
+    pub async fn wait_ready(&self) {
+        self.ready.wait().await
+    }
+
+    /// Get a clone of the startup barrier.
+    ///
+    /// The barrier is `Send`, so it can be passed to tasks outside the lcore
+    /// (e.g. a load generator on a regular tokio runtime).
+    pub fn ready_barrier(&self) -> ReadyBarrier {
+        self.ready.clone()
+    }
+
+    /// Returns true if the app terminates TLS on accepted connections.
+    ///
+    /// Always false without the `tls` feature.
+    #[cfg(feature = "tls")]
+    pub fn is_tls(&self) -> bool {
+        self.tls.is_some()
+    }
+
+    /// Returns true if the app terminates TLS on accepted connections.
+    ///
+    /// Always false without the `tls` feature.
+    #[cfg(not(feature = "tls"))]
+    pub fn is_tls(&self) -> bool {
+        false
+    }
+
+    /// The app's TLS server settings, if any (feature: `tls`).
+    #[cfg(feature = "tls")]
+    pub fn tls_config(&self) -> Option<&Arc<rustls::ServerConfig>> {
+        self.tls.as_ref()
+    }
+
+    /// Run the TLS server handshake on an accepted `stream` (feature: `tls`).
+    ///
+    /// The handshake is driven by this worker's reactor like any other
+    /// stream I/O. The negotiated protocol is in
+    /// [`TlsStream::alpn_protocol`] once it returns.
+    ///
+    /// Fails with [`ErrorKind::Unsupported`](std::io::ErrorKind::Unsupported) if the
+    /// app has no TLS settings.
+    #[cfg(feature = "tls")]
+    pub async fn accept_tls(
+        &self,
+        stream: TcpStream,
+    ) -> std::io::Result<TlsStream<ServerConnection>> {
+        let Some(config) = self.tls.clone() else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "TLS is not configured on this DpdkApp",
+            ));
+        };
+        TlsStream::accept(Rc::new(stream), config).await
+    }
 }
@@ -1,10 +1,16 @@
 use std::fmt;
+use std::io;
+
+use dpdk_net::socket::{TcpConnectError, TcpStreamError};
+
+use crate::proxy::ProxyError;
 
 /// Error type for dpdk-net-util operations.
 #[derive(Debug)]
 pub enum Error {
-    /// TCP connection failed.
-    Connect(smoltcp::socket::tcp::ConnectError),
+    /// TCP connection failed, including hitting the reactor's socket cap
+    /// ([`TcpConnectError::TooManySockets`]).
+    Connect(TcpConnectError),
     /// The TCP connection was refused or timed out.
     ConnectionFailed,
     /// HTTP handshake failed.
@@ -13,8 +19,23 @@ pub enum Error {
     Request(hyper::Error),
     /// Missing host in request URI.
     MissingHost,
+    /// A request could not be built (invalid path or header).
+    InvalidRequest(hyper::http::Error),
     /// The connection is closed or not ready.
     ConnectionNotReady,
+    /// [`Connection::check`](crate::Connection::check) or
+    /// [`Connection::ping`](crate::Connection::ping) found the connection
+    /// dead; the reason says why.
+    Unhealthy(&'static str),
+    /// Establishing the proxy tunnel failed.
+    Proxy(ProxyError),
+    /// Every local port in the reactor's ephemeral range is in use.
+    PortsExhausted,
+    /// The TLS handshake failed (feature: `tls`).
+    Tls(std::io::Error),
+    /// Opening the connection or waiting for the response took longer than
+    /// the limit set in [`ClientConfig`](crate::ClientConfig).
+    Timeout,
 }
 
 impl fmt::Display for Error {
@@ -25,7 +46,13 @@ impl fmt::Display for Error {
             Error::Handshake(e) => write!(f, "HTTP handshake error: {e}"),
             Error::Request(e) => write!(f, "HTTP request error: {e}"),
             Error::MissingHost => write!(f, "missing host in request URI"),
+            Error::InvalidRequest(e) => write!(f, "invalid request: {e}"),
             Error::ConnectionNotReady => write!(f, "connection is closed or not ready"),
+            Error::Unhealthy(reason) => write!(f, "connection failed health check: {reason}"),
+            Error::Proxy(e) => write!(f, "proxy tunnel error: {e}"),
+            Error::PortsExhausted => write!(f, "no free local port in the ephemeral range"),
+            Error::Tls(e) => write!(f, "TLS handshake error: {e}"),
+            Error::Timeout => write!(f, "timed out"),
         }
     }
 }
@@ -35,13 +62,91 @@ impl std::error::Error for Error {
         match self {
             Error::Connect(e) => Some(e),
             Error::Handshake(e) | Error::Request(e) => Some(e),
+            Error::Proxy(e) => Some(e),
+            Error::InvalidRequest(e) => Some(e),
+            Error::Tls(e) => Some(e),
             _ => None,
         }
     }
 }
 
+impl Error {
+    /// The I/O error this one came from, if the transport failed.
+    ///
+    /// hyper reports a failed read or write as an opaque [`hyper::Error`];
+    /// this walks its source chain to the `io::Error` the stream returned.
+    pub fn io_error(&self) -> Option<&io::Error> {
+        let mut source = std::error::Error::source(self);
+        while let Some(e) = source {
+            if let Some(io) = e.downcast_ref::<io::Error>() {
+                return Some(io);
+            }
+            source = e.source();
+        }
+        None
+    }
+
+    /// The dpdk-net stream error behind [`io_error`](Self::io_error), if the
+    /// failure came from the DPDK stream rather than, say, TLS.
+    pub fn stream_error(&self) -> Option<TcpStreamError> {
+        self.io_error()?
+            .get_ref()?
+            .downcast_ref::<TcpStreamError>()
+            .copied()
+    }
+
+    /// Returns true if the connection was reset underneath the request: a
+    /// peer RST, a local abort, or smoltcp giving up on retransmissions.
+    ///
+    /// The request may or may not have reached the server, so only retry
+    /// idempotent requests on a fresh connection.
+    pub fn is_connection_reset(&self) -> bool {
+        self.io_error()
+            .is_some_and(|e| e.kind() == io::ErrorKind::ConnectionReset)
+    }
+}
+
+impl From<TcpConnectError> for Error {
+    fn from(e: TcpConnectError) -> Self {
+        Error::Connect(e)
+    }
+}
+
 impl From<smoltcp::socket::tcp::ConnectError> for Error {
     fn from(e: smoltcp::socket::tcp::ConnectError) -> Self {
-        Error::Connect(e)
+        Error::Connect(e.into())
+    }
+}
+
+impl From<ProxyError> for Error {
+    fn from(e: ProxyError) -> Self {
+        Error::Proxy(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_error_through_source_chain() {
+        let err = Error::Tls(TcpStreamError::ConnectionReset.into());
+        assert!(err.is_connection_reset());
+        assert_eq!(err.stream_error(), Some(TcpStreamError::ConnectionReset));
+
+        let err = Error::Tls(TcpStreamError::Shutdown.into());
+        assert!(!err.is_connection_reset());
+        assert_eq!(
+            err.io_error().map(io::Error::kind),
+            Some(io::ErrorKind::BrokenPipe)
+        );
+
+        // Not from a dpdk-net stream
+        let err = Error::Tls(io::ErrorKind::ConnectionReset.into());
+        assert!(err.is_connection_reset());
+        assert_eq!(err.stream_error(), None);
+
+        assert!(Error::Timeout.io_error().is_none());
+        assert!(!Error::Connect(TcpConnectError::Refused).is_connection_reset());
     }
 }
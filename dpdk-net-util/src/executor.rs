@@ -1,3 +1,11 @@
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::future::{Future, poll_fn};
+use std::rc::Rc;
+use std::task::{Poll, Waker};
+
+use tokio::task::JoinHandle;
+
 /// A local executor for hyper that uses `spawn_local` instead of `spawn`.
 ///
 /// Required for HTTP/2 because hyper needs an executor for background tasks,
@@ -14,3 +22,232 @@ where
         tokio::task::spawn_local(fut);
     }
 }
+
+/// A [`LocalExecutor`] that counts its live tasks against a ceiling.
+///
+/// `spawn_local` alone lets a flood of connections spawn handlers until the
+/// worker runs out of memory. The accept loop spawns through this executor
+/// instead, and either turns a connection away when the ceiling is reached
+/// ([`try_spawn`](Self::try_spawn)) or stops accepting until a task ends
+/// ([`spawn`](Self::spawn)).
+///
+/// hyper's [`Executor::execute`](hyper::rt::Executor::execute) cannot fail or
+/// wait, so tasks hyper spawns (HTTP/2 streams) always run. They count
+/// towards the ceiling, which holds back the next accept instead.
+///
+/// Clones share the count. `!Send`: create one per worker.
+///
+/// # Example
+///
+/// ```ignore
+/// use dpdk_net_util::BoundedLocalExecutor;
+///
+/// // At most 256 connection tasks on this worker
+/// let executor = BoundedLocalExecutor::new(256);
+/// loop {
+///     let stream = listener.accept().await?;
+///     if executor.try_spawn(handle(stream)).is_err() {
+///         // Over the ceiling: the handler future and its stream are dropped
+///     }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct BoundedLocalExecutor {
+    inner: Rc<Bound>,
+}
+
+struct Bound {
+    live: Cell<usize>,
+    max: usize,
+    /// Spawners waiting for a task to end.
+    waiters: RefCell<Vec<Waker>>,
+}
+
+impl BoundedLocalExecutor {
+    /// Create an executor that admits up to `max` live tasks.
+    pub fn new(max: usize) -> Self {
+        Self {
+            inner: Rc::new(Bound {
+                live: Cell::new(0),
+                max,
+                waiters: RefCell::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// The ceiling on live tasks.
+    pub fn max_tasks(&self) -> usize {
+        self.inner.max
+    }
+
+    /// Tasks spawned through this executor that have not finished.
+    pub fn live_tasks(&self) -> usize {
+        self.inner.live.get()
+    }
+
+    /// Returns true if another task may be spawned.
+    pub fn has_capacity(&self) -> bool {
+        self.live_tasks() < self.inner.max
+    }
+
+    /// Spawn `fut` if the executor is under its ceiling.
+    ///
+    /// Returns `fut` unpolled when it is not; dropping it drops whatever it
+    /// owns, such as the connection it would have served.
+    pub fn try_spawn<F>(&self, fut: F) -> Result<JoinHandle<F::Output>, F>
+    where
+        F: Future + 'static,
+        F::Output: 'static,
+    {
+        if !self.has_capacity() {
+            return Err(fut);
+        }
+        Ok(self.spawn_counted(fut))
+    }
+
+    /// Wait until the executor is under its ceiling, then spawn `fut`.
+    pub async fn spawn<F>(&self, fut: F) -> JoinHandle<F::Output>
+    where
+        F: Future + 'static,
+        F::Output: 'static,
+    {
+        self.ready().await;
+        self.spawn_counted(fut)
+    }
+
+    /// Wait until the executor is under its ceiling.
+    pub async fn ready(&self) {
+        poll_fn(|cx| {
+            if self.has_capacity() {
+                return Poll::Ready(());
+            }
+            let mut waiters = self.inner.waiters.borrow_mut();
+            if !waiters.iter().any(|w| w.will_wake(cx.waker())) {
+                waiters.push(cx.waker().clone());
+            }
+            Poll::Pending
+        })
+        .await
+    }
+
+    fn spawn_counted<F>(&self, fut: F) -> JoinHandle<F::Output>
+    where
+        F: Future + 'static,
+        F::Output: 'static,
+    {
+        self.inner.live.set(self.inner.live.get() + 1);
+        let slot = TaskSlot {
+            inner: self.inner.clone(),
+        };
+        tokio::task::spawn_local(async move {
+            let _slot = slot;
+            fut.await
+        })
+    }
+}
+
+impl fmt::Debug for BoundedLocalExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoundedLocalExecutor")
+            .field("live", &self.live_tasks())
+            .field("max", &self.max_tasks())
+            .finish()
+    }
+}
+
+impl<F> hyper::rt::Executor<F> for BoundedLocalExecutor
+where
+    F: std::future::Future + 'static,
+    F::Output: 'static,
+{
+    fn execute(&self, fut: F) {
+        self.spawn_counted(fut);
+    }
+}
+
+/// Held by a spawned task; gives its place back when the task ends or is
+/// aborted.
+struct TaskSlot {
+    inner: Rc<Bound>,
+}
+
+impl Drop for TaskSlot {
+    fn drop(&mut self) {
+        self.inner.live.set(self.inner.live.get() - 1);
+        for waker in self.inner.waiters.take() {
+            waker.wake();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::oneshot;
+    use tokio::task::LocalSet;
+
+    #[tokio::test]
+    async fn test_try_spawn_refuses_over_ceiling() {
+        LocalSet::new()
+            .run_until(async {
+                let executor = BoundedLocalExecutor::new(1);
+                let (tx, rx) = oneshot::channel::<()>();
+                let first = executor
+                    .try_spawn(async move { rx.await.ok() })
+                    .expect("first task refused");
+                assert_eq!(executor.live_tasks(), 1);
+                assert!(executor.try_spawn(async {}).is_err());
+
+                tx.send(()).unwrap();
+                first.await.unwrap();
+                assert_eq!(executor.live_tasks(), 0);
+                assert!(executor.try_spawn(async {}).is_ok());
+            })
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_spawn_waits_for_a_slot() {
+        LocalSet::new()
+            .run_until(async {
+                let executor = BoundedLocalExecutor::new(1);
+                let (tx, rx) = oneshot::channel::<()>();
+                let _first = executor.spawn(async move { rx.await.ok() }).await;
+
+                let spawner = executor.clone();
+                let second = tokio::task::spawn_local(async move {
+                    spawner.spawn(async {}).await.await.unwrap();
+                });
+                tokio::task::yield_now().await;
+                assert!(!second.is_finished());
+                assert_eq!(executor.live_tasks(), 1);
+
+                tx.send(()).unwrap();
+                second.await.unwrap();
+                assert_eq!(executor.live_tasks(), 0);
+            })
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_hyper_tasks_are_counted_not_refused() {
+        LocalSet::new()
+            .run_until(async {
+                use hyper::rt::Executor;
+
+                let executor = BoundedLocalExecutor::new(1);
+                let (tx, rx) = oneshot::channel::<()>();
+                executor.execute(async move {
+                    rx.await.ok();
+                });
+                executor.execute(async {});
+                assert_eq!(executor.live_tasks(), 2);
+                assert!(!executor.has_capacity());
+
+                tx.send(()).unwrap();
+                executor.ready().await;
+                assert_eq!(executor.live_tasks(), 0);
+            })
+            .await;
+    }
+}
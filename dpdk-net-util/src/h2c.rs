@@ -0,0 +1,73 @@
+//! HTTP/2 over cleartext with prior knowledge (h2c).
+//!
+//! The server side speaks HTTP/2 from the first byte: no `Upgrade: h2c`
+//! round trip and no HTTP/1.1 sniffing as done by hyper-util's auto builder.
+//! Use it where every client is known to speak HTTP/2, such as gRPC over
+//! cleartext or service-mesh sidecars.
+//!
+//! The matching client is [`http2_connect`](crate::http2_connect) (or
+//! [`Connection::http2`](crate::Connection::http2)), whose handshake already
+//! assumes prior knowledge.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use dpdk_net::socket::TcpListener;
+//! use dpdk_net_util::h2c::h2c_serve_connection;
+//! use http_body_util::Full;
+//! use hyper::body::Bytes;
+//! use hyper::service::service_fn;
+//! use hyper::Response;
+//!
+//! // Server
+//! let mut listener = TcpListener::bind(&ctx.reactor, 8080, 65536, 65536)?;
+//! loop {
+//!     let stream = listener.accept().await?;
+//!     tokio::task::spawn_local(h2c_serve_connection(
+//!         stream,
+//!         service_fn(|_req| async {
+//!             Ok::<_, hyper::Error>(Response::new(Full::new(Bytes::from("hi"))))
+//!         }),
+//!     ));
+//! }
+//!
+//! // Client
+//! let mut conn = dpdk_net_util::http2_connect(&reactor, addr, 8080, 49152, 65536, 65536).await?;
+//! let resp = conn.send_request(req).await?;
+//! ```
+
+use dpdk_net::socket::TcpStream;
+use hyper::body::{Body, Incoming};
+use hyper::server::conn::http2;
+use hyper::service::HttpService;
+use hyper_util::rt::TokioIo;
+use tokio_util::compat::FuturesAsyncReadCompatExt;
+
+use crate::executor::LocalExecutor;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// An HTTP/2 server builder wired to [`LocalExecutor`].
+///
+/// Use this instead of [`h2c_serve_connection`] to tune window sizes,
+/// concurrent stream limits or keep-alive before serving.
+pub fn h2c_builder() -> http2::Builder<LocalExecutor> {
+    http2::Builder::new(LocalExecutor)
+}
+
+/// Serve one prior-knowledge HTTP/2 connection on an accepted stream.
+///
+/// Resolves when the client closes the connection, or with an error if the
+/// connection fails (including a client that does not send the HTTP/2
+/// preface). Typically spawned with `spawn_local` per accepted stream.
+pub async fn h2c_serve_connection<S, B>(stream: TcpStream, service: S) -> Result<(), hyper::Error>
+where
+    S: HttpService<Incoming, ResBody = B>,
+    S::Future: 'static,
+    S::Error: Into<BoxError>,
+    B: Body + 'static,
+    B::Error: Into<BoxError>,
+{
+    let io = TokioIo::new(stream.compat());
+    h2c_builder().serve_connection(io, service).await
+}
@@ -0,0 +1,77 @@
+//! Request/response interceptor hooks.
+//!
+//! Interceptors are configured once on [`ClientConfig`](crate::ClientConfig)
+//! and run by [`Connection::send_request`](crate::Connection::send_request)
+//! for every request, which keeps logging, metrics and header injection
+//! (e.g. trace context propagation) out of call sites.
+//!
+//! # Ordering
+//!
+//! - `on_request` runs synchronously inside `send_request`, before the request
+//!   is handed to hyper. Header changes made here are sent on the wire.
+//! - `on_response` runs when the response head arrives, before the
+//!   [`ResponseFuture`](crate::ResponseFuture) resolves. It does not run if
+//!   the request fails.
+//! - The client never retries on its own. A caller that retries calls
+//!   `send_request` again, so both hooks run once per attempt.
+//!
+//! Hooks are stored as `Rc<dyn Fn>` and need not be `Send`, matching the rest
+//! of the per-lcore client.
+
+use std::rc::Rc;
+
+use hyper::Response;
+use hyper::body::Incoming;
+use hyper::http::request;
+
+/// Hook invoked with each outgoing request's head (method, URI, headers).
+pub type RequestInterceptor = Rc<dyn Fn(&mut request::Parts)>;
+
+/// Hook invoked with each response (status, headers).
+pub type ResponseInterceptor = Rc<dyn Fn(&Response<Incoming>)>;
+
+/// Set of interceptors attached to a [`Connection`](crate::Connection).
+#[derive(Clone, Default)]
+pub struct Interceptors {
+    pub(crate) on_request: Option<RequestInterceptor>,
+    pub(crate) on_response: Option<ResponseInterceptor>,
+}
+
+impl Interceptors {
+    /// Create an empty set of interceptors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the request hook.
+    pub fn on_request<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut request::Parts) + 'static,
+    {
+        self.on_request = Some(Rc::new(f));
+        self
+    }
+
+    /// Set the response hook.
+    pub fn on_response<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&Response<Incoming>) + 'static,
+    {
+        self.on_response = Some(Rc::new(f));
+        self
+    }
+
+    /// Returns true if no hooks are set.
+    pub fn is_empty(&self) -> bool {
+        self.on_request.is_none() && self.on_response.is_none()
+    }
+}
+
+impl std::fmt::Debug for Interceptors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Interceptors")
+            .field("on_request", &self.on_request.is_some())
+            .field("on_response", &self.on_response.is_some())
+            .finish()
+    }
+}
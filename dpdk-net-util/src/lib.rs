@@ -31,6 +31,8 @@
 //! ```
 
 pub mod app;
+pub mod backoff;
+pub mod bench;
 pub mod bridge;
 pub mod client;
 pub mod connect;
@@ -38,14 +40,39 @@ pub mod connection;
 pub mod context;
 pub mod error;
 pub mod executor;
+pub mod h2c;
+pub mod interceptor;
+pub mod overload;
 pub mod pool;
+pub mod proxy;
+pub mod ready;
+pub mod report;
+pub mod request;
+pub mod runtime;
+pub mod semaphore;
+pub mod serve;
+#[cfg(feature = "tls")]
+pub mod tls;
 
 pub use app::DpdkApp;
+pub use backoff::{Backoff, BackoffConfig};
 pub use bridge::{BridgeError, BridgeTcpListener, BridgeTcpStream, BridgeWorkers, DpdkBridge};
 pub use client::{ClientConfig, DpdkHttpClient};
 pub use connect::{http1_connect, http2_connect};
 pub use connection::{Connection, HttpVersion, ResponseFuture};
 pub use context::WorkerContext;
 pub use error::Error;
-pub use executor::LocalExecutor;
-pub use pool::ConnectionPool;
+pub use executor::{BoundedLocalExecutor, LocalExecutor};
+pub use h2c::h2c_serve_connection;
+pub use interceptor::Interceptors;
+pub use overload::{ServerLoad, ShedPolicy};
+pub use pool::{ConnectionPool, PooledConnection};
+pub use proxy::{ProxyAuth, ProxyConfig, ProxyError, ProxyKind};
+pub use ready::ReadyBarrier;
+pub use report::{QueueReport, RssReport, RunReport, ShutdownStatus};
+pub use request::DpdkRequestBuilder;
+pub use runtime::TokioRuntime;
+pub use semaphore::{LocalPermit, LocalSemaphore};
+pub use serve::{ServeConfig, serve_http, serve_http_with};
+#[cfg(feature = "tls")]
+pub use tls::TlsStream;
@@ -0,0 +1,127 @@
+//! Load shedding for HTTP servers.
+//!
+//! A worker that cannot keep up is better off turning new connections away
+//! quickly than letting them pile up in the listen backlog until clients
+//! time out. With a [`ShedPolicy`] set on [`ServeConfig`](crate::ServeConfig)
+//! or [`Http1Server`](crate::bench::http::Http1Server), every accepted
+//! connection is checked against the worker's [`ServerLoad`]; when the policy
+//! says so, the server writes a bare `503 Service Unavailable` with
+//! `Connection: close` and closes, without reading the request.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use dpdk_net_util::overload::ShedPolicy;
+//! use dpdk_net_util::serve::ServeConfig;
+//!
+//! // Shed above 512 connections per queue, or when the mempool runs low
+//! let config = ServeConfig::new(8080).shed_load(ShedPolicy::new(|load| {
+//!     load.active_connections >= 512 || load.mbufs_available < 1024
+//! }));
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+
+use dpdk_net::socket::TcpStream;
+
+/// Response written to shed connections.
+pub const SERVICE_UNAVAILABLE: &[u8] = b"HTTP/1.1 503 Service Unavailable\r\n\
+    content-length: 0\r\n\
+    connection: close\r\n\
+    retry-after: 1\r\n\r\n";
+
+/// Load of one worker's server when a connection is accepted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServerLoad {
+    /// Connections being served on this queue, excluding the new one.
+    pub active_connections: usize,
+    /// Free mbufs in the device's mempool (shared by all queues).
+    pub mbufs_available: u32,
+}
+
+type Predicate = dyn Fn(&ServerLoad) -> bool + Send + Sync;
+
+/// Decides when a server turns new connections away with a 503.
+///
+/// Cheap to clone; clones share the predicate. The predicate runs on the
+/// worker thread for every accepted connection, so keep it cheap.
+#[derive(Clone)]
+pub struct ShedPolicy {
+    predicate: Arc<Predicate>,
+}
+
+impl ShedPolicy {
+    /// Shed whenever `predicate` returns true.
+    pub fn new<F>(predicate: F) -> Self
+    where
+        F: Fn(&ServerLoad) -> bool + Send + Sync + 'static,
+    {
+        Self {
+            predicate: Arc::new(predicate),
+        }
+    }
+
+    /// Shed once a queue is serving `max` connections.
+    pub fn max_connections(max: usize) -> Self {
+        Self::new(move |load| load.active_connections >= max)
+    }
+
+    /// Shed while fewer than `min` mbufs are free.
+    pub fn min_mbufs(min: u32) -> Self {
+        Self::new(move |load| load.mbufs_available < min)
+    }
+
+    /// Returns true if a connection arriving under `load` should be shed.
+    pub fn should_shed(&self, load: &ServerLoad) -> bool {
+        (self.predicate)(load)
+    }
+}
+
+impl fmt::Debug for ShedPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ShedPolicy").finish_non_exhaustive()
+    }
+}
+
+/// Write [`SERVICE_UNAVAILABLE`] to `stream` and close it.
+///
+/// Errors are ignored: the client may already be gone, and there is nothing
+/// more to tell it either way.
+pub(crate) async fn reject(stream: TcpStream) {
+    if stream.send(SERVICE_UNAVAILABLE).await.is_ok() {
+        let _ = stream.close().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(active_connections: usize, mbufs_available: u32) -> ServerLoad {
+        ServerLoad {
+            active_connections,
+            mbufs_available,
+        }
+    }
+
+    #[test]
+    fn test_builtin_policies() {
+        let conns = ShedPolicy::max_connections(2);
+        assert!(!conns.should_shed(&load(1, 0)));
+        assert!(conns.should_shed(&load(2, 0)));
+
+        let mbufs = ShedPolicy::min_mbufs(100);
+        assert!(mbufs.should_shed(&load(0, 99)));
+        assert!(!mbufs.should_shed(&load(0, 100)));
+    }
+
+    #[test]
+    fn test_response_is_well_formed() {
+        let text = std::str::from_utf8(SERVICE_UNAVAILABLE).unwrap();
+        assert!(text.starts_with("HTTP/1.1 503 "));
+        assert!(text.contains("\r\nconnection: close\r\n"));
+        assert!(text.ends_with("\r\n\r\n"));
+        assert_eq!(text.matches("\r\n\r\n").count(), 1);
+    }
+}
@@ -1,4 +1,8 @@
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+use std::rc::{Rc, Weak};
+use std::time::{Duration, Instant};
 
 use bytes::Bytes;
 use hyper::body::Incoming;
@@ -7,15 +11,35 @@ use hyper::{Request, Response};
 use dpdk_net::runtime::ReactorHandle;
 use smoltcp::wire::IpAddress;
 
-use crate::client::ClientConfig;
-use crate::connection::{Connection, HttpVersion};
+use crate::backoff::EndpointBackoff;
+use crate::client::{ClientConfig, connect_with_backoff};
+use crate::connection::Connection;
 use crate::error::Error;
 
+/// Default for [`ConnectionPool::idle_timeout`].
+pub const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// Bounds on how often the reaper looks for idle connections.
+const MIN_REAP_PERIOD: Duration = Duration::from_millis(10);
+const MAX_REAP_PERIOD: Duration = Duration::from_secs(30);
+
+type Key = (IpAddress, u16);
+
 /// Simple per-host connection pool.
 ///
 /// Maintains idle connections keyed by `(IpAddress, port)` and reuses them
-/// for subsequent requests. Connections that are no longer ready are
-/// discarded automatically.
+/// for subsequent requests. On checkout, connections that fail
+/// [`Connection::check`] (closed by the server, reset, or holding stray
+/// bytes) are discarded instead of being handed out; HTTP/1.1 connections
+/// still waiting for a response ([`Connection::is_busy`]) are skipped and
+/// kept.
+///
+/// A checked-out connection is a [`PooledConnection`], which goes back to
+/// the pool when dropped. Connections idle longer than
+/// [`idle_timeout`](Self::idle_timeout) are closed by a reaper task. The
+/// pool is `!Send`, so the reaper is a `spawn_local` task on the reactor's
+/// `LocalSet`, started when the first connection is returned and stopped
+/// once the pool is empty or dropped.
 ///
 /// # `!Send`
 /// This type is `!Send`. Use one pool per lcore.
@@ -25,17 +49,72 @@ use crate::error::Error;
 /// ```ignore
 /// use dpdk_net_util::ConnectionPool;
 /// use dpdk_net::runtime::ReactorHandle;
+/// use smoltcp::wire::IpAddress;
 ///
 /// async fn run(reactor: &ReactorHandle) {
-///     let mut pool = ConnectionPool::new(reactor.clone());
+///     let pool = ConnectionPool::new(reactor.clone());
 ///     // Connections are created on first use and reused after.
+///     let conn = pool.get_or_connect(IpAddress::v4(10, 0, 0, 1), 8080).await.unwrap();
 /// }
 /// ```
 pub struct ConnectionPool {
     reactor: ReactorHandle,
     config: ClientConfig,
-    connections: HashMap<(IpAddress, u16), Vec<Connection>>,
+    backoff: EndpointBackoff,
+    state: Rc<RefCell<PoolState>>,
+}
+
+struct PoolState {
+    idle: HashMap<Key, Vec<IdleConnection>>,
     max_idle_per_host: usize,
+    idle_timeout: Duration,
+    reaper_running: bool,
+}
+
+struct IdleConnection {
+    conn: Connection,
+    since: Instant,
+}
+
+impl PoolState {
+    /// Take a connection ready for a request to `key`, dropping dead ones.
+    fn checkout(&mut self, key: Key) -> Option<Connection> {
+        let conns = self.idle.get_mut(&key)?;
+        conns.retain(|c| c.conn.check().is_ok() || c.conn.is_busy());
+        let ready = conns.iter().position(|c| c.conn.check().is_ok());
+        let conn = ready.map(|i| conns.remove(i).conn);
+        if conns.is_empty() {
+            self.idle.remove(&key);
+        }
+        conn
+    }
+
+    /// Put `conn` back, making room by closing the longest idle one.
+    fn checkin(&mut self, key: Key, conn: Connection, now: Instant) {
+        if self.max_idle_per_host == 0 || conn.is_closed() {
+            return;
+        }
+        let conns = self.idle.entry(key).or_default();
+        if conns.len() >= self.max_idle_per_host {
+            conns.remove(0);
+        }
+        conns.push(IdleConnection { conn, since: now });
+    }
+
+    /// Close connections that are dead or have been idle for the timeout.
+    /// In-flight HTTP/1.1 connections are kept.
+    fn evict(&mut self, now: Instant) {
+        let timeout = self.idle_timeout;
+        self.idle.retain(|_, conns| {
+            conns.retain(|c| {
+                if c.conn.is_busy() {
+                    return true;
+                }
+                c.conn.check().is_ok() && now.saturating_duration_since(c.since) < timeout
+            });
+            !conns.is_empty()
+        });
+    }
 }
 
 impl ConnectionPool {
@@ -51,77 +130,76 @@ impl ConnectionPool {
         config: ClientConfig,
         max_idle_per_host: usize,
     ) -> Self {
+        let backoff = EndpointBackoff::new(config.reconnect_backoff.clone());
         Self {
             reactor,
             config,
-            connections: HashMap::new(),
-            max_idle_per_host,
+            backoff,
+            state: Rc::new(RefCell::new(PoolState {
+                idle: HashMap::new(),
+                max_idle_per_host,
+                idle_timeout: DEFAULT_IDLE_TIMEOUT,
+                reaper_running: false,
+            })),
         }
     }
 
-    /// Acquire a ready connection to the given host, or create one.
+    /// Close pooled connections that sat unused for longer than `timeout`
+    /// (default: [`DEFAULT_IDLE_TIMEOUT`]).
     ///
-    /// `local_port` is used only when creating a new connection.
-    pub async fn connection(
-        &mut self,
+    /// Servers close idle connections on their own schedule; closing ours
+    /// first avoids handing out one the server is about to drop, and frees
+    /// its socket buffers. `Duration::MAX` keeps connections until they
+    /// fail.
+    pub fn idle_timeout(self, timeout: Duration) -> Self {
+        self.state.borrow_mut().idle_timeout = timeout;
+        self
+    }
+
+    /// Check out a ready connection to `addr:port`, or open a new one from
+    /// a local port picked by [`ReactorHandle::alloc_ephemeral_port`].
+    ///
+    /// Fails with [`Error::PortsExhausted`] if the reactor has no free
+    /// port. When all pooled connections to a host have failed, reconnects
+    /// are spread out by [`ClientConfig::reconnect_backoff`] if configured.
+    pub async fn get_or_connect(
+        &self,
         addr: IpAddress,
         port: u16,
-        local_port: u16,
-    ) -> Result<&mut Connection, Error> {
-        let key = (addr, port);
-
-        // Check for an existing ready connection (immutable borrow, dropped before mutation).
-        let has_ready = self
-            .connections
-            .get(&key)
-            .is_some_and(|conns| conns.iter().any(|c| c.is_ready()));
-
-        if has_ready {
-            let conns = self.connections.get_mut(&key).unwrap();
-            let pos = conns.iter().position(|c| c.is_ready()).unwrap();
-            return Ok(&mut conns[pos]);
+    ) -> Result<PooledConnection, Error> {
+        if let Some(conn) = self.checkout((addr, port)) {
+            return Ok(conn);
         }
+        let local_port = self
+            .reactor
+            .alloc_ephemeral_port()
+            .ok_or(Error::PortsExhausted)?;
+        self.connect((addr, port), local_port).await
+    }
 
-        // Create a new connection.
-        let conn = match self.config.http_version {
-            HttpVersion::Http1 => {
-                Connection::http1(
-                    &self.reactor,
-                    addr,
-                    port,
-                    local_port,
-                    self.config.rx_buffer_size,
-                    self.config.tx_buffer_size,
-                )
-                .await?
-            }
-            HttpVersion::Http2 => {
-                Connection::http2(
-                    &self.reactor,
-                    addr,
-                    port,
-                    local_port,
-                    self.config.rx_buffer_size,
-                    self.config.tx_buffer_size,
-                )
-                .await?
-            }
-        };
-
-        let conns = self.connections.entry(key).or_default();
-
-        // Enforce limit by removing oldest idle connection.
-        if conns.len() >= self.max_idle_per_host {
-            conns.remove(0);
+    /// Check out a ready connection to the given host, or create one.
+    ///
+    /// Like [`get_or_connect`](Self::get_or_connect), but `local_port` is
+    /// used when creating a new connection.
+    pub async fn connection(
+        &self,
+        addr: IpAddress,
+        port: u16,
+        local_port: u16,
+    ) -> Result<PooledConnection, Error> {
+        if let Some(conn) = self.checkout((addr, port)) {
+            return Ok(conn);
         }
-
-        conns.push(conn);
-        Ok(conns.last_mut().unwrap())
+        self.connect((addr, port), local_port).await
     }
 
     /// Send a one-shot request, reusing a pooled connection if available.
+    ///
+    /// The connection goes back to the pool as soon as the request is
+    /// dispatched, so other requests can use it while this response is
+    /// awaited (HTTP/2), or once it has been read (HTTP/1.1).
     pub async fn request<B>(
-        &mut self,
+        &self,
         addr: IpAddress,
         port: u16,
         local_port: u16,
@@ -131,12 +209,130 @@ impl ConnectionPool {
         B: hyper::body::Body<Data = Bytes> + 'static,
         B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
     {
-        let conn = self.connection(addr, port, local_port).await?;
-        conn.send_request(request).await
+        let response = self
+            .connection(addr, port, local_port)
+            .await?
+            .send_request(request);
+        response.await
+    }
+
+    /// Number of idle connections held for `addr:port`.
+    pub fn idle_count(&self, addr: IpAddress, port: u16) -> usize {
+        self.state
+            .borrow()
+            .idle
+            .get(&(addr, port))
+            .map_or(0, Vec::len)
     }
 
     /// Remove all idle connections.
-    pub fn clear(&mut self) {
-        self.connections.clear();
+    pub fn clear(&self) {
+        self.state.borrow_mut().idle.clear();
+    }
+
+    fn checkout(&self, key: Key) -> Option<PooledConnection> {
+        let conn = self.state.borrow_mut().checkout(key)?;
+        Some(self.wrap(key, conn))
+    }
+
+    async fn connect(&self, key: Key, local_port: u16) -> Result<PooledConnection, Error> {
+        let conn = connect_with_backoff(
+            &self.reactor,
+            &self.config,
+            &self.backoff,
+            key.0,
+            key.1,
+            local_port,
+        )
+        .await?;
+        Ok(self.wrap(key, conn))
+    }
+
+    fn wrap(&self, key: Key, conn: Connection) -> PooledConnection {
+        PooledConnection {
+            conn: Some(conn),
+            key,
+            pool: Rc::downgrade(&self.state),
+            reactor: self.reactor.clone(),
+        }
+    }
+}
+
+/// A connection checked out of a [`ConnectionPool`].
+///
+/// Derefs to [`Connection`]. Dropping it returns the connection to the
+/// pool, unless hyper has closed it or the pool is gone.
+pub struct PooledConnection {
+    conn: Option<Connection>,
+    key: Key,
+    pool: Weak<RefCell<PoolState>>,
+    reactor: ReactorHandle,
+}
+
+impl PooledConnection {
+    /// Take the connection out of the pool's care; it is not returned on
+    /// drop.
+    pub fn detach(mut self) -> Connection {
+        self.conn.take().expect("connection present until drop")
     }
 }
+
+impl Deref for PooledConnection {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn.as_ref().expect("connection present until drop")
+    }
+}
+
+impl DerefMut for PooledConnection {
+    fn deref_mut(&mut self) -> &mut Connection {
+        self.conn.as_mut().expect("connection present until drop")
+    }
+}
+
+impl Drop for PooledConnection {
+    fn drop(&mut self) {
+        let (Some(conn), Some(state)) = (self.conn.take(), self.pool.upgrade()) else {
+            return;
+        };
+        let start_reaper = {
+            let mut state = state.borrow_mut();
+            state.checkin(self.key, conn, Instant::now());
+            let start = !state.reaper_running && !state.idle.is_empty();
+            state.reaper_running |= start;
+            start
+        };
+        if start_reaper {
+            spawn_reaper(&self.reactor, Rc::downgrade(&state));
+        }
+    }
+}
+
+/// Close idle connections as they time out, until the pool is empty or
+/// dropped.
+fn spawn_reaper(reactor: &ReactorHandle, pool: Weak<RefCell<PoolState>>) {
+    let Some(state) = pool.upgrade() else {
+        return;
+    };
+    let period = (state.borrow().idle_timeout / 2).clamp(MIN_REAP_PERIOD, MAX_REAP_PERIOD);
+    drop(state);
+
+    let mut interval = reactor.interval(period);
+    tokio::task::spawn_local(async move {
+        // The first tick is immediate
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            let Some(state) = pool.upgrade() else {
+                return;
+            };
+            let mut state = state.borrow_mut();
+            state.evict(Instant::now());
+            if state.idle.is_empty() {
+                state.reaper_running = false;
+                return;
+            }
+        }
+    });
+}
@@ -0,0 +1,455 @@
+//! Tunneling through an egress proxy (HTTP `CONNECT` or SOCKS5).
+//!
+//! When [`ClientConfig::proxy`](crate::ClientConfig::proxy) is set, the client
+//! opens its TCP connection to the proxy, asks it to open a tunnel to the
+//! target `host:port`, and then runs the normal HTTP handshake over the
+//! tunneled stream.
+
+use std::fmt;
+use std::io;
+
+use dpdk_net::socket::TcpStream;
+use smoltcp::wire::IpAddress;
+
+/// Upper bound on the size of an HTTP `CONNECT` response header.
+const MAX_CONNECT_RESPONSE: usize = 8192;
+
+/// Proxy protocol used to open the tunnel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProxyKind {
+    /// HTTP/1.1 `CONNECT host:port`.
+    HttpConnect,
+    /// SOCKS5 (RFC 1928) `CONNECT` command.
+    Socks5,
+}
+
+/// Credentials presented to the proxy.
+///
+/// Sent as `Proxy-Authorization: Basic` for HTTP `CONNECT`, or via
+/// username/password sub-negotiation (RFC 1929) for SOCKS5.
+#[derive(Clone)]
+pub struct ProxyAuth {
+    pub username: String,
+    pub password: String,
+}
+
+impl fmt::Debug for ProxyAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProxyAuth")
+            .field("username", &self.username)
+            .field("password", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Proxy settings for [`DpdkHttpClient`](crate::DpdkHttpClient).
+///
+/// # Example
+///
+/// ```ignore
+/// use dpdk_net_util::{ClientConfig, ProxyConfig};
+/// use smoltcp::wire::IpAddress;
+///
+/// let config = ClientConfig::default().proxy(
+///     ProxyConfig::http_connect(IpAddress::v4(10, 0, 0, 254), 3128)
+///         .auth("user", "secret"),
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct ProxyConfig {
+    /// Proxy IP address.
+    pub addr: IpAddress,
+    /// Proxy port.
+    pub port: u16,
+    /// Tunnel protocol.
+    pub kind: ProxyKind,
+    /// Optional credentials.
+    pub auth: Option<ProxyAuth>,
+}
+
+impl ProxyConfig {
+    /// Tunnel through an HTTP proxy using `CONNECT`.
+    pub fn http_connect(addr: IpAddress, port: u16) -> Self {
+        Self {
+            addr,
+            port,
+            kind: ProxyKind::HttpConnect,
+            auth: None,
+        }
+    }
+
+    /// Tunnel through a SOCKS5 proxy.
+    pub fn socks5(addr: IpAddress, port: u16) -> Self {
+        Self {
+            addr,
+            port,
+            kind: ProxyKind::Socks5,
+            auth: None,
+        }
+    }
+
+    /// Authenticate to the proxy with a username and password.
+    pub fn auth(mut self, username: impl Into<String>, password: impl Into<String>) -> Self {
+        self.auth = Some(ProxyAuth {
+            username: username.into(),
+            password: password.into(),
+        });
+        self
+    }
+}
+
+/// Failure while establishing a proxy tunnel.
+#[derive(Debug)]
+pub enum ProxyError {
+    /// I/O error on the connection to the proxy.
+    Io(io::Error),
+    /// The proxy closed the connection during the handshake.
+    Closed,
+    /// The HTTP proxy answered `CONNECT` with a non-2xx status.
+    ConnectRejected(u16),
+    /// The proxy requires credentials that were not configured.
+    AuthRequired,
+    /// The proxy rejected the configured credentials.
+    AuthFailed,
+    /// The SOCKS5 proxy answered with a non-zero reply code.
+    Socks5Reply(u8),
+    /// The proxy sent a malformed or unexpected response.
+    Protocol(&'static str),
+}
+
+impl fmt::Display for ProxyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProxyError::Io(e) => write!(f, "proxy I/O error: {e}"),
+            ProxyError::Closed => write!(f, "proxy closed the connection during handshake"),
+            ProxyError::ConnectRejected(status) => {
+                write!(f, "proxy rejected CONNECT with status {status}")
+            }
+            ProxyError::AuthRequired => write!(f, "proxy requires authentication"),
+            ProxyError::AuthFailed => write!(f, "proxy authentication failed"),
+            ProxyError::Socks5Reply(code) => {
+                write!(f, "SOCKS5 connect failed: {}", socks5_reply_str(*code))
+            }
+            ProxyError::Protocol(msg) => write!(f, "proxy protocol error: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for ProxyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ProxyError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ProxyError {
+    fn from(e: io::Error) -> Self {
+        ProxyError::Io(e)
+    }
+}
+
+/// Open a tunnel to `target_host:target_port` over an established stream
+/// to the proxy.
+///
+/// `target_host` may be an IP address or a hostname; hostnames are resolved
+/// by the proxy.
+pub(crate) async fn establish_tunnel(
+    stream: &TcpStream,
+    proxy: &ProxyConfig,
+    target_host: &str,
+    target_port: u16,
+) -> Result<(), ProxyError> {
+    match proxy.kind {
+        ProxyKind::HttpConnect => {
+            http_connect(stream, proxy.auth.as_ref(), target_host, target_port).await
+        }
+        ProxyKind::Socks5 => {
+            socks5_connect(stream, proxy.auth.as_ref(), target_host, target_port).await
+        }
+    }
+}
+
+async fn http_connect(
+    stream: &TcpStream,
+    auth: Option<&ProxyAuth>,
+    host: &str,
+    port: u16,
+) -> Result<(), ProxyError> {
+    let request = connect_request(auth, host, port);
+    stream.send(request.as_bytes()).await?;
+
+    // Read the response header. The proxy must not send tunneled bytes before
+    // the client speaks, so anything past the header is a protocol error.
+    let mut buf = Vec::with_capacity(256);
+    let mut chunk = [0u8; 512];
+    let header_len = loop {
+        let n = stream.recv(&mut chunk).await?;
+        if n == 0 {
+            return Err(ProxyError::Closed);
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+        if buf.len() > MAX_CONNECT_RESPONSE {
+            return Err(ProxyError::Protocol("CONNECT response header too large"));
+        }
+    };
+    if buf.len() != header_len {
+        return Err(ProxyError::Protocol(
+            "unexpected data after CONNECT response",
+        ));
+    }
+
+    let status = parse_status_line(&buf[..header_len])?;
+    match status {
+        200..=299 => Ok(()),
+        407 if auth.is_none() => Err(ProxyError::AuthRequired),
+        407 => Err(ProxyError::AuthFailed),
+        other => Err(ProxyError::ConnectRejected(other)),
+    }
+}
+
+/// Build the `CONNECT` request header for `host:port`.
+fn connect_request(auth: Option<&ProxyAuth>, host: &str, port: u16) -> String {
+    // An IPv6 literal is bracketed in the authority (RFC 3986 §3.2.2)
+    let authority = if host.parse::<std::net::Ipv6Addr>().is_ok() {
+        format!("[{host}]:{port}")
+    } else {
+        format!("{host}:{port}")
+    };
+    let mut request = format!("CONNECT {authority} HTTP/1.1\r\nHost: {authority}\r\n");
+    if let Some(auth) = auth {
+        let credentials = format!("{}:{}", auth.username, auth.password);
+        request.push_str("Proxy-Authorization: Basic ");
+        request.push_str(&base64_encode(credentials.as_bytes()));
+        request.push_str("\r\n");
+    }
+    request.push_str("\r\n");
+    request
+}
+
+/// Extract the status code from `HTTP/1.x <code> <reason>`.
+fn parse_status_line(header: &[u8]) -> Result<u16, ProxyError> {
+    let line_end = header
+        .windows(2)
+        .position(|w| w == b"\r\n")
+        .unwrap_or(header.len());
+    let line = std::str::from_utf8(&header[..line_end])
+        .map_err(|_| ProxyError::Protocol("non-UTF-8 status line"))?;
+    let mut parts = line.split(' ');
+    match parts.next() {
+        Some(v) if v.starts_with("HTTP/1.") => {}
+        _ => return Err(ProxyError::Protocol("invalid status line")),
+    }
+    parts
+        .next()
+        .and_then(|code| code.parse().ok())
+        .ok_or(ProxyError::Protocol("invalid status code"))
+}
+
+const SOCKS5_VERSION: u8 = 0x05;
+const SOCKS5_AUTH_NONE: u8 = 0x00;
+const SOCKS5_AUTH_PASSWORD: u8 = 0x02;
+const SOCKS5_AUTH_NO_ACCEPTABLE: u8 = 0xFF;
+const SOCKS5_CMD_CONNECT: u8 = 0x01;
+const SOCKS5_ATYP_IPV4: u8 = 0x01;
+const SOCKS5_ATYP_DOMAIN: u8 = 0x03;
+const SOCKS5_ATYP_IPV6: u8 = 0x04;
+
+async fn socks5_connect(
+    stream: &TcpStream,
+    auth: Option<&ProxyAuth>,
+    host: &str,
+    port: u16,
+) -> Result<(), ProxyError> {
+    // Method negotiation
+    let greeting: &[u8] = if auth.is_some() {
+        &[SOCKS5_VERSION, 2, SOCKS5_AUTH_NONE, SOCKS5_AUTH_PASSWORD]
+    } else {
+        &[SOCKS5_VERSION, 1, SOCKS5_AUTH_NONE]
+    };
+    stream.send(greeting).await?;
+
+    let mut reply = [0u8; 2];
+    recv_exact(stream, &mut reply).await?;
+    if reply[0] != SOCKS5_VERSION {
+        return Err(ProxyError::Protocol("unexpected SOCKS version"));
+    }
+    match (reply[1], auth) {
+        (SOCKS5_AUTH_NONE, _) => {}
+        (SOCKS5_AUTH_PASSWORD, Some(auth)) => socks5_password_auth(stream, auth).await?,
+        (SOCKS5_AUTH_NO_ACCEPTABLE, None) => return Err(ProxyError::AuthRequired),
+        (SOCKS5_AUTH_NO_ACCEPTABLE, Some(_)) => return Err(ProxyError::AuthFailed),
+        _ => return Err(ProxyError::Protocol("unsupported SOCKS5 auth method")),
+    }
+
+    // CONNECT request
+    let mut request = vec![SOCKS5_VERSION, SOCKS5_CMD_CONNECT, 0x00];
+    match host.parse::<std::net::IpAddr>() {
+        Ok(std::net::IpAddr::V4(ip)) => {
+            request.push(SOCKS5_ATYP_IPV4);
+            request.extend_from_slice(&ip.octets());
+        }
+        Ok(std::net::IpAddr::V6(ip)) => {
+            request.push(SOCKS5_ATYP_IPV6);
+            request.extend_from_slice(&ip.octets());
+        }
+        Err(_) => {
+            let len = u8::try_from(host.len())
+                .map_err(|_| ProxyError::Protocol("hostname longer than 255 bytes"))?;
+            request.push(SOCKS5_ATYP_DOMAIN);
+            request.push(len);
+            request.extend_from_slice(host.as_bytes());
+        }
+    }
+    request.extend_from_slice(&port.to_be_bytes());
+    stream.send(&request).await?;
+
+    // Reply: VER REP RSV ATYP BND.ADDR BND.PORT
+    let mut head = [0u8; 4];
+    recv_exact(stream, &mut head).await?;
+    if head[0] != SOCKS5_VERSION {
+        return Err(ProxyError::Protocol("unexpected SOCKS version"));
+    }
+    if head[1] != 0x00 {
+        return Err(ProxyError::Socks5Reply(head[1]));
+    }
+    let addr_len = match head[3] {
+        SOCKS5_ATYP_IPV4 => 4,
+        SOCKS5_ATYP_IPV6 => 16,
+        SOCKS5_ATYP_DOMAIN => {
+            let mut len = [0u8; 1];
+            recv_exact(stream, &mut len).await?;
+            len[0] as usize
+        }
+        _ => return Err(ProxyError::Protocol("invalid SOCKS5 address type")),
+    };
+    let mut bound = vec![0u8; addr_len + 2];
+    recv_exact(stream, &mut bound).await?;
+    Ok(())
+}
+
+/// Username/password sub-negotiation (RFC 1929).
+async fn socks5_password_auth(stream: &TcpStream, auth: &ProxyAuth) -> Result<(), ProxyError> {
+    let user = auth.username.as_bytes();
+    let pass = auth.password.as_bytes();
+    if user.len() > 255 || pass.len() > 255 {
+        return Err(ProxyError::Protocol(
+            "SOCKS5 credentials longer than 255 bytes",
+        ));
+    }
+    let mut msg = Vec::with_capacity(3 + user.len() + pass.len());
+    msg.push(0x01);
+    msg.push(user.len() as u8);
+    msg.extend_from_slice(user);
+    msg.push(pass.len() as u8);
+    msg.extend_from_slice(pass);
+    stream.send(&msg).await?;
+
+    let mut reply = [0u8; 2];
+    recv_exact(stream, &mut reply).await?;
+    if reply[1] != 0x00 {
+        return Err(ProxyError::AuthFailed);
+    }
+    Ok(())
+}
+
+async fn recv_exact(stream: &TcpStream, buf: &mut [u8]) -> Result<(), ProxyError> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        let n = stream.recv(&mut buf[filled..]).await?;
+        if n == 0 {
+            return Err(ProxyError::Closed);
+        }
+        filled += n;
+    }
+    Ok(())
+}
+
+fn socks5_reply_str(code: u8) -> &'static str {
+    match code {
+        0x01 => "general failure",
+        0x02 => "connection not allowed by ruleset",
+        0x03 => "network unreachable",
+        0x04 => "host unreachable",
+        0x05 => "connection refused",
+        0x06 => "TTL expired",
+        0x07 => "command not supported",
+        0x08 => "address type not supported",
+        _ => "unknown error",
+    }
+}
+
+/// Standard base64 (RFC 4648) with padding, for `Proxy-Authorization`.
+fn base64_encode(input: &[u8]) -> String {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(input.len().div_ceil(3) * 4);
+    for chunk in input.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        out.push(TABLE[(n >> 18) as usize & 0x3F] as char);
+        out.push(TABLE[(n >> 12) as usize & 0x3F] as char);
+        out.push(if chunk.len() > 1 {
+            TABLE[(n >> 6) as usize & 0x3F] as char
+        } else {
+            '='
+        });
+        out.push(if chunk.len() > 2 {
+            TABLE[n as usize & 0x3F] as char
+        } else {
+            '='
+        });
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_encode() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"user:secret"), "dXNlcjpzZWNyZXQ=");
+    }
+
+    #[test]
+    fn test_connect_request_brackets_ipv6() {
+        assert_eq!(
+            connect_request(None, "10.0.0.1", 443),
+            "CONNECT 10.0.0.1:443 HTTP/1.1\r\nHost: 10.0.0.1:443\r\n\r\n"
+        );
+        assert_eq!(
+            connect_request(None, "fd00::1", 443),
+            "CONNECT [fd00::1]:443 HTTP/1.1\r\nHost: [fd00::1]:443\r\n\r\n"
+        );
+        assert_eq!(
+            connect_request(None, "example.com", 80),
+            "CONNECT example.com:80 HTTP/1.1\r\nHost: example.com:80\r\n\r\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_status_line() {
+        assert_eq!(
+            parse_status_line(b"HTTP/1.1 200 Connection established\r\n\r\n").unwrap(),
+            200
+        );
+        assert_eq!(
+            parse_status_line(b"HTTP/1.0 407 Proxy Authentication Required\r\n\r\n").unwrap(),
+            407
+        );
+        assert!(parse_status_line(b"SSH-2.0-OpenSSH\r\n\r\n").is_err());
+        assert!(parse_status_line(b"HTTP/1.1 abc\r\n\r\n").is_err());
+    }
+}
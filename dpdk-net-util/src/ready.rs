@@ -0,0 +1,149 @@
+//! Startup barrier for multi-queue workers.
+//!
+//! Each lcore worker starts independently, so a load generator (or test client)
+//! that connects as soon as one queue is up can race the remaining queues.
+//! [`ReadyBarrier`] counts workers that have finished their setup (typically
+//! binding listeners) and releases waiters once every queue has arrived.
+
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use tokio::sync::Notify;
+
+/// Callback invoked once when every worker is ready.
+pub(crate) type OnReady = Box<dyn FnOnce() + Send>;
+
+/// Barrier shared by all workers of a [`DpdkApp`](crate::DpdkApp).
+///
+/// The barrier is `Send + Sync` and cheap to clone, so it can be handed to
+/// tasks on other threads (e.g. a tokio client waiting for the DPDK server).
+///
+/// Workers normally use [`WorkerContext::mark_ready`](crate::WorkerContext::mark_ready)
+/// and [`WorkerContext::wait_ready`](crate::WorkerContext::wait_ready) instead of
+/// calling the barrier directly.
+#[derive(Clone)]
+pub struct ReadyBarrier {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    expected: usize,
+    arrived: AtomicUsize,
+    notify: Notify,
+    on_ready: Mutex<Option<OnReady>>,
+}
+
+impl ReadyBarrier {
+    /// Create a barrier that releases after `expected` arrivals.
+    pub(crate) fn new(expected: usize, on_ready: Option<OnReady>) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                expected,
+                arrived: AtomicUsize::new(0),
+                notify: Notify::new(),
+                on_ready: Mutex::new(on_ready),
+            }),
+        }
+    }
+
+    /// Record one worker as ready.
+    ///
+    /// The arrival that completes the barrier runs the `on_all_ready`
+    /// callback (if any) and wakes all waiters.
+    pub(crate) fn arrive(&self) {
+        let prev = self.inner.arrived.fetch_add(1, Ordering::AcqRel);
+        if prev + 1 == self.inner.expected {
+            let callback = self.inner.on_ready.lock().unwrap().take();
+            if let Some(callback) = callback {
+                callback();
+            }
+            self.inner.notify.notify_waiters();
+        }
+    }
+
+    /// Number of workers the barrier waits for.
+    pub fn expected(&self) -> usize {
+        self.inner.expected
+    }
+
+    /// Number of workers that have reported ready so far.
+    pub fn ready_count(&self) -> usize {
+        self.inner
+            .arrived
+            .load(Ordering::Acquire)
+            .min(self.inner.expected)
+    }
+
+    /// Returns true once every worker has reported ready.
+    pub fn is_ready(&self) -> bool {
+        self.inner.arrived.load(Ordering::Acquire) >= self.inner.expected
+    }
+
+    /// Wait until every worker has reported ready.
+    ///
+    /// Returns immediately if the barrier has already been released.
+    pub async fn wait(&self) {
+        loop {
+            // Register before checking so a concurrent `notify_waiters` is not missed.
+            let notified = self.inner.notify.notified();
+            if self.is_ready() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+impl std::fmt::Debug for ReadyBarrier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReadyBarrier")
+            .field("expected", &self.expected())
+            .field("ready", &self.ready_count())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicBool;
+
+    #[tokio::test]
+    async fn test_wait_releases_after_all_arrive() {
+        let barrier = ReadyBarrier::new(2, None);
+        assert!(!barrier.is_ready());
+
+        barrier.arrive();
+        assert_eq!(barrier.ready_count(), 1);
+        assert!(!barrier.is_ready());
+
+        let waiter = barrier.clone();
+        let handle = tokio::spawn(async move { waiter.wait().await });
+        tokio::task::yield_now().await;
+
+        barrier.arrive();
+        handle.await.unwrap();
+        assert!(barrier.is_ready());
+
+        // Already released: returns immediately
+        barrier.wait().await;
+    }
+
+    #[test]
+    fn test_on_ready_runs_once() {
+        let fired = Arc::new(AtomicBool::new(false));
+        let fired_clone = fired.clone();
+        let barrier = ReadyBarrier::new(
+            1,
+            Some(Box::new(move || {
+                assert!(!fired_clone.swap(true, Ordering::SeqCst));
+            })),
+        );
+
+        barrier.arrive();
+        barrier.arrive();
+        assert!(fired.load(Ordering::SeqCst));
+        assert_eq!(barrier.ready_count(), 1);
+    }
+}
@@ -0,0 +1,161 @@
+//! Outcome of a [`DpdkApp`](crate::DpdkApp) run.
+
+use dpdk_net::api::rte::eth::rss_hf;
+use dpdk_net::api::rte::stats::{EthStats, QueueStats};
+use dpdk_net::device::DeviceStats;
+
+use std::time::Duration;
+
+/// How a worker's reactor wound down after its closure returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownStatus {
+    /// Every socket had finished closing before the reactor stopped.
+    Clean,
+    /// The shutdown timeout elapsed with sockets still open; they were
+    /// dropped without completing their close.
+    TimedOut {
+        /// Sockets still in the reactor when it stopped.
+        open_sockets: usize,
+    },
+}
+
+impl ShutdownStatus {
+    /// Returns true for [`ShutdownStatus::Clean`].
+    pub fn is_clean(&self) -> bool {
+        matches!(self, ShutdownStatus::Clean)
+    }
+}
+
+/// Counters gathered by one worker (one queue pair).
+#[derive(Debug, Clone)]
+pub struct QueueReport {
+    /// Queue ID of the worker.
+    pub queue_id: u16,
+    /// Lcore the worker ran on.
+    pub lcore_id: u32,
+    /// Hardware counters of this queue pair, if the PMD tracks them.
+    pub stats: Option<QueueStats>,
+    /// Connections accepted by listeners on this worker's reactor.
+    pub connections_accepted: u64,
+    /// Counters of this worker's device, including TX allocation failures
+    /// and the mempool high-water mark it observed.
+    pub device: DeviceStats,
+    /// How the reactor wound down.
+    pub shutdown: ShutdownStatus,
+}
+
+/// RSS configuration read back from the device after it started.
+///
+/// What the driver reports may differ from what was requested: some drivers
+/// silently drop hash types they do not support.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RssReport {
+    /// Enabled hash types, as `rss_hf` bits.
+    pub rss_hf: u64,
+    /// Hash key in use.
+    pub key: Vec<u8>,
+}
+
+impl RssReport {
+    /// Returns true if TCP ports are part of the hash (IPv4 or IPv6).
+    ///
+    /// Without this, packets of one TCP connection are steered by address
+    /// only, and multi-queue TCP breaks: a reply can land on a queue whose
+    /// reactor does not own the connection.
+    pub fn hashes_tcp(&self) -> bool {
+        self.rss_hf & (rss_hf::NONFRAG_IPV4_TCP | rss_hf::NONFRAG_IPV6_TCP) != 0
+    }
+}
+
+/// Summary returned by [`DpdkApp::run_reporting`](crate::DpdkApp::run_reporting).
+#[derive(Debug, Clone)]
+pub struct RunReport {
+    /// Port counters read just before the device was stopped.
+    pub port: EthStats,
+    /// One entry per worker, ordered by queue ID.
+    pub queues: Vec<QueueReport>,
+    /// Time from device setup to the last worker finishing.
+    pub elapsed: Duration,
+    /// RSS configuration of a multi-queue run; `None` with a single queue or
+    /// if the driver could not report it.
+    pub rss: Option<RssReport>,
+}
+
+impl RunReport {
+    /// Connections accepted across all workers.
+    pub fn connections_accepted(&self) -> u64 {
+        self.queues.iter().map(|q| q.connections_accepted).sum()
+    }
+
+    /// Returns true if every worker shut down cleanly.
+    pub fn is_clean(&self) -> bool {
+        self.queues.iter().all(|q| q.shutdown.is_clean())
+    }
+
+    /// Most mbufs in use at once, as seen by any worker. Workers share the
+    /// mempool, so this is the pool's high-water mark.
+    pub fn mbufs_in_use_peak(&self) -> u32 {
+        self.queues
+            .iter()
+            .map(|q| q.device.mbufs_in_use_peak)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Transmitted frames dropped for lack of mbufs, across all workers.
+    pub fn tx_alloc_failures(&self) -> u64 {
+        self.queues.iter().map(|q| q.device.tx_alloc_failures).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queue(queue_id: u16, accepted: u64, shutdown: ShutdownStatus) -> QueueReport {
+        QueueReport {
+            queue_id,
+            lcore_id: queue_id as u32,
+            stats: None,
+            connections_accepted: accepted,
+            device: DeviceStats::default(),
+            shutdown,
+        }
+    }
+
+    #[test]
+    fn test_report_aggregates_queues() {
+        let mut report = RunReport {
+            port: EthStats::default(),
+            queues: vec![
+                queue(0, 3, ShutdownStatus::Clean),
+                queue(1, 4, ShutdownStatus::Clean),
+            ],
+            elapsed: Duration::from_secs(1),
+            rss: None,
+        };
+        assert_eq!(report.connections_accepted(), 7);
+        assert!(report.is_clean());
+
+        report.queues[0].device.mbufs_in_use_peak = 300;
+        report.queues[1].device.mbufs_in_use_peak = 250;
+        report.queues[1].device.tx_alloc_failures = 5;
+        assert_eq!(report.mbufs_in_use_peak(), 300);
+        assert_eq!(report.tx_alloc_failures(), 5);
+
+        report.queues[1].shutdown = ShutdownStatus::TimedOut { open_sockets: 2 };
+        assert!(!report.is_clean());
+    }
+
+    #[test]
+    fn test_rss_report_tcp_hashing() {
+        let rss = |rss_hf| RssReport {
+            rss_hf,
+            key: Vec::new(),
+        };
+        assert!(rss(rss_hf::NONFRAG_IPV4_TCP).hashes_tcp());
+        assert!(rss(rss_hf::IP | rss_hf::NONFRAG_IPV6_TCP).hashes_tcp());
+        assert!(!rss(rss_hf::IP).hashes_tcp());
+        assert!(!rss(0).hashes_tcp());
+    }
+}
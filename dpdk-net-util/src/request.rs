@@ -0,0 +1,195 @@
+//! Request construction for a DPDK target address.
+//!
+//! Hand-built requests often get the `Host` header or URI authority wrong
+//! (a missing port, an unbracketed IPv6 literal), and servers answer those
+//! with `400 Bad Request`. [`DpdkRequestBuilder`] derives both from the
+//! target `IpAddress` and port.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use dpdk_net_util::DpdkRequestBuilder;
+//! use smoltcp::wire::IpAddress;
+//!
+//! let req = DpdkRequestBuilder::post(IpAddress::v4(10, 0, 0, 1), 8080, "/echo")
+//!     .header("content-type", "text/plain")
+//!     .body(http_body_util::Full::new(bytes::Bytes::from("hello")))
+//!     .unwrap();
+//! assert_eq!(req.headers()["host"], "10.0.0.1:8080");
+//! ```
+
+use bytes::Bytes;
+use http_body_util::Empty;
+use hyper::header::{HOST, HeaderName, HeaderValue};
+use hyper::http::request::Builder;
+use hyper::{Method, Request};
+use smoltcp::wire::IpAddress;
+
+use crate::connection::HttpVersion;
+
+/// The `host:port` authority for `addr`, with IPv6 literals bracketed.
+pub fn authority(addr: IpAddress, port: u16) -> String {
+    format_authority(&addr.to_string(), port)
+}
+
+fn format_authority(host: &str, port: u16) -> String {
+    if host.contains(':') {
+        format!("[{host}]:{port}")
+    } else {
+        format!("{host}:{port}")
+    }
+}
+
+/// Builder for a [`Request`] aimed at one `addr:port`.
+///
+/// For HTTP/1.1 (the default) the URI is the path alone and a `Host` header
+/// is added. For HTTP/2 the URI is absolute (`http://host:port/path`) so
+/// hyper can fill in `:authority`. An explicitly set `Host` header is kept.
+pub struct DpdkRequestBuilder {
+    builder: Builder,
+    authority: String,
+    path: String,
+    version: HttpVersion,
+    secure: bool,
+}
+
+impl DpdkRequestBuilder {
+    /// Start a request with `method` for `path` on `addr:port`.
+    ///
+    /// A `path` without a leading `/` gets one.
+    pub fn new(method: Method, addr: IpAddress, port: u16, path: &str) -> Self {
+        let path = if path.starts_with('/') {
+            path.to_string()
+        } else {
+            format!("/{path}")
+        };
+        Self {
+            builder: Request::builder().method(method),
+            authority: authority(addr, port),
+            path,
+            version: HttpVersion::Http1,
+            secure: false,
+        }
+    }
+
+    /// Start a `GET` request.
+    pub fn get(addr: IpAddress, port: u16, path: &str) -> Self {
+        Self::new(Method::GET, addr, port, path)
+    }
+
+    /// Start a `POST` request.
+    pub fn post(addr: IpAddress, port: u16, path: &str) -> Self {
+        Self::new(Method::POST, addr, port, path)
+    }
+
+    /// Shape the URI for the connection's HTTP version (default: HTTP/1.1).
+    pub fn version(mut self, version: HttpVersion) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Use the `https` scheme in the HTTP/2 URI, for connections over TLS
+    /// (default: `http`).
+    pub fn secure(mut self, secure: bool) -> Self {
+        self.secure = secure;
+        self
+    }
+
+    /// Add a header. Invalid names or values surface as an error from
+    /// [`body`](Self::body).
+    pub fn header<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: TryInto<HeaderName>,
+        K::Error: Into<hyper::http::Error>,
+        V: TryInto<HeaderValue>,
+        V::Error: Into<hyper::http::Error>,
+    {
+        self.builder = self.builder.header(key, value);
+        self
+    }
+
+    /// Finish the request with `body`.
+    pub fn body<B>(self, body: B) -> Result<Request<B>, hyper::http::Error> {
+        let mut builder = match self.version {
+            HttpVersion::Http1 => self.builder.uri(self.path),
+            HttpVersion::Http2 => {
+                let scheme = if self.secure { "https" } else { "http" };
+                self.builder
+                    .uri(format!("{scheme}://{}{}", self.authority, self.path))
+            }
+        };
+        let has_host = builder
+            .headers_ref()
+            .is_some_and(|headers| headers.contains_key(HOST));
+        if self.version == HttpVersion::Http1 && !has_host {
+            builder = builder.header(HOST, self.authority);
+        }
+        builder.body(body)
+    }
+
+    /// Finish the request with an empty body.
+    pub fn empty(self) -> Result<Request<Empty<Bytes>>, hyper::http::Error> {
+        self.body(Empty::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDR: IpAddress = IpAddress::v4(192, 168, 1, 1);
+
+    #[test]
+    fn test_authority_brackets_ipv6() {
+        assert_eq!(authority(ADDR, 8080), "192.168.1.1:8080");
+        assert_eq!(format_authority("fe80::1", 443), "[fe80::1]:443");
+    }
+
+    #[test]
+    fn test_http1_origin_form_with_host() {
+        let req = DpdkRequestBuilder::get(ADDR, 8080, "health")
+            .empty()
+            .unwrap();
+        assert_eq!(req.method(), Method::GET);
+        assert_eq!(req.uri(), "/health");
+        assert_eq!(req.headers()[HOST], "192.168.1.1:8080");
+    }
+
+    #[test]
+    fn test_http2_absolute_uri() {
+        let req = DpdkRequestBuilder::post(ADDR, 8080, "/echo")
+            .version(HttpVersion::Http2)
+            .body("hi")
+            .unwrap();
+        assert_eq!(req.uri(), "http://192.168.1.1:8080/echo");
+        assert!(!req.headers().contains_key(HOST));
+    }
+
+    #[test]
+    fn test_http2_secure_uses_https() {
+        let req = DpdkRequestBuilder::get(ADDR, 443, "/")
+            .version(HttpVersion::Http2)
+            .secure(true)
+            .empty()
+            .unwrap();
+        assert_eq!(req.uri(), "https://192.168.1.1:443/");
+    }
+
+    #[test]
+    fn test_explicit_host_is_kept() {
+        let req = DpdkRequestBuilder::get(ADDR, 8080, "/")
+            .header("host", "example.com")
+            .empty()
+            .unwrap();
+        assert_eq!(req.headers()[HOST], "example.com");
+        assert_eq!(req.headers().get_all(HOST).iter().count(), 1);
+    }
+
+    #[test]
+    fn test_invalid_header_is_an_error() {
+        let result = DpdkRequestBuilder::get(ADDR, 8080, "/")
+            .header("bad header", "x")
+            .empty();
+        assert!(result.is_err());
+    }
+}
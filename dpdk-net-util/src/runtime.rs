@@ -0,0 +1,37 @@
+//! [`Runtime`] for reactors driven by tokio.
+//!
+//! [`Reactor::run`](dpdk_net::runtime::Reactor::run) spins, which suits an
+//! lcore runtime built without tokio's time driver. On a runtime built with
+//! `enable_time()`, `reactor.run_with::<TokioRuntime>(...)` lets idle passes
+//! sleep instead, and backs the reactor's
+//! [`sleep`](dpdk_net::runtime::ReactorHandle::sleep),
+//! [`timeout`](dpdk_net::runtime::ReactorHandle::timeout) and
+//! [`interval`](dpdk_net::runtime::ReactorHandle::interval) with
+//! `tokio::time`. Handlers keep calling those on the handle, so they do not
+//! import tokio themselves.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::time::{Duration, Instant};
+
+use dpdk_net::runtime::Runtime;
+
+/// [`Runtime`] backed by tokio's scheduler and `tokio::time`.
+///
+/// Panics on use in a runtime built without `enable_time()`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokioRuntime;
+
+impl Runtime for TokioRuntime {
+    fn yield_now() -> impl Future<Output = ()> {
+        tokio::task::yield_now()
+    }
+
+    fn poll_delay(delay: Duration) -> impl Future<Output = ()> {
+        tokio::time::sleep(delay)
+    }
+
+    fn timer(deadline: Instant) -> Option<Pin<Box<dyn Future<Output = ()>>>> {
+        Some(Box::pin(tokio::time::sleep_until(deadline.into())))
+    }
+}
@@ -0,0 +1,263 @@
+//! Single-threaded semaphore for admission control.
+//!
+//! Each worker runs its tasks on one thread, so a limit on in-flight work
+//! needs no atomics or locks. [`LocalSemaphore`] is `Rc`/`Cell` based and
+//! `!Send`: create one per worker and share it by cloning.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use dpdk_net_util::LocalSemaphore;
+//!
+//! // At most 64 requests handled at once on this worker
+//! let limit = LocalSemaphore::new(64);
+//! loop {
+//!     let stream = listener.accept().await?;
+//!     let permit = limit.acquire().await;
+//!     tokio::task::spawn_local(async move {
+//!         let _permit = permit;
+//!         handle(stream).await;
+//!     });
+//! }
+//! ```
+//!
+//! [`Http1Server::concurrency_limit`](crate::bench::http::Http1Server::concurrency_limit)
+//! applies one to request handling.
+
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+/// Counting semaphore for tasks on one thread.
+///
+/// Permits are handed out in the order tasks started waiting once none are
+/// free. Cloning shares the same permits.
+#[derive(Clone)]
+pub struct LocalSemaphore {
+    inner: Rc<Inner>,
+}
+
+struct Inner {
+    permits: Cell<usize>,
+    next_id: Cell<u64>,
+    /// Parked acquirers, oldest first.
+    waiters: RefCell<VecDeque<(u64, Waker)>>,
+}
+
+impl Inner {
+    /// Wake the oldest waiter, if any; it re-checks the count when polled.
+    fn wake_next(&self) {
+        if let Some((_, waker)) = self.waiters.borrow_mut().pop_front() {
+            waker.wake();
+        }
+    }
+
+    fn release(&self) {
+        self.permits.set(self.permits.get() + 1);
+        self.wake_next();
+    }
+}
+
+impl LocalSemaphore {
+    /// Create a semaphore with `permits` permits.
+    pub fn new(permits: usize) -> Self {
+        Self {
+            inner: Rc::new(Inner {
+                permits: Cell::new(permits),
+                next_id: Cell::new(0),
+                waiters: RefCell::new(VecDeque::new()),
+            }),
+        }
+    }
+
+    /// Wait for a permit.
+    ///
+    /// The permit is returned when the [`LocalPermit`] is dropped. The future
+    /// owns a handle to the semaphore, so it and the permit can be moved into
+    /// a spawned task.
+    pub fn acquire(&self) -> Acquire {
+        Acquire {
+            inner: self.inner.clone(),
+            id: None,
+        }
+    }
+
+    /// Take a permit if one is free and nobody is waiting for it.
+    pub fn try_acquire(&self) -> Option<LocalPermit> {
+        if self.inner.permits.get() == 0 || !self.inner.waiters.borrow().is_empty() {
+            return None;
+        }
+        self.inner.permits.set(self.inner.permits.get() - 1);
+        Some(LocalPermit {
+            inner: self.inner.clone(),
+        })
+    }
+
+    /// Permits not currently held.
+    pub fn available_permits(&self) -> usize {
+        self.inner.permits.get()
+    }
+
+    /// Tasks waiting in [`acquire`](Self::acquire).
+    pub fn waiters(&self) -> usize {
+        self.inner.waiters.borrow().len()
+    }
+}
+
+impl fmt::Debug for LocalSemaphore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalSemaphore")
+            .field("available", &self.available_permits())
+            .field("waiters", &self.waiters())
+            .finish()
+    }
+}
+
+/// Future returned by [`LocalSemaphore::acquire`].
+pub struct Acquire {
+    inner: Rc<Inner>,
+    /// Set once this future has queued itself.
+    id: Option<u64>,
+}
+
+impl Future for Acquire {
+    type Output = LocalPermit;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<LocalPermit> {
+        let inner = self.inner.clone();
+        let mut waiters = inner.waiters.borrow_mut();
+        let queued = self
+            .id
+            .and_then(|id| waiters.iter().position(|(w, _)| *w == id));
+
+        // A newcomer only takes a permit when nobody is queued ahead of it;
+        // a woken waiter has already been removed from the queue
+        let may_take = match queued {
+            Some(_) => false,
+            None => self.id.is_some() || waiters.is_empty(),
+        };
+        if may_take && inner.permits.get() > 0 {
+            inner.permits.set(inner.permits.get() - 1);
+            self.id = None;
+            return Poll::Ready(LocalPermit {
+                inner: self.inner.clone(),
+            });
+        }
+
+        match queued {
+            Some(pos) => waiters[pos].1.clone_from(cx.waker()),
+            None => {
+                let id = inner.next_id.get();
+                inner.next_id.set(id + 1);
+                // A woken waiter that lost the race keeps its place at the front
+                if self.id.is_some() {
+                    waiters.push_front((id, cx.waker().clone()));
+                } else {
+                    waiters.push_back((id, cx.waker().clone()));
+                }
+                self.id = Some(id);
+            }
+        }
+        Poll::Pending
+    }
+}
+
+impl Drop for Acquire {
+    fn drop(&mut self) {
+        let Some(id) = self.id else { return };
+        let mut waiters = self.inner.waiters.borrow_mut();
+        if let Some(pos) = waiters.iter().position(|(w, _)| *w == id) {
+            waiters.remove(pos);
+        } else if self.inner.permits.get() > 0 {
+            // Woken for a permit but dropped before taking it: pass the wake on
+            drop(waiters);
+            self.inner.wake_next();
+        }
+    }
+}
+
+/// A permit from a [`LocalSemaphore`], returned on drop.
+pub struct LocalPermit {
+    inner: Rc<Inner>,
+}
+
+impl Drop for LocalPermit {
+    fn drop(&mut self) {
+        self.inner.release();
+    }
+}
+
+impl fmt::Debug for LocalPermit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalPermit").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn poll(acquire: &mut Acquire) -> Poll<LocalPermit> {
+        let mut cx = Context::from_waker(Waker::noop());
+        Pin::new(acquire).poll(&mut cx)
+    }
+
+    #[test]
+    fn test_permits_are_counted() {
+        let sem = LocalSemaphore::new(2);
+        let a = sem.try_acquire().expect("first permit");
+        let _b = sem.try_acquire().expect("second permit");
+        assert!(sem.try_acquire().is_none());
+        assert_eq!(sem.available_permits(), 0);
+
+        drop(a);
+        assert_eq!(sem.available_permits(), 1);
+        assert!(sem.try_acquire().is_some());
+    }
+
+    #[test]
+    fn test_waiters_are_served_in_order() {
+        let sem = LocalSemaphore::new(1);
+        let held = sem.try_acquire().unwrap();
+
+        let mut first = sem.acquire();
+        let mut second = sem.acquire();
+        assert!(poll(&mut first).is_pending());
+        assert!(poll(&mut second).is_pending());
+        assert_eq!(sem.waiters(), 2);
+
+        // The released permit is reserved for the oldest waiter
+        drop(held);
+        assert!(sem.try_acquire().is_none());
+        assert!(poll(&mut second).is_pending());
+        let permit = match poll(&mut first) {
+            Poll::Ready(permit) => permit,
+            Poll::Pending => panic!("oldest waiter should get the permit"),
+        };
+        assert!(poll(&mut second).is_pending());
+
+        drop(permit);
+        assert!(poll(&mut second).is_ready());
+        assert_eq!(sem.waiters(), 0);
+    }
+
+    #[test]
+    fn test_dropped_waiter_passes_permit_on() {
+        let sem = LocalSemaphore::new(1);
+        let held = sem.try_acquire().unwrap();
+
+        let mut first = sem.acquire();
+        let mut second = sem.acquire();
+        assert!(poll(&mut first).is_pending());
+        assert!(poll(&mut second).is_pending());
+
+        drop(held);
+        drop(first);
+        assert!(poll(&mut second).is_ready());
+        assert_eq!(sem.available_permits(), 1);
+    }
+}
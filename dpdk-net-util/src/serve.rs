@@ -0,0 +1,345 @@
+//! One-call HTTP serving on every worker queue.
+//!
+//! [`serve_http`] binds a listener for the same port on every queue of a
+//! [`DpdkApp`], runs hyper-util's auto builder (HTTP/1.1 and h2c) on each
+//! accepted connection, and shuts down gracefully when the configured
+//! [`CancellationToken`] fires. Users only write the request handler.
+//!
+//! With RSS, each queue only sees the flows hashed to it, so a listener per
+//! queue is what makes the port reachable on all of them.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use dpdk_net_util::DpdkApp;
+//! use dpdk_net_util::serve::{ServeConfig, serve_http_with};
+//! use http_body_util::Full;
+//! use hyper::body::Bytes;
+//! use hyper::Response;
+//! use tokio_util::sync::CancellationToken;
+//!
+//! let shutdown = CancellationToken::new();
+//! // e.g. cancel from a signal handler thread
+//! let app = DpdkApp::new().eth_dev(0).ip(ip).gateway(gateway);
+//! let report = serve_http_with(app, ServeConfig::new(8080).shutdown(shutdown), |_req| async {
+//!     Ok::<_, hyper::Error>(Response::new(Full::new(Bytes::from("hello"))))
+//! });
+//! println!("served {} connections", report.connections_accepted());
+//! ```
+
+use std::cell::Cell;
+use std::future::Future;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use dpdk_net::socket::TcpListener;
+use futures_io::{AsyncRead, AsyncWrite};
+use hyper::body::{Body, Incoming};
+use hyper::service::service_fn;
+use hyper::{Request, Response};
+use hyper_util::rt::TokioIo;
+use hyper_util::server::conn::auto::Builder as AutoBuilder;
+use tokio_util::compat::FuturesAsyncReadCompatExt;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+use crate::app::DpdkApp;
+use crate::context::WorkerContext;
+use crate::executor::LocalExecutor;
+use crate::overload::{self, ServerLoad, ShedPolicy};
+use crate::report::RunReport;
+#[cfg(feature = "tls")]
+use crate::tls::TlsStream;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Settings for [`serve_http_with`] and [`serve_worker`].
+#[derive(Debug, Clone)]
+pub struct ServeConfig {
+    /// Port to listen on, on every queue.
+    pub port: u16,
+    /// Listening sockets per queue.
+    pub backlog: usize,
+    /// TCP receive buffer size per connection.
+    pub rx_buffer: usize,
+    /// TCP transmit buffer size per connection.
+    pub tx_buffer: usize,
+    /// Stops accepting and starts graceful shutdown when cancelled.
+    pub shutdown: CancellationToken,
+    /// How long open connections get to finish after shutdown starts.
+    pub grace_period: Duration,
+    /// When set, connections accepted while it says so get a 503.
+    pub shed: Option<ShedPolicy>,
+}
+
+impl ServeConfig {
+    /// Defaults: backlog 64, 16 KiB buffers, a 5 second grace period, a
+    /// shutdown token that is never cancelled, and no load shedding.
+    pub fn new(port: u16) -> Self {
+        Self {
+            port,
+            backlog: 64,
+            rx_buffer: 16384,
+            tx_buffer: 16384,
+            shutdown: CancellationToken::new(),
+            grace_period: Duration::from_secs(5),
+            shed: None,
+        }
+    }
+
+    /// Set the listen backlog per queue.
+    pub fn backlog(mut self, backlog: usize) -> Self {
+        self.backlog = backlog;
+        self
+    }
+
+    /// Set the per-connection TCP buffer sizes.
+    pub fn buffers(mut self, rx: usize, tx: usize) -> Self {
+        self.rx_buffer = rx;
+        self.tx_buffer = tx;
+        self
+    }
+
+    /// Set the token that triggers shutdown.
+    pub fn shutdown(mut self, token: CancellationToken) -> Self {
+        self.shutdown = token;
+        self
+    }
+
+    /// Set how long connections may drain after shutdown starts.
+    pub fn grace_period(mut self, grace: Duration) -> Self {
+        self.grace_period = grace;
+        self
+    }
+
+    /// Answer new connections with `503 Service Unavailable` while `policy`
+    /// reports overload.
+    ///
+    /// The policy sees the queue's active connection count and the free
+    /// mbufs at accept time. A shed connection is not counted as active and
+    /// its request is never read. With TLS (see [`DpdkApp::tls`]), shed
+    /// connections are closed without a response, since answering would
+    /// take a full handshake.
+    pub fn shed_load(mut self, policy: ShedPolicy) -> Self {
+        self.shed = Some(policy);
+        self
+    }
+}
+
+/// Serve `handler` on `port` on every queue of `app` until the process exits.
+///
+/// Shorthand for [`serve_http_with`] with [`ServeConfig::new`]; use that to
+/// tune buffers or to be able to shut down.
+pub fn serve_http<H, Fut, B, E>(app: DpdkApp, port: u16, handler: H) -> RunReport
+where
+    H: Fn(Request<Incoming>) -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = Result<Response<B>, E>> + 'static,
+    B: Body + 'static,
+    B::Error: Into<BoxError>,
+    E: Into<BoxError>,
+{
+    serve_http_with(app, ServeConfig::new(port), handler)
+}
+
+/// Serve `handler` on every queue of `app` until `config.shutdown` fires.
+///
+/// Blocks like [`DpdkApp::run_reporting`] and returns its report, whose
+/// per-queue `connections_accepted` counts the connections served.
+///
+/// # Panics
+///
+/// Panics if a queue cannot bind the listener, in addition to the cases
+/// listed on [`DpdkApp::run`].
+pub fn serve_http_with<H, Fut, B, E>(app: DpdkApp, config: ServeConfig, handler: H) -> RunReport
+where
+    H: Fn(Request<Incoming>) -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = Result<Response<B>, E>> + 'static,
+    B: Body + 'static,
+    B::Error: Into<BoxError>,
+    E: Into<BoxError>,
+{
+    app.run_reporting(move |ctx| {
+        let config = config.clone();
+        let handler = handler.clone();
+        async move { serve_worker(&ctx, &config, handler).await }
+    })
+}
+
+/// Serve `handler` on this worker's queue until `config.shutdown` fires.
+///
+/// The per-queue half of [`serve_http_with`], for workers that also run
+/// other tasks. Binds the listener, marks the worker ready, and accepts until
+/// shutdown. Then it stops accepting, asks every open connection to finish
+/// its in-flight requests and close, and waits up to `grace_period` for them.
+///
+/// If the app was built with [`DpdkApp::tls`], each connection completes its
+/// TLS handshake before hyper sees it. HTTP/1.1 and HTTP/2 are both served,
+/// whichever the client picked through ALPN.
+///
+/// # Panics
+///
+/// Panics if the listener cannot be bound.
+pub async fn serve_worker<H, Fut, B, E>(ctx: &WorkerContext, config: &ServeConfig, handler: H)
+where
+    H: Fn(Request<Incoming>) -> Fut + Clone + 'static,
+    Fut: Future<Output = Result<Response<B>, E>> + 'static,
+    B: Body + 'static,
+    B::Error: Into<BoxError>,
+    E: Into<BoxError>,
+{
+    let queue_id = ctx.queue_id;
+    let mut listener = TcpListener::bind_with_backlog(
+        &ctx.reactor,
+        config.port,
+        config.rx_buffer,
+        config.tx_buffer,
+        config.backlog,
+    )
+    .expect("Failed to bind HTTP listener");
+    ctx.mark_ready();
+    info!(queue_id, port = config.port, "HTTP server listening");
+
+    #[cfg(feature = "tls")]
+    let tls = ctx.tls_config().cloned();
+    let active = Rc::new(Cell::new(0usize));
+    let mut conn_id = 0u64;
+    let mut shed = 0u64;
+
+    loop {
+        tokio::select! {
+            _ = config.shutdown.cancelled() => break,
+            result = listener.accept() => {
+                let stream = match result {
+                    Ok(stream) => stream,
+                    Err(e) => {
+                        warn!(queue_id, error = %e, "HTTP accept failed");
+                        continue;
+                    }
+                };
+                let id = conn_id;
+                conn_id += 1;
+                debug!(queue_id, conn_id = id, "HTTP connection accepted");
+
+                if let Some(policy) = &config.shed {
+                    let load = ServerLoad {
+                        active_connections: active.get(),
+                        mbufs_available: ctx.reactor.mbufs_available(),
+                    };
+                    if policy.should_shed(&load) {
+                        debug!(queue_id, conn_id = id, ?load, "HTTP overloaded, shedding connection");
+                        shed += 1;
+                        if ctx.is_tls() {
+                            tokio::task::spawn_local(async move {
+                                let _ = stream.close().await;
+                            });
+                        } else {
+                            tokio::task::spawn_local(overload::reject(stream));
+                        }
+                        continue;
+                    }
+                }
+
+                let guard = ActiveGuard::new(&active);
+                let shutdown = config.shutdown.clone();
+                let handler = handler.clone();
+                #[cfg(feature = "tls")]
+                let tls = tls.clone();
+                tokio::task::spawn_local(async move {
+                    let _guard = guard;
+                    #[cfg(feature = "tls")]
+                    if let Some(config) = tls {
+                        let stream = tokio::select! {
+                            result = TlsStream::accept(Rc::new(stream), config) => match result {
+                                Ok(stream) => stream,
+                                Err(e) => {
+                                    debug!(queue_id, conn_id = id, error = %e, "TLS handshake failed");
+                                    return;
+                                }
+                            },
+                            _ = shutdown.cancelled() => return,
+                        };
+                        debug!(
+                            queue_id,
+                            conn_id = id,
+                            alpn = ?stream.alpn_protocol().map(String::from_utf8_lossy),
+                            "TLS handshake done"
+                        );
+                        serve_connection(stream, handler, shutdown, queue_id, id).await;
+                        return;
+                    }
+                    serve_connection(stream, handler, shutdown, queue_id, id).await;
+                });
+            }
+        }
+    }
+
+    // Stop accepting before draining
+    drop(listener);
+    let deadline = Instant::now() + config.grace_period;
+    while active.get() > 0 && Instant::now() < deadline {
+        ctx.reactor.sleep(Duration::from_millis(1)).await;
+    }
+    if active.get() > 0 {
+        warn!(
+            queue_id,
+            open = active.get(),
+            "HTTP grace period expired with connections open"
+        );
+    }
+    info!(
+        queue_id,
+        served = conn_id - shed,
+        shed,
+        "HTTP server stopped"
+    );
+}
+
+/// Serve one connection with hyper-util's auto builder until it closes, or
+/// until `shutdown` fires and its in-flight requests are done.
+async fn serve_connection<I, H, Fut, B, E>(
+    io: I,
+    handler: H,
+    shutdown: CancellationToken,
+    queue_id: u16,
+    conn_id: u64,
+) where
+    I: AsyncRead + AsyncWrite + Unpin + 'static,
+    H: Fn(Request<Incoming>) -> Fut + 'static,
+    Fut: Future<Output = Result<Response<B>, E>> + 'static,
+    B: Body + 'static,
+    B::Error: Into<BoxError>,
+    E: Into<BoxError>,
+{
+    let io = TokioIo::new(io.compat());
+    let builder = AutoBuilder::new(LocalExecutor);
+    let conn = builder.serve_connection(io, service_fn(handler));
+    tokio::pin!(conn);
+
+    let result = tokio::select! {
+        result = conn.as_mut() => result,
+        _ = shutdown.cancelled() => {
+            conn.as_mut().graceful_shutdown();
+            conn.await
+        }
+    };
+    match result {
+        Ok(()) => debug!(queue_id, conn_id, "HTTP connection closed"),
+        Err(e) => debug!(queue_id, conn_id, error = %e, "HTTP connection error"),
+    }
+}
+
+/// Counts a connection as active for as long as it is alive.
+pub(crate) struct ActiveGuard(Rc<Cell<usize>>);
+
+impl ActiveGuard {
+    pub(crate) fn new(active: &Rc<Cell<usize>>) -> Self {
+        active.set(active.get() + 1);
+        Self(active.clone())
+    }
+}
+
+impl Drop for ActiveGuard {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() - 1);
+    }
+}
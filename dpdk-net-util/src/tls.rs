@@ -0,0 +1,260 @@
+//! TLS over dpdk-net TCP streams, using rustls (feature: `tls`).
+//!
+//! [`TlsStream`] drives a rustls session over a [`TcpStream`] and exposes
+//! the plaintext through `futures-io`'s `AsyncRead`/`AsyncWrite`, the same
+//! traits the stream itself implements, so it takes the usual
+//! compat + `TokioIo` path into hyper. Like the stream, it is `!Send`.
+//!
+//! [`DpdkHttpClient`](crate::DpdkHttpClient) uses it when
+//! [`ClientConfig::tls`](crate::ClientConfig::tls) is set.
+
+use std::future::poll_fn;
+use std::io::{self, Read, Write};
+use std::ops::DerefMut;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::task::{Context, Poll, ready};
+
+use dpdk_net::socket::TcpStream;
+use futures_io::{AsyncRead, AsyncWrite};
+use rustls::pki_types::ServerName;
+use rustls::{ClientConnection, ConnectionCommon, ServerConnection, SideData};
+
+use crate::connection::HttpVersion;
+
+/// ALPN protocol ID for HTTP/2.
+pub const ALPN_H2: &[u8] = b"h2";
+/// ALPN protocol ID for HTTP/1.1.
+pub const ALPN_HTTP1: &[u8] = b"http/1.1";
+
+/// `config` with `h2` and `http/1.1` offered through ALPN, `preferred`
+/// first, unless it already lists protocols.
+pub(crate) fn with_default_alpn(
+    config: &Arc<rustls::ClientConfig>,
+    preferred: HttpVersion,
+) -> Arc<rustls::ClientConfig> {
+    if !config.alpn_protocols.is_empty() {
+        return config.clone();
+    }
+    let mut config = rustls::ClientConfig::clone(config);
+    config.alpn_protocols = match preferred {
+        HttpVersion::Http2 => vec![ALPN_H2.to_vec(), ALPN_HTTP1.to_vec()],
+        HttpVersion::Http1 => vec![ALPN_HTTP1.to_vec(), ALPN_H2.to_vec()],
+    };
+    Arc::new(config)
+}
+
+/// A rustls session running over a dpdk-net [`TcpStream`].
+///
+/// `C` is the rustls connection: [`ClientConnection`] for outbound
+/// streams, [`ServerConnection`] for accepted ones. Reads return decrypted application data; writes are encrypted
+/// and sent as TLS records. Closing sends `close_notify` before closing
+/// the TCP stream.
+pub struct TlsStream<C = ClientConnection> {
+    stream: Rc<TcpStream>,
+    session: C,
+}
+
+impl TlsStream<ClientConnection> {
+    /// Run a client handshake with `server_name` over `stream`.
+    ///
+    /// The certificate is verified as `config` specifies; its ALPN list is
+    /// offered as is. The negotiated protocol is in
+    /// [`alpn_protocol`](Self::alpn_protocol) once this returns.
+    pub async fn connect(
+        stream: Rc<TcpStream>,
+        config: Arc<rustls::ClientConfig>,
+        server_name: ServerName<'static>,
+    ) -> io::Result<Self> {
+        let session = ClientConnection::new(config, server_name).map_err(io::Error::other)?;
+        Self::handshake(stream, session).await
+    }
+}
+
+impl TlsStream<ServerConnection> {
+    /// Run a server handshake over an accepted `stream`.
+    pub async fn accept(
+        stream: Rc<TcpStream>,
+        config: Arc<rustls::ServerConfig>,
+    ) -> io::Result<Self> {
+        let session = ServerConnection::new(config).map_err(io::Error::other)?;
+        Self::handshake(stream, session).await
+    }
+}
+
+impl<C, D> TlsStream<C>
+where
+    C: DerefMut<Target = ConnectionCommon<D>>,
+    D: SideData,
+{
+    /// The TCP stream under the session.
+    pub fn get_ref(&self) -> &Rc<TcpStream> {
+        &self.stream
+    }
+
+    /// The rustls session, e.g. to inspect the peer's certificates.
+    pub fn session(&self) -> &C {
+        &self.session
+    }
+
+    /// The ALPN protocol the peer agreed to, if any.
+    pub fn alpn_protocol(&self) -> Option<&[u8]> {
+        self.session.alpn_protocol()
+    }
+
+    async fn handshake(stream: Rc<TcpStream>, session: C) -> io::Result<Self> {
+        let mut tls = Self { stream, session };
+        poll_fn(|cx| tls.poll_handshake(cx)).await?;
+        Ok(tls)
+    }
+
+    fn poll_handshake(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while self.session.is_handshaking() {
+            ready!(self.poll_write_tls(cx))?;
+            if !self.session.is_handshaking() || !self.session.wants_read() {
+                break;
+            }
+            if ready!(self.poll_read_tls(cx))? == 0 {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "TCP stream closed during TLS handshake",
+                )));
+            }
+        }
+        // Send our final handshake flight
+        self.poll_write_tls(cx)
+    }
+
+    /// Read TLS records from the TCP stream and process them, returning the
+    /// number of bytes read (0 at end of stream).
+    fn poll_read_tls(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<usize>> {
+        let mut io = SyncIo {
+            stream: &self.stream,
+            cx,
+        };
+        let n = match self.session.read_tls(&mut io) {
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Poll::Pending,
+            Err(e) => return Poll::Ready(Err(e)),
+        };
+        if let Err(e) = self.session.process_new_packets() {
+            // Try to get the alert out before reporting the error
+            let _ = self.poll_write_tls(cx);
+            return Poll::Ready(Err(io::Error::new(io::ErrorKind::InvalidData, e)));
+        }
+        Poll::Ready(Ok(n))
+    }
+
+    /// Send all queued TLS records.
+    fn poll_write_tls(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while self.session.wants_write() {
+            let mut io = SyncIo {
+                stream: &self.stream,
+                cx,
+            };
+            match self.session.write_tls(&mut io) {
+                Ok(0) => return Poll::Ready(Err(io::ErrorKind::WriteZero.into())),
+                Ok(_) => {}
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Poll::Pending,
+                Err(e) => return Poll::Ready(Err(e)),
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<C, D> AsyncRead for TlsStream<C>
+where
+    C: DerefMut<Target = ConnectionCommon<D>> + Unpin,
+    D: SideData,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        loop {
+            match this.session.reader().read(buf) {
+                Ok(n) => return Poll::Ready(Ok(n)),
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => {}
+                Err(e) => return Poll::Ready(Err(e)),
+            }
+            // Records like key updates may need an answer
+            if let Poll::Ready(Err(e)) = this.poll_write_tls(cx) {
+                return Poll::Ready(Err(e));
+            }
+            // At end of stream the reader reports EOF or truncation next
+            ready!(this.poll_read_tls(cx))?;
+        }
+    }
+}
+
+impl<C, D> AsyncWrite for TlsStream<C>
+where
+    C: DerefMut<Target = ConnectionCommon<D>> + Unpin,
+    D: SideData,
+{
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        // Flush earlier records first so rustls' buffer does not grow
+        ready!(this.poll_write_tls(cx))?;
+        let n = this.session.writer().write(buf)?;
+        // Start sending; whatever does not fit goes out on the next call
+        if let Poll::Ready(Err(e)) = this.poll_write_tls(cx) {
+            return Poll::Ready(Err(e));
+        }
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        this.session.writer().flush()?;
+        ready!(this.poll_write_tls(cx))?;
+        Pin::new(&mut &*this.stream).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        this.session.send_close_notify();
+        ready!(this.poll_write_tls(cx))?;
+        Pin::new(&mut &*this.stream).poll_close(cx)
+    }
+}
+
+/// Blocking-style `Read`/`Write` for rustls over the async stream: a
+/// pending operation becomes `WouldBlock`, with `cx` registered for wakeup.
+struct SyncIo<'a, 'b> {
+    stream: &'a TcpStream,
+    cx: &'a mut Context<'b>,
+}
+
+impl Read for SyncIo<'_, '_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match Pin::new(&mut &*self.stream).poll_read(self.cx, buf) {
+            Poll::Ready(result) => result,
+            Poll::Pending => Err(io::ErrorKind::WouldBlock.into()),
+        }
+    }
+}
+
+impl Write for SyncIo<'_, '_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match Pin::new(&mut &*self.stream).poll_write(self.cx, buf) {
+            Poll::Ready(result) => result,
+            Poll::Pending => Err(io::ErrorKind::WouldBlock.into()),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match Pin::new(&mut &*self.stream).poll_flush(self.cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => Err(io::ErrorKind::WouldBlock.into()),
+        }
+    }
+}
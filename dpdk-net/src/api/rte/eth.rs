@@ -4,9 +4,11 @@
 use std::mem::MaybeUninit;
 
 use dpdk_net_sys::ffi;
-use tracing::{debug, warn};
+use nix::errno::Errno;
+use tracing::{debug, error, warn};
 
 use super::pktmbuf::MemPool;
+use super::reta::RetaTable;
 use crate::api::{Result, check_rte_success};
 
 /// Ethernet device port ID
@@ -147,6 +149,60 @@ pub mod rss_hf {
     pub const UDP: u64 = ffi::RUST_RTE_ETH_RSS_UDP;
 }
 
+/// RX/TX offload flags for `EthConf::rx_offloads` / `tx_offloads`
+/// Re-exported from generated bindings (from wrapper.h static consts)
+pub mod offload {
+    use dpdk_net_sys::ffi;
+
+    /// RX: verify IPv4 header checksums
+    pub const RX_IPV4_CKSUM: u64 = ffi::RUST_RTE_ETH_RX_OFFLOAD_IPV4_CKSUM;
+    /// RX: verify UDP checksums
+    pub const RX_UDP_CKSUM: u64 = ffi::RUST_RTE_ETH_RX_OFFLOAD_UDP_CKSUM;
+    /// RX: verify TCP checksums
+    pub const RX_TCP_CKSUM: u64 = ffi::RUST_RTE_ETH_RX_OFFLOAD_TCP_CKSUM;
+    /// TX: compute IPv4 header checksums
+    pub const TX_IPV4_CKSUM: u64 = ffi::RUST_RTE_ETH_TX_OFFLOAD_IPV4_CKSUM;
+    /// TX: compute UDP checksums
+    pub const TX_UDP_CKSUM: u64 = ffi::RUST_RTE_ETH_TX_OFFLOAD_UDP_CKSUM;
+    /// TX: compute TCP checksums
+    pub const TX_TCP_CKSUM: u64 = ffi::RUST_RTE_ETH_TX_OFFLOAD_TCP_CKSUM;
+
+    /// Combined: all RX checksum verification (IPv4 + TCP + UDP)
+    pub const RX_CHECKSUM: u64 = RX_IPV4_CKSUM | RX_UDP_CKSUM | RX_TCP_CKSUM;
+    /// Combined: all TX checksum computation (IPv4 + TCP + UDP)
+    pub const TX_CHECKSUM: u64 = TX_IPV4_CKSUM | TX_UDP_CKSUM | TX_TCP_CKSUM;
+}
+
+/// Which directions of IPv4/TCP/UDP checksum work the NIC takes over.
+///
+/// A direction counts only if the NIC handles all three checksums in it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChecksumOffload {
+    /// The NIC verifies received checksums and flags the result per mbuf.
+    pub rx: bool,
+    /// The NIC computes checksums of transmitted frames.
+    pub tx: bool,
+}
+
+impl ChecksumOffload {
+    /// Both directions offloaded.
+    pub const ALL: Self = Self { rx: true, tx: true };
+
+    /// The directions fully covered by RX and TX offload bitmasks, such as
+    /// the `rx_offload_capa`/`tx_offload_capa` of `rte_eth_dev_info`.
+    pub fn from_offloads(rx_offloads: u64, tx_offloads: u64) -> Self {
+        Self {
+            rx: rx_offloads & offload::RX_CHECKSUM == offload::RX_CHECKSUM,
+            tx: tx_offloads & offload::TX_CHECKSUM == offload::TX_CHECKSUM,
+        }
+    }
+
+    /// Whether either direction is offloaded.
+    pub fn any(&self) -> bool {
+        self.rx || self.tx
+    }
+}
+
 /// Standard Microsoft RSS key (40 bytes) for Toeplitz hash
 /// This key provides good distribution for TCP/IP traffic
 pub const RSS_KEY_40: [u8; 40] = [
@@ -155,6 +211,22 @@ pub const RSS_KEY_40: [u8; 40] = [
     0x6a, 0x42, 0xb7, 0x3b, 0xbe, 0xac, 0x01, 0xfa,
 ];
 
+/// Symmetric RSS key (40 bytes) for Toeplitz hash
+///
+/// `0x6d5a` repeated: swapping source and destination address (and port)
+/// gives the same hash, so both directions of a flow land on one queue.
+pub const RSS_KEY_SYMMETRIC_40: [u8; 40] = [
+    0x6d, 0x5a, 0x6d, 0x5a, 0x6d, 0x5a, 0x6d, 0x5a, 0x6d, 0x5a, 0x6d, 0x5a, 0x6d, 0x5a, 0x6d, 0x5a,
+    0x6d, 0x5a, 0x6d, 0x5a, 0x6d, 0x5a, 0x6d, 0x5a, 0x6d, 0x5a, 0x6d, 0x5a, 0x6d, 0x5a, 0x6d, 0x5a,
+    0x6d, 0x5a, 0x6d, 0x5a, 0x6d, 0x5a, 0x6d, 0x5a,
+];
+
+/// Symmetric RSS key of `len` bytes, for NICs whose
+/// `hash_key_size` is not 40 (e.g. 52)
+pub fn symmetric_rss_key(len: usize) -> Vec<u8> {
+    [0x6d, 0x5a].into_iter().cycle().take(len).collect()
+}
+
 /// Ethernet device configuration
 #[derive(Debug, Clone, Default)]
 pub struct EthConf {
@@ -170,6 +242,8 @@ pub struct EthConf {
     pub rss_hf: u64,
     /// RSS key (None = use driver default, Some = use this key)
     pub rss_key: Option<Vec<u8>>,
+    /// Raise link state change interrupts (see `EthDev::on_link_change`)
+    pub lsc_interrupt: bool,
 }
 
 impl EthConf {
@@ -196,6 +270,32 @@ impl EthConf {
         self
     }
 
+    /// Enable IPv4/TCP/UDP checksum offload in both directions
+    ///
+    /// Drivers refuse offloads they do not advertise, so `configure` fails
+    /// with `EINVAL` on a NIC without them; check
+    /// [`EthDev::checksum_offload_capa`] or use
+    /// [`checksum_offload_for`](Self::checksum_offload_for).
+    pub fn checksum_offload(self) -> Self {
+        self.checksum_offload_for(ChecksumOffload::ALL)
+    }
+
+    /// Enable IPv4/TCP/UDP checksum offload in the given directions
+    pub fn checksum_offload_for(mut self, checksum: ChecksumOffload) -> Self {
+        if checksum.rx {
+            self.rx_mode.offloads |= offload::RX_CHECKSUM;
+        }
+        if checksum.tx {
+            self.tx_mode.offloads |= offload::TX_CHECKSUM;
+        }
+        self
+    }
+
+    /// The checksum offload directions this configuration enables
+    pub fn enabled_checksum_offload(&self) -> ChecksumOffload {
+        ChecksumOffload::from_offloads(self.rx_mode.offloads, self.tx_mode.offloads)
+    }
+
     /// Enable RSS mode with default TCP/IP hash function and standard key
     pub fn rss(mut self) -> Self {
         self.rx_mode.mq_mode = RxMqMode::Rss;
@@ -222,6 +322,29 @@ impl EthConf {
         self
     }
 
+    /// Enable RSS mode with a custom key
+    ///
+    /// The key length must match the device's `hash_key_size` (usually 40
+    /// or 52 bytes), or [`EthDev::configure`] fails with `EINVAL`. A key is
+    /// only installed together with explicit hash types, so `IP | TCP` is
+    /// selected unless [`rss_hf`](Self::rss_hf) already set some.
+    pub fn rss_key(mut self, key: Vec<u8>) -> Self {
+        self.rx_mode.mq_mode = RxMqMode::Rss;
+        if self.rss_hf == 0 {
+            self.rss_hf = rss_hf::IP | rss_hf::TCP;
+        }
+        self.rss_key = Some(key);
+        self
+    }
+
+    /// Enable RSS mode with the symmetric key [`RSS_KEY_SYMMETRIC_40`]
+    ///
+    /// Both directions of a flow hash to the same queue. For a NIC with a
+    /// different key size use `rss_key(symmetric_rss_key(len))`.
+    pub fn symmetric_rss(self) -> Self {
+        self.rss_key(RSS_KEY_SYMMETRIC_40.to_vec())
+    }
+
     /// Set custom RSS hash function flags
     pub fn rss_hf(mut self, hf: u64) -> Self {
         self.rss_hf = hf;
@@ -234,6 +357,16 @@ impl EthConf {
         self
     }
 
+    /// Enable link state change interrupts
+    ///
+    /// Needed for [`EthDev::on_link_change`] callbacks to fire.
+    /// Drivers without LSC support fail `configure` with `EINVAL`; their link
+    /// can still be polled with `EthDev::link_status`.
+    pub fn lsc_interrupt(mut self) -> Self {
+        self.lsc_interrupt = true;
+        self
+    }
+
     /// Convert to raw rte_eth_conf
     /// Returns the config and an optional key buffer that must be kept alive
     fn to_raw(&self) -> (ffi::rte_eth_conf, Option<Vec<u8>>) {
@@ -246,6 +379,7 @@ impl EthConf {
         conf.txmode.mq_mode = self.tx_mode.mq_mode as u32;
         conf.txmode.offloads = self.tx_mode.offloads;
         conf.lpbk_mode = self.loopback_mode;
+        conf.intr_conf.set_lsc(self.lsc_interrupt as u32);
 
         let mut key_buffer: Option<Vec<u8>> = None;
 
@@ -371,6 +505,51 @@ impl TxQueueConf {
     }
 }
 
+/// Descriptor ring size limits reported by a driver (`rte_eth_desc_lim`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DescLimits {
+    /// Largest allowed ring size.
+    pub nb_max: u16,
+    /// Smallest allowed ring size.
+    pub nb_min: u16,
+    /// Ring sizes must be a multiple of this.
+    pub nb_align: u16,
+}
+
+impl DescLimits {
+    fn from_raw(lim: &ffi::rte_eth_desc_lim) -> Self {
+        Self {
+            nb_max: lim.nb_max,
+            nb_min: lim.nb_min,
+            nb_align: lim.nb_align,
+        }
+    }
+
+    fn align(&self) -> u16 {
+        self.nb_align.max(1)
+    }
+
+    /// Returns true if a ring of `nb_desc` descriptors is accepted.
+    ///
+    /// 0 is always accepted: it asks the driver for its default size.
+    pub fn contains(&self, nb_desc: u16) -> bool {
+        nb_desc == 0
+            || ((self.nb_min..=self.nb_max).contains(&nb_desc) && nb_desc % self.align() == 0)
+    }
+
+    /// The nearest accepted ring size to `nb_desc`.
+    ///
+    /// Rounds up to the alignment, then clamps into `[nb_min, nb_max]`
+    /// (rounding down at the top if `nb_max` is not aligned itself). The same
+    /// adjustment `rte_eth_dev_adjust_nb_rx_tx_desc` makes.
+    pub fn clamp(&self, nb_desc: u16) -> u16 {
+        let align = self.align();
+        let max = self.nb_max - self.nb_max % align;
+        let aligned = nb_desc.div_ceil(align).saturating_mul(align);
+        aligned.clamp(self.nb_min.min(max), max)
+    }
+}
+
 /// Ethernet device wrapper
 pub struct EthDev {
     port_id: PortId,
@@ -405,7 +584,7 @@ impl EthDev {
     ///
     /// To get the actual hardware queue count, use the ethtool GCHANNELS ioctl
     /// (equivalent to `ethtool -l <interface>`) before initializing DPDK.
-    /// See [`dpdk_net_test::util::get_ethtool_channels`] for a helper function.
+    /// See [`get_ethtool_channels`](crate::topology::get_ethtool_channels) for a helper function.
     pub fn info(&self) -> Result<ffi::rte_eth_dev_info> {
         let mut info = MaybeUninit::<ffi::rte_eth_dev_info>::uninit();
         let ret = unsafe { ffi::rte_eth_dev_info_get(self.port_id, info.as_mut_ptr()) };
@@ -413,6 +592,24 @@ impl EthDev {
         Ok(unsafe { info.assume_init() })
     }
 
+    /// The checksum offload directions the driver advertises.
+    pub fn checksum_offload_capa(&self) -> Result<ChecksumOffload> {
+        let info = self.info()?;
+        Ok(ChecksumOffload::from_offloads(
+            info.rx_offload_capa,
+            info.tx_offload_capa,
+        ))
+    }
+
+    /// Get the driver's RX and TX descriptor ring limits, in that order.
+    pub fn desc_limits(&self) -> Result<(DescLimits, DescLimits)> {
+        let info = self.info()?;
+        Ok((
+            DescLimits::from_raw(&info.rx_desc_lim),
+            DescLimits::from_raw(&info.tx_desc_lim),
+        ))
+    }
+
     /// Get the NUMA socket ID of the device
     pub fn socket_id(&self) -> i32 {
         unsafe { ffi::rte_eth_dev_socket_id(self.port_id) }
@@ -434,8 +631,24 @@ impl EthDev {
         Ok(unsafe { stats.assume_init() })
     }
 
+    /// Get device statistics as a typed [`EthStats`](super::stats::EthStats).
+    ///
+    /// Use [`StatsSampler`](super::stats::StatsSampler) to turn successive
+    /// samples into rates.
+    pub fn stats_typed(&self) -> Result<super::stats::EthStats> {
+        self.stats().map(|raw| (&raw).into())
+    }
+
     /// Configure the device
+    ///
+    /// An RSS key whose length differs from the device's `hash_key_size` is
+    /// rejected with `EINVAL` before the driver sees it.
     pub fn configure(&self, nb_rx_queues: u16, nb_tx_queues: u16, conf: &EthConf) -> Result<()> {
+        if conf.rx_mode.mq_mode == RxMqMode::Rss
+            && let Some(key) = &conf.rss_key
+        {
+            check_rss_key(self.port_id, key.len(), self.info()?.hash_key_size)?;
+        }
         let (raw_conf, _key_buffer) = conf.to_raw();
         // Note: _key_buffer is kept alive until after rte_eth_dev_configure returns
         let ret = unsafe {
@@ -520,6 +733,59 @@ impl EthDev {
         check_rte_success(ret)
     }
 
+    /// Accept frames for every multicast MAC address
+    pub fn allmulticast_enable(&self) -> Result<()> {
+        let ret = unsafe { ffi::rte_eth_allmulticast_enable(self.port_id) };
+        check_rte_success(ret)
+    }
+
+    /// Stop accepting multicast frames not in the device's MAC filter
+    pub fn allmulticast_disable(&self) -> Result<()> {
+        let ret = unsafe { ffi::rte_eth_allmulticast_disable(self.port_id) };
+        check_rte_success(ret)
+    }
+
+    /// Whether all-multicast mode is enabled
+    pub fn allmulticast_enabled(&self) -> Result<bool> {
+        let ret = unsafe { ffi::rte_eth_allmulticast_get(self.port_id) };
+        check_rte_success(ret)?;
+        Ok(ret == 1)
+    }
+
+    /// Change the MTU (L3 payload size, as in `EthConf::mtu`)
+    ///
+    /// Works on a started port where the driver allows it, e.g. to switch
+    /// to 9000-byte jumbo frames once the link is up. The value must lie in
+    /// the driver's `min_mtu..=max_mtu` (see [`info`](Self::info)), and the
+    /// frames must fit the RX mbufs unless scattered RX is enabled. Some
+    /// drivers only accept a change while the port is stopped; then
+    /// [`stop`](Self::stop), reconfigure with `EthConf::mtu`, and start it
+    /// again. Whatever the driver refuses with (commonly `EBUSY`, `EINVAL`
+    /// or `ENOTSUP`) is returned unchanged.
+    ///
+    /// The stack's view of the MTU is separate; on a running reactor use
+    /// [`ReactorHandle::set_mtu`](crate::runtime::ReactorHandle::set_mtu),
+    /// which calls this and updates the stack too. Lowering the port's MTU
+    /// underneath a running stack leaves it sending frames the port refuses.
+    pub fn set_mtu(&self, mtu: u32) -> Result<()> {
+        let mtu = u16::try_from(mtu).map_err(|_| Errno::EINVAL)?;
+        let ret = unsafe { ffi::rte_eth_dev_set_mtu(self.port_id, mtu) };
+        if ret < 0 {
+            return Err(Errno::from_raw(-ret));
+        }
+        Ok(())
+    }
+
+    /// The port's current MTU
+    pub fn mtu(&self) -> Result<u32> {
+        let mut mtu: u16 = 0;
+        let ret = unsafe { ffi::rte_eth_dev_get_mtu(self.port_id, &mut mtu) };
+        if ret < 0 {
+            return Err(Errno::from_raw(-ret));
+        }
+        Ok(mtu as u32)
+    }
+
     /// Query the actual RSS hash configuration from the device.
     ///
     /// Returns the RSS hash functions that are actually enabled (not just advertised).
@@ -568,26 +834,47 @@ impl EthDev {
             return Ok(());
         }
 
+        self.set_rss_reta(RetaTable::round_robin(reta_size, nb_rx_queues).entries())
+    }
+
+    /// Program the RSS RETA with `entries`, one queue ID per table slot.
+    ///
+    /// Any mapping is allowed, e.g. keeping queue 0 out of the table to
+    /// reserve it for control traffic, or listing a queue more often to
+    /// weight it. `entries.len()` must equal the device's `reta_size`, or
+    /// `EINVAL` is returned before the device is touched. Queue IDs are left
+    /// to the driver to check; see [`apply_reta_from`](Self::apply_reta_from)
+    /// for a fully validated and verified update.
+    pub fn set_rss_reta(&self, entries: &[u16]) -> Result<()> {
+        let reta_size = self.info()?.reta_size;
+        if entries.len() != reta_size as usize {
+            error!(
+                port_id = self.port_id,
+                len = entries.len(),
+                reta_size,
+                "RETA table size does not match the device"
+            );
+            return Err(Errno::EINVAL);
+        }
+
         // Each rte_eth_rss_reta_entry64 covers 64 entries
-        let num_groups = (reta_size as usize).div_ceil(64);
+        let num_groups = entries.len().div_ceil(64);
 
         // Allocate RETA configuration
         let mut reta_conf: Vec<ffi::rte_eth_rss_reta_entry64> =
             vec![unsafe { std::mem::zeroed() }; num_groups];
 
-        // Configure each entry to map to queues in round-robin
-        for (group_idx, group) in reta_conf.iter_mut().enumerate() {
+        for (group, chunk) in reta_conf.iter_mut().zip(entries.chunks(64)) {
             group.mask = u64::MAX; // Update all entries in this group
-            for i in 0..64 {
-                let entry_idx = group_idx * 64 + i;
-                if entry_idx < reta_size as usize {
-                    group.reta[i] = (entry_idx % nb_rx_queues as usize) as u16;
-                }
-            }
+            group.reta[..chunk.len()].copy_from_slice(chunk);
         }
 
         let ret = unsafe {
-            ffi::rte_eth_dev_rss_reta_update(self.port_id, reta_conf.as_mut_ptr(), reta_size)
+            ffi::rte_eth_dev_rss_reta_update(
+                self.port_id,
+                reta_conf.as_mut_ptr(),
+                entries.len() as u16,
+            )
         };
         check_rte_success(ret)
     }
@@ -716,6 +1003,11 @@ impl EthDevBuilder {
 
     /// Build and start the device
     ///
+    /// The RX and TX descriptor counts are checked against
+    /// [`EthDev::desc_limits`] first; a count the driver would refuse fails
+    /// with `EINVAL` and an error log naming the limits and the nearest
+    /// valid count, before anything is configured.
+    ///
     /// This will:
     /// 1. Configure the device
     /// 2. Setup all RX queues
@@ -727,6 +1019,10 @@ impl EthDevBuilder {
     pub fn build(self, mempool: &MemPool) -> Result<EthDev> {
         let dev = EthDev::new(self.port_id);
 
+        let (rx_lim, tx_lim) = dev.desc_limits()?;
+        check_desc(self.port_id, "RX", self.rx_queue_conf.nb_desc, &rx_lim)?;
+        check_desc(self.port_id, "TX", self.tx_queue_conf.nb_desc, &tx_lim)?;
+
         // Configure device
         dev.configure(self.nb_rx_queues, self.nb_tx_queues, &self.eth_conf)?;
 
@@ -776,6 +1072,37 @@ impl EthDevBuilder {
     }
 }
 
+/// Reject a descriptor count outside the driver's limits, explaining why.
+fn check_desc(port_id: PortId, dir: &str, nb_desc: u16, lim: &DescLimits) -> Result<()> {
+    if lim.contains(nb_desc) {
+        return Ok(());
+    }
+    error!(
+        port_id,
+        nb_desc,
+        min = lim.nb_min,
+        max = lim.nb_max,
+        align = lim.nb_align,
+        suggested = lim.clamp(nb_desc),
+        "{dir} descriptor count outside the driver's limits"
+    );
+    Err(Errno::EINVAL)
+}
+
+/// Reject an RSS key the device cannot take, explaining why.
+///
+/// A `hash_key_size` of 0 means the driver does not report one.
+fn check_rss_key(port_id: PortId, key_len: usize, hash_key_size: u8) -> Result<()> {
+    if hash_key_size == 0 || key_len == hash_key_size as usize {
+        return Ok(());
+    }
+    error!(
+        port_id,
+        key_len, hash_key_size, "RSS key length does not match the device's hash key size"
+    );
+    Err(Errno::EINVAL)
+}
+
 /// Iterate over available port IDs
 pub fn iter_ports() -> impl Iterator<Item = PortId> {
     0..EthDev::count_avail()
@@ -793,3 +1120,97 @@ pub fn format_mac_addr(addr: &ffi::rte_ether_addr) -> String {
         addr.addr_bytes[5]
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIMITS: DescLimits = DescLimits {
+        nb_max: 4096,
+        nb_min: 64,
+        nb_align: 32,
+    };
+
+    #[test]
+    fn test_desc_limits_contains() {
+        assert!(LIMITS.contains(0));
+        assert!(LIMITS.contains(64));
+        assert!(LIMITS.contains(1024));
+        assert!(LIMITS.contains(4096));
+        assert!(!LIMITS.contains(32));
+        assert!(!LIMITS.contains(100));
+        assert!(!LIMITS.contains(8192));
+    }
+
+    #[test]
+    fn test_desc_limits_clamp() {
+        assert_eq!(LIMITS.clamp(100), 128);
+        assert_eq!(LIMITS.clamp(16), 64);
+        assert_eq!(LIMITS.clamp(u16::MAX), 4096);
+        assert_eq!(LIMITS.clamp(1024), 1024);
+
+        // Drivers that leave the limits at their defaults accept anything
+        let any = DescLimits {
+            nb_max: u16::MAX,
+            nb_min: 0,
+            nb_align: 1,
+        };
+        assert_eq!(any.clamp(1000), 1000);
+        assert!(any.contains(1000));
+    }
+
+    #[test]
+    fn test_checksum_offload_conf() {
+        let conf = EthConf::new().checksum_offload_for(ChecksumOffload {
+            rx: false,
+            tx: true,
+        });
+        assert_eq!(conf.rx_mode.offloads, 0);
+        assert_eq!(conf.tx_mode.offloads, offload::TX_CHECKSUM);
+        assert_eq!(
+            conf.enabled_checksum_offload(),
+            ChecksumOffload {
+                rx: false,
+                tx: true
+            }
+        );
+
+        // A direction missing one of its checksums is not offloaded
+        let partial = ChecksumOffload::from_offloads(
+            offload::RX_IPV4_CKSUM | offload::RX_TCP_CKSUM,
+            offload::TX_CHECKSUM,
+        );
+        assert!(!partial.rx);
+        assert!(partial.tx);
+        assert_eq!(
+            EthConf::new().checksum_offload().enabled_checksum_offload(),
+            ChecksumOffload::ALL
+        );
+    }
+
+    #[test]
+    fn test_rss_key_conf() {
+        let conf = EthConf::new().symmetric_rss();
+        assert_eq!(conf.rx_mode.mq_mode, RxMqMode::Rss);
+        assert_eq!(conf.rss_hf, rss_hf::IP | rss_hf::TCP);
+        assert_eq!(conf.rss_key.as_deref(), Some(&RSS_KEY_SYMMETRIC_40[..]));
+        assert_eq!(symmetric_rss_key(40), RSS_KEY_SYMMETRIC_40);
+
+        // Hash types chosen first are kept
+        let conf = EthConf::new()
+            .rss_hf(rss_hf::IP)
+            .rss_key(symmetric_rss_key(52));
+        assert_eq!(conf.rss_hf, rss_hf::IP);
+        assert_eq!(conf.rss_key.as_ref().map(Vec::len), Some(52));
+        let (raw, key) = conf.to_raw();
+        assert_eq!(raw.rx_adv_conf.rss_conf.rss_key_len, 52);
+        assert_eq!(key.as_ref().map(Vec::len), Some(52));
+    }
+
+    #[test]
+    fn test_check_rss_key() {
+        assert!(check_rss_key(0, 40, 40).is_ok());
+        assert!(check_rss_key(0, 52, 0).is_ok());
+        assert_eq!(check_rss_key(0, 40, 52), Err(Errno::EINVAL));
+    }
+}
@@ -0,0 +1,333 @@
+// Flow rules steering IPv4 traffic to a chosen RX queue
+// See rte_flow_create and rte_flow_destroy in rte_flow.h
+//
+// RSS spreads flows by hash, so which queue a connection lands on depends on
+// the key and the RETA. A flow rule overrides that for the traffic it matches:
+// e.g. everything to TCP port 8080 goes to queue 1, every time. The pattern
+// is always ETH / IPV4 / [TCP | UDP]; the C wrapper builds the rte_flow items
+// so their bitfields and unions stay out of Rust. Drivers differ widely in
+// which matches they accept, so `FlowBuilder::validate` first.
+
+use std::ffi::{CStr, c_char};
+use std::net::{Ipv4Addr, SocketAddrV4};
+
+use dpdk_net_sys::ffi;
+use nix::errno::Errno;
+use tracing::{error, warn};
+
+use super::eth::PortId;
+use crate::api::{Result, check_rte_success, rte_errno};
+
+const IPPROTO_TCP: u8 = 6;
+const IPPROTO_UDP: u8 = 17;
+
+/// L4 protocol of a flow rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlowProto {
+    /// Match TCP segments.
+    Tcp,
+    /// Match UDP datagrams.
+    Udp,
+}
+
+/// Builder for an ingress rule sending matching IPv4 packets to one queue.
+///
+/// Fields left unset match anything. Port matches need a protocol.
+///
+/// ```ignore
+/// use dpdk_net::api::rte::flow::{FlowBuilder, FlowProto};
+///
+/// // All traffic for the listener on port 8080 goes to queue 0
+/// let flow = FlowBuilder::new(0)
+///     .proto(FlowProto::Tcp)
+///     .dst_port(8080)
+///     .queue(0)
+///     .create()?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlowBuilder {
+    port_id: PortId,
+    priority: u32,
+    proto: Option<FlowProto>,
+    src_ip: Option<(Ipv4Addr, u8)>,
+    dst_ip: Option<(Ipv4Addr, u8)>,
+    src_port: Option<u16>,
+    dst_port: Option<u16>,
+    queue: u16,
+}
+
+impl FlowBuilder {
+    /// Start a rule on `port_id` that sends its matches to queue 0.
+    pub fn new(port_id: PortId) -> Self {
+        Self {
+            port_id,
+            priority: 0,
+            proto: None,
+            src_ip: None,
+            dst_ip: None,
+            src_port: None,
+            dst_port: None,
+            queue: 0,
+        }
+    }
+
+    /// A rule matching one connection's packets as the NIC receives them:
+    /// from `remote` to `local`.
+    pub fn five_tuple(
+        port_id: PortId,
+        proto: FlowProto,
+        remote: SocketAddrV4,
+        local: SocketAddrV4,
+    ) -> Self {
+        Self::new(port_id)
+            .proto(proto)
+            .src_ip(*remote.ip(), 32)
+            .src_port(remote.port())
+            .dst_ip(*local.ip(), 32)
+            .dst_port(local.port())
+    }
+
+    /// Set the rule priority; 0 is the highest (default: 0).
+    pub fn priority(mut self, priority: u32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Match only this L4 protocol.
+    pub fn proto(mut self, proto: FlowProto) -> Self {
+        self.proto = Some(proto);
+        self
+    }
+
+    /// Match source addresses in `addr/prefix_len`.
+    pub fn src_ip(mut self, addr: Ipv4Addr, prefix_len: u8) -> Self {
+        self.src_ip = Some((addr, prefix_len));
+        self
+    }
+
+    /// Match destination addresses in `addr/prefix_len`.
+    pub fn dst_ip(mut self, addr: Ipv4Addr, prefix_len: u8) -> Self {
+        self.dst_ip = Some((addr, prefix_len));
+        self
+    }
+
+    /// Match this source port.
+    pub fn src_port(mut self, port: u16) -> Self {
+        self.src_port = Some(port);
+        self
+    }
+
+    /// Match this destination port.
+    pub fn dst_port(mut self, port: u16) -> Self {
+        self.dst_port = Some(port);
+        self
+    }
+
+    /// Send matching packets to RX queue `queue` (default: 0).
+    pub fn queue(mut self, queue: u16) -> Self {
+        self.queue = queue;
+        self
+    }
+
+    /// Ask the driver whether it would accept the rule, without creating it.
+    ///
+    /// Returns `EINVAL` without asking if a port is matched but no protocol
+    /// is set, or a prefix is longer than 32. Driver refusals (commonly
+    /// `ENOTSUP`) are logged with the driver's explanation.
+    pub fn validate(&self) -> Result<()> {
+        let m = self.to_raw()?;
+        let mut message: *const c_char = std::ptr::null();
+        let ret = unsafe {
+            ffi::rust_flow_validate_queue(self.port_id, self.priority, &m, self.queue, &mut message)
+        };
+        if ret < 0 {
+            let errno = rte_errno();
+            error!(
+                port_id = self.port_id,
+                rule = ?self,
+                %errno,
+                reason = flow_message(message),
+                "Flow rule rejected"
+            );
+            return Err(errno);
+        }
+        Ok(())
+    }
+
+    /// Install the rule. It stays active until the returned [`Flow`] is
+    /// dropped or destroyed.
+    ///
+    /// Fails like [`validate`](Self::validate). Create rules after the
+    /// device is started; some drivers drop them when the port restarts.
+    pub fn create(&self) -> Result<Flow> {
+        let m = self.to_raw()?;
+        let mut message: *const c_char = std::ptr::null();
+        let raw = unsafe {
+            ffi::rust_flow_create_queue(self.port_id, self.priority, &m, self.queue, &mut message)
+        };
+        if raw.is_null() {
+            let errno = rte_errno();
+            error!(
+                port_id = self.port_id,
+                rule = ?self,
+                %errno,
+                reason = flow_message(message),
+                "Failed to create flow rule"
+            );
+            return Err(errno);
+        }
+        Ok(Flow {
+            port_id: self.port_id,
+            raw,
+        })
+    }
+
+    fn to_raw(&self) -> Result<ffi::rust_flow_match> {
+        let has_ports = self.src_port.is_some() || self.dst_port.is_some();
+        if has_ports && self.proto.is_none() {
+            error!(rule = ?self, "Flow rule matches a port but no protocol");
+            return Err(Errno::EINVAL);
+        }
+        let ip = |net: Option<(Ipv4Addr, u8)>| -> Result<(u32, u32)> {
+            match net {
+                None => Ok((0, 0)),
+                Some((addr, len)) => {
+                    let mask = prefix_mask(len).ok_or(Errno::EINVAL)?;
+                    Ok((u32::from(addr), mask))
+                }
+            }
+        };
+        let (src_ip, src_ip_mask) = ip(self.src_ip)?;
+        let (dst_ip, dst_ip_mask) = ip(self.dst_ip)?;
+        let port = |p: Option<u16>| p.map_or((0, 0), |p| (p, u16::MAX));
+        let (src_port, src_port_mask) = port(self.src_port);
+        let (dst_port, dst_port_mask) = port(self.dst_port);
+        Ok(ffi::rust_flow_match {
+            proto: match self.proto {
+                None => 0,
+                Some(FlowProto::Tcp) => IPPROTO_TCP,
+                Some(FlowProto::Udp) => IPPROTO_UDP,
+            },
+            src_ip,
+            src_ip_mask,
+            dst_ip,
+            dst_ip_mask,
+            src_port,
+            src_port_mask,
+            dst_port,
+            dst_port_mask,
+        })
+    }
+}
+
+/// An installed flow rule; removed from the device on drop.
+pub struct Flow {
+    port_id: PortId,
+    raw: *mut ffi::rte_flow,
+}
+
+// A flow handle is an opaque per-port token; DPDK serializes flow operations
+// on a port itself.
+unsafe impl Send for Flow {}
+
+impl Flow {
+    /// The port the rule is installed on.
+    pub fn port_id(&self) -> PortId {
+        self.port_id
+    }
+
+    /// Remove the rule now and report whether the driver did.
+    pub fn destroy(self) -> Result<()> {
+        let this = std::mem::ManuallyDrop::new(self);
+        this.destroy_raw()
+    }
+
+    fn destroy_raw(&self) -> Result<()> {
+        let mut message: *const c_char = std::ptr::null();
+        let ret = unsafe { ffi::rust_flow_destroy(self.port_id, self.raw, &mut message) };
+        check_rte_success(ret).inspect_err(|errno| {
+            warn!(
+                port_id = self.port_id,
+                %errno,
+                reason = flow_message(message),
+                "Failed to destroy flow rule"
+            )
+        })
+    }
+}
+
+impl Drop for Flow {
+    fn drop(&mut self) {
+        // Already logged; a device that is gone took the rule with it
+        let _ = self.destroy_raw();
+    }
+}
+
+impl std::fmt::Debug for Flow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Flow")
+            .field("port_id", &self.port_id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Netmask for a prefix length, or `None` if it is longer than 32.
+fn prefix_mask(len: u8) -> Option<u32> {
+    match len {
+        0 => Some(0),
+        1..=32 => Some(u32::MAX << (32 - len)),
+        _ => None,
+    }
+}
+
+/// The driver's explanation of a flow error, if it gave one.
+fn flow_message(message: *const c_char) -> String {
+    if message.is_null() {
+        return String::new();
+    }
+    unsafe { CStr::from_ptr(message) }
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefix_mask() {
+        assert_eq!(prefix_mask(0), Some(0));
+        assert_eq!(prefix_mask(24), Some(0xffff_ff00));
+        assert_eq!(prefix_mask(32), Some(u32::MAX));
+        assert_eq!(prefix_mask(33), None);
+    }
+
+    #[test]
+    fn test_five_tuple_match() {
+        let remote = SocketAddrV4::new(Ipv4Addr::new(10, 0, 0, 2), 49152);
+        let local = SocketAddrV4::new(Ipv4Addr::new(10, 0, 0, 1), 8080);
+        let m = FlowBuilder::five_tuple(0, FlowProto::Tcp, remote, local)
+            .to_raw()
+            .unwrap();
+        assert_eq!(m.proto, IPPROTO_TCP);
+        assert_eq!((m.src_ip, m.src_ip_mask), (0x0a00_0002, u32::MAX));
+        assert_eq!((m.dst_port, m.dst_port_mask), (8080, u16::MAX));
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        assert_eq!(
+            FlowBuilder::new(0).dst_port(80).to_raw().err(),
+            Some(Errno::EINVAL)
+        );
+        assert_eq!(
+            FlowBuilder::new(0)
+                .dst_ip(Ipv4Addr::LOCALHOST, 40)
+                .to_raw()
+                .err(),
+            Some(Errno::EINVAL)
+        );
+        let any = FlowBuilder::new(0).to_raw().unwrap();
+        assert_eq!((any.proto, any.dst_ip_mask, any.dst_port_mask), (0, 0, 0));
+    }
+}
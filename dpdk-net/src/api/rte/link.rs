@@ -0,0 +1,212 @@
+// Link status and link state change notifications
+// See rte_eth_link_get_nowait and rte_eth_dev_callback_register in rte_ethdev.h
+//
+// LSC callbacks run on DPDK's interrupt thread, not on an lcore: the handler
+// must be Send + Sync, must not block, and must not touch a reactor (which is
+// !Send). Use `EthDev::link_events` to hand events to lcore code instead.
+
+use std::ffi::{c_int, c_void};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use dpdk_net_sys::ffi;
+use nix::errno::Errno;
+
+use super::eth::{EthDev, PortId};
+use crate::api::{Result, check_rte_success};
+
+/// Link state of a port.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LinkStatus {
+    /// Carrier is up.
+    pub up: bool,
+    /// Negotiated speed in Mbps (0 when down or unknown).
+    pub speed_mbps: u32,
+    /// Full duplex (false when down).
+    pub full_duplex: bool,
+}
+
+impl LinkStatus {
+    /// A link that is down.
+    pub const DOWN: LinkStatus = LinkStatus {
+        up: false,
+        speed_mbps: 0,
+        full_duplex: false,
+    };
+}
+
+/// How often [`EthDev::wait_for_link`] checks the link.
+const LINK_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+type LinkHandler = Box<dyn Fn(LinkStatus) + Send + Sync>;
+
+/// Registration of a link change callback; unregisters on drop.
+///
+/// Returned by [`EthDev::on_link_change`].
+pub struct LinkCallback {
+    port_id: PortId,
+    handler: *mut LinkHandler,
+}
+
+// The handler is Send + Sync; the pointer is only freed once DPDK no longer
+// calls it.
+unsafe impl Send for LinkCallback {}
+
+impl LinkCallback {
+    /// The port the callback is registered on.
+    pub fn port_id(&self) -> PortId {
+        self.port_id
+    }
+}
+
+impl Drop for LinkCallback {
+    fn drop(&mut self) {
+        loop {
+            let ret = unsafe {
+                ffi::rte_eth_dev_callback_unregister(
+                    self.port_id,
+                    ffi::RUST_RTE_ETH_EVENT_INTR_LSC,
+                    Some(lsc_trampoline),
+                    self.handler as *mut c_void,
+                )
+            };
+            // -EAGAIN: the callback is running right now; wait for it to return
+            if ret != -(Errno::EAGAIN as i32) {
+                break;
+            }
+            std::thread::yield_now();
+        }
+        drop(unsafe { Box::from_raw(self.handler) });
+    }
+}
+
+/// Link changes delivered through a channel, for polling from lcore code.
+///
+/// Returned by [`EthDev::link_events`]. Dropping it unregisters the callback.
+pub struct LinkEvents {
+    rx: mpsc::Receiver<LinkStatus>,
+    _callback: LinkCallback,
+}
+
+impl LinkEvents {
+    /// Take the next pending change without blocking.
+    pub fn try_recv(&self) -> Option<LinkStatus> {
+        self.rx.try_recv().ok()
+    }
+
+    /// Drain pending changes and return the most recent one.
+    pub fn latest(&self) -> Option<LinkStatus> {
+        self.rx.try_iter().last()
+    }
+}
+
+impl EthDev {
+    /// Read the current link state without waiting for autonegotiation.
+    pub fn link_status(&self) -> Result<LinkStatus> {
+        let mut speed = 0u32;
+        let mut up = 0;
+        let mut full_duplex = 0;
+        let ret = unsafe {
+            ffi::rust_eth_link_get_nowait(self.port_id(), &mut speed, &mut up, &mut full_duplex)
+        };
+        check_rte_success(ret)?;
+        if up == 0 {
+            return Ok(LinkStatus::DOWN);
+        }
+        Ok(LinkStatus {
+            up: true,
+            speed_mbps: speed,
+            full_duplex: full_duplex != 0,
+        })
+    }
+
+    /// Block until the link is up, for at most `timeout`.
+    ///
+    /// Returns the link state once it is up, or `ETIMEDOUT`. The device must
+    /// be started. The link is polled every 100 ms with the non-waiting
+    /// query: `rte_eth_link_get` can block for seconds per call on some
+    /// drivers, which would overshoot `timeout`. This blocks the calling
+    /// thread, so call it before lcores start serving, not from a reactor.
+    pub fn wait_for_link(&self, timeout: Duration) -> Result<LinkStatus> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let status = self.link_status()?;
+            if status.up {
+                return Ok(status);
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(Errno::ETIMEDOUT);
+            }
+            std::thread::sleep(LINK_POLL_INTERVAL.min(deadline - now));
+        }
+    }
+
+    /// Call `handler` with the new link state each time the link goes up or
+    /// down.
+    ///
+    /// The device must be configured with
+    /// [`EthConf::lsc_interrupt`](super::eth::EthConf::lsc_interrupt), or the
+    /// callback never fires. `handler` runs on DPDK's interrupt thread: keep
+    /// it short and non-blocking, and do not reach into a reactor from it. A
+    /// panic in `handler` is caught and the event dropped.
+    ///
+    /// The callback stays registered until the returned guard is dropped.
+    pub fn on_link_change<F>(&self, handler: F) -> Result<LinkCallback>
+    where
+        F: Fn(LinkStatus) + Send + Sync + 'static,
+    {
+        let handler: *mut LinkHandler = Box::into_raw(Box::new(Box::new(handler)));
+        let ret = unsafe {
+            ffi::rte_eth_dev_callback_register(
+                self.port_id(),
+                ffi::RUST_RTE_ETH_EVENT_INTR_LSC,
+                Some(lsc_trampoline),
+                handler as *mut c_void,
+            )
+        };
+        if let Err(e) = check_rte_success(ret) {
+            drop(unsafe { Box::from_raw(handler) });
+            return Err(e);
+        }
+        Ok(LinkCallback {
+            port_id: self.port_id(),
+            handler,
+        })
+    }
+
+    /// Deliver link changes through a channel.
+    ///
+    /// A reactor task can poll [`LinkEvents::try_recv`] on each pass (or on a
+    /// timer) to pause accepting or fail over when the link drops. The same
+    /// [`EthConf::lsc_interrupt`](super::eth::EthConf::lsc_interrupt)
+    /// requirement as [`on_link_change`](Self::on_link_change) applies.
+    pub fn link_events(&self) -> Result<LinkEvents> {
+        let (tx, rx) = mpsc::channel();
+        let callback = self.on_link_change(move |status| {
+            // The receiver being gone only means nobody is listening anymore
+            let _ = tx.send(status);
+        })?;
+        Ok(LinkEvents {
+            rx,
+            _callback: callback,
+        })
+    }
+}
+
+/// Entry point DPDK calls for LSC events.
+unsafe extern "C" fn lsc_trampoline(
+    port_id: u16,
+    _event: ffi::rte_eth_event_type,
+    cb_arg: *mut c_void,
+    _ret_param: *mut c_void,
+) -> c_int {
+    let handler = unsafe { &*(cb_arg as *const LinkHandler) };
+    let status = EthDev::new(port_id)
+        .link_status()
+        .unwrap_or(LinkStatus::DOWN);
+    // Unwinding into DPDK's interrupt thread would abort the process
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| handler(status)));
+    0
+}
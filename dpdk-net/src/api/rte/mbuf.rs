@@ -173,6 +173,24 @@ impl Mbuf {
         unsafe { ffi::rust_pktmbuf_reset(self.inner.as_ptr()) }
     }
 
+    /// Get the offload flags (`RTE_MBUF_F_*`), e.g. the RX checksum status.
+    #[inline]
+    pub fn ol_flags(&self) -> u64 {
+        unsafe { ffi::rust_pktmbuf_ol_flags(self.inner.as_ptr()) }
+    }
+
+    /// Ask the NIC to fill in the IPv4 header and TCP/UDP checksums of the
+    /// frame in this mbuf.
+    ///
+    /// Sets the header lengths and TX offload flags, zeroes the IPv4 header
+    /// checksum and seeds the L4 checksum with the pseudo-header sum. Call it
+    /// once the frame is complete. Non-IP frames are left untouched. The port
+    /// must have been configured with the matching TX offloads.
+    #[inline]
+    pub fn request_tx_checksum_offload(&mut self) {
+        unsafe { ffi::rust_pktmbuf_tx_cksum_offload(self.inner.as_ptr()) }
+    }
+
     /// Extend the data length by `len` bytes (unsafe - doesn't check bounds).
     ///
     /// # Safety
@@ -8,8 +8,18 @@ pub mod pktmbuf;
 
 pub mod eth;
 
+pub mod flow;
+
+pub mod link;
+
 pub mod mbuf;
 
 pub mod queue;
 
+pub mod reta;
+
+pub mod service;
+
+pub mod stats;
+
 pub mod thread;
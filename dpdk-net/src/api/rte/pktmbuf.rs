@@ -3,9 +3,19 @@
 
 use std::ffi::CString;
 use std::ptr::NonNull;
+use std::sync::atomic::{AtomicU32, Ordering};
 
 use dpdk_net_sys::ffi;
 
+/// Longest pool name DPDK accepts, excluding the NUL.
+///
+/// `RTE_MEMPOOL_NAMESIZE` is the 32-byte memzone name minus the `MP_` prefix
+/// the pool's memzone gets.
+pub const MEMPOOL_NAME_MAX: usize = 28;
+
+/// Suffix counter for [`MemPool::unique_name`].
+static UNIQUE_POOL_ID: AtomicU32 = AtomicU32::new(0);
+
 /// Wrapper for DPDK rte_mempool for packet mbufs (owning)
 pub struct MemPool {
     inner: NonNull<ffi::rte_mempool>,
@@ -107,7 +117,17 @@ impl MemPoolConfig {
 
     /// Set the per-core cache size.
     ///
-    /// Set to 0 to disable caching. Should be less than num_mbufs / 1.5.
+    /// Each lcore keeps up to this many mbufs (briefly up to 1.5x) in a
+    /// private cache, so most allocs and frees never touch the shared ring.
+    /// With several queues on one pool a small cache turns every burst into
+    /// ring contention; keep it at least a few RX bursts deep (the default
+    /// 256 suits most servers). The cached mbufs are unavailable to other
+    /// lcores, so size the pool for `queues * (rx_desc + tx_desc +
+    /// 1.5 * cache_size)` plus in-flight packets.
+    ///
+    /// Set to 0 to disable caching. [`MemPool::create`] rejects values above
+    /// `RTE_MEMPOOL_CACHE_MAX_SIZE` or above `num_mbufs / 1.5`; see
+    /// [`validate`](Self::validate).
     pub fn cache_size(mut self, size: u32) -> Self {
         self.cache_size = size;
         self
@@ -134,6 +154,21 @@ impl MemPoolConfig {
         self.socket_id = id;
         self
     }
+
+    /// Check the cache size against DPDK's limits.
+    ///
+    /// Returns `EINVAL` if `cache_size` exceeds `RTE_MEMPOOL_CACHE_MAX_SIZE`,
+    /// or if its flush threshold (1.5x) exceeds `num_mbufs`, the same checks
+    /// `rte_mempool_create` applies.
+    pub fn validate(&self) -> crate::api::Result<()> {
+        let flush_threshold = self.cache_size as u64 * 3 / 2;
+        if self.cache_size > ffi::RTE_MEMPOOL_CACHE_MAX_SIZE
+            || flush_threshold > self.num_mbufs as u64
+        {
+            return Err(nix::errno::Errno::EINVAL);
+        }
+        Ok(())
+    }
 }
 
 impl MemPool {
@@ -142,10 +177,20 @@ impl MemPool {
     /// # Arguments
     /// * `name` - Pool name (anything convertible to CString)
     /// * `config` - Pool configuration
+    ///
+    /// Fails with `EINVAL` if the config does not pass
+    /// [`MemPoolConfig::validate`].
+    ///
+    /// Fails with `EEXIST` if a pool with this name already exists. Besides a
+    /// second `create` in the same process, that happens when a previous run
+    /// without `--in-memory` exited without EAL cleanup and left the pool in
+    /// its hugepage files under the same file prefix. Pass a name from
+    /// [`MemPool::unique_name`], or give each run its own EAL file prefix.
     pub fn create<S>(name: S, config: &MemPoolConfig) -> crate::api::Result<Self>
     where
         S: Into<Vec<u8>>,
     {
+        config.validate()?;
         let c_name = CString::new(name).map_err(|_| nix::errno::Errno::EINVAL)?;
         let ptr = unsafe {
             ffi::rte_pktmbuf_pool_create(
@@ -162,6 +207,23 @@ impl MemPool {
             .ok_or_else(crate::api::rte_errno)
     }
 
+    /// Build a pool name that no other run or pool is using: `prefix`, the
+    /// process ID and a per-process counter.
+    ///
+    /// `prefix` is shortened as needed to fit [`MEMPOOL_NAME_MAX`].
+    pub fn unique_name(prefix: &str) -> String {
+        let id = UNIQUE_POOL_ID.fetch_add(1, Ordering::Relaxed);
+        let suffix = format!("_{}_{}", std::process::id(), id);
+        let keep = MEMPOOL_NAME_MAX.saturating_sub(suffix.len());
+        let end = prefix
+            .char_indices()
+            .map(|(i, c)| i + c.len_utf8())
+            .take_while(|&end| end <= keep)
+            .last()
+            .unwrap_or(0);
+        format!("{}{}", &prefix[..end], suffix)
+    }
+
     /// Create a mempool with default configuration
     pub fn create_default<S>(name: S, num_mbufs: u32) -> crate::api::Result<Self>
     where
@@ -202,6 +264,13 @@ impl MemPool {
         unsafe { ffi::rte_mempool_avail_count(self.inner.as_ptr()) }
     }
 
+    /// Get the number of objects allocated from the pool, including those
+    /// held in per-lcore caches
+    #[inline]
+    pub fn in_use_count(&self) -> u32 {
+        unsafe { ffi::rte_mempool_in_use_count(self.inner.as_ptr()) }
+    }
+
     /// Try to allocate an mbuf from this pool.
     ///
     /// Returns `None` if the pool is exhausted.
@@ -234,6 +303,27 @@ impl MemPool {
         count
     }
 
+    /// Fault in the pool's memory by writing every mbuf's data room once.
+    ///
+    /// Pool memory is mapped lazily, so without this the first packets into
+    /// each mbuf pay for page faults. Allocates every free mbuf, zeroes its
+    /// data room and frees it again, returning how many were touched.
+    ///
+    /// Call this right after creating the pool, before it is handed to an
+    /// ethdev: while it runs the pool is empty, and mbufs already sitting in
+    /// RX rings or other lcores' caches are not reached.
+    pub fn warm(&self) -> u32 {
+        let mut mbufs = Vec::with_capacity(self.avail_count() as usize);
+        while let Some(mut mbuf) = self.try_alloc() {
+            let room = mbuf.tailroom();
+            if let Some(data) = mbuf.append(room) {
+                data.fill(0);
+            }
+            mbufs.push(mbuf);
+        }
+        mbufs.len() as u32
+    }
+
     /// Get the data room size for mbufs in this pool.
     #[inline]
     pub fn data_room_size(&self) -> u16 {
@@ -248,3 +338,35 @@ impl Drop for MemPool {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unique_name() {
+        let a = MemPool::unique_name("dpdk_app_pool");
+        let b = MemPool::unique_name("dpdk_app_pool");
+        assert_ne!(a, b);
+        assert!(a.starts_with(&format!("dpdk_app_pool_{}_", std::process::id())));
+
+        let long = MemPool::unique_name(&"p".repeat(64));
+        assert!(long.len() <= MEMPOOL_NAME_MAX);
+        assert!(long.starts_with('p'));
+    }
+
+    #[test]
+    fn test_config_cache_size_limits() {
+        assert!(MemPoolConfig::new().validate().is_ok());
+        assert!(MemPoolConfig::new().cache_size(0).validate().is_ok());
+
+        let max = ffi::RTE_MEMPOOL_CACHE_MAX_SIZE;
+        assert!(MemPoolConfig::new().cache_size(max).validate().is_ok());
+        assert!(MemPoolConfig::new().cache_size(max + 1).validate().is_err());
+
+        // The flush threshold (1.5x the cache) must fit in the pool
+        let config = MemPoolConfig::new().num_mbufs(300);
+        assert!(config.clone().cache_size(200).validate().is_ok());
+        assert!(config.cache_size(201).validate().is_err());
+    }
+}
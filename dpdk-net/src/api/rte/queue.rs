@@ -43,7 +43,20 @@ impl RxQueue {
     /// Packets are appended to the `mbufs` vector (up to its remaining capacity).
     #[inline]
     pub fn rx<const N: usize>(&self, mbufs: &mut arrayvec::ArrayVec<Mbuf, N>) -> usize {
-        let capacity = mbufs.capacity() - mbufs.len();
+        self.rx_up_to(mbufs, MAX_BURST_SIZE)
+    }
+
+    /// Receive a burst of at most `max` packets into the provided buffer.
+    ///
+    /// Like [`rx`](Self::rx), but smaller bursts leave room in `mbufs` and
+    /// hand the driver fewer descriptors per call.
+    #[inline]
+    pub fn rx_up_to<const N: usize>(
+        &self,
+        mbufs: &mut arrayvec::ArrayVec<Mbuf, N>,
+        max: usize,
+    ) -> usize {
+        let capacity = (mbufs.capacity() - mbufs.len()).min(max);
         if capacity == 0 {
             return 0;
         }
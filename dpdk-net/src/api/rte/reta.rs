@@ -0,0 +1,185 @@
+// RSS redirection table (RETA) as data
+// See rte_eth_dev_rss_reta_update and rte_eth_dev_rss_reta_query in rte_ethdev.h
+//
+// A RETA maps the low bits of the RSS hash to an RX queue. Loading a fixed
+// table (instead of the round-robin one `EthDevBuilder` programs) pins which
+// flows land on which queue, so sharding is the same on every run and machine.
+// With the `serde` feature, `RetaTable` (de)serializes as a plain array of
+// queue IDs, e.g. `[0, 1, 0, 1, ...]` in JSON.
+
+use nix::errno::Errno;
+use tracing::{error, warn};
+
+use super::eth::EthDev;
+use crate::api::Result;
+
+/// Contents of an RSS redirection table: entry `i` is the RX queue for hash
+/// bucket `i`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct RetaTable {
+    entries: Vec<u16>,
+}
+
+impl RetaTable {
+    /// Wrap a list of queue IDs.
+    pub fn new(entries: Vec<u16>) -> Self {
+        Self { entries }
+    }
+
+    /// The table `EthDevBuilder` programs: bucket `i` goes to queue
+    /// `i % nb_rx_queues`.
+    pub fn round_robin(reta_size: u16, nb_rx_queues: u16) -> Self {
+        let nb_rx_queues = nb_rx_queues.max(1);
+        Self::new((0..reta_size).map(|i| i % nb_rx_queues).collect())
+    }
+
+    /// Queue IDs, one per bucket.
+    pub fn entries(&self) -> &[u16] {
+        &self.entries
+    }
+
+    /// Number of buckets.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the table has no buckets.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Buckets mapped to each queue, indexed by queue ID.
+    pub fn queue_counts(&self, nb_rx_queues: u16) -> Vec<usize> {
+        let mut counts = vec![0; nb_rx_queues as usize];
+        for &q in &self.entries {
+            if let Some(c) = counts.get_mut(q as usize) {
+                *c += 1;
+            }
+        }
+        counts
+    }
+
+    /// Check the table fits a device with `reta_size` buckets and
+    /// `nb_rx_queues` configured RX queues.
+    pub fn validate(&self, reta_size: u16, nb_rx_queues: u16) -> Result<()> {
+        validate_entries(&self.entries, reta_size, nb_rx_queues)
+    }
+
+    /// Bucket indices where `self` and `other` differ, including buckets
+    /// present in only one of them.
+    pub fn mismatches(&self, other: &[u16]) -> Vec<usize> {
+        let len = self.entries.len().max(other.len());
+        (0..len)
+            .filter(|&i| self.entries.get(i) != other.get(i))
+            .collect()
+    }
+}
+
+impl From<Vec<u16>> for RetaTable {
+    fn from(entries: Vec<u16>) -> Self {
+        Self::new(entries)
+    }
+}
+
+fn validate_entries(entries: &[u16], reta_size: u16, nb_rx_queues: u16) -> Result<()> {
+    if entries.len() != reta_size as usize {
+        error!(
+            len = entries.len(),
+            reta_size, "RETA table size does not match the device"
+        );
+        return Err(Errno::EINVAL);
+    }
+    if let Some((bucket, &queue)) = entries
+        .iter()
+        .enumerate()
+        .find(|&(_, &q)| q >= nb_rx_queues)
+    {
+        error!(
+            bucket,
+            queue, nb_rx_queues, "RETA entry names a queue that is not configured"
+        );
+        return Err(Errno::EINVAL);
+    }
+    Ok(())
+}
+
+impl EthDev {
+    /// Read the RSS RETA as a [`RetaTable`].
+    ///
+    /// The table is empty if the device has no RETA.
+    pub fn rss_reta_table(&self) -> Result<RetaTable> {
+        self.query_rss_reta().map(RetaTable::new)
+    }
+
+    /// Program the RSS RETA with `entries` and verify the driver kept them.
+    ///
+    /// `entries` must have exactly `reta_size` entries, each below the
+    /// number of configured RX queues; otherwise `EINVAL` is returned before
+    /// the device is touched. A device without a RETA fails with `ENOTSUP`.
+    ///
+    /// After the update the table is read back. Some drivers accept an update
+    /// and quietly store something else; if the read-back differs from
+    /// `entries`, the differing buckets are logged and `EIO` is returned.
+    ///
+    /// Call it after the device is configured (e.g. after
+    /// [`EthDevBuilder::build`](super::eth::EthDevBuilder::build), which
+    /// programs a round-robin table first).
+    pub fn apply_reta_from(&self, entries: &[u16]) -> Result<()> {
+        let info = self.info()?;
+        if info.reta_size == 0 {
+            error!(port_id = self.port_id(), "Device has no RSS RETA");
+            return Err(Errno::ENOTSUP);
+        }
+        validate_entries(entries, info.reta_size, info.nb_rx_queues)?;
+
+        self.set_rss_reta(entries)?;
+
+        let applied = self.query_rss_reta()?;
+        let mismatches = RetaTable::new(applied.clone()).mismatches(entries);
+        if let Some(&first) = mismatches.first() {
+            warn!(
+                port_id = self.port_id(),
+                count = mismatches.len(),
+                first_bucket = first,
+                expected = entries.get(first),
+                actual = applied.get(first),
+                "Driver altered the RSS RETA"
+            );
+            return Err(Errno::EIO);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_robin_matches_builder() {
+        let table = RetaTable::round_robin(8, 3);
+        assert_eq!(table.entries(), &[0, 1, 2, 0, 1, 2, 0, 1]);
+        assert_eq!(table.queue_counts(3), vec![3, 3, 2]);
+    }
+
+    #[test]
+    fn test_validate() {
+        let table = RetaTable::new(vec![0, 1, 1, 0]);
+        assert!(table.validate(4, 2).is_ok());
+        assert_eq!(table.validate(8, 2), Err(Errno::EINVAL));
+        assert_eq!(table.validate(4, 1), Err(Errno::EINVAL));
+    }
+
+    #[test]
+    fn test_mismatches() {
+        let table = RetaTable::new(vec![0, 1, 2, 3]);
+        assert!(table.mismatches(&[0, 1, 2, 3]).is_empty());
+        assert_eq!(table.mismatches(&[0, 0, 2, 0]), vec![1, 3]);
+        assert_eq!(table.mismatches(&[0, 1]), vec![2, 3]);
+    }
+}
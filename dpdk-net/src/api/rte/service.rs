@@ -0,0 +1,270 @@
+//! Service cores.
+//!
+//! A DPDK service is a callback that the EAL runs over and over on the
+//! service lcores it is mapped to. Periodic housekeeping (stats aggregation,
+//! ARP refresh, ...) can run there instead of taking cycles from a worker's
+//! reactor.
+//!
+//! A service runs once three things hold: it is registered
+//! ([`Service::register`]), started ([`Service::start`]), and mapped to a
+//! started [`ServiceCore`].
+//!
+//! # Example
+//!
+//! ```no_run
+//! use dpdk_net::api::rte::lcore::Lcore;
+//! use dpdk_net::api::rte::service::{Service, ServiceCore};
+//!
+//! let service = Service::register("stats", || {
+//!     // aggregate counters...
+//! })
+//! .unwrap();
+//!
+//! // Take the last worker out of the worker pool
+//! let lcore = Lcore::workers().last().unwrap();
+//! let core = ServiceCore::add(lcore).unwrap();
+//! core.map(&service).unwrap();
+//! service.start().unwrap();
+//! core.start().unwrap();
+//!
+//! // ... later
+//! service.stop().unwrap();
+//! core.stop().unwrap();
+//! ```
+
+use std::ffi::c_void;
+use std::panic::{self, AssertUnwindSafe};
+
+use dpdk_net_sys::ffi;
+use nix::errno::Errno;
+
+use super::lcore::{Lcore, Role};
+use crate::api::Result;
+
+/// Longest service name, in bytes, DPDK accepts.
+pub const SERVICE_NAME_MAX: usize = ffi::RTE_SERVICE_NAME_MAX as usize - 1;
+
+type ServiceFn = Box<dyn FnMut() + Send>;
+
+/// Map a DPDK service return code (`0` or a negative errno) to a result.
+fn check(ret: i32) -> Result<()> {
+    if ret < 0 {
+        Err(Errno::from_raw(-ret))
+    } else {
+        Ok(())
+    }
+}
+
+/// A registered service; unregisters on drop.
+///
+/// The callback is never run by two lcores at once: the service is not
+/// registered as multi-thread safe, so DPDK serializes it even when it is
+/// mapped to several service cores.
+pub struct Service {
+    id: u32,
+    func: *mut ServiceFn,
+}
+
+// The callback is Send; the pointer is only freed once DPDK no longer
+// calls it.
+unsafe impl Send for Service {}
+
+impl Service {
+    /// Register `func` as a service named `name`.
+    ///
+    /// The service starts out stopped and mapped to no lcore. A panic in
+    /// `func` is caught, so the service keeps running.
+    ///
+    /// Fails with `EINVAL` if `name` is empty, longer than
+    /// [`SERVICE_NAME_MAX`] or contains a NUL byte, and with `ENOSPC` if
+    /// DPDK's service table is full.
+    pub fn register<F>(name: &str, func: F) -> Result<Self>
+    where
+        F: FnMut() + Send + 'static,
+    {
+        if name.is_empty() || name.len() > SERVICE_NAME_MAX || name.contains('\0') {
+            return Err(Errno::EINVAL);
+        }
+
+        let func: *mut ServiceFn = Box::into_raw(Box::new(Box::new(func)));
+        let mut spec: ffi::rte_service_spec = unsafe { std::mem::zeroed() };
+        for (dst, &src) in spec.name.iter_mut().zip(name.as_bytes()) {
+            *dst = src as _;
+        }
+        spec.callback = Some(service_trampoline);
+        spec.callback_userdata = func as *mut c_void;
+        spec.socket_id = -1; // SOCKET_ID_ANY
+
+        let mut id = 0u32;
+        let ret = unsafe { ffi::rte_service_component_register(&spec, &mut id) };
+        if let Err(e) = check(ret) {
+            drop(unsafe { Box::from_raw(func) });
+            return Err(e);
+        }
+        // The component is ready; whether it runs is up to start()/stop()
+        unsafe { ffi::rte_service_component_runstate_set(id, 1) };
+        Ok(Service { id, func })
+    }
+
+    /// DPDK's ID for this service.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Let the service run on the service cores it is mapped to.
+    pub fn start(&self) -> Result<()> {
+        check(unsafe { ffi::rte_service_runstate_set(self.id, 1) })
+    }
+
+    /// Stop scheduling the service.
+    ///
+    /// A call already in progress on a service core finishes; see
+    /// [`may_be_active`](Self::may_be_active).
+    pub fn stop(&self) -> Result<()> {
+        check(unsafe { ffi::rte_service_runstate_set(self.id, 0) })
+    }
+
+    /// Returns true if the service is started.
+    pub fn is_running(&self) -> bool {
+        unsafe { ffi::rte_service_runstate_get(self.id) == 1 }
+    }
+
+    /// Returns true if a service core may be running the callback right now.
+    pub fn may_be_active(&self) -> bool {
+        unsafe { ffi::rte_service_may_be_active(self.id) == 1 }
+    }
+
+    /// Map the service to (`enable`) or unmap it from a service lcore.
+    ///
+    /// Fails with `EINVAL` if `lcore` is not a service core.
+    pub fn map_lcore(&self, lcore: Lcore, enable: bool) -> Result<()> {
+        check(unsafe { ffi::rte_service_map_lcore_set(self.id, lcore.id(), enable as u32) })
+    }
+
+    /// Run the callback once on the calling thread.
+    ///
+    /// Useful without service cores, e.g. from a reactor loop or a test.
+    /// The service must be started. Fails with `EBUSY` if a service core is
+    /// running it at the same moment.
+    pub fn run_once(&self) -> Result<()> {
+        check(unsafe { ffi::rte_service_run_iter_on_app_lcore(self.id, 1) })
+    }
+}
+
+impl Drop for Service {
+    fn drop(&mut self) {
+        unsafe { ffi::rte_service_runstate_set(self.id, 0) };
+        // Wait out a call in progress before the callback is freed
+        while self.may_be_active() {
+            std::thread::yield_now();
+        }
+        unsafe {
+            ffi::rte_service_component_runstate_set(self.id, 0);
+            ffi::rte_service_component_unregister(self.id);
+        }
+        drop(unsafe { Box::from_raw(self.func) });
+    }
+}
+
+unsafe extern "C" fn service_trampoline(args: *mut c_void) -> i32 {
+    let func = unsafe { &mut *(args as *mut ServiceFn) };
+    // Unwinding into the EAL's service loop would abort the process
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| func()));
+    0
+}
+
+/// An lcore dedicated to running services.
+///
+/// Adding an lcore takes it out of the worker pool: it no longer shows up
+/// in [`Lcore::workers`] and cannot be launched on until the `ServiceCore`
+/// is dropped, which stops it and hands it back as a worker.
+pub struct ServiceCore {
+    lcore: Lcore,
+}
+
+impl ServiceCore {
+    /// Turn `lcore` into a service core.
+    ///
+    /// `lcore` must be an idle worker; the main lcore cannot be used. Fails
+    /// with `EALREADY` if it already is a service core.
+    pub fn add(lcore: Lcore) -> Result<Self> {
+        if lcore.is_main() {
+            return Err(Errno::EINVAL);
+        }
+        check(unsafe { ffi::rte_service_lcore_add(lcore.id()) })?;
+        Ok(ServiceCore { lcore })
+    }
+
+    /// The lcore this service core runs on.
+    pub fn lcore(&self) -> Lcore {
+        self.lcore
+    }
+
+    /// Number of service cores.
+    pub fn count() -> u32 {
+        unsafe { ffi::rte_service_lcore_count() as u32 }
+    }
+
+    /// Run `service` on this core; see [`Service::map_lcore`].
+    pub fn map(&self, service: &Service) -> Result<()> {
+        service.map_lcore(self.lcore, true)
+    }
+
+    /// Stop running `service` on this core.
+    pub fn unmap(&self, service: &Service) -> Result<()> {
+        service.map_lcore(self.lcore, false)
+    }
+
+    /// Start the lcore's service loop.
+    ///
+    /// Fails with `EALREADY` if it is already running.
+    pub fn start(&self) -> Result<()> {
+        check(unsafe { ffi::rte_service_lcore_start(self.lcore.id()) })
+    }
+
+    /// Stop the lcore's service loop and wait for it to return.
+    ///
+    /// Fails with `EBUSY` while a started service is mapped to this core
+    /// only: stop the service (or map it elsewhere) first.
+    pub fn stop(&self) -> Result<()> {
+        check(unsafe { ffi::rte_service_lcore_stop(self.lcore.id()) })?;
+        self.lcore.wait();
+        Ok(())
+    }
+}
+
+impl Lcore {
+    /// Register `func` as a service; see [`Service::register`].
+    ///
+    /// Run it on a [`ServiceCore`] to keep the work off the workers.
+    pub fn register_service<F>(name: &str, func: F) -> Result<Service>
+    where
+        F: FnMut() + Send + 'static,
+    {
+        Service::register(name, func)
+    }
+
+    /// Returns true if this lcore is a service core.
+    pub fn is_service_core(&self) -> bool {
+        self.role() == Role::Service
+    }
+}
+
+impl Drop for ServiceCore {
+    fn drop(&mut self) {
+        let id = self.lcore.id();
+        // A core never started, or already stopped, reports EALREADY
+        let ret = unsafe { ffi::rte_service_lcore_stop(id) };
+        if ret == -(Errno::EBUSY as i32) {
+            tracing::warn!(
+                lcore = id,
+                "Service core still runs a started service, leaving it in place"
+            );
+            return;
+        }
+        self.lcore.wait();
+        if let Err(e) = check(unsafe { ffi::rte_service_lcore_del(id) }) {
+            tracing::warn!(lcore = id, error = %e, "Failed to return service core to workers");
+        }
+    }
+}
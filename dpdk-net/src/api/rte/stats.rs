@@ -0,0 +1,331 @@
+// Typed Ethernet device statistics and derived rates
+// See rte_eth_stats and rte_eth_xstats_get in /usr/local/include/rte_ethdev.h
+
+use std::ffi::c_char;
+use std::time::{Duration, Instant};
+
+use dpdk_net_sys::ffi;
+use nix::errno::Errno;
+
+use super::eth::{EthDev, PortId};
+use crate::api::Result;
+
+/// Cumulative port counters, as returned by [`EthDev::stats_typed`].
+///
+/// Per-queue counters of `rte_eth_stats` are not included; use
+/// [`EthDev::stats`] for those.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EthStats {
+    /// Packets received.
+    pub rx_packets: u64,
+    /// Packets transmitted.
+    pub tx_packets: u64,
+    /// Bytes received.
+    pub rx_bytes: u64,
+    /// Bytes transmitted.
+    pub tx_bytes: u64,
+    /// RX packets dropped by the hardware (RX queues full).
+    pub rx_missed: u64,
+    /// Erroneous received packets.
+    pub rx_errors: u64,
+    /// Failed transmissions.
+    pub tx_errors: u64,
+    /// RX mbuf allocation failures.
+    pub rx_nombuf: u64,
+}
+
+impl From<&ffi::rte_eth_stats> for EthStats {
+    fn from(raw: &ffi::rte_eth_stats) -> Self {
+        Self {
+            rx_packets: raw.ipackets,
+            tx_packets: raw.opackets,
+            rx_bytes: raw.ibytes,
+            tx_bytes: raw.obytes,
+            rx_missed: raw.imissed,
+            rx_errors: raw.ierrors,
+            tx_errors: raw.oerrors,
+            rx_nombuf: raw.rx_nombuf,
+        }
+    }
+}
+
+/// Cumulative counters of one queue pair, from the per-queue arrays of
+/// `rte_eth_stats`.
+///
+/// DPDK only keeps `RTE_ETHDEV_QUEUE_STAT_CNTRS` of these per port, and not
+/// every PMD fills them in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueueStats {
+    /// Packets received on the RX queue.
+    pub rx_packets: u64,
+    /// Packets transmitted on the TX queue.
+    pub tx_packets: u64,
+    /// Bytes received on the RX queue.
+    pub rx_bytes: u64,
+    /// Bytes transmitted on the TX queue.
+    pub tx_bytes: u64,
+}
+
+impl QueueStats {
+    /// Extract the counters of `queue_id`, or `None` if DPDK does not track
+    /// a queue with that index.
+    pub fn from_raw(raw: &ffi::rte_eth_stats, queue_id: u16) -> Option<Self> {
+        let q = queue_id as usize;
+        if q >= ffi::RTE_ETHDEV_QUEUE_STAT_CNTRS as usize {
+            return None;
+        }
+        Some(Self {
+            rx_packets: raw.q_ipackets[q],
+            tx_packets: raw.q_opackets[q],
+            rx_bytes: raw.q_ibytes[q],
+            tx_bytes: raw.q_obytes[q],
+        })
+    }
+}
+
+/// Per-second rates computed from two [`EthStats`] samples.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EthRates {
+    /// Received packets per second.
+    pub rx_pps: f64,
+    /// Transmitted packets per second.
+    pub tx_pps: f64,
+    /// Received bits per second.
+    pub rx_bps: f64,
+    /// Transmitted bits per second.
+    pub tx_bps: f64,
+    /// Hardware RX drops per second.
+    pub rx_missed_pps: f64,
+    /// RX errors per second.
+    pub rx_errors_pps: f64,
+    /// TX errors per second.
+    pub tx_errors_pps: f64,
+    /// RX mbuf allocation failures per second.
+    pub rx_nombuf_pps: f64,
+}
+
+impl EthRates {
+    /// Compute rates between `prev` and `cur`, taken `elapsed` apart.
+    ///
+    /// A counter that went backwards (e.g. after a stats reset) yields a
+    /// rate of 0. A zero `elapsed` yields all-zero rates.
+    pub fn between(prev: &EthStats, cur: &EthStats, elapsed: Duration) -> Self {
+        let secs = elapsed.as_secs_f64();
+        if secs == 0.0 {
+            return Self::default();
+        }
+        let rate = |a: u64, b: u64| b.saturating_sub(a) as f64 / secs;
+        Self {
+            rx_pps: rate(prev.rx_packets, cur.rx_packets),
+            tx_pps: rate(prev.tx_packets, cur.tx_packets),
+            rx_bps: rate(prev.rx_bytes, cur.rx_bytes) * 8.0,
+            tx_bps: rate(prev.tx_bytes, cur.tx_bytes) * 8.0,
+            rx_missed_pps: rate(prev.rx_missed, cur.rx_missed),
+            rx_errors_pps: rate(prev.rx_errors, cur.rx_errors),
+            tx_errors_pps: rate(prev.tx_errors, cur.tx_errors),
+            rx_nombuf_pps: rate(prev.rx_nombuf, cur.rx_nombuf),
+        }
+    }
+
+    /// Fraction of arriving packets dropped by the hardware, in `0.0..=1.0`.
+    pub fn rx_drop_ratio(&self) -> f64 {
+        let arrived = self.rx_pps + self.rx_missed_pps;
+        if arrived == 0.0 {
+            0.0
+        } else {
+            self.rx_missed_pps / arrived
+        }
+    }
+}
+
+/// Periodically samples a port's counters and reports rates.
+///
+/// Each call to [`sample`](Self::sample) reads the counters and returns the
+/// rates since the previous call.
+///
+/// # Example
+///
+/// ```ignore
+/// let mut sampler = StatsSampler::new(0);
+/// sampler.sample()?; // first call establishes the baseline
+/// // ... some time later
+/// if let Some(rates) = sampler.sample()? {
+///     println!("rx {:.0} pps, {:.1} Mbit/s", rates.rx_pps, rates.rx_bps / 1e6);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct StatsSampler {
+    port_id: PortId,
+    last: Option<(EthStats, Instant)>,
+}
+
+impl StatsSampler {
+    /// Create a sampler for the given port. No sample is taken yet.
+    pub fn new(port_id: PortId) -> Self {
+        Self {
+            port_id,
+            last: None,
+        }
+    }
+
+    /// Read the counters and return the rates since the previous sample.
+    ///
+    /// Returns `Ok(None)` on the first call, which only records a baseline.
+    pub fn sample(&mut self) -> Result<Option<EthRates>> {
+        let stats = EthDev::new(self.port_id).stats_typed()?;
+        let now = Instant::now();
+        let rates = self
+            .last
+            .map(|(prev, at)| EthRates::between(&prev, &stats, now - at));
+        self.last = Some((stats, now));
+        Ok(rates)
+    }
+
+    /// The most recent sample, if any.
+    pub fn last(&self) -> Option<&EthStats> {
+        self.last.as_ref().map(|(stats, _)| stats)
+    }
+}
+
+impl EthDev {
+    /// Read the driver's extended statistics as `(name, value)` pairs.
+    ///
+    /// Names and their meaning are driver specific (`rx_missed_errors`,
+    /// `rx_q0_packets`, ...) and cover what `rte_eth_stats` leaves out, such
+    /// as per-queue counters beyond `RTE_ETHDEV_QUEUE_STAT_CNTRS` and the
+    /// reasons behind drops. Pairs come in the driver's order.
+    pub fn xstats(&self) -> Result<Vec<(String, u64)>> {
+        let port_id = self.port_id();
+        loop {
+            let n = xstat_count(unsafe {
+                ffi::rte_eth_xstats_get_names(port_id, std::ptr::null_mut(), 0)
+            })?;
+            let mut names: Vec<ffi::rte_eth_xstat_name> =
+                (0..n).map(|_| unsafe { std::mem::zeroed() }).collect();
+            let mut values: Vec<ffi::rte_eth_xstat> =
+                (0..n).map(|_| unsafe { std::mem::zeroed() }).collect();
+
+            let got_names = xstat_count(unsafe {
+                ffi::rte_eth_xstats_get_names(port_id, names.as_mut_ptr(), n as u32)
+            })?;
+            let got_values = xstat_count(unsafe {
+                ffi::rte_eth_xstats_get(port_id, values.as_mut_ptr(), n as u32)
+            })?;
+            // The driver grew its list in between (e.g. queues were added)
+            if got_names > n || got_values > n {
+                continue;
+            }
+
+            let names: Vec<String> = names[..got_names]
+                .iter()
+                .map(|name| xstat_name(&name.name))
+                .collect();
+            let values: Vec<(u64, u64)> = values[..got_values]
+                .iter()
+                .map(|xstat| (xstat.id, xstat.value))
+                .collect();
+            return Ok(pair_xstats(&names, &values));
+        }
+    }
+}
+
+/// The xstats calls return a count or a negative errno.
+fn xstat_count(ret: i32) -> Result<usize> {
+    if ret < 0 {
+        Err(Errno::from_raw(-ret))
+    } else {
+        Ok(ret as usize)
+    }
+}
+
+/// Decode a NUL-padded xstat name.
+fn xstat_name(raw: &[c_char]) -> String {
+    let bytes: Vec<u8> = raw
+        .iter()
+        .map(|&c| c as u8)
+        .take_while(|&b| b != 0)
+        .collect();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Match `(id, value)` pairs to `names`, indexed by id. Values with an id
+/// past the end of `names` are dropped.
+fn pair_xstats(names: &[String], values: &[(u64, u64)]) -> Vec<(String, u64)> {
+    values
+        .iter()
+        .filter_map(|&(id, value)| {
+            let name = names.get(usize::try_from(id).ok()?)?;
+            Some((name.clone(), value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rates_between_samples() {
+        let prev = EthStats {
+            rx_packets: 1_000,
+            tx_packets: 500,
+            rx_bytes: 64_000,
+            tx_bytes: 32_000,
+            rx_missed: 10,
+            ..EthStats::default()
+        };
+        let cur = EthStats {
+            rx_packets: 3_000,
+            tx_packets: 1_500,
+            rx_bytes: 192_000,
+            tx_bytes: 96_000,
+            rx_missed: 30,
+            ..EthStats::default()
+        };
+        let rates = EthRates::between(&prev, &cur, Duration::from_secs(2));
+        assert_eq!(rates.rx_pps, 1_000.0);
+        assert_eq!(rates.tx_pps, 500.0);
+        assert_eq!(rates.rx_bps, 512_000.0);
+        assert_eq!(rates.tx_bps, 256_000.0);
+        assert_eq!(rates.rx_missed_pps, 10.0);
+        assert!((rates.rx_drop_ratio() - 10.0 / 1_010.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_rates_after_reset_and_zero_elapsed() {
+        let prev = EthStats {
+            rx_packets: 5_000,
+            ..EthStats::default()
+        };
+        let cur = EthStats {
+            rx_packets: 100,
+            ..EthStats::default()
+        };
+        let rates = EthRates::between(&prev, &cur, Duration::from_secs(1));
+        assert_eq!(rates.rx_pps, 0.0);
+
+        let rates = EthRates::between(&EthStats::default(), &prev, Duration::ZERO);
+        assert_eq!(rates, EthRates::default());
+        assert_eq!(rates.rx_drop_ratio(), 0.0);
+    }
+
+    #[test]
+    fn test_xstats_decoding() {
+        let mut raw = [0 as c_char; 16];
+        for (dst, &b) in raw.iter_mut().zip(b"rx_good_packets") {
+            *dst = b as c_char;
+        }
+        assert_eq!(xstat_name(&raw), "rx_good_packets");
+
+        let names = ["rx_missed_errors".to_string(), "rx_q0_packets".to_string()];
+        assert_eq!(
+            pair_xstats(&names, &[(1, 7), (0, 3), (9, 1)]),
+            vec![
+                ("rx_q0_packets".to_string(), 7),
+                ("rx_missed_errors".to_string(), 3)
+            ]
+        );
+        assert_eq!(xstat_count(-(Errno::ENOTSUP as i32)), Err(Errno::ENOTSUP));
+        assert_eq!(xstat_count(4), Ok(4));
+    }
+}
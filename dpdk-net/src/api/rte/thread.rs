@@ -1,9 +1,11 @@
 // DPDK Thread Registration API
 // See: /usr/local/include/rte_thread.h
 
+use std::ffi::CString;
 use std::marker::PhantomData;
 
 use dpdk_net_sys::ffi;
+use nix::errno::Errno;
 use nix::sched::{CpuSet, sched_setaffinity};
 use nix::unistd::Pid;
 
@@ -33,6 +35,32 @@ pub fn set_cpu_affinity(cpu_id: usize) -> Result<(), nix::Error> {
     sched_setaffinity(Pid::from_raw(0), &cpu_set) // 0 = current thread
 }
 
+/// Longest thread name, in bytes, the kernel keeps.
+pub const THREAD_NAME_MAX: usize = 15;
+
+/// Set the current thread's name, as shown by `top -H`, `perf` and `gdb`.
+///
+/// Names longer than [`THREAD_NAME_MAX`] bytes are cut short (on a char
+/// boundary). Naming the main thread also renames the process in `ps`.
+///
+/// Fails with `EINVAL` if `name` contains a NUL byte.
+///
+/// # Example
+/// ```no_run
+/// use dpdk_net::api::rte::thread::set_thread_name;
+///
+/// set_thread_name("dpdk-q3").expect("Failed to name thread");
+/// ```
+pub fn set_thread_name(name: &str) -> crate::api::Result<()> {
+    let mut end = name.len().min(THREAD_NAME_MAX);
+    while !name.is_char_boundary(end) {
+        end -= 1;
+    }
+    let name = CString::new(&name[..end]).map_err(|_| Errno::EINVAL)?;
+    unsafe { ffi::rte_thread_set_name(ffi::rte_thread_self(), name.as_ptr()) };
+    Ok(())
+}
+
 /// RAII guard for DPDK thread registration.
 ///
 /// When a non-EAL thread (e.g., a Rust `std::thread` or tokio worker) needs to
@@ -86,6 +114,16 @@ impl ThreadRegistration {
         })
     }
 
+    /// Register the current thread with DPDK and name it `name`.
+    ///
+    /// Threads registered this way otherwise keep the name of whoever spawned
+    /// them; see [`set_thread_name`] for how `name` is applied.
+    pub fn with_name(name: &str) -> crate::api::Result<Self> {
+        let registration = Self::new()?;
+        set_thread_name(name)?;
+        Ok(registration)
+    }
+
     /// Try to register the current thread, returning None if already registered.
     ///
     /// This is useful when you're not sure if the thread is already an EAL thread
@@ -33,22 +33,51 @@ use std::collections::HashMap;
 use std::net::Ipv4Addr;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
 
 /// A MAC address (6 bytes).
 pub type MacAddress = [u8; 6];
 
+/// Default lifetime of a [`SharedArpCache`] entry without a fresh reply.
+pub const DEFAULT_ARP_TTL: Duration = Duration::from_secs(300);
+
+/// A learned neighbor: its MAC and when queue 0 last saw a reply for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArpEntry {
+    /// The peer's MAC address.
+    pub mac: MacAddress,
+    /// When the entry was inserted or last refreshed.
+    pub learned: Instant,
+}
+
+impl ArpEntry {
+    /// Whether the entry is older than `ttl` at `now`.
+    #[inline]
+    pub fn is_expired(&self, now: Instant, ttl: Duration) -> bool {
+        now.saturating_duration_since(self.learned) >= ttl
+    }
+}
+
 /// Thread-safe shared ARP cache using lock-free SPMC pattern.
 ///
 /// Optimized for single-producer (queue 0) multi-consumer (all queues):
 /// - Reads: Lock-free atomic load
 /// - Writes: Copy-on-write with atomic store (no concurrent writer synchronization)
 /// - Length: Relaxed atomic for eventual consistency (avoids Arc load on hot path)
+///
+/// Entries live for a TTL ([`DEFAULT_ARP_TTL`] unless set with
+/// [`with_ttl`](Self::with_ttl)) after the last reply that refreshed them.
+/// Expired entries read as absent, and queue 0 removes them with
+/// [`expire`](Self::expire) and asks for the peer again, so a MAC that
+/// changed (gateway failover, VRRP) is re-learned instead of injected forever.
 #[derive(Clone)]
 pub struct SharedArpCache {
-    inner: Arc<ArcSwap<HashMap<Ipv4Addr, MacAddress>>>,
-    /// Version counter that increments on every insert (even updates).
+    inner: Arc<ArcSwap<HashMap<Ipv4Addr, ArpEntry>>>,
+    /// Version counter that increments on every insert (even updates) and
+    /// every expiry.
     /// Used by consumers to detect any change, including MAC updates for existing IPs.
     version: Arc<AtomicUsize>,
+    ttl: Duration,
 }
 
 impl Default for SharedArpCache {
@@ -58,23 +87,37 @@ impl Default for SharedArpCache {
 }
 
 impl SharedArpCache {
-    /// Create a new empty shared ARP cache.
+    /// Create a new empty shared ARP cache with [`DEFAULT_ARP_TTL`].
     pub fn new() -> Self {
+        Self::with_ttl(DEFAULT_ARP_TTL)
+    }
+
+    /// Create a new empty shared ARP cache whose entries expire `ttl` after
+    /// their last refresh. `Duration::MAX` keeps them forever.
+    pub fn with_ttl(ttl: Duration) -> Self {
         Self {
             inner: Arc::new(ArcSwap::from_pointee(HashMap::new())),
             version: Arc::new(AtomicUsize::new(0)),
+            ttl,
         }
     }
 
-    /// Look up a MAC address for an IP.
+    /// How long entries live without a refresh.
+    #[inline]
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Look up the MAC address for an IP, unless its entry has expired.
     ///
     /// Lock-free: single atomic load.
     #[inline]
     pub fn get(&self, ip: &Ipv4Addr) -> Option<MacAddress> {
-        self.inner.load().get(ip).copied()
+        let entry = *self.inner.load().get(ip)?;
+        (!entry.is_expired(Instant::now(), self.ttl)).then_some(entry.mac)
     }
 
-    /// Insert or update a MAC address for an IP.
+    /// Insert or update a MAC address for an IP, restarting its TTL.
     ///
     /// SPMC optimization: Since only queue 0 writes, we use simple
     /// copy-on-write with atomic store (no rcu needed for concurrent writers).
@@ -85,37 +128,63 @@ impl SharedArpCache {
         // Load current map
         let current = self.inner.load();
 
-        // Check if already present with same value
-        // TODO: if number of queue is large, this is expensive.
-        if current.get(&ip) == Some(&mac) {
-            // MAC unchanged, but still bump version so consumers re-inject.
-            // This is needed because smoltcp's internal neighbor cache expires
-            // independently (60s) and needs periodic ARP refreshes.
-            self.version.fetch_add(1, Ordering::Release);
-            return;
-        }
-
-        // Copy-on-write: clone and update
+        // Copy-on-write: clone and update. Even an unchanged MAC is stored
+        // again, to refresh its timestamp.
+        // TODO: ARP replies are rare, but the copy grows with the cache.
         let mut new_map = (**current).clone();
-        new_map.insert(ip, mac);
+        new_map.insert(
+            ip,
+            ArpEntry {
+                mac,
+                learned: Instant::now(),
+            },
+        );
 
         // Atomic store - safe because we're the only writer (SPMC)
         self.inner.store(Arc::new(new_map));
 
-        // Always bump version so consumers know to re-inject
-        // (even if it was just an update of existing entry)
+        // Always bump version so consumers re-inject, even if the MAC is
+        // unchanged: smoltcp's internal neighbor cache expires independently
+        // (60s) and needs periodic ARP refreshes.
         self.version.fetch_add(1, Ordering::Release);
     }
 
-    /// Check if an IP is in the cache.
+    /// Remove the entries that have expired at `now` and return their IPs.
+    ///
+    /// Bumps the version if anything was removed. Queue 0 calls this
+    /// periodically and re-ARPs the returned peers.
+    ///
+    /// # Safety
+    /// Only call this from the single producer (queue 0).
+    pub fn expire(&self, now: Instant) -> Vec<Ipv4Addr> {
+        let current = self.inner.load();
+        let expired: Vec<Ipv4Addr> = current
+            .iter()
+            .filter(|(_, entry)| entry.is_expired(now, self.ttl))
+            .map(|(&ip, _)| ip)
+            .collect();
+        if expired.is_empty() {
+            return expired;
+        }
+
+        let mut new_map = (**current).clone();
+        for ip in &expired {
+            new_map.remove(ip);
+        }
+        self.inner.store(Arc::new(new_map));
+        self.version.fetch_add(1, Ordering::Release);
+        expired
+    }
+
+    /// Check if an IP is in the cache and not expired.
     ///
     /// Lock-free: single atomic load.
     #[inline]
     pub fn contains(&self, ip: &Ipv4Addr) -> bool {
-        self.inner.load().contains_key(ip)
+        self.get(ip).is_some()
     }
 
-    /// Get the version counter (increments on every insert/update).
+    /// Get the version counter (increments on every insert/update/expiry).
     ///
     /// Use this to detect changes including MAC updates for existing IPs.
     #[inline(always)]
@@ -129,11 +198,12 @@ impl SharedArpCache {
         self.inner.load().is_empty()
     }
 
-    /// Get a snapshot of all entries for iteration.
+    /// Get a snapshot of all entries for iteration, expired ones included
+    /// until queue 0 removes them; filter with [`ArpEntry::is_expired`].
     ///
     /// Lock-free: single atomic load, returns Arc to shared data.
     #[inline]
-    pub fn snapshot(&self) -> arc_swap::Guard<Arc<HashMap<Ipv4Addr, MacAddress>>> {
+    pub fn snapshot(&self) -> arc_swap::Guard<Arc<HashMap<Ipv4Addr, ArpEntry>>> {
         self.inner.load()
     }
 }
@@ -219,6 +289,40 @@ pub fn build_arp_reply_for_injection(
     packet
 }
 
+/// Build a broadcast ARP request asking for `target_ip`'s MAC.
+///
+/// # Arguments
+/// * `our_mac` - Our interface's MAC address
+/// * `our_ip` - Our interface's IP address
+/// * `target_ip` - The IP address to resolve
+///
+/// # Returns
+/// A complete Ethernet frame containing the ARP request.
+pub fn build_arp_request(our_mac: MacAddress, our_ip: Ipv4Addr, target_ip: Ipv4Addr) -> Vec<u8> {
+    let mut packet = vec![0u8; 42]; // Ethernet (14) + ARP (28)
+
+    // Ethernet header
+    packet[0..6].copy_from_slice(&[0xff; 6]); // Destination MAC (broadcast)
+    packet[6..12].copy_from_slice(&our_mac); // Source MAC (us)
+    packet[12..14].copy_from_slice(&[0x08, 0x06]); // EtherType: ARP
+
+    // ARP header
+    packet[14..16].copy_from_slice(&[0x00, 0x01]); // Hardware type: Ethernet
+    packet[16..18].copy_from_slice(&[0x08, 0x00]); // Protocol type: IPv4
+    packet[18] = 6; // Hardware address length
+    packet[19] = 4; // Protocol address length
+    packet[20..22].copy_from_slice(&[0x00, 0x01]); // Operation: ARP Request
+
+    // Sender (us) hardware and protocol address
+    packet[22..28].copy_from_slice(&our_mac);
+    packet[28..32].copy_from_slice(&our_ip.octets());
+
+    // Target hardware address unknown (left zero), protocol address
+    packet[38..42].copy_from_slice(&target_ip.octets());
+
+    packet
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -274,4 +378,58 @@ mod tests {
 
         assert!(parse_arp_reply(&packet).is_none());
     }
+
+    #[test]
+    fn test_arp_cache_expiry() {
+        let cache = SharedArpCache::with_ttl(Duration::from_secs(60));
+        let ip = Ipv4Addr::new(10, 0, 0, 1);
+        let mac = [0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc];
+
+        cache.insert(ip, mac);
+        let version = cache.version();
+        let learned = cache.snapshot()[&ip].learned;
+        assert!(cache.expire(learned + Duration::from_secs(30)).is_empty());
+        assert_eq!(cache.version(), version);
+
+        assert_eq!(cache.expire(learned + Duration::from_secs(60)), vec![ip]);
+        assert!(cache.version() > version);
+        assert!(cache.is_empty());
+        assert!(cache.get(&ip).is_none());
+    }
+
+    #[test]
+    fn test_arp_cache_refresh() {
+        let cache = SharedArpCache::with_ttl(Duration::ZERO);
+        let ip = Ipv4Addr::new(10, 0, 0, 1);
+        let mac = [0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc];
+
+        // A zero TTL expires entries as soon as they are read
+        cache.insert(ip, mac);
+        assert!(cache.get(&ip).is_none());
+
+        let cache = SharedArpCache::new();
+        cache.insert(ip, mac);
+        let first = cache.snapshot()[&ip];
+        let version = cache.version();
+        cache.insert(ip, mac);
+        assert!(cache.version() > version);
+        assert!(cache.snapshot()[&ip].learned >= first.learned);
+        assert_eq!(cache.get(&ip), Some(mac));
+    }
+
+    #[test]
+    fn test_build_arp_request() {
+        let our_mac = [0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff];
+        let packet = build_arp_request(
+            our_mac,
+            Ipv4Addr::new(10, 0, 0, 5),
+            Ipv4Addr::new(10, 0, 0, 1),
+        );
+        assert_eq!(&packet[0..6], &[0xff; 6]);
+        assert_eq!(&packet[20..22], &[0x00, 0x01]);
+        assert_eq!(&packet[22..28], &our_mac);
+        assert_eq!(&packet[38..42], &[10, 0, 0, 1]);
+        // Not mistaken for a reply by queue 0's scanner
+        assert!(parse_arp_reply(&packet).is_none());
+    }
 }
@@ -0,0 +1,273 @@
+//! Software checksum verification of received frames, for diagnostics and
+//! as the fallback when NIC checksum offload cannot vouch for a frame.
+//!
+//! Used by [`DpdkDevice::with_checksum_audit`](super::DpdkDevice::with_checksum_audit)
+//! to count corrupted frames independently of what smoltcp is told to verify,
+//! and by [`DpdkDevice::with_checksum_offload`](super::DpdkDevice::with_checksum_offload).
+
+use dpdk_net_sys::ffi;
+use smoltcp::phy::{Checksum, ChecksumCapabilities};
+use smoltcp::wire::{
+    EthernetFrame, EthernetProtocol, IpAddress, IpProtocol, Ipv4Packet, Ipv6Packet, TcpPacket,
+    UdpPacket,
+};
+
+use crate::api::rte::eth::ChecksumOffload;
+
+/// The checksum a received frame failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumFault {
+    /// IPv4 header checksum.
+    Ipv4,
+    /// TCP checksum (header and payload).
+    Tcp,
+    /// UDP checksum (header and payload).
+    Udp,
+}
+
+/// Verify the IPv4 header checksum and the TCP/UDP checksum of `frame`.
+///
+/// Returns `None` for frames that carry nothing to check: non-IP frames
+/// and frames too short or malformed to parse. L4 checksums of IPv4
+/// fragments and of IPv6 packets with extension headers are skipped. A UDP
+/// checksum of zero means "not computed" and passes.
+pub fn audit_frame(frame: &[u8]) -> Option<Result<(), ChecksumFault>> {
+    let eth = EthernetFrame::new_checked(frame).ok()?;
+    let (src, dst, next_header, payload) = match eth.ethertype() {
+        EthernetProtocol::Ipv4 => {
+            let ip = Ipv4Packet::new_checked(eth.payload()).ok()?;
+            if !ip.verify_checksum() {
+                return Some(Err(ChecksumFault::Ipv4));
+            }
+            if ip.more_frags() || ip.frag_offset() != 0 {
+                return Some(Ok(()));
+            }
+            (
+                IpAddress::Ipv4(ip.src_addr()),
+                IpAddress::Ipv4(ip.dst_addr()),
+                ip.next_header(),
+                ip.payload(),
+            )
+        }
+        EthernetProtocol::Ipv6 => {
+            let ip = Ipv6Packet::new_checked(eth.payload()).ok()?;
+            (
+                IpAddress::Ipv6(ip.src_addr()),
+                IpAddress::Ipv6(ip.dst_addr()),
+                ip.next_header(),
+                ip.payload(),
+            )
+        }
+        _ => return None,
+    };
+
+    match next_header {
+        IpProtocol::Tcp => {
+            let tcp = TcpPacket::new_checked(payload).ok()?;
+            if !tcp.verify_checksum(&src, &dst) {
+                return Some(Err(ChecksumFault::Tcp));
+            }
+        }
+        IpProtocol::Udp => {
+            let udp = UdpPacket::new_checked(payload).ok()?;
+            if !udp.verify_checksum(&src, &dst) {
+                return Some(Err(ChecksumFault::Udp));
+            }
+        }
+        _ => {}
+    }
+    Some(Ok(()))
+}
+
+/// What the NIC found when verifying a received frame's checksums.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NicChecksum {
+    /// IP and L4 checksums both verified good.
+    Good,
+    /// At least one checksum verified bad.
+    Bad,
+    /// Not fully verified: unknown protocol, IPv6 (no header checksum),
+    /// or a driver that did not look.
+    Unknown,
+}
+
+/// Read the RX checksum status from an mbuf's `ol_flags`.
+pub(crate) fn nic_checksum(ol_flags: u64) -> NicChecksum {
+    let ip = ol_flags & ffi::RUST_RTE_MBUF_F_RX_IP_CKSUM_MASK;
+    let l4 = ol_flags & ffi::RUST_RTE_MBUF_F_RX_L4_CKSUM_MASK;
+    if ip == ffi::RUST_RTE_MBUF_F_RX_IP_CKSUM_BAD || l4 == ffi::RUST_RTE_MBUF_F_RX_L4_CKSUM_BAD {
+        NicChecksum::Bad
+    } else if ip == ffi::RUST_RTE_MBUF_F_RX_IP_CKSUM_GOOD
+        && l4 == ffi::RUST_RTE_MBUF_F_RX_L4_CKSUM_GOOD
+    {
+        NicChecksum::Good
+    } else {
+        NicChecksum::Unknown
+    }
+}
+
+/// `caps` with the IPv4/TCP/UDP checksum work the NIC does removed, so
+/// smoltcp only does what is left in software.
+pub(crate) fn without_offloaded(
+    caps: &ChecksumCapabilities,
+    offload: ChecksumOffload,
+) -> ChecksumCapabilities {
+    let strip = |c: &Checksum| match (c.rx() && !offload.rx, c.tx() && !offload.tx) {
+        (true, true) => Checksum::Both,
+        (true, false) => Checksum::Rx,
+        (false, true) => Checksum::Tx,
+        (false, false) => Checksum::None,
+    };
+    let mut caps = caps.clone();
+    caps.ipv4 = strip(&caps.ipv4);
+    caps.tcp = strip(&caps.tcp);
+    caps.udp = strip(&caps.udp);
+    caps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use smoltcp::wire::{Ipv4Address, Ipv4Repr, Ipv6Address, Ipv6Repr, UdpRepr};
+
+    const ETH_LEN: usize = 14;
+    const IP_LEN: usize = 20;
+
+    fn udp_frame(payload: &[u8]) -> Vec<u8> {
+        let src = Ipv4Address::new(10, 0, 0, 1);
+        let dst = Ipv4Address::new(10, 0, 0, 2);
+        let udp = UdpRepr {
+            src_port: 1234,
+            dst_port: 5678,
+        };
+        let ip = Ipv4Repr {
+            src_addr: src,
+            dst_addr: dst,
+            next_header: IpProtocol::Udp,
+            payload_len: udp.header_len() + payload.len(),
+            hop_limit: 64,
+        };
+        let caps = ChecksumCapabilities::default();
+
+        let mut buf = vec![0u8; ETH_LEN + IP_LEN + udp.header_len() + payload.len()];
+        let mut eth = EthernetFrame::new_unchecked(&mut buf[..]);
+        eth.set_ethertype(EthernetProtocol::Ipv4);
+        let mut ip_packet = Ipv4Packet::new_unchecked(eth.payload_mut());
+        ip.emit(&mut ip_packet, &caps);
+        let mut udp_packet = UdpPacket::new_unchecked(ip_packet.payload_mut());
+        udp.emit(
+            &mut udp_packet,
+            &IpAddress::Ipv4(src),
+            &IpAddress::Ipv4(dst),
+            payload.len(),
+            |buf| buf.copy_from_slice(payload),
+            &caps,
+        );
+        buf
+    }
+
+    #[test]
+    fn test_audit_valid_frame() {
+        assert_eq!(audit_frame(&udp_frame(b"hello")), Some(Ok(())));
+    }
+
+    #[test]
+    fn test_audit_detects_corruption() {
+        let mut frame = udp_frame(b"hello");
+        *frame.last_mut().unwrap() ^= 0xff;
+        assert_eq!(audit_frame(&frame), Some(Err(ChecksumFault::Udp)));
+
+        // TTL lives in the IPv4 header, which has its own checksum
+        let mut frame = udp_frame(b"hello");
+        frame[ETH_LEN + 8] ^= 0xff;
+        assert_eq!(audit_frame(&frame), Some(Err(ChecksumFault::Ipv4)));
+    }
+
+    #[test]
+    fn test_audit_skips_non_ip() {
+        let mut frame = udp_frame(b"hello");
+        EthernetFrame::new_unchecked(&mut frame[..]).set_ethertype(EthernetProtocol::Arp);
+        assert_eq!(audit_frame(&frame), None);
+        assert_eq!(audit_frame(&[0u8; 4]), None);
+    }
+
+    #[test]
+    fn test_audit_ipv6_udp() {
+        let src = Ipv6Address::new(0xfd00, 0, 0, 0, 0, 0, 0, 1);
+        let dst = Ipv6Address::new(0xfd00, 0, 0, 0, 0, 0, 0, 2);
+        let udp = UdpRepr {
+            src_port: 1234,
+            dst_port: 5678,
+        };
+        let payload = b"hello";
+        let ip = Ipv6Repr {
+            src_addr: src,
+            dst_addr: dst,
+            next_header: IpProtocol::Udp,
+            payload_len: udp.header_len() + payload.len(),
+            hop_limit: 64,
+        };
+        let caps = ChecksumCapabilities::default();
+
+        let mut frame = vec![0u8; ETH_LEN + ip.buffer_len() + ip.payload_len];
+        let mut eth = EthernetFrame::new_unchecked(&mut frame[..]);
+        eth.set_ethertype(EthernetProtocol::Ipv6);
+        let mut ip_packet = Ipv6Packet::new_unchecked(eth.payload_mut());
+        ip.emit(&mut ip_packet);
+        udp.emit(
+            &mut UdpPacket::new_unchecked(ip_packet.payload_mut()),
+            &IpAddress::Ipv6(src),
+            &IpAddress::Ipv6(dst),
+            payload.len(),
+            |buf| buf.copy_from_slice(payload),
+            &caps,
+        );
+        assert_eq!(audit_frame(&frame), Some(Ok(())));
+
+        *frame.last_mut().unwrap() ^= 0xff;
+        assert_eq!(audit_frame(&frame), Some(Err(ChecksumFault::Udp)));
+    }
+
+    #[test]
+    fn test_nic_checksum() {
+        use ffi::*;
+        let good = RUST_RTE_MBUF_F_RX_IP_CKSUM_GOOD | RUST_RTE_MBUF_F_RX_L4_CKSUM_GOOD;
+        assert_eq!(nic_checksum(good), NicChecksum::Good);
+        assert_eq!(
+            nic_checksum(RUST_RTE_MBUF_F_RX_IP_CKSUM_GOOD | RUST_RTE_MBUF_F_RX_L4_CKSUM_BAD),
+            NicChecksum::Bad
+        );
+        assert_eq!(
+            nic_checksum(RUST_RTE_MBUF_F_RX_IP_CKSUM_BAD),
+            NicChecksum::Bad
+        );
+        // IPv6 has no header checksum to report
+        assert_eq!(
+            nic_checksum(RUST_RTE_MBUF_F_RX_L4_CKSUM_GOOD),
+            NicChecksum::Unknown
+        );
+        assert_eq!(nic_checksum(0), NicChecksum::Unknown);
+    }
+
+    #[test]
+    fn test_without_offloaded() {
+        let caps = ChecksumCapabilities::default();
+        let rx_only = ChecksumOffload {
+            rx: true,
+            tx: false,
+        };
+        let stripped = without_offloaded(&caps, rx_only);
+        assert!(matches!(stripped.ipv4, Checksum::Tx));
+        assert!(matches!(stripped.tcp, Checksum::Tx));
+        assert!(matches!(stripped.udp, Checksum::Tx));
+        // ICMP is never offloaded
+        assert!(matches!(stripped.icmpv4, Checksum::Both));
+
+        let stripped = without_offloaded(&caps, ChecksumOffload::ALL);
+        assert!(matches!(stripped.tcp, Checksum::None));
+        assert!(matches!(
+            without_offloaded(&caps, ChecksumOffload::default()).udp,
+            Checksum::Both
+        ));
+    }
+}
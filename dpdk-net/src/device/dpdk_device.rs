@@ -1,14 +1,17 @@
 use arrayvec::ArrayVec;
-use smoltcp::phy::{self, Device, DeviceCapabilities, Medium};
-use smoltcp::time::Instant;
+use smoltcp::phy::{self, ChecksumCapabilities, Device, DeviceCapabilities, Medium};
+use smoltcp::time::{Duration, Instant};
 use std::net::Ipv4Addr;
 use std::sync::Arc;
+use std::task::Waker;
 
+use crate::api::rte::eth::ChecksumOffload;
 use crate::api::rte::mbuf::Mbuf;
 use crate::api::rte::pktmbuf::MemPool;
-use crate::api::rte::queue::{RxQueue, TxQueue};
+use crate::api::rte::queue::{MAX_BURST_SIZE, RxQueue, TxQueue};
 
-use super::arp_cache::{SharedArpCache, parse_arp_reply};
+use super::arp_cache::{SharedArpCache, build_arp_request, parse_arp_reply};
+use super::checksum::{ChecksumFault, NicChecksum, audit_frame, nic_checksum, without_offloaded};
 
 /// Default headroom reserved at the front of each mbuf (matches RTE_PKTMBUF_HEADROOM)
 pub const DEFAULT_MBUF_HEADROOM: usize = 128;
@@ -16,9 +19,18 @@ pub const DEFAULT_MBUF_HEADROOM: usize = 128;
 /// Default data room size for mbufs (2048 bytes of usable space + headroom)
 pub const DEFAULT_MBUF_DATA_ROOM_SIZE: usize = 2048 + DEFAULT_MBUF_HEADROOM;
 
+/// Default number of frames requested from the RX queue per burst
+pub const DEFAULT_RX_BURST_SIZE: usize = 32;
+
 /// Maximum packet overhead: Ethernet (14) + IP (20) + TCP with options (60)
 const MAX_PACKET_OVERHEAD: usize = 14 + 20 + 60;
 
+/// How often queue 0 looks for expired shared ARP cache entries
+const ARP_EXPIRY_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often the mempool's in-use count is sampled for the high-water mark
+const MEMPOOL_SAMPLE_INTERVAL: Duration = Duration::from_millis(10);
+
 pub struct DpdkRxToken {
     mbuf: Mbuf,
 }
@@ -33,15 +45,58 @@ impl phy::RxToken for DpdkRxToken {
     }
 }
 
+/// Counters kept by a [`DpdkDevice`], read with [`DpdkDevice::stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DeviceStats {
+    /// IP frames whose checksums were verified by the checksum audit.
+    pub checksum_audited: u64,
+    /// Audited frames with a bad IPv4 header checksum.
+    pub ipv4_checksum_errors: u64,
+    /// Audited frames with a bad TCP checksum.
+    pub tcp_checksum_errors: u64,
+    /// Audited frames with a bad UDP checksum.
+    pub udp_checksum_errors: u64,
+    /// RX bursts that returned at least one frame.
+    pub rx_bursts: u64,
+    /// Frames received from the RX queue, before any checksum drops.
+    pub rx_frames: u64,
+    /// Frames dropped with RX checksum offload on, because the NIC flagged
+    /// a bad checksum or the software fallback found one.
+    pub rx_checksum_drops: u64,
+    /// Frames smoltcp built but that were dropped for being larger than one
+    /// mbuf. The MTU check in [`DpdkDevice::new`] and
+    /// [`DpdkDevice::set_mtu`] keeps this at zero.
+    pub tx_oversize_drops: u64,
+    /// Frames smoltcp built but that were dropped because the mempool had
+    /// no mbuf to carry them. TCP retransmits them; UDP and ICMP do not.
+    pub tx_alloc_failures: u64,
+    /// Highest mempool in-use count seen, sampled every few milliseconds
+    /// and on every TX allocation failure. The pool may be shared with
+    /// other queues, so this is the pool's peak, not this queue's.
+    pub mbufs_in_use_peak: u32,
+}
+
+impl DeviceStats {
+    /// Audited frames that failed any checksum.
+    pub fn checksum_errors(&self) -> u64 {
+        self.ipv4_checksum_errors + self.tcp_checksum_errors + self.udp_checksum_errors
+    }
+}
+
 /// More complete implementation with mempool access
 pub struct DpdkDevice {
     rxq: RxQueue,
     txq: TxQueue,
     mempool: Arc<MemPool>,
-    rx_batch: ArrayVec<Mbuf, 64>,
+    /// Received frames not yet handed to smoltcp, in reverse arrival order
+    rx_batch: ArrayVec<Mbuf, MAX_BURST_SIZE>,
+    /// Frames requested per RX burst
+    rx_burst_size: usize,
     tx_batch: ArrayVec<Mbuf, 256>,
+    /// Woken by `flush_tx` once `tx_batch` is empty
+    tx_drain_wakers: Vec<Waker>,
     mtu: usize,
-    #[allow(dead_code)] // Validated in constructor, stored for debugging/future use
+    /// Usable mbuf bytes, the limit for [`set_mtu`](Self::set_mtu)
     mbuf_capacity: usize,
     /// Queue ID (0 = producer for shared ARP cache)
     queue_id: u16,
@@ -53,6 +108,17 @@ pub struct DpdkDevice {
     our_ip: Option<Ipv4Addr>,
     /// Last seen cache version (skip injection if unchanged)
     last_cache_version: usize,
+    /// Queue 0: when to next expire shared ARP cache entries
+    next_arp_expiry: Instant,
+    /// Checksum capabilities reported to smoltcp
+    checksum: ChecksumCapabilities,
+    /// Verify checksums of received frames in software and count failures
+    checksum_audit: bool,
+    /// Checksum work done by the NIC instead of smoltcp
+    checksum_offload: ChecksumOffload,
+    stats: DeviceStats,
+    /// When to next sample the mempool for `stats.mbufs_in_use_peak`
+    next_pool_sample: Instant,
 }
 
 impl DpdkDevice {
@@ -87,7 +153,9 @@ impl DpdkDevice {
             txq,
             mempool,
             rx_batch: ArrayVec::new(),
+            rx_burst_size: DEFAULT_RX_BURST_SIZE,
             tx_batch: ArrayVec::new(),
+            tx_drain_wakers: Vec::new(),
             mtu,
             mbuf_capacity,
             queue_id: 0,
@@ -95,9 +163,123 @@ impl DpdkDevice {
             our_mac: None,
             our_ip: None,
             last_cache_version: 0,
+            next_arp_expiry: Instant::ZERO,
+            checksum: ChecksumCapabilities::default(),
+            checksum_audit: false,
+            checksum_offload: ChecksumOffload::default(),
+            stats: DeviceStats::default(),
+            next_pool_sample: Instant::ZERO,
         }
     }
 
+    /// Set the checksum capabilities reported to smoltcp.
+    ///
+    /// The default computes and verifies every checksum in software.
+    /// Directions handed to the NIC with
+    /// [`with_checksum_offload`](Self::with_checksum_offload) are removed
+    /// from these caps before smoltcp sees them.
+    pub fn set_checksum_caps(&mut self, checksum: ChecksumCapabilities) {
+        self.checksum = checksum;
+    }
+
+    /// Checksum capabilities reported to smoltcp.
+    pub fn checksum_caps(&self) -> &ChecksumCapabilities {
+        &self.checksum
+    }
+
+    /// Verify IPv4/TCP/UDP checksums of every received frame in software and
+    /// count the failures in [`stats`](Self::stats).
+    ///
+    /// A troubleshooting aid, off by default: it tells frames arriving
+    /// corrupted (NIC, cable, or a peer bug) apart from frames not arriving
+    /// at all. It runs regardless of [`set_checksum_caps`](Self::set_checksum_caps)
+    /// and does not drop anything; whether smoltcp discards bad frames still
+    /// depends on those caps. Each bad frame is logged at debug level.
+    pub fn with_checksum_audit(mut self, enabled: bool) -> Self {
+        self.checksum_audit = enabled;
+        self
+    }
+
+    /// Let the NIC handle IPv4/TCP/UDP checksums in the given directions.
+    ///
+    /// The port must have been configured with the same offloads, e.g.
+    /// through `EthConf::checksum_offload_for`. smoltcp then skips that work:
+    /// - RX: frames the NIC flags bad are dropped before smoltcp sees them.
+    ///   Frames it could not fully verify (IPv6, or drivers that report
+    ///   "unknown") are checked in software instead.
+    /// - TX: every outgoing mbuf asks the NIC to fill in the checksums.
+    ///
+    /// ICMP checksums stay in software.
+    pub fn with_checksum_offload(mut self, offload: ChecksumOffload) -> Self {
+        self.checksum_offload = offload;
+        self
+    }
+
+    /// Checksum directions handled by the NIC.
+    pub fn checksum_offload(&self) -> ChecksumOffload {
+        self.checksum_offload
+    }
+
+    /// Set how many frames each RX burst asks the queue for (default
+    /// [`DEFAULT_RX_BURST_SIZE`]), clamped to `1..=MAX_BURST_SIZE`.
+    ///
+    /// A burst is only requested once the previous one is drained, one
+    /// frame per smoltcp ingress poll, so larger bursts spread the cost of
+    /// touching the ring over more frames. Room left above the burst size
+    /// takes ARP entries injected from the shared cache.
+    pub fn with_rx_burst_size(mut self, size: usize) -> Self {
+        self.rx_burst_size = size.clamp(1, MAX_BURST_SIZE);
+        self
+    }
+
+    /// Frames requested per RX burst.
+    pub fn rx_burst_size(&self) -> usize {
+        self.rx_burst_size
+    }
+
+    /// The MTU reported to smoltcp.
+    pub fn mtu(&self) -> usize {
+        self.mtu
+    }
+
+    /// Change the MTU reported to smoltcp, e.g. after `EthDev::set_mtu`
+    /// raised the port's.
+    ///
+    /// Returns `false` and keeps the old MTU if MTU + maximum packet
+    /// overhead would not fit the mbuf capacity given to [`new`](Self::new);
+    /// jumbo frames need a mempool with a larger data room. A smoltcp
+    /// `Interface` keeps the capabilities it read when it was created, so
+    /// set the MTU before building the reactor on this device; once it runs,
+    /// use [`ReactorHandle::set_mtu`](crate::runtime::ReactorHandle::set_mtu).
+    pub fn set_mtu(&mut self, mtu: usize) -> bool {
+        if mtu + MAX_PACKET_OVERHEAD > self.mbuf_capacity {
+            tracing::warn!(
+                mtu,
+                mbuf_capacity = self.mbuf_capacity,
+                "MTU does not fit the mbufs, keeping {}",
+                self.mtu
+            );
+            return false;
+        }
+        self.mtu = mtu;
+        true
+    }
+
+    /// Counters kept by this device.
+    pub fn stats(&self) -> DeviceStats {
+        self.stats
+    }
+
+    /// The port this device's queues belong to.
+    pub fn port_id(&self) -> u16 {
+        self.rxq.port_id()
+    }
+
+    /// The mempool RX and TX buffers come from.
+    pub fn mempool(&self) -> &Arc<MemPool> {
+        &self.mempool
+    }
+
     /// Configure shared ARP cache for multi-queue support.
     ///
     /// # Arguments
@@ -106,7 +288,8 @@ impl DpdkDevice {
     /// * `our_mac` - Our interface MAC address
     /// * `our_ip` - Our interface IP address
     ///
-    /// Queue 0 will update the cache when it receives ARP replies.
+    /// Queue 0 will update the cache when it receives ARP replies, and
+    /// re-ARP peers whose entries expire.
     /// Other queues will check the cache and inject ARP packets into smoltcp.
     pub fn with_shared_arp_cache(
         mut self,
@@ -122,14 +305,26 @@ impl DpdkDevice {
         self
     }
 
-    fn poll_rx(&mut self) {
+    fn poll_rx(&mut self, timestamp: Instant) {
         // First flush any pending TX packets
         self.flush_tx();
 
         // Poll from network only when rx_batch is empty (drain-then-refill pattern).
         // This minimizes DPDK API calls and improves cache locality.
         if self.rx_batch.is_empty() {
-            self.rxq.rx(&mut self.rx_batch);
+            let received = self.rxq.rx_up_to(&mut self.rx_batch, self.rx_burst_size);
+            if received > 0 {
+                self.stats.rx_bursts += 1;
+                self.stats.rx_frames += received as u64;
+            }
+
+            if self.checksum_offload.rx {
+                self.filter_rx_checksums();
+            }
+
+            if self.checksum_audit {
+                self.audit_rx_batch();
+            }
 
             // If we have a shared ARP cache, process received packets
             if let Some(ref cache) = self.shared_arp_cache {
@@ -142,6 +337,97 @@ impl DpdkDevice {
                     }
                 }
             }
+
+            // receive() pops from the back, so reverse to hand frames to
+            // smoltcp in arrival order and keep a flow's segments in sequence
+            self.rx_batch.reverse();
+        }
+
+        if self.queue_id == 0 && timestamp >= self.next_arp_expiry {
+            self.next_arp_expiry = timestamp + ARP_EXPIRY_CHECK_INTERVAL;
+            self.refresh_expired_arp();
+        }
+
+        // Walking the per-lcore caches is not free, so only now and then
+        if timestamp >= self.next_pool_sample {
+            self.next_pool_sample = timestamp + MEMPOOL_SAMPLE_INTERVAL;
+            let in_use = self.mempool.in_use_count();
+            self.stats.mbufs_in_use_peak = self.stats.mbufs_in_use_peak.max(in_use);
+        }
+    }
+
+    /// Queue 0: drop expired shared ARP entries and ask their peers again.
+    ///
+    /// The reply comes back to queue 0 and re-enters the cache with the
+    /// peer's current MAC, which the other queues then inject.
+    fn refresh_expired_arp(&mut self) {
+        let (Some(cache), Some(our_mac), Some(our_ip)) =
+            (&self.shared_arp_cache, self.our_mac, self.our_ip)
+        else {
+            return;
+        };
+
+        for ip in cache.expire(std::time::Instant::now()) {
+            let request = build_arp_request(our_mac, our_ip, ip);
+            if self.tx_batch.len() < self.tx_batch.capacity()
+                && let Some(mut mbuf) = self.mempool.try_alloc()
+                && mbuf.copy_from_slice(&request)
+            {
+                tracing::debug!(%ip, "Shared ARP entry expired, re-requesting");
+                self.tx_batch.push(mbuf);
+            } else {
+                // smoltcp re-ARPs on its own once its neighbor entry ages out
+                tracing::warn!(%ip, "Failed to send ARP request for expired entry");
+            }
+        }
+    }
+
+    /// Drop frames of a freshly received batch whose checksums are bad.
+    ///
+    /// smoltcp is not verifying them, so the NIC's verdict is final; frames
+    /// it did not vouch for get the software check.
+    fn filter_rx_checksums(&mut self) {
+        let queue_id = self.queue_id;
+        let stats = &mut self.stats;
+        self.rx_batch.retain(|mbuf| {
+            let ok = match nic_checksum(mbuf.ol_flags()) {
+                NicChecksum::Good => true,
+                NicChecksum::Bad => false,
+                NicChecksum::Unknown => !matches!(audit_frame(mbuf.data()), Some(Err(_))),
+            };
+            if !ok {
+                stats.rx_checksum_drops += 1;
+                tracing::debug!(
+                    queue_id,
+                    len = mbuf.data_len(),
+                    "Dropped received frame with a bad checksum"
+                );
+            }
+            ok
+        });
+    }
+
+    /// Run the checksum audit over a freshly received batch.
+    fn audit_rx_batch(&mut self) {
+        for mbuf in &self.rx_batch {
+            let Some(verdict) = audit_frame(mbuf.data()) else {
+                continue;
+            };
+            self.stats.checksum_audited += 1;
+            let Err(fault) = verdict else {
+                continue;
+            };
+            match fault {
+                ChecksumFault::Ipv4 => self.stats.ipv4_checksum_errors += 1,
+                ChecksumFault::Tcp => self.stats.tcp_checksum_errors += 1,
+                ChecksumFault::Udp => self.stats.udp_checksum_errors += 1,
+            }
+            tracing::debug!(
+                queue_id = self.queue_id,
+                ?fault,
+                len = mbuf.data_len(),
+                "Received frame failed checksum verification"
+            );
         }
     }
 
@@ -177,10 +463,17 @@ impl DpdkDevice {
 
         // Load the current cache snapshot (lock-free)
         let cache_snapshot = cache.snapshot();
-
-        // Inject all entries (we only get here when there are new/updated ones)
-        // Re-injecting already-known entries is harmless - smoltcp deduplicates
-        for (&ip, &mac) in cache_snapshot.iter() {
+        let now = std::time::Instant::now();
+        let ttl = cache.ttl();
+
+        // Inject all live entries (we only get here when there are new/updated ones)
+        // Re-injecting already-known entries is harmless - smoltcp deduplicates.
+        // Expired ones wait for queue 0 to re-learn them.
+        for (&ip, entry) in cache_snapshot.iter() {
+            if entry.is_expired(now, ttl) {
+                continue;
+            }
+            let mac = entry.mac;
             let arp_packet = build_arp_reply_for_injection(our_mac, our_ip, mac, ip);
 
             if self.rx_batch.len() < self.rx_batch.capacity()
@@ -203,10 +496,28 @@ impl DpdkDevice {
     ///
     /// This tries to send packets from tx_batch but doesn't spin if the TX ring is full.
     /// Remaining packets stay in tx_batch and will be retried on next call.
+    /// Once tx_batch is empty, wakers registered with
+    /// [`register_tx_drain_waker`](Self::register_tx_drain_waker) are woken.
     pub(crate) fn flush_tx(&mut self) {
         if !self.tx_batch.is_empty() {
             self.txq.tx(&mut self.tx_batch);
         }
+        if self.tx_batch.is_empty() {
+            self.tx_drain_wakers.drain(..).for_each(Waker::wake);
+        }
+    }
+
+    /// Wake `waker` once a later [`flush_tx`](Self::flush_tx) has handed
+    /// every frame in tx_batch to the hardware TX ring.
+    pub(crate) fn register_tx_drain_waker(&mut self, waker: &Waker) {
+        if !self.tx_drain_wakers.iter().any(|w| w.will_wake(waker)) {
+            self.tx_drain_wakers.push(waker.clone());
+        }
+    }
+
+    /// Frames waiting in tx_batch for room in the hardware TX ring.
+    pub(crate) fn tx_pending(&self) -> usize {
+        self.tx_batch.len()
     }
 
     /// Inject a packet into the receive path.
@@ -245,8 +556,8 @@ impl Device for DpdkDevice {
     where
         Self: 'a;
 
-    fn receive(&mut self, _timestamp: Instant) -> Option<(Self::RxToken<'_>, Self::TxToken<'_>)> {
-        self.poll_rx();
+    fn receive(&mut self, timestamp: Instant) -> Option<(Self::RxToken<'_>, Self::TxToken<'_>)> {
+        self.poll_rx(timestamp);
 
         // Inject ARP entries after poll_rx (which may have reversed the batch).
         // This ensures injected ARPs are at the back, processed first by pop() = high priority.
@@ -258,6 +569,9 @@ impl Device for DpdkDevice {
             let tx_token = DpdkTxTokenWithPool {
                 mempool: &self.mempool,
                 tx_batch: &mut self.tx_batch,
+                tx_checksum_offload: self.checksum_offload.tx,
+                stats: &mut self.stats,
+                queue_id: self.queue_id,
             };
             Some((rx_token, tx_token))
         } else {
@@ -270,6 +584,9 @@ impl Device for DpdkDevice {
             Some(DpdkTxTokenWithPool {
                 mempool: &self.mempool,
                 tx_batch: &mut self.tx_batch,
+                tx_checksum_offload: self.checksum_offload.tx,
+                stats: &mut self.stats,
+                queue_id: self.queue_id,
             })
         } else {
             // TX batch is full - try to flush to hardware.
@@ -280,6 +597,9 @@ impl Device for DpdkDevice {
                 Some(DpdkTxTokenWithPool {
                     mempool: &self.mempool,
                     tx_batch: &mut self.tx_batch,
+                    tx_checksum_offload: self.checksum_offload.tx,
+                    stats: &mut self.stats,
+                    queue_id: self.queue_id,
                 })
             } else {
                 // Hardware TX ring is full - caller will have to wait
@@ -292,6 +612,7 @@ impl Device for DpdkDevice {
         let mut caps = DeviceCapabilities::default();
         caps.max_transmission_unit = self.mtu;
         caps.medium = Medium::Ethernet;
+        caps.checksum = without_offloaded(&self.checksum, self.checksum_offload);
         caps
     }
 }
@@ -299,33 +620,70 @@ impl Device for DpdkDevice {
 pub struct DpdkTxTokenWithPool<'a> {
     mempool: &'a MemPool,
     tx_batch: &'a mut ArrayVec<Mbuf, 256>,
+    tx_checksum_offload: bool,
+    stats: &'a mut DeviceStats,
+    queue_id: u16,
+}
+
+impl DpdkTxTokenWithPool<'_> {
+    /// Count a frame dropped for lack of mbufs. Logs the first failure and
+    /// every power of two after it, so a long exhaustion does not flood the
+    /// log.
+    fn alloc_failed(&mut self, len: usize) {
+        self.stats.tx_alloc_failures += 1;
+        let in_use = self.mempool.in_use_count();
+        self.stats.mbufs_in_use_peak = self.stats.mbufs_in_use_peak.max(in_use);
+        if self.stats.tx_alloc_failures.is_power_of_two() {
+            tracing::warn!(
+                queue_id = self.queue_id,
+                len,
+                in_use,
+                failures = self.stats.tx_alloc_failures,
+                "Mempool exhausted, dropping transmitted frame"
+            );
+        }
+    }
+
+    /// Queue a finished frame for the next flush.
+    fn push(&mut self, mut mbuf: Mbuf) {
+        if self.tx_checksum_offload {
+            mbuf.request_tx_checksum_offload();
+        }
+        // Safety: transmit() only returns a token when tx_batch has space
+        self.tx_batch
+            .try_push(mbuf)
+            .expect("tx_batch should have space (checked in transmit())");
+    }
 }
 
 impl<'a> phy::TxToken for DpdkTxTokenWithPool<'a> {
-    fn consume<R, F>(self, len: usize, f: F) -> R
+    fn consume<R, F>(mut self, len: usize, f: F) -> R
     where
         F: FnOnce(&mut [u8]) -> R,
     {
-        // Allocate mbuf from mempool
-        if let Some(mut mbuf) = self.mempool.try_alloc() {
-            unsafe {
-                mbuf.extend(len);
-            }
-
-            // Let smoltcp write directly to the mbuf
-            let result = f(mbuf.data_mut());
-
-            // Add to tx batch (will be flushed later)
-            // Safety: transmit() only returns a token when tx_batch has space
-            self.tx_batch
-                .try_push(mbuf)
-                .expect("tx_batch should have space (checked in transmit())");
+        let Some(mut mbuf) = self.mempool.try_alloc() else {
+            // smoltcp still has to build the frame, but it is dropped
+            self.alloc_failed(len);
+            let mut buffer = vec![0u8; len];
+            return f(&mut buffer);
+        };
 
-            result
-        } else {
-            // Fallback if allocation fails - packet data is lost
+        // smoltcp serializes straight into the mbuf's data room
+        let Some(data) = mbuf.append(len) else {
+            // Ports are not set up for multi-segment frames, so one that
+            // does not fit a single mbuf cannot be sent
+            self.stats.tx_oversize_drops += 1;
+            tracing::warn!(
+                queue_id = self.queue_id,
+                len,
+                tailroom = mbuf.tailroom(),
+                "Frame larger than an mbuf, dropping"
+            );
             let mut buffer = vec![0u8; len];
-            f(&mut buffer)
-        }
+            return f(&mut buffer);
+        };
+        let result = f(data);
+        self.push(mbuf);
+        result
     }
 }
@@ -3,6 +3,7 @@
 //! This module provides:
 //! - [`DpdkDevice`]: A smoltcp `Device` implementation backed by DPDK RX/TX queues
 //! - [`SharedArpCache`]: Thread-safe ARP cache for multi-queue DPDK setups
+//! - [`audit_frame`]: Software checksum check behind [`DpdkDevice::with_checksum_audit`]
 //!
 //! # Multi-Queue ARP Sharing
 //!
@@ -14,11 +15,17 @@
 //!
 //! 1. Create a [`SharedArpCache`] and share it between queues
 //! 2. Create [`DpdkDevice`] for each queue, passing the shared cache
-//! 3. Queue 0 will update the cache when it receives ARP replies
+//! 3. Queue 0 will update the cache when it receives ARP replies, and re-ARP
+//!    peers whose entries outlive the cache's TTL
 //! 4. Other queues will check the cache and inject ARP packets into smoltcp
 
 mod arp_cache;
+mod checksum;
 mod dpdk_device;
 
-pub use arp_cache::{MacAddress, SharedArpCache, build_arp_reply_for_injection, parse_arp_reply};
+pub use arp_cache::{
+    ArpEntry, DEFAULT_ARP_TTL, MacAddress, SharedArpCache, build_arp_reply_for_injection,
+    build_arp_request, parse_arp_reply,
+};
+pub use checksum::{ChecksumFault, audit_frame};
 pub use dpdk_device::*;
@@ -1,7 +1,9 @@
 pub mod api;
 pub mod device;
+pub mod generator;
 pub mod runtime;
 pub mod socket;
+pub mod topology;
 
 /// A boxed error type for dpdk-net operations.
 pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
@@ -0,0 +1,229 @@
+//! Typed configuration for the smoltcp layer of a reactor.
+
+use std::ops::RangeInclusive;
+
+use smoltcp::phy::ChecksumCapabilities;
+use smoltcp::wire::{EthernetAddress, IpAddress, IpCidr, Ipv4Address};
+
+use super::ports::EPHEMERAL_PORTS;
+
+/// Settings used by [`Reactor::new_with_config`](super::Reactor::new_with_config)
+/// to build the smoltcp `Interface`.
+///
+/// The defaults match what callers previously set up by hand: software
+/// checksums, a zero random seed, no any-IP, no socket or half-open cap, the
+/// full IANA ephemeral port range, and no addresses or routes.
+///
+/// The medium is always Ethernet for [`DpdkDevice`](crate::device::DpdkDevice),
+/// so it is implied by `hardware_addr`. The neighbor cache size is fixed at
+/// compile time by smoltcp's `iface-neighbor-cache-count-*` features and cannot
+/// be set here.
+///
+/// # Example
+///
+/// ```ignore
+/// use dpdk_net::runtime::{Reactor, ReactorConfig};
+/// use smoltcp::wire::{EthernetAddress, IpAddress, IpCidr, Ipv4Address};
+///
+/// let config = ReactorConfig::new(EthernetAddress([0x02, 0, 0, 0, 0, 1]))
+///     .ip_addr(IpCidr::new(IpAddress::v4(10, 0, 0, 10), 24))
+///     .ipv4_gateway(Ipv4Address::new(10, 0, 0, 1))
+///     .random_seed(0x1234_5678);
+/// let reactor = Reactor::new_with_config(device, config)?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReactorConfig {
+    /// MAC address of the interface.
+    pub hardware_addr: EthernetAddress,
+    /// IP addresses assigned to the interface.
+    pub ip_addrs: Vec<IpCidr>,
+    /// Default IPv4 route.
+    pub ipv4_gateway: Option<Ipv4Address>,
+    /// Additional routes as `(destination, next hop)`, checked with
+    /// [`check_routes`] before they are installed. smoltcp picks the longest
+    /// matching prefix, so a more specific route wins over the default.
+    pub routes: Vec<(IpCidr, IpAddress)>,
+    /// Seed for smoltcp's RNG (TCP initial sequence numbers, ephemeral choices).
+    ///
+    /// Defaults to 0, as in `smoltcp::iface::Config`. Use a random value in
+    /// production so ISNs are not predictable across restarts.
+    pub random_seed: u64,
+    /// Accept packets addressed to any IP, not just the configured ones.
+    pub any_ip: bool,
+    /// Checksum capabilities reported by the device to smoltcp.
+    pub checksum: ChecksumCapabilities,
+    /// Cap on the reactor's socket count; see
+    /// [`ReactorHandle::set_max_sockets`](super::ReactorHandle::set_max_sockets).
+    pub max_sockets: Option<usize>,
+    /// Cap on the reactor's socket buffer memory; see
+    /// [`ReactorHandle::set_max_buffer_bytes`](super::ReactorHandle::set_max_buffer_bytes).
+    pub max_buffer_bytes: Option<usize>,
+    /// Cap on half-open connections; see
+    /// [`ReactorHandle::set_max_half_open`](super::ReactorHandle::set_max_half_open).
+    pub max_half_open: Option<usize>,
+    /// Local ports for outbound connections; see
+    /// [`ReactorHandle::alloc_ephemeral_port`](super::ReactorHandle::alloc_ephemeral_port).
+    pub ephemeral_ports: RangeInclusive<u16>,
+}
+
+impl ReactorConfig {
+    /// Create a config with default settings for the given MAC address.
+    pub fn new(hardware_addr: EthernetAddress) -> Self {
+        Self {
+            hardware_addr,
+            ip_addrs: Vec::new(),
+            ipv4_gateway: None,
+            routes: Vec::new(),
+            random_seed: 0,
+            any_ip: false,
+            checksum: ChecksumCapabilities::default(),
+            max_sockets: None,
+            max_buffer_bytes: None,
+            max_half_open: None,
+            ephemeral_ports: EPHEMERAL_PORTS,
+        }
+    }
+
+    /// Add an IP address to the interface (repeatable).
+    pub fn ip_addr(mut self, cidr: IpCidr) -> Self {
+        self.ip_addrs.push(cidr);
+        self
+    }
+
+    /// Set the default IPv4 gateway.
+    pub fn ipv4_gateway(mut self, gateway: Ipv4Address) -> Self {
+        self.ipv4_gateway = Some(gateway);
+        self
+    }
+
+    /// Route `cidr` via `next_hop` (repeatable).
+    ///
+    /// A `0.0.0.0/0` route is a default route and conflicts with
+    /// [`ipv4_gateway`](Self::ipv4_gateway).
+    pub fn route(mut self, cidr: IpCidr, next_hop: IpAddress) -> Self {
+        self.routes.push((cidr, next_hop));
+        self
+    }
+
+    /// Set the smoltcp random seed.
+    pub fn random_seed(mut self, seed: u64) -> Self {
+        self.random_seed = seed;
+        self
+    }
+
+    /// Enable or disable any-IP mode.
+    pub fn any_ip(mut self, enabled: bool) -> Self {
+        self.any_ip = enabled;
+        self
+    }
+
+    /// Set the checksum capabilities.
+    pub fn checksum(mut self, checksum: ChecksumCapabilities) -> Self {
+        self.checksum = checksum;
+        self
+    }
+
+    /// Limit the number of sockets the reactor will hold.
+    pub fn max_sockets(mut self, max: usize) -> Self {
+        self.max_sockets = Some(max);
+        self
+    }
+
+    /// Limit the total rx+tx buffer bytes of the reactor's sockets.
+    pub fn max_buffer_bytes(mut self, max: usize) -> Self {
+        self.max_buffer_bytes = Some(max);
+        self
+    }
+
+    /// Limit the number of connections in `SynReceived` at once.
+    pub fn max_half_open(mut self, max: usize) -> Self {
+        self.max_half_open = Some(max);
+        self
+    }
+
+    /// Set the range ephemeral local ports are picked from.
+    pub fn ephemeral_ports(mut self, range: RangeInclusive<u16>) -> Self {
+        self.ephemeral_ports = range;
+        self
+    }
+}
+
+/// Check that `routes` can be installed next to `gateway`.
+///
+/// Rejects a destination listed twice (which includes a second default
+/// route, whether from `0.0.0.0/0` entries or from `gateway`) and a next hop
+/// that is unspecified or multicast. An IPv6 default route is a `::/0` entry
+/// in `routes`; `gateway` only stands for the IPv4 one.
+pub fn check_routes(
+    gateway: Option<Ipv4Address>,
+    routes: &[(IpCidr, IpAddress)],
+) -> Result<(), String> {
+    let default = IpCidr::new(IpAddress::Ipv4(Ipv4Address::UNSPECIFIED), 0);
+    let mut seen: Vec<(IpCidr, IpAddress)> = Vec::with_capacity(routes.len() + 1);
+    if let Some(gateway) = gateway {
+        seen.push((default, IpAddress::Ipv4(gateway)));
+    }
+
+    for &(cidr, next_hop) in routes {
+        if next_hop.is_unspecified() || next_hop.is_multicast() {
+            return Err(format!("route {cidr}: invalid next hop {next_hop}"));
+        }
+        // Compare networks, so 10.1.2.3/16 and 10.1.0.0/16 count as one
+        let network = cidr.network();
+        if let Some((_, existing)) = seen.iter().find(|(c, _)| c.network() == network) {
+            let what = if cidr.prefix_len() == 0 {
+                "conflicting default routes"
+            } else {
+                "duplicate route"
+            };
+            return Err(format!(
+                "{what}: {network} via {existing} and via {next_hop}"
+            ));
+        }
+        seen.push((cidr, next_hop));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cidr(a: u8, b: u8, c: u8, d: u8, len: u8) -> IpCidr {
+        IpCidr::new(IpAddress::v4(a, b, c, d), len)
+    }
+
+    #[test]
+    fn test_check_routes() {
+        let gw = Some(Ipv4Address::new(10, 0, 0, 1));
+        let hop = IpAddress::v4(10, 0, 0, 2);
+        let routes = [
+            (cidr(192, 168, 0, 0, 16), hop),
+            (cidr(192, 168, 7, 0, 24), IpAddress::v4(10, 0, 0, 3)),
+        ];
+        assert!(check_routes(gw, &routes).is_ok());
+
+        // Same network, different host bits
+        let dup = [
+            (cidr(192, 168, 0, 0, 16), hop),
+            (cidr(192, 168, 1, 1, 16), hop),
+        ];
+        assert!(check_routes(None, &dup).unwrap_err().contains("duplicate"));
+
+        let default = [(cidr(0, 0, 0, 0, 0), hop)];
+        assert!(check_routes(None, &default).is_ok());
+        assert!(check_routes(gw, &default).unwrap_err().contains("default"));
+
+        let bad_hop = [(cidr(192, 168, 0, 0, 16), IpAddress::v4(0, 0, 0, 0))];
+        assert!(check_routes(None, &bad_hop).is_err());
+
+        // An IPv6 default does not clash with the IPv4 gateway, only with itself
+        let v6_hop = IpAddress::v6(0xfd00, 0, 0, 0, 0, 0, 0, 1);
+        let v6_default = (
+            IpCidr::new(IpAddress::v6(0, 0, 0, 0, 0, 0, 0, 0), 0),
+            v6_hop,
+        );
+        assert!(check_routes(gw, &[v6_default]).is_ok());
+        assert!(check_routes(gw, &[v6_default, v6_default]).is_err());
+    }
+}
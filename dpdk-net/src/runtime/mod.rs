@@ -10,7 +10,9 @@
 //!
 //! Unlike interrupt-driven systems (tokio with epoll), DPDK requires continuous
 //! polling - there are no interrupts to notify us when packets arrive.
-//! The `Reactor::run()` method polls DPDK in a loop.
+//! The `Reactor::run()` method polls DPDK in a loop, keeping its core busy.
+//! [`Reactor::run_with`] takes a [`Runtime`] whose timers let it sleep
+//! briefly (up to [`DEFAULT_IDLE_POLL_INTERVAL`]) while nothing arrives.
 //!
 //! ## How Wakers Work
 //!
@@ -19,6 +21,29 @@
 //! 3. **smoltcp wakes those wakers** when socket state changes during poll
 //! 4. **The executor schedules those tasks** to run
 //!
+//! ## Fairness
+//!
+//! All tasks on a reactor share one thread, and a socket with data always
+//! readable never returns `Pending` on its own. Each `TcpStream` recv/send
+//! poll therefore spends one unit of a per-reactor yield budget
+//! ([`DEFAULT_YIELD_BUDGET`] by default); once it is spent, socket futures
+//! yield until the reactor has polled again. Code that loops without
+//! touching sockets can check [`ReactorHandle::should_yield`].
+//!
+//! ## Embedding in an External Loop
+//!
+//! Instead of spawning [`Reactor::run`], a host loop can drive the reactor
+//! through its handle: call [`ReactorHandle::poll_once`], run the tasks it
+//! woke, and when the pass was idle wait at most
+//! [`ReactorHandle::poll_delay`] (capped at the host's latency budget, since
+//! packet arrival raises no event) before polling again.
+//!
+//! ## Several Interfaces
+//!
+//! A reactor drives one device. [`MultiReactor`] drives several reactors
+//! from one loop, so a single thread can own two NICs and forward between
+//! sockets on either side.
+//!
 //! # Example
 //!
 //! ```ignore
@@ -48,6 +73,19 @@
 //! }
 //! ```
 
+mod config;
+mod multi;
+mod ports;
 mod reactor;
+mod time;
 
-pub use reactor::{Reactor, ReactorHandle, ReactorInner};
+pub use config::{ReactorConfig, check_routes};
+pub use multi::MultiReactor;
+pub use ports::{EPHEMERAL_PORTS, EphemeralPorts, queue_port_range};
+pub(crate) use reactor::ListenSpec;
+pub use reactor::{
+    DEFAULT_IDLE_POLL_INTERVAL, DEFAULT_INGRESS_BATCH_SIZE, DEFAULT_STALL_WARN_POLLS,
+    DEFAULT_YIELD_BUDGET, PollActivity, PollConfig, Reactor, ReactorHandle, ReactorInner,
+    ReactorStats, Runtime, SpinRuntime,
+};
+pub use time::{Elapsed, Interval, Sleep};
@@ -0,0 +1,128 @@
+//! One loop driving several reactors.
+//!
+//! Each [`Reactor`] owns one device and one smoltcp interface. A router or a
+//! host with two NICs wants several of them on the same thread, so a task can
+//! read from a socket on one interface and write to a socket on another
+//! without crossing threads. [`MultiReactor`] polls every reactor it holds in
+//! one loop; sockets stay scoped to the interface whose handle created them.
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use smoltcp::time::Instant;
+
+use super::reactor::{PollConfig, Reactor, ReactorHandle, Runtime, SpinRuntime};
+use crate::device::DpdkDevice;
+
+/// Several reactors, each with its own device and interface, driven by one
+/// loop.
+///
+/// Interfaces are numbered in the order they were [added](Self::add). Create
+/// sockets through [`handle`](Self::handle) of the interface they belong to;
+/// smoltcp routes nothing between interfaces, so forwarding is up to the
+/// application.
+///
+/// # Example
+///
+/// ```ignore
+/// let mut reactors = MultiReactor::new();
+/// let lan = reactors.add(Reactor::new_with_config(lan_device, lan_config)?);
+/// let wan = reactors.add(Reactor::new_with_config(wan_device, wan_config)?);
+/// let lan = reactors.handle(lan).unwrap();
+/// let wan = reactors.handle(wan).unwrap();
+///
+/// tokio::task::spawn_local(reactors.run(cancel));
+///
+/// // Forward datagrams from the LAN side to the WAN side
+/// let inside = UdpSocket::bind(&lan, 5000, 64, 64, 1500)?;
+/// let outside = UdpSocket::bind(&wan, 5000, 64, 64, 1500)?;
+/// ```
+#[derive(Default)]
+pub struct MultiReactor {
+    reactors: Vec<ReactorHandle>,
+}
+
+impl MultiReactor {
+    /// Create a multi-reactor with no interfaces.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take over driving `reactor`; returns its interface index.
+    pub fn add(&mut self, reactor: Reactor<DpdkDevice>) -> usize {
+        self.reactors.push(reactor.handle());
+        self.reactors.len() - 1
+    }
+
+    /// Number of interfaces.
+    pub fn len(&self) -> usize {
+        self.reactors.len()
+    }
+
+    /// Returns true if no interface was added.
+    pub fn is_empty(&self) -> bool {
+        self.reactors.is_empty()
+    }
+
+    /// Handle to the reactor of interface `index`, for creating its sockets.
+    pub fn handle(&self, index: usize) -> Option<ReactorHandle> {
+        self.reactors.get(index).cloned()
+    }
+
+    /// Handles to all reactors, by interface index.
+    pub fn handles(&self) -> Vec<ReactorHandle> {
+        self.reactors.clone()
+    }
+
+    /// Run every reactor with the default [`PollConfig`], never sleeping;
+    /// see [`Reactor::run`].
+    pub async fn run(self, cancel: Rc<Cell<bool>>) {
+        self.run_with_config::<SpinRuntime>(PollConfig::new(), cancel)
+            .await
+    }
+
+    /// Run every reactor with the limits from `config`, which apply to each
+    /// interface separately; see [`Reactor::run_with_config`].
+    ///
+    /// One pass polls the interfaces in order. The loop only waits through
+    /// [`R::poll_delay`](Runtime::poll_delay) when every interface was idle,
+    /// and then no longer than the earliest timer of any of them. Timers
+    /// created meanwhile use [`R::timer`](Runtime::timer).
+    pub async fn run_with_config<R: Runtime>(self, config: PollConfig, cancel: Rc<Cell<bool>>) {
+        let timers: Vec<_> = self
+            .reactors
+            .iter()
+            .map(|handle| handle.inner.borrow().timers.clone())
+            .collect();
+        for heap in &timers {
+            heap.set_runtime_timer(Some(R::timer));
+        }
+        while !cancel.get() {
+            let now = Instant::now();
+            let mut idle = true;
+            for handle in &self.reactors {
+                let activity = handle.inner.borrow_mut().poll_pass(
+                    now,
+                    config.ingress_batch,
+                    config.egress_batch,
+                );
+                idle &= activity.is_idle() && !activity.more_pending;
+            }
+
+            let idle_delay = config.idle_sleep.filter(|_| idle).map(|max| {
+                self.reactors
+                    .iter()
+                    .filter_map(|handle| handle.poll_delay(now))
+                    .fold(max, Duration::min)
+            });
+            match idle_delay {
+                Some(delay) if !delay.is_zero() => R::poll_delay(delay).await,
+                _ => R::yield_now().await,
+            }
+        }
+        for heap in &timers {
+            heap.set_runtime_timer(None);
+        }
+    }
+}
@@ -0,0 +1,162 @@
+//! Ephemeral local port selection.
+//!
+//! Each reactor owns an independent smoltcp stack, so two reactors on one
+//! port (one per queue, as `DpdkApp` in dpdk-net-util sets up) know nothing of each
+//! other's connections. If both picked the same local port towards the same
+//! server, their connections would share a 4-tuple on the wire. Splitting the
+//! ephemeral range with [`queue_port_range`] keeps each reactor's choices
+//! disjoint.
+//!
+//! # RSS
+//!
+//! Disjoint ranges prevent collisions; they do not steer replies. The NIC
+//! picks the RX queue of a reply by hashing its 5-tuple with the RSS key and
+//! looking the hash up in the RETA, and nothing about a port range makes that
+//! hash land on the queue that opened the connection. A symmetric RSS key only
+//! guarantees both directions of a flow hash alike, which matters to servers
+//! (the SYN decides the queue) but not to outbound connections. For a client
+//! reactor to see its replies, either pick local ports whose reply hash maps
+//! to its queue (see [`EphemeralPorts::next_free_matching`]) or install a flow
+//! rule that steers the range to the queue.
+
+use std::ops::RangeInclusive;
+
+/// The IANA dynamic port range, used when nothing else is configured.
+pub const EPHEMERAL_PORTS: RangeInclusive<u16> = 49152..=65535;
+
+/// The slice of [`EPHEMERAL_PORTS`] for queue `queue_id` of `num_queues`.
+///
+/// Slices are contiguous, disjoint and cover the whole range; the last
+/// queue takes the remainder.
+///
+/// # Panics
+///
+/// Panics if `queue_id >= num_queues`, or if there are more queues than
+/// ports in the range.
+pub fn queue_port_range(queue_id: u16, num_queues: u16) -> RangeInclusive<u16> {
+    assert!(
+        queue_id < num_queues,
+        "queue {queue_id} out of range for {num_queues} queues"
+    );
+    let first = *EPHEMERAL_PORTS.start() as u32;
+    let total = *EPHEMERAL_PORTS.end() as u32 - first + 1;
+    let per_queue = total / num_queues as u32;
+    assert!(
+        per_queue > 0,
+        "{num_queues} queues exceed the ephemeral range"
+    );
+
+    let start = first + queue_id as u32 * per_queue;
+    let end = if queue_id + 1 == num_queues {
+        *EPHEMERAL_PORTS.end() as u32
+    } else {
+        start + per_queue - 1
+    };
+    start as u16..=end as u16
+}
+
+/// Round-robin cursor over a range of local ports.
+///
+/// Ports are handed out in order, wrapping at the end, so a port freed by a
+/// closed connection is not reused until the rest of the range has been
+/// tried. That gives the peer's TIME-WAIT state for the old connection the
+/// longest possible time to expire.
+#[derive(Debug, Clone)]
+pub struct EphemeralPorts {
+    range: RangeInclusive<u16>,
+    next: u16,
+}
+
+impl EphemeralPorts {
+    /// Hand out ports from `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is empty or includes port 0.
+    pub fn new(range: RangeInclusive<u16>) -> Self {
+        assert!(
+            !range.is_empty() && *range.start() > 0,
+            "invalid ephemeral port range {range:?}"
+        );
+        Self {
+            next: *range.start(),
+            range,
+        }
+    }
+
+    /// Hand out ports from this queue's slice; see [`queue_port_range`].
+    pub fn for_queue(queue_id: u16, num_queues: u16) -> Self {
+        Self::new(queue_port_range(queue_id, num_queues))
+    }
+
+    /// The ports this allocator chooses from.
+    pub fn range(&self) -> RangeInclusive<u16> {
+        self.range.clone()
+    }
+
+    /// The next port for which `in_use` returns false, or `None` if every
+    /// port in the range is taken.
+    pub fn next_free(&mut self, in_use: impl Fn(u16) -> bool) -> Option<u16> {
+        self.next_free_matching(in_use, |_| true)
+    }
+
+    /// Like [`next_free`](Self::next_free), but also skips ports `accept`
+    /// rejects, e.g. ports whose reply would hash to another RX queue.
+    pub fn next_free_matching(
+        &mut self,
+        in_use: impl Fn(u16) -> bool,
+        accept: impl Fn(u16) -> bool,
+    ) -> Option<u16> {
+        let (start, end) = (*self.range.start(), *self.range.end());
+        let len = end as u32 - start as u32 + 1;
+        for _ in 0..len {
+            let port = self.next;
+            self.next = if port == end { start } else { port + 1 };
+            if !in_use(port) && accept(port) {
+                return Some(port);
+            }
+        }
+        None
+    }
+}
+
+impl Default for EphemeralPorts {
+    fn default() -> Self {
+        Self::new(EPHEMERAL_PORTS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queue_ranges_are_disjoint_and_complete() {
+        for num_queues in [1u16, 3, 4, 7] {
+            let ranges: Vec<_> = (0..num_queues)
+                .map(|q| queue_port_range(q, num_queues))
+                .collect();
+            assert_eq!(*ranges[0].start(), *EPHEMERAL_PORTS.start());
+            assert_eq!(*ranges.last().unwrap().end(), *EPHEMERAL_PORTS.end());
+            for pair in ranges.windows(2) {
+                assert_eq!(*pair[0].end() + 1, *pair[1].start());
+            }
+        }
+    }
+
+    #[test]
+    fn test_next_free_wraps_and_skips() {
+        let mut ports = EphemeralPorts::new(100..=102);
+        assert_eq!(ports.next_free(|p| p == 100), Some(101));
+        assert_eq!(ports.next_free(|_| false), Some(102));
+        assert_eq!(ports.next_free(|_| false), Some(100));
+        assert_eq!(ports.next_free(|_| true), None);
+        assert_eq!(ports.next_free_matching(|_| false, |p| p == 102), Some(102));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_queue_out_of_range() {
+        queue_port_range(4, 4);
+    }
+}
@@ -3,16 +3,30 @@
 //! The reactor drives the network stack by continuously polling DPDK for packets
 //! and processing them through smoltcp.
 
+use super::config::{ReactorConfig, check_routes};
+use super::ports::EphemeralPorts;
+use super::time::{
+    Elapsed, Interval, Sleep, TimerHeap, reactor_interval_at, reactor_sleep_until, with_deadline,
+};
+use crate::api::Errno;
+use crate::api::rte::eth::EthDev;
 use crate::device::DpdkDevice;
+use crate::socket::TcpSocketOptions;
 
-use smoltcp::iface::{Interface, PollIngressSingleResult, SocketHandle, SocketSet};
-use smoltcp::phy::Device;
+use smoltcp::iface::{
+    Config, Interface, MulticastError, PollIngressSingleResult, PollResult, Route, SocketHandle,
+    SocketSet,
+};
+use smoltcp::phy::{Device, DeviceCapabilities};
 use smoltcp::time::Instant;
+use smoltcp::wire::{IpListenEndpoint, Ipv4Address};
 use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::future::Future;
 use std::pin::Pin;
 use std::rc::Rc;
-use std::task::{Context, Poll};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
 
 /// Yield control back to the async runtime scheduler.
 ///
@@ -39,9 +53,270 @@ fn yield_now() -> impl Future<Output = ()> {
     YieldNow(false)
 }
 
+/// How the reactor loop hands control back to the executor.
+///
+/// [`Reactor::run_with`] awaits [`yield_now`](Self::yield_now) after a pass
+/// that did work, and [`poll_delay`](Self::poll_delay) after an idle one.
+/// Implement it for an executor that has timers to let an idle reactor
+/// sleep instead of spinning, and to back [`ReactorHandle::sleep`] with
+/// them; the defaults just yield and use the reactor's own timer heap,
+/// which is what [`SpinRuntime`] does.
+///
+/// ```ignore
+/// struct Tokio;
+///
+/// impl Runtime for Tokio {
+///     fn yield_now() -> impl Future<Output = ()> {
+///         tokio::task::yield_now()
+///     }
+///
+///     fn poll_delay(delay: Duration) -> impl Future<Output = ()> {
+///         // Needs a runtime built with `enable_time()`
+///         tokio::time::sleep(delay)
+///     }
+///
+///     fn timer(deadline: std::time::Instant) -> Option<Pin<Box<dyn Future<Output = ()>>>> {
+///         Some(Box::pin(tokio::time::sleep_until(deadline.into())))
+///     }
+/// }
+/// ```
+pub trait Runtime {
+    /// Let other tasks run, then resume.
+    fn yield_now() -> impl Future<Output = ()>;
+
+    /// Wait about `delay` (never zero) with nothing to do.
+    ///
+    /// Sleeping longer delays received packets and anything tasks send in
+    /// the meantime, since only the next pass moves them. Timers with a
+    /// coarser resolution (tokio's is one millisecond) round up to it.
+    fn poll_delay(delay: Duration) -> impl Future<Output = ()> {
+        let _ = delay;
+        Self::yield_now()
+    }
+
+    /// A timer completing at `deadline`, for the [`ReactorHandle::sleep`],
+    /// [`timeout`](ReactorHandle::timeout) and
+    /// [`interval`](ReactorHandle::interval) created while the reactor runs
+    /// on this runtime.
+    ///
+    /// The default `None` parks them in the reactor's timer heap, which the
+    /// loop fires on each pass and which works on any executor.
+    fn timer(deadline: std::time::Instant) -> Option<Pin<Box<dyn Future<Output = ()>>>> {
+        let _ = deadline;
+        None
+    }
+}
+
+/// [`Runtime`] that never sleeps: works with any executor, at the cost of a
+/// fully busy core. Used by [`Reactor::run`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SpinRuntime;
+
+impl Runtime for SpinRuntime {
+    fn yield_now() -> impl Future<Output = ()> {
+        yield_now()
+    }
+}
+
+/// Longest [`Reactor::run_with`] waits after an idle pass.
+///
+/// DPDK raises no event when packets arrive, so this is also the added
+/// receive latency when the reactor was idle.
+pub const DEFAULT_IDLE_POLL_INTERVAL: Duration = Duration::from_micros(100);
+
 /// Default number of packets to process before yielding to other tasks.
 /// This balances responsiveness with throughput.
-const DEFAULT_INGRESS_BATCH_SIZE: usize = 32;
+pub const DEFAULT_INGRESS_BATCH_SIZE: usize = 32;
+
+/// How much work one pass of [`Reactor::run_with_config`] does, and what
+/// the loop does between passes.
+///
+/// Not to be confused with [`ReactorConfig`], which sets up the interface
+/// the loop drives.
+///
+/// ```ignore
+/// let config = PollConfig::new().ingress_batch(64).egress_batch(64);
+/// reactor.run_with_config::<SpinRuntime>(config, cancel).await;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PollConfig {
+    /// Received packets processed per pass.
+    pub ingress_batch: usize,
+    /// Transmissions per pass; `None` sends everything that is ready.
+    ///
+    /// Without a cap, a pass after a burst of writes (or of ACK-eliciting
+    /// ingress) keeps the thread until every socket's queue is on the wire.
+    /// With one, the rest waits for the next pass, after other tasks ran.
+    pub egress_batch: Option<usize>,
+    /// Longest wait after an idle pass (see [`Reactor::run_with`]); `None`
+    /// never waits and only yields.
+    pub idle_sleep: Option<Duration>,
+}
+
+impl PollConfig {
+    /// [`DEFAULT_INGRESS_BATCH_SIZE`] packets in, no egress cap, and no
+    /// idle sleep: what [`Reactor::run`] does.
+    pub fn new() -> Self {
+        Self {
+            ingress_batch: DEFAULT_INGRESS_BATCH_SIZE,
+            egress_batch: None,
+            idle_sleep: None,
+        }
+    }
+
+    /// Set the packets processed per pass (at least 1).
+    pub fn ingress_batch(mut self, packets: usize) -> Self {
+        self.ingress_batch = packets.max(1);
+        self
+    }
+
+    /// Cap the transmissions per pass (at least 1).
+    pub fn egress_batch(mut self, packets: usize) -> Self {
+        self.egress_batch = Some(packets.max(1));
+        self
+    }
+
+    /// Wait up to `max` after an idle pass; needs a [`Runtime`] with timers.
+    pub fn idle_sleep(mut self, max: Duration) -> Self {
+        self.idle_sleep = Some(max);
+        self
+    }
+}
+
+impl Default for PollConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Device adapter that hands out at most `left` transmit tokens, used to
+/// bound one egress pass. It never receives.
+struct EgressBudget<'d, D: Device> {
+    device: &'d mut D,
+    left: usize,
+    /// A transmit was refused because the budget ran out
+    exhausted: bool,
+}
+
+impl<'d, D: Device> Device for EgressBudget<'d, D> {
+    type RxToken<'a>
+        = D::RxToken<'a>
+    where
+        Self: 'a;
+    type TxToken<'a>
+        = D::TxToken<'a>
+    where
+        Self: 'a;
+
+    fn receive(&mut self, _timestamp: Instant) -> Option<(Self::RxToken<'_>, Self::TxToken<'_>)> {
+        None
+    }
+
+    fn transmit(&mut self, timestamp: Instant) -> Option<Self::TxToken<'_>> {
+        if self.left == 0 {
+            self.exhausted = true;
+            return None;
+        }
+        let token = self.device.transmit(timestamp)?;
+        self.left -= 1;
+        Some(token)
+    }
+
+    fn capabilities(&self) -> DeviceCapabilities {
+        self.device.capabilities()
+    }
+}
+
+/// Default number of socket operations allowed between reactor polls.
+pub const DEFAULT_YIELD_BUDGET: usize = 128;
+
+/// Default for [`ReactorHandle::set_stall_warning`] in debug builds.
+pub const DEFAULT_STALL_WARN_POLLS: u32 = 1024;
+
+/// How long the reactor must go without a pass before a stall is reported.
+/// A burst of wakeups can legitimately produce many `Pending` returns
+/// between two passes; it cannot make a running reactor skip a second.
+const STALL_WARN_AFTER: Duration = Duration::from_secs(1);
+
+/// Detects socket futures waiting on a reactor that is not polling.
+#[derive(Debug)]
+struct StallCheck {
+    /// `Pending` returns before warning; `None` disables the check.
+    warn_after: Option<u32>,
+    /// Reactor generation when the current count started.
+    generation: u64,
+    /// When the current count started.
+    since: std::time::Instant,
+    /// `Pending` returns seen during `generation`.
+    pending: u32,
+    /// Already warned during `generation`.
+    warned: bool,
+}
+
+impl StallCheck {
+    fn new() -> Self {
+        Self {
+            warn_after: cfg!(debug_assertions).then_some(DEFAULT_STALL_WARN_POLLS),
+            generation: 0,
+            since: std::time::Instant::now(),
+            pending: 0,
+            warned: false,
+        }
+    }
+}
+
+/// What one [`ReactorHandle::poll_once`] pass did.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PollActivity {
+    /// Packets taken from the RX queue and processed.
+    pub packets_processed: usize,
+    /// Some socket changed state (data, connection state, buffer space),
+    /// so wakers may have fired.
+    pub sockets_changed: bool,
+    /// The ingress batch limit was hit, so more packets are likely waiting,
+    /// or the egress cap left packets to transmit.
+    pub more_pending: bool,
+    /// Reactor timers that expired and woke their task.
+    pub timers_fired: usize,
+}
+
+impl PollActivity {
+    /// Returns true if nothing was received, no socket changed and no timer
+    /// fired.
+    pub fn is_idle(&self) -> bool {
+        self.packets_processed == 0 && !self.sockets_changed && self.timers_fired == 0
+    }
+}
+
+/// Cumulative counters of a reactor's loop, from [`ReactorHandle::stats`].
+///
+/// Counted by every pass, whether driven by [`Reactor::run`] (and its
+/// variants) or by [`ReactorHandle::poll_once`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReactorStats {
+    /// Passes completed; the same count as [`ReactorHandle::generation`].
+    pub passes: u64,
+    /// Passes that received nothing and changed no socket.
+    pub idle_passes: u64,
+    /// Received packets processed.
+    pub packets_processed: u64,
+    /// Egress polls, including the inline ones of eager egress.
+    pub egress_polls: u64,
+    /// Closed sockets, left behind by dropped streams, removed from the set.
+    pub orphans_cleaned: u64,
+    /// Reactor timers ([`ReactorHandle::sleep`], [`ReactorHandle::timeout`])
+    /// that expired and woke their task.
+    pub timers_fired: u64,
+}
+
+/// How a `TcpListener` backlog socket was set up, so the reactor can
+/// rebuild it in place.
+pub(crate) struct ListenSpec {
+    pub(crate) endpoint: IpListenEndpoint,
+    pub(crate) options: TcpSocketOptions,
+    /// Waker of the listener's pending `accept`, if any.
+    pub(crate) accept_waker: Option<Waker>,
+}
 
 /// Shared state for the async reactor
 ///
@@ -57,6 +332,49 @@ pub struct ReactorInner<D: Device> {
     /// Orphaned sockets that are in graceful close but no longer owned by a TcpStream.
     /// These will be cleaned up once they reach Closed or TimeWait state.
     pub(crate) orphaned_closing: Vec<SocketHandle>,
+    /// Data queued by `TcpStream::connect_with_data`, written as soon as the
+    /// handshake completes and before the reactor's next egress.
+    pub(crate) early_data: Vec<(SocketHandle, Vec<u8>)>,
+    /// Cap on the number of sockets in `sockets`, checked when TCP sockets
+    /// are created. `None` means unlimited.
+    pub(crate) max_sockets: Option<usize>,
+    /// Cap on the bytes of socket buffers in `sockets`, checked when TCP
+    /// sockets are created. `None` means unlimited.
+    pub(crate) max_buffer_bytes: Option<usize>,
+    /// Listeners waiting for room for one more socket with this many bytes
+    /// of buffers, to refill their backlog. Woken by `poll_pass`.
+    pub(crate) capacity_waiters: Vec<(Waker, usize)>,
+    /// Socket operations allowed between reactor polls; `None` disables the budget.
+    pub(crate) yield_budget: Option<usize>,
+    /// Socket operations since the reactor last polled.
+    pub(crate) ops_since_poll: usize,
+    /// Cap on sockets in `SynReceived`; `None` means unlimited.
+    pub(crate) max_half_open: Option<usize>,
+    /// Half-open sockets admitted under `max_half_open`, oldest first.
+    half_open: Vec<SocketHandle>,
+    /// SYNs dropped because `max_half_open` was reached.
+    syn_dropped: u64,
+    /// Setup of every `TcpListener` backlog socket, by handle.
+    pub(crate) listen_specs: HashMap<SocketHandle, ListenSpec>,
+    /// Connections handed out by `TcpListener::accept`.
+    pub(crate) connections_accepted: u64,
+    /// Transmit SYNs and first writes immediately instead of on the next poll.
+    pub(crate) eager_egress: bool,
+    /// Local ports handed out by `ReactorHandle::alloc_ephemeral_port`.
+    pub(crate) ephemeral_ports: EphemeralPorts,
+    /// Reactor passes completed, bumped by every `poll_pass`.
+    pub(crate) generation: u64,
+    /// Wakers of `ReactorHandle::sleep` timers, fired by every `poll_pass`.
+    pub(crate) timers: Rc<TimerHeap>,
+    /// `ReactorConfig::random_seed`, the base seed when `iface` is rebuilt.
+    random_seed: u64,
+    /// Groups joined through `ReactorHandle::join_multicast_v4`, rejoined
+    /// when `iface` is rebuilt.
+    multicast_groups: Vec<Ipv4Address>,
+    /// Loop counters; `passes` is filled in from `generation` on read.
+    stats: ReactorStats,
+    /// Stall detection for socket futures; see `note_pending`.
+    stall: StallCheck,
 }
 
 impl<D: Device> ReactorInner<D> {
@@ -74,14 +392,326 @@ impl<D: Device> ReactorInner<D> {
     }
 
     /// Transmit queued packets (bounded work).
-    fn poll_egress(&mut self, timestamp: Instant) {
+    fn poll_egress(&mut self, timestamp: Instant) -> PollResult {
+        self.stats.egress_polls += 1;
         let ReactorInner {
             device,
             iface,
             sockets,
             ..
         } = self;
-        iface.poll_egress(timestamp, device, sockets);
+        iface.poll_egress(timestamp, device, sockets)
+    }
+
+    /// Transmit queued packets, at most `limit` of them.
+    ///
+    /// Also returns whether the limit cut the pass short.
+    fn poll_egress_limited(&mut self, timestamp: Instant, limit: usize) -> (PollResult, bool) {
+        self.stats.egress_polls += 1;
+        let ReactorInner {
+            device,
+            iface,
+            sockets,
+            ..
+        } = self;
+        let mut budget = EgressBudget {
+            device,
+            left: limit,
+            exhausted: false,
+        };
+        let result = iface.poll_egress(timestamp, &mut budget, sockets);
+        (result, budget.exhausted)
+    }
+
+    /// Time until smoltcp's or the timer heap's next deadline; see
+    /// `ReactorHandle::poll_delay`.
+    fn poll_delay(&mut self, now: Instant) -> Option<Duration> {
+        let ReactorInner {
+            iface,
+            sockets,
+            timers,
+            ..
+        } = self;
+        let smoltcp = iface.poll_delay(now, sockets).map(Duration::from);
+        let timer = timers
+            .next_deadline()
+            .map(|deadline| deadline.saturating_duration_since(std::time::Instant::now()));
+        match (smoltcp, timer) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+
+    /// One pass of the reactor loop: up to `batch_size` ingress packets,
+    /// then egress (up to `egress_batch` packets) and orphan cleanup.
+    pub(super) fn poll_pass(
+        &mut self,
+        timestamp: Instant,
+        batch_size: usize,
+        egress_batch: Option<usize>,
+    ) -> PollActivity {
+        let mut activity = PollActivity::default();
+
+        // Wake expired timers first; their tasks run once this pass yields
+        activity.timers_fired = self.timers.fire_expired(std::time::Instant::now());
+        self.stats.timers_fired += activity.timers_fired as u64;
+
+        // Process ingress in batches
+        loop {
+            match self.poll_ingress_single(timestamp) {
+                PollIngressSingleResult::None => break,
+                result => {
+                    activity.packets_processed += 1;
+                    activity.sockets_changed |=
+                        matches!(result, PollIngressSingleResult::SocketStateChanged);
+                    if activity.packets_processed >= batch_size {
+                        // Hit batch limit - break to run egress before yielding
+                        // This prevents DoS: we must send ACKs/responses, not just receive
+                        activity.more_pending = true;
+                        break;
+                    }
+                }
+            }
+        }
+
+        // Process egress (bounded work - just transmits queued packets)
+        if activity.packets_processed > 0 {
+            self.enforce_half_open_limit();
+        }
+        self.flush_early_data();
+        let egress = match egress_batch {
+            Some(limit) => {
+                let (result, exhausted) = self.poll_egress_limited(timestamp, limit);
+                activity.more_pending |= exhausted;
+                result
+            }
+            None => self.poll_egress(timestamp),
+        };
+        activity.sockets_changed |= matches!(egress, PollResult::SocketStateChanged);
+        self.ops_since_poll = 0;
+        self.generation = self.generation.wrapping_add(1);
+        self.stats.packets_processed += activity.packets_processed as u64;
+        if activity.is_idle() {
+            self.stats.idle_passes += 1;
+        }
+
+        // Clean up orphaned closing sockets that have completed their handshake
+        self.cleanup_orphaned();
+        self.wake_capacity_waiters();
+        activity
+    }
+
+    /// Move queued early data into sockets whose handshake has completed.
+    ///
+    /// Runs between ingress and egress so the first data segment leaves in
+    /// the same poll that processed the SYN-ACK. Entries for sockets that
+    /// closed before establishing are dropped.
+    pub(crate) fn flush_early_data(&mut self) {
+        use smoltcp::socket::tcp::{Socket, State};
+
+        if self.early_data.is_empty() {
+            return;
+        }
+        let sockets = &mut self.sockets;
+        self.early_data.retain_mut(|(handle, data)| {
+            let socket = sockets.get_mut::<Socket>(*handle);
+            if socket.may_send() {
+                if let Ok(n) = socket.send_slice(data) {
+                    data.drain(..n);
+                }
+                !data.is_empty()
+            } else {
+                !matches!(socket.state(), State::Closed | State::TimeWait)
+            }
+        });
+    }
+
+    /// Returns true if early data for `handle` is still waiting to be written.
+    pub(crate) fn has_early_data(&self, handle: SocketHandle) -> bool {
+        self.early_data.iter().any(|(h, _)| *h == handle)
+    }
+
+    /// Charge one socket operation against the yield budget.
+    ///
+    /// Once the budget is spent, returns `Pending` after waking the caller,
+    /// which sends the task to the back of the run queue. The reactor resets
+    /// the budget on its next poll, so the task resumes after the reactor and
+    /// other ready tasks have run.
+    pub(crate) fn poll_budget(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        if self.budget_exhausted() {
+            cx.waker().wake_by_ref();
+            self.note_pending();
+            return Poll::Pending;
+        }
+        self.ops_since_poll += 1;
+        Poll::Ready(())
+    }
+
+    /// Record that a socket future is about to return `Pending`.
+    ///
+    /// Debug builds only. Once `warn_after` consecutive `Pending` returns
+    /// and at least [`STALL_WARN_AFTER`] pass without a reactor pass, warns
+    /// once that the reactor may not be running; the next pass rearms it.
+    pub(crate) fn note_pending(&mut self) {
+        if !cfg!(debug_assertions) {
+            return;
+        }
+        let Some(warn_after) = self.stall.warn_after else {
+            return;
+        };
+        let stall = &mut self.stall;
+        if stall.generation != self.generation {
+            stall.generation = self.generation;
+            stall.since = std::time::Instant::now();
+            stall.pending = 0;
+            stall.warned = false;
+        }
+        stall.pending = stall.pending.saturating_add(1);
+        if stall.pending >= warn_after && !stall.warned {
+            let stalled_for = stall.since.elapsed();
+            if stalled_for >= STALL_WARN_AFTER {
+                tracing::warn!(
+                    pending_polls = stall.pending,
+                    ?stalled_for,
+                    generation = self.generation,
+                    "Socket futures keep waiting but the reactor has not polled; \
+                     is Reactor::run spawned and is the executor free to run it?"
+                );
+                stall.warned = true;
+            }
+        }
+    }
+
+    fn budget_exhausted(&self) -> bool {
+        self.yield_budget
+            .is_some_and(|budget| self.ops_since_poll >= budget)
+    }
+
+    /// Number of sockets currently in the socket set.
+    ///
+    /// Includes listener backlog sockets and orphaned sockets that are still
+    /// closing.
+    pub(crate) fn socket_count(&self) -> usize {
+        self.sockets.iter().count()
+    }
+
+    /// Returns true if one more socket fits under the cap.
+    pub(crate) fn has_socket_capacity(&self) -> bool {
+        self.max_sockets.is_none_or(|max| self.socket_count() < max)
+    }
+
+    /// Bytes of rx and tx buffer held by the sockets in the socket set.
+    ///
+    /// Summed from the sockets themselves, so it stays exact however a
+    /// socket leaves the set. UDP payload buffers count; packet metadata
+    /// does not.
+    pub(crate) fn buffer_bytes(&self) -> usize {
+        use smoltcp::socket::Socket;
+
+        self.sockets
+            .iter()
+            .map(|(_, s)| match s {
+                Socket::Tcp(t) => t.recv_capacity() + t.send_capacity(),
+                Socket::Udp(u) => u.payload_recv_capacity() + u.payload_send_capacity(),
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// Returns true if `bytes` more of socket buffers fit under the cap.
+    pub(crate) fn has_buffer_capacity(&self, bytes: usize) -> bool {
+        self.max_buffer_bytes
+            .is_none_or(|max| self.buffer_bytes().saturating_add(bytes) <= max)
+    }
+
+    /// Wake the listeners in `capacity_waiters` whose socket now fits under
+    /// the caps.
+    fn wake_capacity_waiters(&mut self) {
+        if self.capacity_waiters.is_empty() || !self.has_socket_capacity() {
+            return;
+        }
+        let buffer_bytes = self.buffer_bytes();
+        let max_buffer_bytes = self.max_buffer_bytes;
+        self.capacity_waiters.retain(|(waker, bytes)| {
+            let fits =
+                max_buffer_bytes.is_none_or(|max| buffer_bytes.saturating_add(*bytes) <= max);
+            if fits {
+                waker.wake_by_ref();
+            }
+            !fits
+        });
+    }
+
+    /// Returns true if a TCP socket is bound to, or listening on, local `port`.
+    pub(crate) fn local_port_in_use(&self, port: u16) -> bool {
+        use smoltcp::socket::{Socket, tcp::State};
+
+        self.sockets.iter().any(|(_, s)| match s {
+            Socket::Tcp(t) => {
+                t.local_endpoint().is_some_and(|ep| ep.port == port)
+                    || (t.state() == State::Listen && t.listen_endpoint().port == port)
+            }
+            _ => false,
+        })
+    }
+
+    /// Number of sockets in `SynReceived` (SYN seen, handshake not complete).
+    pub(crate) fn half_open_count(&self) -> usize {
+        use smoltcp::socket::{Socket, tcp::State};
+
+        self.sockets
+            .iter()
+            .filter(|(_, s)| matches!(s, Socket::Tcp(t) if t.state() == State::SynReceived))
+            .count()
+    }
+
+    /// Drop half-open connections beyond `max_half_open`.
+    ///
+    /// Runs after ingress and before egress, so a SYN that would exceed the
+    /// limit never gets a SYN-ACK. Its socket is swapped in place for a fresh
+    /// listening socket with the listener's endpoint and options, which keeps
+    /// the owning `TcpListener`'s handle valid, and its pending `accept` is
+    /// woken to wait on the new socket. Connections admitted earlier keep
+    /// their slot until they complete or fail.
+    fn enforce_half_open_limit(&mut self) {
+        use smoltcp::socket::{Socket, tcp};
+
+        let Some(max) = self.max_half_open else {
+            return;
+        };
+
+        let sockets = &self.sockets;
+        let is_half_open =
+            |h: &SocketHandle| sockets.get::<tcp::Socket>(*h).state() == tcp::State::SynReceived;
+        self.half_open.retain(is_half_open);
+
+        let new: Vec<SocketHandle> = self
+            .sockets
+            .iter()
+            .filter(|(h, s)| {
+                matches!(s, Socket::Tcp(t) if t.state() == tcp::State::SynReceived)
+                    && !self.half_open.contains(h)
+            })
+            .map(|(h, _)| h)
+            .collect();
+
+        for handle in new {
+            if self.half_open.len() < max {
+                self.half_open.push(handle);
+                continue;
+            }
+            let Some(spec) = self.listen_specs.get_mut(&handle) else {
+                continue;
+            };
+            let mut fresh = spec.options.socket();
+            if fresh.listen(spec.endpoint).is_ok() {
+                *self.sockets.get_mut::<tcp::Socket>(handle) = fresh;
+                self.syn_dropped += 1;
+                if let Some(waker) = spec.accept_waker.take() {
+                    waker.wake();
+                }
+            }
+        }
     }
 
     /// Clean up orphaned sockets that have completed their graceful close.
@@ -96,12 +726,54 @@ impl<D: Device> ReactorInner<D> {
                 State::Closed | State::TimeWait => {
                     // Socket is fully closed, remove it
                     self.sockets.remove(handle);
+                    self.stats.orphans_cleaned += 1;
                     false // Remove from orphan list
                 }
                 _ => true, // Keep in orphan list, still closing
             }
         });
     }
+
+    /// Recreate `iface` so it picks up the device's current capabilities.
+    ///
+    /// smoltcp reads them once, in `Interface::new`. The addresses, routes,
+    /// any-IP setting and the multicast groups joined through the handle
+    /// carry over; the neighbor cache starts empty.
+    fn rebuild_iface(&mut self) {
+        let mut config = Config::new(self.iface.hardware_addr());
+        // Vary the seed so the new interface does not replay the old one's
+        // initial sequence numbers
+        config.random_seed = self.random_seed ^ self.generation;
+        let mut iface = Interface::new(config, &mut self.device, Instant::now());
+        iface.set_any_ip(self.iface.any_ip());
+        iface.set_reassembly_timeout(self.iface.reassembly_timeout());
+        iface.update_ip_addrs(|addrs| addrs.extend(self.iface.ip_addrs().iter().copied()));
+
+        let mut routes = Default::default();
+        self.iface
+            .routes_mut()
+            .update(|table| routes = table.clone());
+        iface.routes_mut().update(|table| *table = routes);
+
+        for &group in &self.multicast_groups {
+            if let Err(e) = iface.join_multicast_group(group) {
+                tracing::warn!(%group, error = %e, "Failed to rejoin multicast group");
+            }
+        }
+        self.iface = iface;
+    }
+}
+
+impl ReactorInner<DpdkDevice> {
+    /// Run an egress pass and push the resulting frames to the NIC now.
+    ///
+    /// The regular loop leaves egress frames in the device's TX batch until
+    /// the next ingress poll flushes it, so without this a SYN written by
+    /// `connect` waits for the executor to come back around to the reactor.
+    pub(crate) fn egress_now(&mut self) {
+        self.poll_egress(Instant::now());
+        self.device.flush_tx();
+    }
 }
 
 /// The async reactor that drives DPDK + smoltcp
@@ -121,10 +793,111 @@ impl Reactor<DpdkDevice> {
                 iface,
                 sockets: SocketSet::new(vec![]),
                 orphaned_closing: Vec::new(),
+                early_data: Vec::new(),
+                max_sockets: None,
+                max_buffer_bytes: None,
+                capacity_waiters: Vec::new(),
+                yield_budget: Some(DEFAULT_YIELD_BUDGET),
+                ops_since_poll: 0,
+                max_half_open: None,
+                half_open: Vec::new(),
+                syn_dropped: 0,
+                listen_specs: HashMap::new(),
+                connections_accepted: 0,
+                eager_egress: true,
+                ephemeral_ports: EphemeralPorts::default(),
+                generation: 0,
+                timers: Rc::default(),
+                random_seed: 0,
+                multicast_groups: Vec::new(),
+                stats: ReactorStats::default(),
+                stall: StallCheck::new(),
             })),
         }
     }
 
+    /// Create a reactor, building the smoltcp interface from `config`.
+    ///
+    /// Applies the checksum capabilities to the device, then creates the
+    /// `Interface` with the configured addresses, default route, extra
+    /// routes, random seed and any-IP setting.
+    ///
+    /// Returns an error if the addresses exceed smoltcp's per-interface limit,
+    /// the routes fail [`check_routes`](super::check_routes) or do not fit the
+    /// route table, or the ephemeral port range is empty.
+    pub fn new_with_config(mut device: DpdkDevice, config: ReactorConfig) -> crate::Result<Self> {
+        device.set_checksum_caps(config.checksum.clone());
+
+        let mut iface_config = Config::new(config.hardware_addr.into());
+        iface_config.random_seed = config.random_seed;
+        let mut iface = Interface::new(iface_config, &mut device, Instant::now());
+        iface.set_any_ip(config.any_ip);
+
+        let mut overflow = false;
+        iface.update_ip_addrs(|ip_addrs| {
+            for cidr in &config.ip_addrs {
+                if ip_addrs.push(*cidr).is_err() {
+                    overflow = true;
+                    break;
+                }
+            }
+        });
+        if overflow {
+            return Err(format!(
+                "too many IP addresses ({}) for smoltcp interface",
+                config.ip_addrs.len()
+            )
+            .into());
+        }
+
+        if let Some(gateway) = config.ipv4_gateway {
+            iface
+                .routes_mut()
+                .add_default_ipv4_route(gateway)
+                .map_err(|_| "smoltcp route table full")?;
+        }
+
+        check_routes(config.ipv4_gateway, &config.routes)?;
+        let mut full = false;
+        iface.routes_mut().update(|table| {
+            for &(cidr, via_router) in &config.routes {
+                let route = Route {
+                    cidr,
+                    via_router,
+                    preferred_until: None,
+                    expires_at: None,
+                };
+                if table.push(route).is_err() {
+                    full = true;
+                    break;
+                }
+            }
+        });
+        if full {
+            return Err(format!(
+                "too many routes ({}) for smoltcp route table",
+                config.routes.len() + config.ipv4_gateway.is_some() as usize
+            )
+            .into());
+        }
+
+        let ports = &config.ephemeral_ports;
+        if ports.is_empty() || *ports.start() == 0 {
+            return Err(format!("invalid ephemeral port range {ports:?}").into());
+        }
+
+        let reactor = Self::new(device, iface);
+        {
+            let mut inner = reactor.inner.borrow_mut();
+            inner.max_sockets = config.max_sockets;
+            inner.max_buffer_bytes = config.max_buffer_bytes;
+            inner.max_half_open = config.max_half_open;
+            inner.ephemeral_ports = EphemeralPorts::new(config.ephemeral_ports);
+            inner.random_seed = config.random_seed;
+        }
+        Ok(reactor)
+    }
+
     /// Get a handle to the reactor's inner state (for creating sockets)
     pub fn handle(&self) -> ReactorHandle {
         ReactorHandle {
@@ -197,46 +970,60 @@ impl Reactor<DpdkDevice> {
     /// # }
     /// ```
     pub async fn run_with_batch_size(self, batch_size: usize, cancel: Rc<Cell<bool>>) {
-        while !cancel.get() {
-            let timestamp = Instant::now();
-            let mut packets_processed = 0;
-
-            // Process ingress in batches
-            loop {
-                let result = {
-                    let mut inner = self.inner.borrow_mut();
-                    inner.poll_ingress_single(timestamp)
-                };
-
-                match result {
-                    PollIngressSingleResult::None => break,
-                    _ => {
-                        packets_processed += 1;
-                        if packets_processed >= batch_size {
-                            // Hit batch limit - break to run egress before yielding
-                            // This prevents DoS: we must send ACKs/responses, not just receive
-                            break;
-                        }
-                    }
-                }
-            }
+        self.run_with::<SpinRuntime>(batch_size, cancel).await
+    }
 
-            // Process egress (bounded work - just transmits queued packets)
-            {
-                let mut inner = self.inner.borrow_mut();
-                inner.poll_egress(timestamp);
-            }
+    /// Run the reactor, letting runtime `R` wait out idle periods.
+    ///
+    /// After a pass that received nothing and changed no socket, the loop
+    /// awaits [`R::poll_delay`](Runtime::poll_delay) for smoltcp's next timer
+    /// deadline, capped at [`DEFAULT_IDLE_POLL_INTERVAL`] so new packets are
+    /// still picked up promptly. Otherwise it awaits
+    /// [`R::yield_now`](Runtime::yield_now) and polls again. With
+    /// [`SpinRuntime`] this is [`run_with_batch_size`](Self::run_with_batch_size).
+    ///
+    /// An executor only gets to sleep when no task is ready. Timers from
+    /// [`ReactorHandle::sleep`] and [`ReactorHandle::interval`] do not keep
+    /// it busy while pending, and the idle wait also ends at their earliest
+    /// deadline.
+    pub async fn run_with<R: Runtime>(self, batch_size: usize, cancel: Rc<Cell<bool>>) {
+        let config = PollConfig::new()
+            .ingress_batch(batch_size)
+            .idle_sleep(DEFAULT_IDLE_POLL_INTERVAL);
+        self.run_with_config::<R>(config, cancel).await
+    }
 
-            // Clean up orphaned closing sockets that have completed their handshake
-            {
+    /// Run the reactor with the batch limits and idle wait from `config`.
+    ///
+    /// Each pass processes up to `ingress_batch` received packets, then
+    /// transmits up to `egress_batch` packets, so replies to a burst go out
+    /// between chunks of it rather than after all of it. When either limit
+    /// is hit, the loop only yields before the next pass. After an idle
+    /// pass it waits through [`R::poll_delay`](Runtime::poll_delay) for up
+    /// to `idle_sleep`, if set. Timers created meanwhile use
+    /// [`R::timer`](Runtime::timer).
+    pub async fn run_with_config<R: Runtime>(self, config: PollConfig, cancel: Rc<Cell<bool>>) {
+        let timers = self.inner.borrow().timers.clone();
+        timers.set_runtime_timer(Some(R::timer));
+        while !cancel.get() {
+            let idle_delay = {
+                let now = Instant::now();
                 let mut inner = self.inner.borrow_mut();
-                inner.cleanup_orphaned();
-            }
+                let activity = inner.poll_pass(now, config.ingress_batch, config.egress_batch);
+                config
+                    .idle_sleep
+                    .filter(|_| activity.is_idle() && !activity.more_pending)
+                    .map(|max| inner.poll_delay(now).map_or(max, |d| d.min(max)))
+            };
 
-            // Yield to let other async tasks run (accept handlers, recv futures, etc.)
+            // Let other async tasks run (accept handlers, recv futures, etc.)
             // Without this, spawned tasks would starve during idle periods
-            yield_now().await;
+            match idle_delay {
+                Some(delay) if !delay.is_zero() => R::poll_delay(delay).await,
+                _ => R::yield_now().await,
+            }
         }
+        timers.set_runtime_timer(None);
     }
 }
 
@@ -254,4 +1041,474 @@ impl ReactorHandle {
         let inner = self.inner.borrow();
         inner.iface.ip_addrs().first().map(|cidr| cidr.address())
     }
+
+    /// Join the IPv4 multicast group `group`.
+    ///
+    /// smoltcp announces the membership with an IGMP report on the next
+    /// pass and from then on delivers datagrams sent to `group` to UDP
+    /// sockets bound to the destination port. Joining a group twice is not
+    /// an error.
+    ///
+    /// The NIC drops multicast frames its MAC filter does not list, so this
+    /// also puts the port in all-multicast mode. That is port-wide and stays
+    /// on after [`leave_multicast_v4`](Self::leave_multicast_v4), since other
+    /// reactors on the port may still be members; turn it off with
+    /// [`EthDev::allmulticast_disable`](crate::api::rte::eth::EthDev::allmulticast_disable)
+    /// once none are. A driver that cannot enable it is logged and ignored,
+    /// as the port may be promiscuous already. With several RX queues, RSS
+    /// decides which queue's reactor receives a group's traffic, so the
+    /// reactor that joins is not necessarily the one that sees it.
+    pub fn join_multicast_v4(&self, group: Ipv4Address) -> Result<(), MulticastError> {
+        let mut inner = self.inner.borrow_mut();
+        inner.iface.join_multicast_group(group)?;
+        if !inner.multicast_groups.contains(&group) {
+            inner.multicast_groups.push(group);
+        }
+
+        let port_id = inner.device.port_id();
+        if let Err(errno) = EthDev::new(port_id).allmulticast_enable() {
+            tracing::warn!(port_id, %group, %errno, "Failed to enable all-multicast mode");
+        }
+        Ok(())
+    }
+
+    /// Leave the IPv4 multicast group `group`.
+    ///
+    /// smoltcp sends an IGMP leave on the next pass and stops delivering the
+    /// group's datagrams. Leaving a group that was never joined is not an
+    /// error. The port's all-multicast mode is left as it is; see
+    /// [`join_multicast_v4`](Self::join_multicast_v4).
+    pub fn leave_multicast_v4(&self, group: Ipv4Address) -> Result<(), MulticastError> {
+        let mut inner = self.inner.borrow_mut();
+        inner.iface.leave_multicast_group(group)?;
+        inner.multicast_groups.retain(|&g| g != group);
+        Ok(())
+    }
+
+    /// Returns true if this reactor's interface is a member of `group`.
+    pub fn has_multicast_group(&self, group: Ipv4Address) -> bool {
+        self.inner.borrow().iface.has_multicast_group(group)
+    }
+
+    /// The MTU the stack sends with; see [`set_mtu`](Self::set_mtu).
+    pub fn mtu(&self) -> usize {
+        self.inner.borrow().device.mtu()
+    }
+
+    /// Change the MTU of the port and of this reactor's stack, e.g. to switch
+    /// to jumbo frames once the link is up.
+    ///
+    /// Sets the port's MTU with [`EthDev::set_mtu`] unless it already has
+    /// `mtu`, has the device report the new value, and rebuilds the smoltcp
+    /// `Interface`, which reads the device's capabilities only when it is
+    /// created. The interface keeps its IP addresses, routes and the groups
+    /// joined with [`join_multicast_v4`](Self::join_multicast_v4); its
+    /// neighbor cache starts empty, so peers are resolved again. Sockets are
+    /// untouched, and established TCP connections keep the segment size
+    /// they negotiated.
+    ///
+    /// The port MTU is shared by all queues, so call this on every queue's
+    /// reactor: the first call changes the port, the others find it set.
+    ///
+    /// Returns `EINVAL` if frames of `mtu` would not fit the device's mbufs,
+    /// `EBUSY` when lowering the MTU while the reactor holds sockets, which
+    /// may already queue segments sized for the old one, and otherwise
+    /// whatever the driver refuses the change with. On error the port and
+    /// the stack keep the old MTU.
+    pub fn set_mtu(&self, mtu: usize) -> crate::api::Result<()> {
+        let mut inner = self.inner.borrow_mut();
+        let old = inner.device.mtu();
+        if mtu == old {
+            return Ok(());
+        }
+        if mtu < old && inner.sockets.iter().next().is_some() {
+            tracing::warn!(mtu, old, "Cannot lower the MTU while sockets are open");
+            return Err(Errno::EBUSY);
+        }
+        if !inner.device.set_mtu(mtu) {
+            return Err(Errno::EINVAL);
+        }
+
+        let port = EthDev::new(inner.device.port_id());
+        if port.mtu() != Ok(mtu as u32)
+            && let Err(errno) = port.set_mtu(mtu as u32)
+        {
+            inner.device.set_mtu(old);
+            return Err(errno);
+        }
+        inner.rebuild_iface();
+        Ok(())
+    }
+
+    /// Wait until `duration` has elapsed, on this reactor's clock.
+    ///
+    /// While the reactor runs on a [`Runtime`] with a
+    /// [`timer`](Runtime::timer), such as tokio's, this is that timer, so
+    /// handlers stay portable without importing the runtime. Otherwise the
+    /// waker sits in the reactor's timer heap, and the reactor loop wakes it
+    /// on the first pass at or after the deadline, shortening its idle wait
+    /// to get there on time; the resolution is then one reactor pass, and
+    /// the timer never fires once the reactor has stopped. Either way the
+    /// pending timer does not keep the executor busy.
+    pub fn sleep(&self, duration: Duration) -> Sleep {
+        self.sleep_until(std::time::Instant::now() + duration)
+    }
+
+    /// Wait until `deadline`, on this reactor's clock; see
+    /// [`sleep`](Self::sleep).
+    pub fn sleep_until(&self, deadline: std::time::Instant) -> Sleep {
+        reactor_sleep_until(self.inner.borrow().timers.clone(), deadline)
+    }
+
+    /// Run `future`, giving up with [`Elapsed`] after `duration` on this
+    /// reactor's clock.
+    ///
+    /// `future` is dropped, and so cancelled, when the time runs out. It is
+    /// polled before the timer, so one that is ready right at the deadline
+    /// still completes. `Elapsed` converts to an `io::Error` of kind
+    /// `TimedOut`.
+    pub fn timeout<F: Future>(
+        &self,
+        duration: Duration,
+        future: F,
+    ) -> impl Future<Output = Result<F::Output, Elapsed>> {
+        with_deadline(self.sleep(duration), future)
+    }
+
+    /// Tick immediately, then every `period`, on this reactor's clock; see
+    /// [`Interval`].
+    ///
+    /// Each wait for a tick is a [`sleep`](Self::sleep), so a pending tick
+    /// costs nothing per pass.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `period` is zero.
+    pub fn interval(&self, period: Duration) -> Interval {
+        self.interval_at(std::time::Instant::now(), period)
+    }
+
+    /// Tick at `start`, then every `period`, on this reactor's clock; see
+    /// [`interval`](Self::interval).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `period` is zero.
+    pub fn interval_at(&self, start: std::time::Instant, period: Duration) -> Interval {
+        reactor_interval_at(self.inner.borrow().timers.clone(), start, period)
+    }
+
+    /// Counters of the reactor's device, such as checksum audit failures.
+    pub fn device_stats(&self) -> crate::device::DeviceStats {
+        self.inner.borrow().device.stats()
+    }
+
+    /// Free mbufs in the device's mempool.
+    ///
+    /// The pool may be shared with other queues, so this is the headroom
+    /// left for all of them: a low count means frames are about to be
+    /// dropped, on RX for lack of buffers and on TX for lack of copies
+    /// (counted in
+    /// [`DeviceStats::tx_alloc_failures`](crate::device::DeviceStats::tx_alloc_failures)).
+    pub fn mbufs_available(&self) -> u32 {
+        self.inner.borrow().device.mempool().avail_count()
+    }
+
+    /// Mbufs of the device's mempool currently allocated, by any queue.
+    ///
+    /// The peak is tracked in
+    /// [`DeviceStats::mbufs_in_use_peak`](crate::device::DeviceStats::mbufs_in_use_peak).
+    pub fn mbufs_in_use(&self) -> u32 {
+        self.inner.borrow().device.mempool().in_use_count()
+    }
+
+    /// Set the cap on the number of sockets this reactor holds.
+    ///
+    /// Once the cap is reached, `TcpStream::connect` and `TcpListener::bind`
+    /// fail with `TooManySockets`, and `TcpListener::accept` resets new
+    /// connections and returns `TooManySockets` instead of allocating past
+    /// the cap; the listener's backlog runs short until there is room again.
+    /// Every socket counts: listener backlog sockets, open streams,
+    /// UDP sockets and streams still closing after drop. UDP binds are not
+    /// refused. Lowering the cap below the current count does not close
+    /// anything. `None` (the default) removes the cap.
+    pub fn set_max_sockets(&self, max: Option<usize>) {
+        self.inner.borrow_mut().max_sockets = max;
+    }
+
+    /// The current socket cap, if any.
+    pub fn max_sockets(&self) -> Option<usize> {
+        self.inner.borrow().max_sockets
+    }
+
+    /// Number of sockets currently held by the reactor.
+    pub fn socket_count(&self) -> usize {
+        self.inner.borrow().socket_count()
+    }
+
+    /// Number of dropped streams still finishing their close.
+    ///
+    /// A stream dropped before it reached `Closed` stays in the reactor
+    /// until its FIN exchange completes (or it ends up in `TimeWait`), so
+    /// the peer sees a clean close. Zero means every such close is done.
+    pub fn closing_count(&self) -> usize {
+        self.inner.borrow().orphaned_closing.len()
+    }
+
+    /// Cap the total rx+tx buffer memory of this reactor's sockets, in bytes.
+    ///
+    /// Socket buffers come from the heap, sized by each `connect`, `bind`
+    /// and `accept`, so the socket cap alone bounds memory only when every
+    /// socket has the same buffers. With this cap, `TcpStream::connect` and
+    /// `TcpListener::bind` fail with `MemoryLimit` when the new sockets'
+    /// buffers do not fit, and `TcpListener::accept` resets the connection
+    /// and returns `MemoryLimit` when the backlog socket replacing it does
+    /// not. Usage counts the same sockets as
+    /// [`set_max_sockets`](Self::set_max_sockets), and like there UDP binds
+    /// are not refused. Lowering the cap below current usage closes nothing.
+    /// `None` (the default) removes the cap.
+    pub fn set_max_buffer_bytes(&self, max: Option<usize>) {
+        self.inner.borrow_mut().max_buffer_bytes = max;
+    }
+
+    /// The current buffer memory cap, if any.
+    pub fn max_buffer_bytes(&self) -> Option<usize> {
+        self.inner.borrow().max_buffer_bytes
+    }
+
+    /// Bytes of rx and tx buffer currently held by the reactor's sockets.
+    ///
+    /// Computed by walking the socket set, so the cost grows with the
+    /// socket count; fine for metrics, not for a per-packet path.
+    pub fn buffer_bytes(&self) -> usize {
+        self.inner.borrow().buffer_bytes()
+    }
+
+    /// Limit the number of half-open (`SynReceived`) connections.
+    ///
+    /// Once `max` connections are mid-handshake, further SYNs are dropped
+    /// without a SYN-ACK, across all listeners on this reactor. Legitimate
+    /// clients retransmit their SYN and get in once a slot frees up; a SYN
+    /// flood only ever occupies `max` backlog sockets. `None` removes the limit.
+    pub fn set_max_half_open(&self, max: Option<usize>) {
+        let mut inner = self.inner.borrow_mut();
+        inner.max_half_open = max;
+        inner.half_open.clear();
+    }
+
+    /// The current half-open limit, if any.
+    pub fn max_half_open(&self) -> Option<usize> {
+        self.inner.borrow().max_half_open
+    }
+
+    /// Number of connections currently in `SynReceived`.
+    pub fn half_open_count(&self) -> usize {
+        self.inner.borrow().half_open_count()
+    }
+
+    /// Snapshot of the reactor loop's counters.
+    ///
+    /// Cheap enough to call per scrape; compare two snapshots to tell a
+    /// stuck reactor (no new passes) from an idle one (only `idle_passes`
+    /// growing).
+    pub fn stats(&self) -> ReactorStats {
+        let inner = self.inner.borrow();
+        ReactorStats {
+            passes: inner.generation,
+            ..inner.stats
+        }
+    }
+
+    /// Total SYNs dropped by the half-open limit since the reactor started.
+    pub fn syn_dropped(&self) -> u64 {
+        self.inner.borrow().syn_dropped
+    }
+
+    /// Pick a local port for an outbound connection.
+    ///
+    /// Cycles through the reactor's ephemeral range (all of
+    /// [`EPHEMERAL_PORTS`](super::EPHEMERAL_PORTS) unless configured
+    /// otherwise) and returns the first port no TCP socket on this reactor
+    /// is using. Returns `None` if the whole range is taken. Other reactors
+    /// are not consulted; give each one its own range with
+    /// [`set_ephemeral_ports`](Self::set_ephemeral_ports).
+    pub fn alloc_ephemeral_port(&self) -> Option<u16> {
+        let mut inner = self.inner.borrow_mut();
+        let mut ports = inner.ephemeral_ports.clone();
+        let port = ports.next_free(|p| inner.local_port_in_use(p));
+        inner.ephemeral_ports = ports;
+        port
+    }
+
+    /// Restrict [`alloc_ephemeral_port`](Self::alloc_ephemeral_port) to
+    /// `range`, e.g. this queue's [`queue_port_range`](super::queue_port_range).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is empty or includes port 0.
+    pub fn set_ephemeral_ports(&self, range: std::ops::RangeInclusive<u16>) {
+        self.inner.borrow_mut().ephemeral_ports = EphemeralPorts::new(range);
+    }
+
+    /// The range [`alloc_ephemeral_port`](Self::alloc_ephemeral_port) picks from.
+    pub fn ephemeral_ports(&self) -> std::ops::RangeInclusive<u16> {
+        self.inner.borrow().ephemeral_ports.range()
+    }
+
+    /// Run one pass of the reactor loop, for embedding in an external loop.
+    ///
+    /// Processes up to 32 received packets, then runs egress and reaps closed
+    /// orphaned sockets, exactly like one iteration of [`Reactor::run`].
+    /// Frames produced by the pass are handed to the NIC before returning.
+    /// Wakers of affected sockets fire during the call, so the caller's
+    /// executor should poll its tasks afterwards.
+    ///
+    /// `now` is a smoltcp timestamp, normally `smoltcp::time::Instant::now()`.
+    ///
+    /// Do not call this while [`Reactor::run`] is driving the same reactor.
+    /// Panics if called from inside a socket operation (the reactor state is
+    /// already borrowed).
+    pub fn poll_once(&self, now: Instant) -> PollActivity {
+        let mut inner = self.inner.borrow_mut();
+        let activity = inner.poll_pass(now, DEFAULT_INGRESS_BATCH_SIZE, None);
+        inner.device.flush_tx();
+        activity
+    }
+
+    /// How long the caller may wait before smoltcp needs [`poll_once`](Self::poll_once)
+    /// again for timer-driven work.
+    ///
+    /// Covers retransmissions, delayed ACKs, keep-alives, TIME-WAIT expiry,
+    /// ARP retries and timers from [`sleep`](Self::sleep).
+    /// `Some(Duration::ZERO)` means a pass is due now; `None` means no socket
+    /// has a timer pending.
+    ///
+    /// DPDK raises no interrupt when packets arrive, so this bound only holds
+    /// for timers: an embedder that sleeps for the full delay also delays
+    /// every packet received meanwhile. Either cap the sleep at the latency
+    /// budget, or poll immediately while [`PollActivity::more_pending`] is set
+    /// and back off only while passes are idle. Socket operations (send,
+    /// close, connect) can shorten the delay, so query it again after running
+    /// tasks rather than caching it.
+    pub fn poll_delay(&self, now: Instant) -> Option<Duration> {
+        self.inner.borrow_mut().poll_delay(now)
+    }
+
+    /// Total connections accepted by listeners on this reactor.
+    ///
+    /// Connections reset at the socket cap are not counted.
+    pub fn connections_accepted(&self) -> u64 {
+        self.inner.borrow().connections_accepted
+    }
+
+    /// Enable or disable eager egress (default: enabled).
+    ///
+    /// When enabled, `TcpStream::connect` and the first successful write on
+    /// each stream run an egress pass inline, so the SYN or first request
+    /// segment is transmitted before the calling task yields. Disable it to
+    /// leave all transmission to the reactor loop.
+    pub fn set_eager_egress(&self, enabled: bool) {
+        self.inner.borrow_mut().eager_egress = enabled;
+    }
+
+    /// Whether eager egress is enabled.
+    pub fn eager_egress(&self) -> bool {
+        self.inner.borrow().eager_egress
+    }
+
+    /// Set how many socket operations may run between reactor polls.
+    ///
+    /// Each `recv`/`send` poll on a `TcpStream` spends one unit. When the
+    /// budget runs out, socket futures return `Pending` (after waking
+    /// themselves) until the reactor polls again, so a handler looping on
+    /// one busy connection cannot keep the reactor and other connections
+    /// from running. Defaults to [`DEFAULT_YIELD_BUDGET`]; `None` disables it.
+    pub fn set_yield_budget(&self, budget: Option<usize>) {
+        self.inner.borrow_mut().yield_budget = budget;
+    }
+
+    /// The current yield budget, if any.
+    pub fn yield_budget(&self) -> Option<usize> {
+        self.inner.borrow().yield_budget
+    }
+
+    /// Warn when socket futures wait on a reactor that is not polling.
+    ///
+    /// Sockets only make progress while something drives the reactor
+    /// ([`Reactor::run`] or [`poll_once`](Self::poll_once)). Without it,
+    /// they wait forever with no error. With this check on, once socket
+    /// futures have returned `Pending` `polls` times, and more than a second
+    /// has gone by, without a reactor pass, a `tracing` warning says the
+    /// reactor may not be running. It fires once per stall.
+    ///
+    /// A task that waits once and is never woken again is not polled, so
+    /// it cannot trigger the warning; tasks that retry (the yield budget,
+    /// timers raced against socket futures) do.
+    ///
+    /// Debug builds only: defaults to [`DEFAULT_STALL_WARN_POLLS`] there,
+    /// and release builds compile the check out, so this setting is
+    /// ignored. `None` turns it off.
+    pub fn set_stall_warning(&self, polls: Option<u32>) {
+        self.inner.borrow_mut().stall.warn_after = polls;
+    }
+
+    /// The current stall warning threshold, if any.
+    pub fn stall_warning(&self) -> Option<u32> {
+        self.inner.borrow().stall.warn_after
+    }
+
+    /// Reactor passes completed so far.
+    ///
+    /// Increases by one per pass of [`Reactor::run`] or
+    /// [`poll_once`](Self::poll_once); an unchanged value across awaits means
+    /// nothing is driving the reactor.
+    pub fn generation(&self) -> u64 {
+        self.inner.borrow().generation
+    }
+
+    /// Returns true once this poll slice's budget is spent.
+    ///
+    /// Loops that do CPU work between socket calls, or drive something other
+    /// than dpdk-net sockets, can check this and yield:
+    ///
+    /// ```ignore
+    /// loop {
+    ///     let n = stream.recv(&mut buf).await?;
+    ///     process(&buf[..n]);
+    ///     if reactor.should_yield() {
+    ///         tokio::task::yield_now().await;
+    ///     }
+    /// }
+    /// ```
+    pub fn should_yield(&self) -> bool {
+        self.inner.borrow().budget_exhausted()
+    }
+
+    /// Abort every open TCP connection on this reactor.
+    ///
+    /// Each connected socket is reset (`abort()`); the RSTs go out on the next
+    /// reactor poll. Any unsent or unacknowledged data is discarded, and
+    /// pending reads on the affected streams return an error or EOF.
+    /// Listening sockets are left untouched, so listeners keep accepting.
+    ///
+    /// Intended for chaos testing and fast teardown; use `TcpStream::close`
+    /// for graceful shutdown. Returns the number of connections aborted.
+    pub fn abort_all(&self) -> usize {
+        use smoltcp::socket::tcp::State;
+
+        let mut inner = self.inner.borrow_mut();
+        let mut aborted = 0;
+        for (_, socket) in inner.sockets.iter_mut() {
+            if let smoltcp::socket::Socket::Tcp(tcp) = socket {
+                match tcp.state() {
+                    State::Closed | State::Listen | State::TimeWait => {}
+                    _ => {
+                        tcp.abort();
+                        aborted += 1;
+                    }
+                }
+            }
+        }
+        aborted
+    }
 }
@@ -0,0 +1,555 @@
+//! Runtime-agnostic timers.
+//!
+//! Timers are created from a reactor, so handlers need not import the
+//! executor's own: [`ReactorHandle::sleep`](super::ReactorHandle::sleep),
+//! [`ReactorHandle::timeout`](super::ReactorHandle::timeout) and
+//! [`ReactorHandle::interval`](super::ReactorHandle::interval) wait on the
+//! [`Runtime::timer`](super::Runtime::timer) of the runtime the reactor runs
+//! on. A runtime without one, such as [`SpinRuntime`](super::SpinRuntime)
+//! (lcore runtimes are typically built without tokio's time driver), leaves
+//! them in the reactor's timer heap instead, which the reactor loop checks
+//! against `Instant::now()` on every pass; the loop shortens its idle wait
+//! to the earliest deadline, so the reactor is the clock for sockets and
+//! timers alike. Either way a pending timer does not keep the executor busy.
+
+use std::cell::{Cell, RefCell};
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::fmt;
+use std::future::{Future, poll_fn};
+use std::pin::{Pin, pin};
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
+
+/// A [`Runtime::timer`](super::Runtime::timer), with the runtime erased.
+pub(crate) type RuntimeTimer = fn(Instant) -> Option<Pin<Box<dyn Future<Output = ()>>>>;
+
+/// Future returned by [`ReactorHandle::sleep`](super::ReactorHandle::sleep)
+/// and [`ReactorHandle::sleep_until`](super::ReactorHandle::sleep_until).
+#[derive(Debug)]
+pub struct Sleep {
+    deadline: Instant,
+    timer: Timer,
+}
+
+/// What a [`Sleep`] waits on.
+enum Timer {
+    /// The reactor's timer heap.
+    Heap {
+        heap: Rc<TimerHeap>,
+        /// The parked waker, once first polled.
+        slot: Option<Rc<TimerSlot>>,
+    },
+    /// The timer of the runtime the reactor runs on.
+    Runtime(Pin<Box<dyn Future<Output = ()>>>),
+}
+
+impl fmt::Debug for Timer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Timer::Heap { slot, .. } => f
+                .debug_struct("Heap")
+                .field("parked", &slot.is_some())
+                .finish(),
+            Timer::Runtime(_) => f.write_str("Runtime"),
+        }
+    }
+}
+
+impl Sleep {
+    /// The instant at which this future completes.
+    pub fn deadline(&self) -> Instant {
+        self.deadline
+    }
+
+    /// Returns true if the deadline has passed.
+    pub fn is_elapsed(&self) -> bool {
+        Instant::now() >= self.deadline
+    }
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.get_mut();
+        if this.is_elapsed() {
+            return Poll::Ready(());
+        }
+        match &mut this.timer {
+            Timer::Heap {
+                slot: Some(slot), ..
+            } => slot.set_waker(cx.waker()),
+            Timer::Heap { heap, slot } => *slot = Some(heap.register(this.deadline, cx.waker())),
+            Timer::Runtime(sleep) => return sleep.as_mut().poll(cx),
+        }
+        Poll::Pending
+    }
+}
+
+impl Drop for Sleep {
+    fn drop(&mut self) {
+        if let Timer::Heap {
+            heap,
+            slot: Some(slot),
+        } = &self.timer
+        {
+            heap.cancel(slot);
+        }
+    }
+}
+
+/// Wait until `deadline` on the reactor that owns `heap`: on its runtime's
+/// timer if it has one, else in `heap`.
+pub(crate) fn reactor_sleep_until(heap: Rc<TimerHeap>, deadline: Instant) -> Sleep {
+    let timer = match heap.runtime_timer.get().and_then(|timer| timer(deadline)) {
+        Some(sleep) => Timer::Runtime(sleep),
+        None => Timer::Heap { heap, slot: None },
+    };
+    Sleep { deadline, timer }
+}
+
+/// Error returned by [`ReactorHandle::timeout`](super::ReactorHandle::timeout)
+/// when the deadline passes first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elapsed(());
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("deadline has elapsed")
+    }
+}
+
+impl std::error::Error for Elapsed {}
+
+impl From<Elapsed> for std::io::Error {
+    fn from(_: Elapsed) -> Self {
+        std::io::ErrorKind::TimedOut.into()
+    }
+}
+
+/// Race `future` against `sleep`; the building block of the timeouts.
+pub(crate) async fn with_deadline<F: Future>(
+    mut sleep: Sleep,
+    future: F,
+) -> Result<F::Output, Elapsed> {
+    let mut future = pin!(future);
+    poll_fn(|cx| {
+        if let Poll::Ready(output) = future.as_mut().poll(cx) {
+            return Poll::Ready(Ok(output));
+        }
+        Pin::new(&mut sleep).poll(cx).map(|()| Err(Elapsed(())))
+    })
+    .await
+}
+
+/// Waker of a parked reactor timer, shared by the [`Sleep`] and its heap
+/// entry. Emptied when the timer fires or the `Sleep` is dropped.
+#[derive(Debug, Default)]
+pub(crate) struct TimerSlot {
+    waker: RefCell<Option<Waker>>,
+    /// Set while the entry is in the heap.
+    queued: Cell<bool>,
+    /// Set when the `Sleep` was dropped with its entry still in the heap.
+    cancelled: Cell<bool>,
+}
+
+impl TimerSlot {
+    fn set_waker(&self, waker: &Waker) {
+        let mut slot = self.waker.borrow_mut();
+        match &*slot {
+            Some(current) if current.will_wake(waker) => {}
+            _ => *slot = Some(waker.clone()),
+        }
+    }
+}
+
+struct TimerEntry {
+    deadline: Instant,
+    /// Registration order, so timers with the same deadline fire in order.
+    seq: u64,
+    slot: Rc<TimerSlot>,
+}
+
+impl PartialEq for TimerEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for TimerEntry {}
+
+impl PartialOrd for TimerEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TimerEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.deadline, self.seq).cmp(&(other.deadline, other.seq))
+    }
+}
+
+/// Min-heap of a reactor's pending timers, keyed by deadline.
+///
+/// Entries of dropped timers are removed once they reach the top, or all at
+/// once when they outnumber the live ones, so timeouts that are cancelled
+/// long before their deadline do not pile up.
+#[derive(Default)]
+pub(crate) struct TimerHeap {
+    entries: RefCell<BinaryHeap<Reverse<TimerEntry>>>,
+    next_seq: Cell<u64>,
+    /// Entries whose timer was dropped.
+    cancelled: Cell<usize>,
+    /// Timer of the runtime the reactor is running on, tried before the
+    /// heap for new timers.
+    runtime_timer: Cell<Option<RuntimeTimer>>,
+}
+
+impl TimerHeap {
+    /// Have new timers wait on `timer` instead, or on the heap again with
+    /// `None`. Timers already pending keep what they wait on.
+    pub(crate) fn set_runtime_timer(&self, timer: Option<RuntimeTimer>) {
+        self.runtime_timer.set(timer);
+    }
+
+    /// Park `waker` until `deadline`.
+    fn register(&self, deadline: Instant, waker: &Waker) -> Rc<TimerSlot> {
+        let slot = Rc::new(TimerSlot::default());
+        slot.set_waker(waker);
+        slot.queued.set(true);
+        let seq = self.next_seq.get();
+        self.next_seq.set(seq.wrapping_add(1));
+        self.entries.borrow_mut().push(Reverse(TimerEntry {
+            deadline,
+            seq,
+            slot: slot.clone(),
+        }));
+        slot
+    }
+
+    /// Wake every timer whose deadline is at or before `now`, returning how
+    /// many were still waited on.
+    pub(crate) fn fire_expired(&self, now: Instant) -> usize {
+        let mut fired = 0;
+        loop {
+            // Release the heap before waking, in case a waker polls inline
+            let entry = {
+                let mut entries = self.entries.borrow_mut();
+                match entries.peek() {
+                    Some(Reverse(entry)) if entry.deadline <= now => entries.pop(),
+                    _ => None,
+                }
+            };
+            let Some(Reverse(entry)) = entry else {
+                return fired;
+            };
+            self.unqueue(&entry.slot);
+            if let Some(waker) = entry.slot.waker.borrow_mut().take() {
+                waker.wake();
+                fired += 1;
+            }
+        }
+    }
+
+    /// Drop the entry of a timer that will not be polled again.
+    fn cancel(&self, slot: &TimerSlot) {
+        slot.waker.borrow_mut().take();
+        if !slot.queued.get() || slot.cancelled.replace(true) {
+            return;
+        }
+        self.cancelled.set(self.cancelled.get() + 1);
+
+        let mut entries = self.entries.borrow_mut();
+        while let Some(Reverse(top)) = entries.peek()
+            && top.slot.cancelled.get()
+        {
+            let Some(Reverse(entry)) = entries.pop() else {
+                break;
+            };
+            self.unqueue(&entry.slot);
+        }
+        if self.cancelled.get() * 2 > entries.len() {
+            entries.retain(|Reverse(entry)| !entry.slot.cancelled.get());
+            self.cancelled.set(0);
+        }
+    }
+
+    /// Note that `slot`'s entry has left the heap.
+    fn unqueue(&self, slot: &TimerSlot) {
+        slot.queued.set(false);
+        if slot.cancelled.get() {
+            self.cancelled.set(self.cancelled.get() - 1);
+        }
+    }
+
+    /// The earliest pending deadline, if any.
+    pub(crate) fn next_deadline(&self) -> Option<Instant> {
+        self.entries
+            .borrow()
+            .peek()
+            .map(|Reverse(entry)| entry.deadline)
+    }
+
+    /// Entries not yet fired, including dropped timers not yet removed.
+    pub(crate) fn len(&self) -> usize {
+        self.entries.borrow().len()
+    }
+}
+
+impl fmt::Debug for TimerHeap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TimerHeap")
+            .field("pending", &self.len())
+            .field("next_deadline", &self.next_deadline())
+            .finish()
+    }
+}
+
+/// Create an [`Interval`] that first ticks at `start`, then every `period`,
+/// woken by the reactor that owns `heap`.
+///
+/// # Panics
+///
+/// Panics if `period` is zero.
+pub(crate) fn reactor_interval_at(
+    heap: Rc<TimerHeap>,
+    start: Instant,
+    period: Duration,
+) -> Interval {
+    assert!(!period.is_zero(), "interval period must be non-zero");
+    Interval {
+        next: start,
+        period,
+        heap,
+        sleep: None,
+    }
+}
+
+/// Periodic timer returned by
+/// [`ReactorHandle::interval`](super::ReactorHandle::interval) and
+/// [`ReactorHandle::interval_at`](super::ReactorHandle::interval_at).
+///
+/// Ticks that were missed because the task ran late are skipped rather than
+/// fired back to back: after a stall, the next tick lands on the next
+/// multiple of `period` from the start. A heartbeat therefore never bursts.
+/// Each wait is a [`ReactorHandle::sleep`](super::ReactorHandle::sleep).
+#[derive(Debug)]
+pub struct Interval {
+    next: Instant,
+    period: Duration,
+    /// Heap of the reactor the ticks are timed by.
+    heap: Rc<TimerHeap>,
+    /// The timer for the next tick, once parked.
+    sleep: Option<Sleep>,
+}
+
+impl Interval {
+    /// Wait for the next tick and return the instant it was scheduled for.
+    pub async fn tick(&mut self) -> Instant {
+        std::future::poll_fn(|cx| self.poll_tick(cx)).await
+    }
+
+    /// Poll for the next tick; the building block of [`Interval::tick`].
+    pub fn poll_tick(&mut self, cx: &mut Context<'_>) -> Poll<Instant> {
+        loop {
+            if let Some(tick) = self.tick_at(Instant::now()) {
+                self.sleep = None;
+                return Poll::Ready(tick);
+            }
+            // A reset moves the next tick, and with it the timer
+            let sleep = match &mut self.sleep {
+                Some(sleep) if sleep.deadline() == self.next => sleep,
+                sleep => sleep.insert(reactor_sleep_until(self.heap.clone(), self.next)),
+            };
+            if Pin::new(sleep).poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+        }
+    }
+
+    /// The period between ticks.
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+
+    /// Restart the schedule so the next tick is one period from now.
+    pub fn reset(&mut self) {
+        self.next = Instant::now() + self.period;
+    }
+
+    /// Take the tick due at `now`, if any, and schedule the following one.
+    fn tick_at(&mut self, now: Instant) -> Option<Instant> {
+        if now < self.next {
+            return None;
+        }
+        let tick = self.next;
+        let missed = (now - tick).as_nanos() / self.period.as_nanos();
+        let advance = self.period.as_nanos() * (missed + 1);
+        self.next = tick + Duration::from_nanos(advance as u64);
+        Some(tick)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interval_at(start: Instant, period: Duration) -> Interval {
+        reactor_interval_at(Rc::default(), start, period)
+    }
+
+    #[test]
+    fn test_interval_ticks_on_schedule() {
+        let start = Instant::now();
+        let period = Duration::from_millis(10);
+        let mut interval = interval_at(start, period);
+
+        assert_eq!(interval.tick_at(start), Some(start));
+        assert_eq!(interval.tick_at(start + Duration::from_millis(5)), None);
+        assert_eq!(interval.tick_at(start + period), Some(start + period));
+    }
+
+    #[test]
+    fn test_interval_skips_missed_ticks() {
+        let start = Instant::now();
+        let period = Duration::from_millis(10);
+        let mut interval = interval_at(start, period);
+        interval.tick_at(start);
+
+        // Stalled past three ticks: one fires late, the rest are skipped
+        let late = start + Duration::from_millis(35);
+        assert_eq!(interval.tick_at(late), Some(start + period));
+        assert_eq!(interval.tick_at(late), None);
+        let next = start + Duration::from_millis(40);
+        assert_eq!(interval.tick_at(next), Some(next));
+    }
+
+    #[test]
+    fn test_timer_heap_fires_in_deadline_order() {
+        use std::sync::{Arc, Mutex};
+        use std::task::Wake;
+
+        struct Record(Arc<Mutex<Vec<u32>>>, u32);
+        impl Wake for Record {
+            fn wake(self: Arc<Self>) {
+                self.0.lock().unwrap().push(self.1);
+            }
+        }
+
+        let woken = Arc::new(Mutex::new(Vec::new()));
+        let waker = |id| Waker::from(Arc::new(Record(woken.clone(), id)));
+        let heap = TimerHeap::default();
+        let start = Instant::now();
+        let _late = heap.register(start + Duration::from_millis(20), &waker(2));
+        let _early = heap.register(start + Duration::from_millis(10), &waker(1));
+        let dropped = heap.register(start + Duration::from_millis(10), &waker(3));
+        dropped.waker.borrow_mut().take();
+        assert_eq!(
+            heap.next_deadline(),
+            Some(start + Duration::from_millis(10))
+        );
+
+        assert_eq!(heap.fire_expired(start), 0);
+        assert_eq!(heap.fire_expired(start + Duration::from_millis(15)), 1);
+        assert_eq!(*woken.lock().unwrap(), [1]);
+        assert_eq!(heap.len(), 1);
+        assert_eq!(heap.fire_expired(start + Duration::from_millis(20)), 1);
+        assert_eq!(*woken.lock().unwrap(), [1, 2]);
+        assert_eq!(heap.next_deadline(), None);
+    }
+
+    #[test]
+    fn test_timer_heap_removes_cancelled() {
+        let heap = TimerHeap::default();
+        let start = Instant::now();
+        let waker = Waker::noop();
+        let slots: Vec<_> = (1..=4)
+            .map(|ms| heap.register(start + Duration::from_millis(ms), waker))
+            .collect();
+
+        // Not at the top and not the majority: stays until pruned
+        heap.cancel(&slots[2]);
+        assert_eq!(heap.len(), 4);
+        heap.cancel(&slots[2]);
+        assert_eq!(heap.cancelled.get(), 1);
+
+        // At the top: removed along with the cancelled entries behind it
+        heap.cancel(&slots[1]);
+        heap.cancel(&slots[0]);
+        assert_eq!(heap.len(), 1);
+        assert_eq!(heap.next_deadline(), Some(start + Duration::from_millis(4)));
+        assert_eq!(heap.cancelled.get(), 0);
+
+        // Fired entries are not counted when their timer is dropped later
+        assert_eq!(heap.fire_expired(start + Duration::from_millis(4)), 1);
+        heap.cancel(&slots[3]);
+        assert_eq!(heap.cancelled.get(), 0);
+        assert_eq!(heap.len(), 0);
+    }
+
+    #[test]
+    fn test_timer_heap_compacts_when_mostly_cancelled() {
+        let heap = TimerHeap::default();
+        let start = Instant::now();
+        let waker = Waker::noop();
+        let slots: Vec<_> = (1..=5)
+            .map(|ms| heap.register(start + Duration::from_millis(ms), waker))
+            .collect();
+
+        // The earliest stays live, so nothing reaches the top
+        heap.cancel(&slots[1]);
+        heap.cancel(&slots[2]);
+        assert_eq!(heap.len(), 5);
+        heap.cancel(&slots[3]);
+        assert_eq!(heap.len(), 2);
+        assert_eq!(heap.cancelled.get(), 0);
+        assert_eq!(heap.fire_expired(start + Duration::from_millis(5)), 2);
+    }
+
+    #[test]
+    fn test_reactor_interval_parks_in_heap() {
+        let heap = Rc::new(TimerHeap::default());
+        let period = Duration::from_secs(60);
+        let mut interval = reactor_interval_at(heap.clone(), Instant::now() + period, period);
+        let mut cx = Context::from_waker(Waker::noop());
+
+        assert!(interval.poll_tick(&mut cx).is_pending());
+        assert!(interval.poll_tick(&mut cx).is_pending());
+        assert_eq!(heap.len(), 1);
+
+        // The timer of the old schedule goes with it
+        interval.reset();
+        assert!(interval.poll_tick(&mut cx).is_pending());
+        assert_eq!(heap.len(), 1);
+        drop(interval);
+        assert_eq!(heap.len(), 0);
+    }
+
+    #[test]
+    fn test_runtime_timer_bypasses_heap() {
+        let heap = Rc::new(TimerHeap::default());
+        let mut cx = Context::from_waker(Waker::noop());
+        let deadline = Instant::now() + Duration::from_secs(60);
+        heap.set_runtime_timer(Some(|_| Some(Box::pin(std::future::ready(())))));
+
+        // The runtime's timer decides, not the deadline
+        let mut sleep = reactor_sleep_until(heap.clone(), deadline);
+        assert!(Pin::new(&mut sleep).poll(&mut cx).is_ready());
+        assert_eq!(heap.len(), 0);
+
+        // A runtime without a timer falls back to the heap
+        heap.set_runtime_timer(Some(|_| None));
+        let mut sleep = reactor_sleep_until(heap.clone(), deadline);
+        assert!(Pin::new(&mut sleep).poll(&mut cx).is_pending());
+        assert_eq!(heap.len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "non-zero")]
+    fn test_interval_zero_period_panics() {
+        interval_at(Instant::now(), Duration::ZERO);
+    }
+}
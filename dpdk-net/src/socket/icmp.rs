@@ -0,0 +1,242 @@
+//! Async ICMP socket implementation
+
+use crate::device::DpdkDevice;
+use crate::runtime::{ReactorHandle, ReactorInner};
+use smoltcp::iface::SocketHandle;
+use smoltcp::phy::ChecksumCapabilities;
+use smoltcp::socket::icmp::{self, BindError, Endpoint, RecvError, SendError};
+use smoltcp::wire::{Icmpv4Message, Icmpv4Packet, Icmpv4Repr, IpAddress};
+use std::cell::RefCell;
+use std::fmt;
+use std::future::poll_fn;
+use std::rc::Rc;
+use std::task::Poll;
+use std::time::{Duration, Instant};
+
+/// Packets each direction of an [`IcmpSocket::bind`] socket can queue.
+pub const DEFAULT_ICMP_PACKETS: usize = 8;
+
+/// Largest ICMP message, header included, an [`IcmpSocket::bind`] socket
+/// can send or receive.
+pub const DEFAULT_ICMP_PACKET_SIZE: usize = 1500;
+
+/// Error returned by [`IcmpSocket::ping`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PingError {
+    /// The echo request could not be queued. IPv6 destinations are not
+    /// supported and fail with [`SendError::Unaddressable`].
+    Send(SendError),
+    /// Receiving failed, e.g. a reply did not fit the receive buffer.
+    Recv(RecvError),
+}
+
+impl fmt::Display for PingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PingError::Send(e) => write!(f, "sending echo request failed: {e}"),
+            PingError::Recv(e) => write!(f, "receiving echo reply failed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for PingError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PingError::Send(e) => Some(e),
+            PingError::Recv(e) => Some(e),
+        }
+    }
+}
+
+impl From<SendError> for PingError {
+    fn from(e: SendError) -> Self {
+        PingError::Send(e)
+    }
+}
+
+impl From<RecvError> for PingError {
+    fn from(e: RecvError) -> Self {
+        PingError::Recv(e)
+    }
+}
+
+/// An async ICMP socket, for ping.
+///
+/// Bound to an echo identifier, it receives the echo requests and replies
+/// carrying it. The interface itself answers echo requests to its own
+/// addresses, so no socket is needed to be pinged.
+///
+/// # Example
+///
+/// ```ignore
+/// use dpdk_net::socket::IcmpSocket;
+///
+/// let socket = IcmpSocket::bind(&reactor, 0x1234)?;
+/// let rtt = reactor
+///     .timeout(Duration::from_secs(1), socket.ping(gateway, 1, b"hello"))
+///     .await??;
+/// println!("gateway answered in {rtt:?}");
+/// ```
+pub struct IcmpSocket {
+    handle: SocketHandle,
+    reactor: Rc<RefCell<ReactorInner<DpdkDevice>>>,
+    ident: u16,
+}
+
+impl IcmpSocket {
+    /// Creates an ICMP socket bound to echo identifier `ident`, with
+    /// [`DEFAULT_ICMP_PACKETS`] packets of up to [`DEFAULT_ICMP_PACKET_SIZE`]
+    /// bytes of buffer each way.
+    pub fn bind(handle: &ReactorHandle, ident: u16) -> Result<Self, BindError> {
+        Self::bind_with_buffers(
+            handle,
+            ident,
+            DEFAULT_ICMP_PACKETS,
+            DEFAULT_ICMP_PACKETS,
+            DEFAULT_ICMP_PACKET_SIZE,
+        )
+    }
+
+    /// Creates an ICMP socket bound to echo identifier `ident`.
+    ///
+    /// # Arguments
+    /// * `handle` - The reactor handle
+    /// * `ident` - The echo identifier to send and receive
+    /// * `rx_buffer_packets` - Number of packets the receive buffer can hold
+    /// * `tx_buffer_packets` - Number of packets the transmit buffer can hold
+    /// * `max_packet_size` - Maximum size of a single ICMP message
+    pub fn bind_with_buffers(
+        handle: &ReactorHandle,
+        ident: u16,
+        rx_buffer_packets: usize,
+        tx_buffer_packets: usize,
+        max_packet_size: usize,
+    ) -> Result<Self, BindError> {
+        let mut inner = handle.inner.borrow_mut();
+
+        let rx_meta = vec![icmp::PacketMetadata::EMPTY; rx_buffer_packets];
+        let rx_payload = vec![0u8; rx_buffer_packets * max_packet_size];
+        let tx_meta = vec![icmp::PacketMetadata::EMPTY; tx_buffer_packets];
+        let tx_payload = vec![0u8; tx_buffer_packets * max_packet_size];
+
+        let rx_buffer = icmp::PacketBuffer::new(rx_meta, rx_payload);
+        let tx_buffer = icmp::PacketBuffer::new(tx_meta, tx_payload);
+
+        let mut socket = icmp::Socket::new(rx_buffer, tx_buffer);
+        socket.bind(Endpoint::Ident(ident))?;
+
+        let socket_handle = inner.sockets.add(socket);
+
+        Ok(IcmpSocket {
+            handle: socket_handle,
+            reactor: handle.inner.clone(),
+            ident,
+        })
+    }
+
+    /// Get the underlying socket handle
+    pub fn socket_handle(&self) -> SocketHandle {
+        self.handle
+    }
+
+    /// The echo identifier this socket is bound to.
+    pub fn ident(&self) -> u16 {
+        self.ident
+    }
+
+    /// Send a raw ICMP message, header included, to `addr`.
+    ///
+    /// Waits while the transmit buffer is full. The checksum must already be
+    /// filled in.
+    pub async fn send_to(&self, data: &[u8], addr: IpAddress) -> Result<(), SendError> {
+        poll_fn(|cx| {
+            let mut inner = self.reactor.borrow_mut();
+            let socket = inner.sockets.get_mut::<icmp::Socket>(self.handle);
+            match socket.send_slice(data, addr) {
+                Err(SendError::BufferFull) => {
+                    socket.register_send_waker(cx.waker());
+                    inner.note_pending();
+                    Poll::Pending
+                }
+                result => Poll::Ready(result),
+            }
+        })
+        .await
+    }
+
+    /// Receive an ICMP message, header included, and the address it came
+    /// from.
+    pub async fn recv_from(&self, buf: &mut [u8]) -> Result<(usize, IpAddress), RecvError> {
+        poll_fn(|cx| {
+            let mut inner = self.reactor.borrow_mut();
+            let socket = inner.sockets.get_mut::<icmp::Socket>(self.handle);
+            match socket.recv_slice(buf) {
+                Err(RecvError::Exhausted) => {
+                    socket.register_recv_waker(cx.waker());
+                    inner.note_pending();
+                    Poll::Pending
+                }
+                result => Poll::Ready(result),
+            }
+        })
+        .await
+    }
+
+    /// Send an echo request with sequence number `seq` to `addr` and wait
+    /// for the matching reply, returning the round-trip time.
+    ///
+    /// Messages that are not the reply (other sequence numbers, late
+    /// replies, our own request on a loopback device) are skipped. A lost
+    /// request is never answered, so bound the wait with
+    /// [`ReactorHandle::timeout`]. Only IPv4 is supported.
+    pub async fn ping(
+        &self,
+        addr: IpAddress,
+        seq: u16,
+        payload: &[u8],
+    ) -> Result<Duration, PingError> {
+        if !matches!(addr, IpAddress::Ipv4(_)) {
+            return Err(SendError::Unaddressable.into());
+        }
+        let repr = Icmpv4Repr::EchoRequest {
+            ident: self.ident,
+            seq_no: seq,
+            data: payload,
+        };
+        let mut request = vec![0u8; repr.buffer_len()];
+        repr.emit(
+            &mut Icmpv4Packet::new_unchecked(&mut request),
+            &ChecksumCapabilities::default(),
+        );
+
+        let sent = Instant::now();
+        self.send_to(&request, addr).await?;
+
+        let mut buf = vec![0u8; request.len()];
+        loop {
+            let (len, from) = match self.recv_from(&mut buf).await {
+                // Larger than our request, so not its reply
+                Err(RecvError::Truncated) => continue,
+                result => result?,
+            };
+            let Ok(reply) = Icmpv4Packet::new_checked(&buf[..len]) else {
+                continue;
+            };
+            if from == addr
+                && reply.msg_type() == Icmpv4Message::EchoReply
+                && reply.echo_ident() == self.ident
+                && reply.echo_seq_no() == seq
+                && reply.data() == payload
+            {
+                return Ok(sent.elapsed());
+            }
+        }
+    }
+}
+
+impl Drop for IcmpSocket {
+    fn drop(&mut self) {
+        let mut inner = self.reactor.borrow_mut();
+        inner.sockets.remove(self.handle);
+    }
+}
@@ -1,23 +1,39 @@
-//! Async socket implementations for TCP and UDP.
+//! Async socket implementations for TCP, UDP and ICMP.
 //!
-//! This module provides async TCP and UDP sockets backed by DPDK and smoltcp.
+//! This module provides async TCP, UDP and ICMP sockets backed by DPDK and smoltcp.
 //!
 //! # TCP Sockets
 //!
 //! - [`TcpStream`]: A connected TCP stream for bidirectional data transfer
 //! - [`TcpListener`]: A TCP listener for accepting incoming connections
+//! - [`OwnedReadHalf`] / [`OwnedWriteHalf`]: halves of a stream split with
+//!   [`TcpStream::into_split`], for use in separate tasks
 //!
 //! # UDP Sockets
 //!
 //! - [`UdpSocket`]: A UDP socket for connectionless datagram transfer
+//!
+//! # ICMP Sockets
+//!
+//! - [`IcmpSocket`]: An ICMP socket bound to an echo identifier, for ping
 
+mod icmp;
 mod tcp;
 mod udp;
 
-pub use tcp::{AcceptFuture, TcpListener, TcpStream, WaitConnectedFuture};
-pub use udp::{UdpRecvFuture, UdpSendFuture, UdpSocket};
+pub use icmp::{DEFAULT_ICMP_PACKET_SIZE, DEFAULT_ICMP_PACKETS, IcmpSocket, PingError};
+
+pub use tcp::{
+    AcceptFuture, Incoming, NonblockingError, OwnedReadHalf, OwnedWriteHalf, ReuniteError,
+    TcpConnectError, TcpListenError, TcpListener, TcpPeekFuture, TcpSendVectoredFuture,
+    TcpSocketOptions, TcpStream, TcpStreamError, TcpStreamStats, WaitConnectedFuture,
+};
+pub use udp::{UdpFlushFuture, UdpRecvFuture, UdpSendFuture, UdpSocket};
 
 // Re-export smoltcp error types for convenience
+pub use smoltcp::socket::icmp::{
+    BindError as IcmpBindError, RecvError as IcmpRecvError, SendError as IcmpSendError,
+};
 pub use smoltcp::socket::tcp::{ConnectError, ListenError};
 pub use smoltcp::socket::udp::{
     BindError as UdpBindError, RecvError as UdpRecvError, SendError as UdpSendError, UdpMetadata,
@@ -1,17 +1,394 @@
 //! Async TCP socket implementation
 
 use crate::device::DpdkDevice;
-use crate::runtime::{ReactorHandle, ReactorInner};
+use crate::runtime::{ListenSpec, ReactorHandle, ReactorInner};
+use futures_core::Stream;
 use futures_io::{AsyncRead, AsyncWrite};
 use smoltcp::iface::SocketHandle;
 use smoltcp::socket::tcp::{self, ConnectError, ListenError, RecvError, State};
-use smoltcp::wire::IpAddress;
-use std::cell::RefCell;
+use smoltcp::wire::{IpAddress, IpEndpoint, IpListenEndpoint};
+use std::cell::{Cell, RefCell};
+use std::fmt;
 use std::future::Future;
-use std::io;
+use std::io::{self, IoSlice};
+use std::net::{Shutdown, SocketAddr};
 use std::pin::Pin;
 use std::rc::Rc;
-use std::task::{Context, Poll};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+/// Error returned by [`TcpStream::connect`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TcpConnectError {
+    /// smoltcp rejected the connect (bad state, unaddressable, ...).
+    Connect(ConnectError),
+    /// The reactor is at its socket cap; see [`ReactorHandle::set_max_sockets`].
+    TooManySockets,
+    /// The new socket buffers would exceed the reactor's memory cap; see
+    /// [`ReactorHandle::set_max_buffer_bytes`].
+    MemoryLimit,
+    /// [`TcpStream::connect_ephemeral`] found every port in the reactor's
+    /// ephemeral range in use.
+    PortsExhausted,
+    /// [`TcpStream::connect_timeout`] gave up before the handshake completed.
+    TimedOut,
+    /// The peer reset the connection attempt, e.g. nothing listens on the
+    /// port.
+    Refused,
+}
+
+impl fmt::Display for TcpConnectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TcpConnectError::Connect(e) => write!(f, "{e}"),
+            TcpConnectError::TooManySockets => write!(f, "reactor socket limit reached"),
+            TcpConnectError::MemoryLimit => write!(f, "reactor buffer memory limit reached"),
+            TcpConnectError::PortsExhausted => {
+                write!(f, "no free local port in the ephemeral range")
+            }
+            TcpConnectError::TimedOut => write!(f, "connection attempt timed out"),
+            TcpConnectError::Refused => write!(f, "connection refused"),
+        }
+    }
+}
+
+impl std::error::Error for TcpConnectError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TcpConnectError::Connect(e) => Some(e),
+            TcpConnectError::TooManySockets
+            | TcpConnectError::MemoryLimit
+            | TcpConnectError::PortsExhausted
+            | TcpConnectError::TimedOut
+            | TcpConnectError::Refused => None,
+        }
+    }
+}
+
+impl From<ConnectError> for TcpConnectError {
+    fn from(e: ConnectError) -> Self {
+        TcpConnectError::Connect(e)
+    }
+}
+
+/// Error returned by [`TcpListener::bind`] and [`TcpListener::accept`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TcpListenError {
+    /// smoltcp rejected the listen, or every backlog socket has died.
+    Listen(ListenError),
+    /// The reactor is at its socket cap; see [`ReactorHandle::set_max_sockets`].
+    TooManySockets,
+    /// The new socket buffers would exceed the reactor's memory cap; see
+    /// [`ReactorHandle::set_max_buffer_bytes`].
+    MemoryLimit,
+}
+
+impl fmt::Display for TcpListenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TcpListenError::Listen(e) => write!(f, "{e}"),
+            TcpListenError::TooManySockets => write!(f, "reactor socket limit reached"),
+            TcpListenError::MemoryLimit => write!(f, "reactor buffer memory limit reached"),
+        }
+    }
+}
+
+impl std::error::Error for TcpListenError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TcpListenError::Listen(e) => Some(e),
+            TcpListenError::TooManySockets | TcpListenError::MemoryLimit => None,
+        }
+    }
+}
+
+impl From<ListenError> for TcpListenError {
+    fn from(e: ListenError) -> Self {
+        TcpListenError::Listen(e)
+    }
+}
+
+/// Split a std socket address into the smoltcp address and port.
+///
+/// IPv4-mapped IPv6 addresses (`::ffff:a.b.c.d`) become IPv4, since smoltcp
+/// does not translate between the two families.
+fn smoltcp_endpoint(addr: SocketAddr) -> (IpAddress, u16) {
+    let ip = match addr {
+        SocketAddr::V4(v4) => IpAddress::Ipv4(*v4.ip()),
+        SocketAddr::V6(v6) => match v6.ip().to_ipv4_mapped() {
+            Some(v4) => IpAddress::Ipv4(v4),
+            None => IpAddress::Ipv6(*v6.ip()),
+        },
+    };
+    (ip, addr.port())
+}
+
+/// Split a smoltcp endpoint into address and port, or `None` if the
+/// address is unspecified.
+fn endpoint_parts(ep: IpEndpoint) -> Option<(IpAddress, u16)> {
+    (!ep.addr.is_unspecified()).then_some((ep.addr, ep.port))
+}
+
+/// Drop everything in `socket`'s receive buffer, reopening its window.
+fn discard_recv(socket: &mut tcp::Socket) {
+    while socket.recv(|data| (data.len(), data.len())).unwrap_or(0) > 0 {}
+}
+
+/// Why a [`TcpStream`] read or write failed.
+///
+/// `recv`/`send` (and the `AsyncRead`/`AsyncWrite` impls) return
+/// `io::Error`s that wrap one of these, with the matching
+/// [`io::ErrorKind`]. Downcast with `err.get_ref()` to match exactly.
+///
+/// Closing states map as follows:
+///
+/// | State                                   | `recv`                    | `send`            |
+/// |-----------------------------------------|---------------------------|-------------------|
+/// | `SynSent`, `SynReceived`                | waits for the handshake   | waits             |
+/// | `Established`                           | data                      | data              |
+/// | `CloseWait` (peer sent FIN)             | buffered data, then EOF   | data              |
+/// | `FinWait1`, `FinWait2` (we sent FIN)    | data                      | `Shutdown`        |
+/// | `Closing`, `LastAck`, `TimeWait`        | buffered data, then EOF   | `Shutdown`        |
+/// | `Closed` after FIN exchange             | EOF                       | `Shutdown`        |
+/// | `Closed` after RST, abort or timeout    | `ConnectionReset`         | `ConnectionReset` |
+/// | `Listen`                                | `NotConnected`            | `NotConnected`    |
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TcpStreamError {
+    /// The socket never connected.
+    NotConnected,
+    /// The connection was reset (peer RST, local abort, or retransmit timeout).
+    ConnectionReset,
+    /// The write side was closed by [`TcpStream::close`].
+    Shutdown,
+}
+
+impl TcpStreamError {
+    /// The `io::ErrorKind` used when this error is surfaced as `io::Error`.
+    pub fn kind(self) -> io::ErrorKind {
+        match self {
+            TcpStreamError::NotConnected => io::ErrorKind::NotConnected,
+            TcpStreamError::ConnectionReset => io::ErrorKind::ConnectionReset,
+            TcpStreamError::Shutdown => io::ErrorKind::BrokenPipe,
+        }
+    }
+
+    /// Error for a recv that smoltcp rejected in `state`, or `None` to wait.
+    fn for_recv(state: State) -> Option<Self> {
+        match state {
+            State::SynSent | State::SynReceived => None,
+            State::Closed => Some(TcpStreamError::ConnectionReset),
+            _ => Some(TcpStreamError::NotConnected),
+        }
+    }
+
+    /// Error for a send that smoltcp rejected in `state`, or `None` to wait.
+    ///
+    /// `fin_received` tells a graceful close apart from a reset once the
+    /// socket is `Closed`.
+    fn for_send(state: State, fin_received: bool) -> Option<Self> {
+        match state {
+            State::SynSent | State::SynReceived => None,
+            State::Closed if fin_received => Some(TcpStreamError::Shutdown),
+            State::Closed => Some(TcpStreamError::ConnectionReset),
+            State::FinWait1
+            | State::FinWait2
+            | State::Closing
+            | State::LastAck
+            | State::TimeWait => Some(TcpStreamError::Shutdown),
+            _ => Some(TcpStreamError::NotConnected),
+        }
+    }
+}
+
+impl fmt::Display for TcpStreamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TcpStreamError::NotConnected => write!(f, "socket is not connected"),
+            TcpStreamError::ConnectionReset => write!(f, "connection reset"),
+            TcpStreamError::Shutdown => write!(f, "socket was closed for writing"),
+        }
+    }
+}
+
+impl std::error::Error for TcpStreamError {}
+
+impl From<TcpStreamError> for io::Error {
+    fn from(e: TcpStreamError) -> Self {
+        io::Error::new(e.kind(), e)
+    }
+}
+
+/// Error returned by [`TcpStream::send_nonblocking`] and
+/// [`TcpStream::recv_nonblocking`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NonblockingError {
+    /// Nothing could be moved right now; drive the reactor and retry.
+    WouldBlock,
+    /// The stream failed; retrying will not help.
+    Stream(TcpStreamError),
+}
+
+impl NonblockingError {
+    /// Returns true for [`NonblockingError::WouldBlock`].
+    pub fn is_would_block(&self) -> bool {
+        matches!(self, NonblockingError::WouldBlock)
+    }
+}
+
+impl fmt::Display for NonblockingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NonblockingError::WouldBlock => write!(f, "operation would block"),
+            NonblockingError::Stream(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for NonblockingError {}
+
+impl From<TcpStreamError> for NonblockingError {
+    fn from(e: TcpStreamError) -> Self {
+        NonblockingError::Stream(e)
+    }
+}
+
+impl From<NonblockingError> for io::Error {
+    fn from(e: NonblockingError) -> Self {
+        match e {
+            NonblockingError::WouldBlock => io::ErrorKind::WouldBlock.into(),
+            NonblockingError::Stream(e) => e.into(),
+        }
+    }
+}
+
+/// Per-connection counters returned by [`TcpStream::stats`].
+///
+/// Byte counts are kept by the stream wrapper and count what the application
+/// moved through `send`/`recv` (or the `AsyncRead`/`AsyncWrite` impls), not
+/// what is on the wire. The queue lengths and state come from smoltcp.
+///
+/// smoltcp keeps its RTT estimator and retransmission bookkeeping private,
+/// so neither is reported here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TcpStreamStats {
+    /// Bytes accepted into the transmit buffer.
+    pub bytes_sent: u64,
+    /// Bytes read out of the receive buffer.
+    pub bytes_received: u64,
+    /// Bytes in the transmit buffer, sent-but-unacked included.
+    pub send_queue: usize,
+    /// Bytes received and not yet read.
+    pub recv_queue: usize,
+    /// Current TCP state.
+    pub state: State,
+}
+
+/// smoltcp's default delay before a lone ACK is sent.
+const DEFAULT_ACK_DELAY: Duration = Duration::from_millis(10);
+
+/// Buffer sizes and smoltcp tuning for a TCP socket.
+///
+/// Given to [`TcpStream::connect_with_options`] for one connection, or to
+/// [`TcpListener::bind_with_options`] for every socket of a listener, and so
+/// every stream it accepts.
+///
+/// ```ignore
+/// // Give up on peers silent for 30s, probing idle connections every 10s
+/// let options = TcpSocketOptions::new(16384, 16384)
+///     .keep_alive(Some(Duration::from_secs(10)))
+///     .timeout(Some(Duration::from_secs(30)));
+/// let listener = TcpListener::bind_with_options(&reactor, 8080, options)?;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TcpSocketOptions {
+    /// Receive buffer size in bytes.
+    pub rx_buffer_size: usize,
+    /// Transmit buffer size in bytes.
+    pub tx_buffer_size: usize,
+    /// Send a keep-alive probe after the connection has been idle this long.
+    /// `None` (the default) sends none.
+    pub keep_alive: Option<Duration>,
+    /// Reset the connection once the peer has been silent this long, while
+    /// data is unacknowledged or keep-alive probes go unanswered. `None`
+    /// (the default) waits forever.
+    pub timeout: Option<Duration>,
+    /// Delay before sending an ACK with no data, in the hope of piggybacking
+    /// it; `None` acks every segment at once. Defaults to 10ms.
+    pub ack_delay: Option<Duration>,
+    /// Nagle's algorithm; on by default. See [`TcpStream::set_nodelay`].
+    pub nagle: bool,
+    /// IP hop limit (TTL) of sent packets; `None` uses smoltcp's default of
+    /// 64. Must not be zero.
+    pub hop_limit: Option<u8>,
+}
+
+impl TcpSocketOptions {
+    /// The given buffer sizes and smoltcp's defaults for everything else.
+    pub fn new(rx_buffer_size: usize, tx_buffer_size: usize) -> Self {
+        Self {
+            rx_buffer_size,
+            tx_buffer_size,
+            keep_alive: None,
+            timeout: None,
+            ack_delay: Some(DEFAULT_ACK_DELAY),
+            nagle: true,
+            hop_limit: None,
+        }
+    }
+
+    /// Set the keep-alive interval.
+    pub fn keep_alive(mut self, interval: Option<Duration>) -> Self {
+        self.keep_alive = interval;
+        self
+    }
+
+    /// Set the dead-peer timeout.
+    pub fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the delayed-ACK delay.
+    pub fn ack_delay(mut self, delay: Option<Duration>) -> Self {
+        self.ack_delay = delay;
+        self
+    }
+
+    /// Enable or disable Nagle's algorithm.
+    pub fn nagle(mut self, enabled: bool) -> Self {
+        self.nagle = enabled;
+        self
+    }
+
+    /// Set the hop limit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hop_limit` is `Some(0)`.
+    pub fn hop_limit(mut self, hop_limit: Option<u8>) -> Self {
+        assert_ne!(hop_limit, Some(0), "hop limit must not be zero");
+        self.hop_limit = hop_limit;
+        self
+    }
+
+    /// Create a socket with these buffers and settings.
+    pub(crate) fn socket(&self) -> tcp::Socket<'static> {
+        let rx_buffer = tcp::SocketBuffer::new(vec![0; self.rx_buffer_size]);
+        let tx_buffer = tcp::SocketBuffer::new(vec![0; self.tx_buffer_size]);
+        let mut socket = tcp::Socket::new(rx_buffer, tx_buffer);
+        socket.set_keep_alive(self.keep_alive.map(Into::into));
+        socket.set_timeout(self.timeout.map(Into::into));
+        socket.set_ack_delay(self.ack_delay.map(Into::into));
+        socket.set_nagle_enabled(self.nagle);
+        socket.set_hop_limit(self.hop_limit);
+        socket
+    }
+
+    /// Buffer memory one socket takes.
+    fn buffer_bytes(&self) -> usize {
+        self.rx_buffer_size.saturating_add(self.tx_buffer_size)
+    }
+}
 
 /// A TCP stream between a local and a remote socket.
 ///
@@ -23,13 +400,24 @@ use std::task::{Context, Poll};
 pub struct TcpStream {
     pub(crate) handle: SocketHandle,
     pub(crate) reactor: Rc<RefCell<ReactorInner<DpdkDevice>>>,
+    /// Set once data has been written, so only the first write flushes eagerly.
+    written: Cell<bool>,
+    bytes_sent: Cell<u64>,
+    bytes_received: Cell<u64>,
+    /// Set by `shutdown(Read)`: reads report EOF and discard incoming data.
+    read_shutdown: Cell<bool>,
+    /// In-flight count of the listener that accepted this stream
+    inflight: Option<Rc<Inflight>>,
 }
 
 impl TcpStream {
     /// Opens a TCP connection to a remote host.
     ///
     /// Returns an error if the connection cannot be initiated (e.g., invalid
-    /// state, unspecified local/remote addresses, or port already in use).
+    /// state, unspecified local/remote addresses, or port already in use),
+    /// [`TcpConnectError::TooManySockets`] if the reactor's socket cap is
+    /// reached, or [`TcpConnectError::MemoryLimit`] if the buffers would
+    /// exceed its memory cap.
     pub fn connect(
         handle: &ReactorHandle,
         remote_addr: IpAddress,
@@ -37,12 +425,34 @@ impl TcpStream {
         local_port: u16,
         rx_buffer_size: usize,
         tx_buffer_size: usize,
-    ) -> Result<Self, ConnectError> {
+    ) -> Result<Self, TcpConnectError> {
+        Self::connect_with_options(
+            handle,
+            remote_addr,
+            remote_port,
+            local_port,
+            TcpSocketOptions::new(rx_buffer_size, tx_buffer_size),
+        )
+    }
+
+    /// Opens a TCP connection with the buffers and tuning from `options`;
+    /// otherwise like [`TcpStream::connect`].
+    pub fn connect_with_options(
+        handle: &ReactorHandle,
+        remote_addr: IpAddress,
+        remote_port: u16,
+        local_port: u16,
+        options: TcpSocketOptions,
+    ) -> Result<Self, TcpConnectError> {
         let mut inner = handle.inner.borrow_mut();
+        if !inner.has_socket_capacity() {
+            return Err(TcpConnectError::TooManySockets);
+        }
+        if !inner.has_buffer_capacity(options.buffer_bytes()) {
+            return Err(TcpConnectError::MemoryLimit);
+        }
 
-        let rx_buffer = tcp::SocketBuffer::new(vec![0; rx_buffer_size]);
-        let tx_buffer = tcp::SocketBuffer::new(vec![0; tx_buffer_size]);
-        let mut socket = tcp::Socket::new(rx_buffer, tx_buffer);
+        let mut socket = options.socket();
 
         // Connect before adding to socket set
         socket.connect(
@@ -53,12 +463,150 @@ impl TcpStream {
 
         let socket_handle = inner.sockets.add(socket);
 
+        // Put the SYN on the wire now rather than on the next reactor pass
+        if inner.eager_egress {
+            inner.egress_now();
+        }
+
         Ok(TcpStream {
             handle: socket_handle,
             reactor: handle.inner.clone(),
+            written: Cell::new(false),
+            bytes_sent: Cell::new(0),
+            bytes_received: Cell::new(0),
+            read_shutdown: Cell::new(false),
+            inflight: None,
         })
     }
 
+    /// Opens a TCP connection from a local port picked by
+    /// [`ReactorHandle::alloc_ephemeral_port`].
+    ///
+    /// The port is free again once the stream is dropped and its socket
+    /// closed. Fails with [`TcpConnectError::PortsExhausted`] if the
+    /// reactor's ephemeral range is used up; otherwise like
+    /// [`TcpStream::connect`]. The chosen port is in
+    /// [`local_addr`](Self::local_addr).
+    pub fn connect_ephemeral(
+        handle: &ReactorHandle,
+        remote_addr: IpAddress,
+        remote_port: u16,
+        rx_buffer_size: usize,
+        tx_buffer_size: usize,
+    ) -> Result<Self, TcpConnectError> {
+        let local_port = handle
+            .alloc_ephemeral_port()
+            .ok_or(TcpConnectError::PortsExhausted)?;
+        Self::connect(
+            handle,
+            remote_addr,
+            remote_port,
+            local_port,
+            rx_buffer_size,
+            tx_buffer_size,
+        )
+    }
+
+    /// Opens a TCP connection and waits at most `timeout` for the handshake.
+    ///
+    /// Unlike [`connect`](Self::connect) followed by
+    /// [`wait_connected`](Self::wait_connected), this cannot hang when the SYN
+    /// or its answer is lost. The wait runs on the reactor's timer (see
+    /// [`ReactorHandle::timeout`]), so the reactor must be running.
+    ///
+    /// Fails with [`TcpConnectError::TimedOut`] when the time runs out; the
+    /// half-open socket is then aborted and removed from the reactor at once.
+    /// Fails with [`TcpConnectError::Refused`] if the peer resets the attempt,
+    /// and otherwise like [`connect`](Self::connect).
+    pub async fn connect_timeout(
+        handle: &ReactorHandle,
+        remote_addr: IpAddress,
+        remote_port: u16,
+        local_port: u16,
+        rx_buffer_size: usize,
+        tx_buffer_size: usize,
+        timeout: Duration,
+    ) -> Result<Self, TcpConnectError> {
+        let stream = Self::connect(
+            handle,
+            remote_addr,
+            remote_port,
+            local_port,
+            rx_buffer_size,
+            tx_buffer_size,
+        )?;
+        let connected = handle.timeout(timeout, stream.wait_connected()).await;
+        match connected {
+            Ok(Ok(())) => Ok(stream),
+            Ok(Err(())) => Err(TcpConnectError::Refused),
+            Err(_) => {
+                // A closed socket is removed on drop rather than left to the
+                // orphan sweep. The peer, if the SYN reached it, gets a RST
+                // for any late SYN-ACK.
+                stream.abort();
+                Err(TcpConnectError::TimedOut)
+            }
+        }
+    }
+
+    /// Opens a TCP connection to a `std::net` socket address.
+    ///
+    /// Same as [`TcpStream::connect`], for callers that already hold a
+    /// `SocketAddr` (from config parsing or DNS). A `SocketAddrV4` converts
+    /// with `.into()`. An IPv6 destination needs an IPv6 address on the
+    /// interface to send from; without one the connect fails with
+    /// `TcpConnectError::Connect(ConnectError::Unaddressable)`.
+    pub fn connect_std(
+        handle: &ReactorHandle,
+        remote: SocketAddr,
+        local_port: u16,
+        rx_buffer_size: usize,
+        tx_buffer_size: usize,
+    ) -> Result<Self, TcpConnectError> {
+        let (remote_addr, remote_port) = smoltcp_endpoint(remote);
+        Self::connect(
+            handle,
+            remote_addr,
+            remote_port,
+            local_port,
+            rx_buffer_size,
+            tx_buffer_size,
+        )
+    }
+
+    /// Opens a TCP connection and queues `data` as the first write.
+    ///
+    /// smoltcp does not support TCP Fast Open, so the data cannot ride in the
+    /// SYN. Instead it is held by the reactor and written into the socket in
+    /// the same poll that completes the handshake, so it goes out with (or
+    /// right after) the final ACK without waiting for this task to be
+    /// scheduled. Later writes via `send` are ordered after it.
+    ///
+    /// Useful for short request/response protocols where the client speaks first.
+    pub fn connect_with_data(
+        handle: &ReactorHandle,
+        remote_addr: IpAddress,
+        remote_port: u16,
+        local_port: u16,
+        rx_buffer_size: usize,
+        tx_buffer_size: usize,
+        data: &[u8],
+    ) -> Result<Self, TcpConnectError> {
+        let stream = Self::connect(
+            handle,
+            remote_addr,
+            remote_port,
+            local_port,
+            rx_buffer_size,
+            tx_buffer_size,
+        )?;
+        if !data.is_empty() {
+            let mut inner = stream.reactor.borrow_mut();
+            inner.early_data.push((stream.handle, data.to_vec()));
+        }
+        Ok(stream)
+    }
+
     /// Create a TcpStream from an already-connected socket handle.
     ///
     /// This is used internally by TcpListener::accept().
@@ -66,7 +614,15 @@ impl TcpStream {
         handle: SocketHandle,
         reactor: Rc<RefCell<ReactorInner<DpdkDevice>>>,
     ) -> Self {
-        TcpStream { handle, reactor }
+        TcpStream {
+            handle,
+            reactor,
+            written: Cell::new(false),
+            bytes_sent: Cell::new(0),
+            bytes_received: Cell::new(0),
+            read_shutdown: Cell::new(false),
+            inflight: None,
+        }
     }
 
     /// Get the underlying socket handle
@@ -95,9 +651,146 @@ impl TcpStream {
         socket.state()
     }
 
+    /// Address and port of the remote end.
+    ///
+    /// Available once the connection has a peer, for both connected and
+    /// accepted streams. `None` otherwise, including after the connection
+    /// has fully closed: smoltcp forgets the endpoints when the socket
+    /// returns to `Closed`.
+    pub fn peer_addr(&self) -> Option<(IpAddress, u16)> {
+        let inner = self.reactor.borrow();
+        let socket = inner.sockets.get::<tcp::Socket>(self.handle);
+        socket.remote_endpoint().and_then(endpoint_parts)
+    }
+
+    /// Handle to the reactor this stream runs on.
+    pub fn reactor(&self) -> ReactorHandle {
+        ReactorHandle {
+            inner: self.reactor.clone(),
+        }
+    }
+
+    /// Local address and port of this connection.
+    ///
+    /// `None` under the same conditions as [`peer_addr`](Self::peer_addr).
+    pub fn local_addr(&self) -> Option<(IpAddress, u16)> {
+        let inner = self.reactor.borrow();
+        let socket = inner.sockets.get::<tcp::Socket>(self.handle);
+        socket.local_endpoint().and_then(endpoint_parts)
+    }
+
+    /// Disable (`true`) or re-enable (`false`) Nagle's algorithm.
+    ///
+    /// smoltcp enables Nagle by default, which holds back small writes while
+    /// earlier data is unacknowledged. Request/response protocols usually
+    /// want it off.
+    pub fn set_nodelay(&self, enabled: bool) {
+        let mut inner = self.reactor.borrow_mut();
+        let socket = inner.sockets.get_mut::<tcp::Socket>(self.handle);
+        socket.set_nagle_enabled(!enabled);
+    }
+
+    /// Returns true if Nagle's algorithm is disabled on this stream.
+    pub fn nodelay(&self) -> bool {
+        let inner = self.reactor.borrow();
+        let socket = inner.sockets.get::<tcp::Socket>(self.handle);
+        !socket.nagle_enabled()
+    }
+
+    /// Send a keep-alive probe after the connection has been idle for
+    /// `interval`; see [`TcpSocketOptions::keep_alive`].
+    pub fn set_keep_alive(&self, interval: Option<Duration>) {
+        let mut inner = self.reactor.borrow_mut();
+        let socket = inner.sockets.get_mut::<tcp::Socket>(self.handle);
+        socket.set_keep_alive(interval.map(Into::into));
+    }
+
+    /// The keep-alive interval, if any.
+    pub fn keep_alive(&self) -> Option<Duration> {
+        let inner = self.reactor.borrow();
+        let socket = inner.sockets.get::<tcp::Socket>(self.handle);
+        socket.keep_alive().map(Into::into)
+    }
+
+    /// Reset the connection once the peer has been silent for `timeout`;
+    /// see [`TcpSocketOptions::timeout`].
+    pub fn set_timeout(&self, timeout: Option<Duration>) {
+        let mut inner = self.reactor.borrow_mut();
+        let socket = inner.sockets.get_mut::<tcp::Socket>(self.handle);
+        socket.set_timeout(timeout.map(Into::into));
+    }
+
+    /// The dead-peer timeout, if any.
+    pub fn timeout(&self) -> Option<Duration> {
+        let inner = self.reactor.borrow();
+        let socket = inner.sockets.get::<tcp::Socket>(self.handle);
+        socket.timeout().map(Into::into)
+    }
+
+    /// Free space in the transmit buffer, in bytes.
+    ///
+    /// This is how much a call to `send` can enqueue without waiting. Computed
+    /// as smoltcp's buffer capacity minus [`send_queue_len`](Self::send_queue_len).
+    pub fn send_capacity(&self) -> usize {
+        let inner = self.reactor.borrow();
+        let socket = inner.sockets.get::<tcp::Socket>(self.handle);
+        socket.send_capacity() - socket.send_queue()
+    }
+
+    /// Bytes currently held in the transmit buffer.
+    ///
+    /// Taken directly from smoltcp's `send_queue()`. This includes both data
+    /// not yet transmitted and data sent but not yet acknowledged; smoltcp
+    /// does not expose the split, so this is an upper bound on bytes in flight.
+    pub fn send_queue_len(&self) -> usize {
+        let inner = self.reactor.borrow();
+        let socket = inner.sockets.get::<tcp::Socket>(self.handle);
+        socket.send_queue()
+    }
+
+    /// Total size of the transmit buffer, in bytes (smoltcp's `send_capacity()`).
+    pub fn send_buffer_size(&self) -> usize {
+        let inner = self.reactor.borrow();
+        let socket = inner.sockets.get::<tcp::Socket>(self.handle);
+        socket.send_capacity()
+    }
+
+    /// Bytes received and waiting to be read (smoltcp's `recv_queue()`).
+    pub fn recv_queue_len(&self) -> usize {
+        let inner = self.reactor.borrow();
+        let socket = inner.sockets.get::<tcp::Socket>(self.handle);
+        socket.recv_queue()
+    }
+
+    /// Total size of the receive buffer, in bytes (smoltcp's `recv_capacity()`).
+    ///
+    /// Buffers are fixed for the life of the socket: smoltcp has no way to
+    /// swap them on an open connection. To give later connections larger
+    /// buffers, use [`TcpListener::set_buffer_sizes`].
+    pub fn recv_buffer_size(&self) -> usize {
+        let inner = self.reactor.borrow();
+        let socket = inner.sockets.get::<tcp::Socket>(self.handle);
+        socket.recv_capacity()
+    }
+
+    /// Snapshot of this connection's counters.
+    pub fn stats(&self) -> TcpStreamStats {
+        let inner = self.reactor.borrow();
+        let socket = inner.sockets.get::<tcp::Socket>(self.handle);
+        TcpStreamStats {
+            bytes_sent: self.bytes_sent.get(),
+            bytes_received: self.bytes_received.get(),
+            send_queue: socket.send_queue(),
+            recv_queue: socket.recv_queue(),
+            state: socket.state(),
+        }
+    }
+
     /// Send all data asynchronously (write-all semantics).
     ///
     /// Returns the total number of bytes sent when all data has been written.
+    /// Waits if the handshake is still in progress. Errors wrap a
+    /// [`TcpStreamError`].
     pub async fn send(&self, data: &[u8]) -> io::Result<usize> {
         let mut offset = 0;
         while offset < data.len() {
@@ -107,14 +800,41 @@ impl TcpStream {
         Ok(data.len())
     }
 
+    /// Send every slice of `bufs`, in order, without joining them first.
+    ///
+    /// Like [`send`](Self::send) this writes everything before completing,
+    /// and resolves to the total length of `bufs`. Each slice is copied
+    /// straight into the transmit buffer; when that fills up mid-slice, the
+    /// rest follows once space frees up.
+    pub fn send_vectored<'a>(&'a self, bufs: &'a [IoSlice<'a>]) -> TcpSendVectoredFuture<'a> {
+        TcpSendVectoredFuture {
+            stream: self,
+            bufs,
+            written: 0,
+        }
+    }
+
     /// Receive data asynchronously.
     ///
     /// Returns the number of bytes received when the operation completes.
-    /// Returns `Ok(0)` if the connection was closed gracefully (EOF).
+    /// Returns `Ok(0)` once the peer has closed its side and all buffered data
+    /// has been read (EOF), including after our own `close`. Errors wrap a
+    /// [`TcpStreamError`]; see its docs for the per-state behavior.
     pub async fn recv(&self, buf: &mut [u8]) -> io::Result<usize> {
         std::future::poll_fn(|cx| self.poll_recv(cx, buf)).await
     }
 
+    /// Copy received data into `buf` without consuming it.
+    ///
+    /// Waits like [`recv`](Self::recv) until data is available; the bytes
+    /// returned stay buffered, so a later `recv` (or `peek`) sees them
+    /// again. Returns `Ok(0)` at EOF. Peeking does not count toward
+    /// [`stats`](Self::stats), and can only see as much as the receive
+    /// buffer holds.
+    pub fn peek<'a>(&'a self, buf: &'a mut [u8]) -> TcpPeekFuture<'a> {
+        TcpPeekFuture { stream: self, buf }
+    }
+
     /// Wait for the connection to be fully established
     ///
     /// This is useful after `connect()` to wait for the TCP handshake to complete.
@@ -130,6 +850,58 @@ impl TcpStream {
         std::future::poll_fn(|cx| self.poll_close_io(cx)).await
     }
 
+    /// Shut down the read side, the write side, or both, without waiting.
+    ///
+    /// - `Write` sends a FIN once queued data is out. The peer sees EOF,
+    ///   while this stream keeps receiving until the peer closes too; later
+    ///   sends fail with [`TcpStreamError::Shutdown`].
+    /// - `Read` makes reads return EOF. Data already buffered, and data
+    ///   arriving later, is discarded as reads come in; the peer is not told.
+    /// - `Both` does both. Unlike [`close`](Self::close) it does not wait
+    ///   for the connection to finish closing.
+    ///
+    /// Dropping a stream after `shutdown(Write)` lets the close finish
+    /// gracefully in the background rather than resetting the connection.
+    /// Fails with [`TcpStreamError::NotConnected`] before the connection is
+    /// established.
+    pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+        let mut inner = self.reactor.borrow_mut();
+        let socket = inner.sockets.get_mut::<tcp::Socket>(self.handle);
+        if matches!(
+            socket.state(),
+            State::Listen | State::SynSent | State::SynReceived
+        ) {
+            return Err(TcpStreamError::NotConnected.into());
+        }
+        if matches!(how, Shutdown::Read | Shutdown::Both) {
+            self.read_shutdown.set(true);
+            discard_recv(socket);
+        }
+        if matches!(how, Shutdown::Write | Shutdown::Both) {
+            // smoltcp's close() is a half-close: the FIN goes out after the
+            // queued data and the receive side stays open
+            socket.close();
+        }
+        Ok(())
+    }
+
+    /// Split into a read half and a write half that can be moved into
+    /// separate tasks.
+    ///
+    /// Both halves drive the same socket. The connection is cleaned up as
+    /// for a dropped `TcpStream` once both halves are gone; dropping only
+    /// one leaves the other usable. Use [`OwnedReadHalf::reunite`] to get
+    /// the stream back.
+    pub fn into_split(self) -> (OwnedReadHalf, OwnedWriteHalf) {
+        let stream = Rc::new(self);
+        (
+            OwnedReadHalf {
+                stream: stream.clone(),
+            },
+            OwnedWriteHalf { stream },
+        )
+    }
+
     /// Abort the connection immediately
     ///
     /// This sends a RST and terminates the connection.
@@ -139,6 +911,35 @@ impl TcpStream {
         socket.abort();
     }
 
+    /// Try to queue `data` for sending without waiting.
+    ///
+    /// Makes a single attempt and returns how many bytes were accepted,
+    /// which may be fewer than `data.len()`. When the send buffer is full or
+    /// the handshake is still in progress, returns
+    /// [`NonblockingError::WouldBlock`] instead of registering a waker.
+    ///
+    /// Nothing moves unless the caller drives the reactor, e.g. with
+    /// [`ReactorHandle::poll_once`], between attempts.
+    pub fn send_nonblocking(&self, data: &[u8]) -> Result<usize, NonblockingError> {
+        let mut inner = self.reactor.borrow_mut();
+        self.send_once(&mut inner, data)
+    }
+
+    /// Try to read received data without waiting.
+    ///
+    /// Returns `Ok(0)` at EOF, like [`recv`](Self::recv), and
+    /// [`NonblockingError::WouldBlock`] when no data is buffered yet. As with
+    /// [`send_nonblocking`](Self::send_nonblocking), the caller is
+    /// responsible for driving the reactor.
+    pub fn recv_nonblocking(&self, buf: &mut [u8]) -> Result<usize, NonblockingError> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let mut inner = self.reactor.borrow_mut();
+        let socket = inner.sockets.get_mut::<tcp::Socket>(self.handle);
+        self.recv_once(socket, buf)
+    }
+
     /// Poll for reading data from the socket.
     ///
     /// This is the core poll implementation used by both [`AsyncRead`] and [`recv`](Self::recv).
@@ -148,19 +949,72 @@ impl TcpStream {
         }
 
         let mut inner = self.reactor.borrow_mut();
+        if inner.poll_budget(cx).is_pending() {
+            return Poll::Pending;
+        }
         let socket = inner.sockets.get_mut::<tcp::Socket>(self.handle);
 
-        match socket.recv_slice(buf) {
-            Ok(0) => {
+        match self.recv_once(socket, buf) {
+            Err(NonblockingError::WouldBlock) => {
+                socket.register_recv_waker(cx.waker());
+                inner.note_pending();
+                Poll::Pending
+            }
+            result => Poll::Ready(result.map_err(io::Error::from)),
+        }
+    }
+
+    /// Poll for data to peek at; the [`TcpPeekFuture`] implementation.
+    fn poll_peek(&self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        if buf.is_empty() || self.read_shutdown.get() {
+            return Poll::Ready(Ok(0));
+        }
+
+        let mut inner = self.reactor.borrow_mut();
+        if inner.poll_budget(cx).is_pending() {
+            return Poll::Pending;
+        }
+        let socket = inner.sockets.get_mut::<tcp::Socket>(self.handle);
+
+        let error = match socket.peek_slice(buf) {
+            Ok(0) => None,
+            Ok(n) => return Poll::Ready(Ok(n)),
+            Err(RecvError::Finished) => return Poll::Ready(Ok(0)),
+            Err(RecvError::InvalidState) => TcpStreamError::for_recv(socket.state()),
+        };
+        match error {
+            Some(e) => Poll::Ready(Err(e.into())),
+            None => {
                 socket.register_recv_waker(cx.waker());
+                inner.note_pending();
                 Poll::Pending
             }
-            Ok(n) => Poll::Ready(Ok(n)),
-            Err(RecvError::Finished) => Poll::Ready(Ok(0)),
-            Err(RecvError::InvalidState) => Poll::Ready(Err(io::Error::new(
-                io::ErrorKind::NotConnected,
-                "socket in invalid state for receiving",
-            ))),
+        }
+    }
+
+    /// One `recv_slice` attempt, shared by [`poll_recv`](Self::poll_recv)
+    /// and [`recv_nonblocking`](Self::recv_nonblocking).
+    fn recv_once(
+        &self,
+        socket: &mut tcp::Socket,
+        buf: &mut [u8],
+    ) -> Result<usize, NonblockingError> {
+        if self.read_shutdown.get() {
+            discard_recv(socket);
+            return Ok(0);
+        }
+        match socket.recv_slice(buf) {
+            Ok(0) => Err(NonblockingError::WouldBlock),
+            Ok(n) => {
+                self.bytes_received
+                    .set(self.bytes_received.get() + n as u64);
+                Ok(n)
+            }
+            Err(RecvError::Finished) => Ok(0),
+            Err(RecvError::InvalidState) => match TcpStreamError::for_recv(socket.state()) {
+                Some(e) => Err(e.into()),
+                None => Err(NonblockingError::WouldBlock),
+            },
         }
     }
 
@@ -169,18 +1023,105 @@ impl TcpStream {
     /// This is the core poll implementation used by both [`AsyncWrite`] and [`send`](Self::send).
     fn poll_send(&self, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
         let mut inner = self.reactor.borrow_mut();
-        let socket = inner.sockets.get_mut::<tcp::Socket>(self.handle);
+        if inner.poll_budget(cx).is_pending() {
+            return Poll::Pending;
+        }
 
-        match socket.send_slice(buf) {
-            Ok(0) if !buf.is_empty() => {
+        match self.send_once(&mut inner, buf) {
+            Err(NonblockingError::WouldBlock) => {
+                let socket = inner.sockets.get_mut::<tcp::Socket>(self.handle);
                 socket.register_send_waker(cx.waker());
+                inner.note_pending();
                 Poll::Pending
             }
-            Ok(n) => Poll::Ready(Ok(n)),
-            Err(tcp::SendError::InvalidState) => Poll::Ready(Err(io::Error::new(
-                io::ErrorKind::NotConnected,
-                "socket in invalid state for sending",
-            ))),
+            result => Poll::Ready(result.map_err(io::Error::from)),
+        }
+    }
+
+    /// Queue as much of `bufs` as fits, skipping its first `skip` bytes.
+    ///
+    /// Returns the bytes queued by this call, which is non-zero unless the
+    /// remainder is empty; registers a send waker when nothing fits.
+    fn poll_send_vectored(
+        &self,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+        mut skip: usize,
+    ) -> Poll<io::Result<usize>> {
+        let mut inner = self.reactor.borrow_mut();
+        if inner.poll_budget(cx).is_pending() {
+            return Poll::Pending;
+        }
+
+        let mut written = 0;
+        let mut remaining = false;
+        for buf in bufs {
+            if skip >= buf.len() {
+                skip -= buf.len();
+                continue;
+            }
+            let rest = &buf[skip..];
+            skip = 0;
+            remaining = true;
+            match self.send_once(&mut inner, rest) {
+                Ok(n) => {
+                    written += n;
+                    if n < rest.len() {
+                        break;
+                    }
+                }
+                Err(NonblockingError::WouldBlock) => break,
+                Err(e) if written == 0 => return Poll::Ready(Err(e.into())),
+                // Report what got through; the error resurfaces on the next write
+                Err(_) => break,
+            }
+        }
+
+        if written == 0 && remaining {
+            let socket = inner.sockets.get_mut::<tcp::Socket>(self.handle);
+            socket.register_send_waker(cx.waker());
+            inner.note_pending();
+            return Poll::Pending;
+        }
+        Poll::Ready(Ok(written))
+    }
+
+    /// One `send_slice` attempt, shared by [`poll_send`](Self::poll_send)
+    /// and [`send_nonblocking`](Self::send_nonblocking).
+    fn send_once(
+        &self,
+        inner: &mut ReactorInner<DpdkDevice>,
+        buf: &[u8],
+    ) -> Result<usize, NonblockingError> {
+        // Early data from connect_with_data must be written first
+        if inner.has_early_data(self.handle) {
+            inner.flush_early_data();
+            if inner.has_early_data(self.handle) {
+                return Err(NonblockingError::WouldBlock);
+            }
+        }
+
+        let socket = inner.sockets.get_mut::<tcp::Socket>(self.handle);
+
+        match socket.send_slice(buf) {
+            Ok(0) if !buf.is_empty() => Err(NonblockingError::WouldBlock),
+            Ok(n) => {
+                self.bytes_sent.set(self.bytes_sent.get() + n as u64);
+                // The first segment of a request is latency-sensitive; later
+                // writes batch up for the reactor's egress pass
+                if n > 0 && !self.written.replace(true) && inner.eager_egress {
+                    inner.egress_now();
+                }
+                Ok(n)
+            }
+            Err(tcp::SendError::InvalidState) => {
+                // With an empty buffer this only reports whether a FIN arrived
+                let fin_received = matches!(socket.recv_slice(&mut []), Err(RecvError::Finished));
+                match TcpStreamError::for_send(socket.state(), fin_received) {
+                    Some(e) => Err(e.into()),
+                    None => Err(NonblockingError::WouldBlock),
+                }
+            }
         }
     }
 
@@ -189,13 +1130,14 @@ impl TcpStream {
         let inner = self.reactor.borrow_mut();
         let socket = inner.sockets.get::<tcp::Socket>(self.handle);
 
-        if socket.send_queue() == 0 {
+        if socket.send_queue() == 0 && !inner.has_early_data(self.handle) {
             Poll::Ready(Ok(()))
         } else {
             drop(inner);
             let mut inner = self.reactor.borrow_mut();
             let socket = inner.sockets.get_mut::<tcp::Socket>(self.handle);
             socket.register_send_waker(cx.waker());
+            inner.note_pending();
             Poll::Pending
         }
     }
@@ -213,22 +1155,61 @@ impl TcpStream {
             }
         }
 
-        {
-            let mut inner = self.reactor.borrow_mut();
-            let socket = inner.sockets.get_mut::<tcp::Socket>(self.handle);
+        {
+            let mut inner = self.reactor.borrow_mut();
+            let socket = inner.sockets.get_mut::<tcp::Socket>(self.handle);
+
+            match socket.state() {
+                State::Closed | State::TimeWait => Poll::Ready(Ok(())),
+                _ => {
+                    socket.register_send_waker(cx.waker());
+                    inner.note_pending();
+                    Poll::Pending
+                }
+            }
+        }
+    }
+}
+
+impl AsyncRead for TcpStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        self.poll_recv(cx, buf)
+    }
+}
+
+impl AsyncWrite for TcpStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.poll_send(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        self.poll_send_vectored(cx, bufs, 0)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.poll_flush_io(cx)
+    }
 
-            match socket.state() {
-                State::Closed | State::TimeWait => Poll::Ready(Ok(())),
-                _ => {
-                    socket.register_send_waker(cx.waker());
-                    Poll::Pending
-                }
-            }
-        }
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.poll_close_io(cx)
     }
 }
 
-impl AsyncRead for TcpStream {
+// Like `std::net::TcpStream`, a shared reference can do I/O, so a stream held
+// in an `Rc` can be read and written while other owners inspect its state.
+impl AsyncRead for &TcpStream {
     fn poll_read(
         self: Pin<&mut Self>,
         cx: &mut Context<'_>,
@@ -238,7 +1219,7 @@ impl AsyncRead for TcpStream {
     }
 }
 
-impl AsyncWrite for TcpStream {
+impl AsyncWrite for &TcpStream {
     fn poll_write(
         self: Pin<&mut Self>,
         cx: &mut Context<'_>,
@@ -247,6 +1228,14 @@ impl AsyncWrite for TcpStream {
         self.poll_send(cx, buf)
     }
 
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        self.poll_send_vectored(cx, bufs, 0)
+    }
+
     fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
         self.poll_flush_io(cx)
     }
@@ -258,7 +1247,12 @@ impl AsyncWrite for TcpStream {
 
 impl Drop for TcpStream {
     fn drop(&mut self) {
+        if let Some(inflight) = &self.inflight {
+            inflight.release();
+        }
+
         let mut inner = self.reactor.borrow_mut();
+        inner.early_data.retain(|(h, _)| *h != self.handle);
 
         // Check the socket state to decide how to clean up
         let socket = inner.sockets.get_mut::<tcp::Socket>(self.handle);
@@ -267,7 +1261,8 @@ impl Drop for TcpStream {
             State::Closed | State::TimeWait => {
                 inner.sockets.remove(self.handle);
             }
-            // In graceful shutdown - add to orphan list for deferred cleanup
+            // In graceful shutdown (close, or shutdown(Write)) - add to orphan
+            // list for deferred cleanup
             // The reactor will remove these once they reach Closed/TimeWait
             State::FinWait1 | State::FinWait2 | State::Closing | State::LastAck => {
                 inner.orphaned_closing.push(self.handle);
@@ -283,6 +1278,162 @@ impl Drop for TcpStream {
     }
 }
 
+/// The read half of a [`TcpStream`], from [`TcpStream::into_split`].
+///
+/// State accessors (`state`, `stats`, `peer_addr`, ...) are reachable
+/// through `as_ref()`.
+pub struct OwnedReadHalf {
+    stream: Rc<TcpStream>,
+}
+
+impl OwnedReadHalf {
+    /// Receive data; see [`TcpStream::recv`].
+    pub async fn recv(&self, buf: &mut [u8]) -> io::Result<usize> {
+        self.stream.recv(buf).await
+    }
+
+    /// Try to read received data without waiting; see
+    /// [`TcpStream::recv_nonblocking`].
+    pub fn recv_nonblocking(&self, buf: &mut [u8]) -> Result<usize, NonblockingError> {
+        self.stream.recv_nonblocking(buf)
+    }
+
+    /// Put the halves back together into the original stream.
+    ///
+    /// Fails, handing both halves back, if they were split from different
+    /// streams.
+    pub fn reunite(self, other: OwnedWriteHalf) -> Result<TcpStream, ReuniteError> {
+        if !Rc::ptr_eq(&self.stream, &other.stream) {
+            return Err(ReuniteError(self, other));
+        }
+        drop(other);
+        // The halves held the only two references
+        Ok(Rc::into_inner(self.stream).expect("stream shared beyond its halves"))
+    }
+}
+
+impl AsRef<TcpStream> for OwnedReadHalf {
+    fn as_ref(&self) -> &TcpStream {
+        &self.stream
+    }
+}
+
+impl AsyncRead for OwnedReadHalf {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        self.stream.poll_recv(cx, buf)
+    }
+}
+
+/// The write half of a [`TcpStream`], from [`TcpStream::into_split`].
+///
+/// Closing it sends a FIN: the peer sees EOF, while the read half keeps
+/// receiving until the peer closes too.
+pub struct OwnedWriteHalf {
+    stream: Rc<TcpStream>,
+}
+
+impl OwnedWriteHalf {
+    /// Send all of `data`; see [`TcpStream::send`].
+    pub async fn send(&self, data: &[u8]) -> io::Result<usize> {
+        self.stream.send(data).await
+    }
+
+    /// Send every slice of `bufs`; see [`TcpStream::send_vectored`].
+    pub fn send_vectored<'a>(&'a self, bufs: &'a [IoSlice<'a>]) -> TcpSendVectoredFuture<'a> {
+        self.stream.send_vectored(bufs)
+    }
+
+    /// Try to queue `data` without waiting; see
+    /// [`TcpStream::send_nonblocking`].
+    pub fn send_nonblocking(&self, data: &[u8]) -> Result<usize, NonblockingError> {
+        self.stream.send_nonblocking(data)
+    }
+
+    /// Close the connection gracefully; see [`TcpStream::close`].
+    pub async fn close(&self) -> io::Result<()> {
+        self.stream.close().await
+    }
+
+    /// Put the halves back together; see [`OwnedReadHalf::reunite`].
+    pub fn reunite(self, other: OwnedReadHalf) -> Result<TcpStream, ReuniteError> {
+        other.reunite(self)
+    }
+}
+
+impl AsRef<TcpStream> for OwnedWriteHalf {
+    fn as_ref(&self) -> &TcpStream {
+        &self.stream
+    }
+}
+
+impl AsyncWrite for OwnedWriteHalf {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.stream.poll_send(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        self.stream.poll_send_vectored(cx, bufs, 0)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.stream.poll_flush_io(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.stream.poll_close_io(cx)
+    }
+}
+
+/// Error returned by `reunite` for halves of two different streams.
+///
+/// Holds both halves, unchanged.
+pub struct ReuniteError(pub OwnedReadHalf, pub OwnedWriteHalf);
+
+impl fmt::Debug for ReuniteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ReuniteError").finish_non_exhaustive()
+    }
+}
+
+impl fmt::Display for ReuniteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "tried to reunite halves of different streams")
+    }
+}
+
+impl std::error::Error for ReuniteError {}
+
+/// Accepted streams of a [`TcpListener`] that are still alive, shared with
+/// each of them so their drop can wake an accept held back by
+/// [`TcpListener::set_max_inflight`].
+#[derive(Default)]
+struct Inflight {
+    live: Cell<usize>,
+    /// The accept waiting for a stream to drop
+    waker: Cell<Option<Waker>>,
+}
+
+impl Inflight {
+    fn release(&self) {
+        self.live.set(self.live.get() - 1);
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
 /// A TCP socket server, listening for connections.
 ///
 /// Similar to `std::net::TcpListener`, this listens for incoming TCP connections.
@@ -294,10 +1445,17 @@ impl Drop for TcpStream {
 pub struct TcpListener {
     /// Pool of sockets for handling concurrent connections
     handles: Vec<SocketHandle>,
+    /// Size `handles` is refilled to; it runs short while the reactor is at
+    /// a cap
+    backlog: usize,
     reactor: Rc<RefCell<ReactorInner<DpdkDevice>>>,
-    port: u16,
-    rx_buffer_size: usize,
-    tx_buffer_size: usize,
+    /// Port, and the local address if bound to one, of every backlog socket
+    endpoint: IpListenEndpoint,
+    /// Applied to every backlog socket, and so to accepted streams
+    options: TcpSocketOptions,
+    /// Accepted streams not yet dropped
+    inflight: Rc<Inflight>,
+    max_inflight: Option<usize>,
 }
 
 impl TcpListener {
@@ -309,59 +1467,232 @@ impl TcpListener {
         port: u16,
         rx_buffer_size: usize,
         tx_buffer_size: usize,
-    ) -> Result<Self, ListenError> {
+    ) -> Result<Self, TcpListenError> {
         Self::bind_with_backlog(handle, port, rx_buffer_size, tx_buffer_size, 2)
     }
 
+    /// Creates a new TcpListener for a `std::net` socket address, with the
+    /// default backlog of 2.
+    ///
+    /// `0.0.0.0:port` and `[::]:port` accept on every address of the
+    /// interface, like [`TcpListener::bind`]; any other address only
+    /// accepts connections to it, like [`TcpListener::bind_addr`].
+    pub fn bind_std(
+        handle: &ReactorHandle,
+        addr: SocketAddr,
+        rx_buffer_size: usize,
+        tx_buffer_size: usize,
+    ) -> Result<Self, TcpListenError> {
+        let (ip, port) = smoltcp_endpoint(addr);
+        Self::bind_addr(handle, ip, port, rx_buffer_size, tx_buffer_size)
+    }
+
+    /// Creates a new TcpListener that only accepts connections to `local`,
+    /// with the default backlog of 2.
+    ///
+    /// On an interface with several addresses (see
+    /// `ReactorConfig::ip_addrs`), SYNs to the port on any other address
+    /// are not answered by this listener, so another listener may serve the
+    /// same port there. An unspecified `local` listens on every address, as
+    /// [`TcpListener::bind`] does.
+    ///
+    /// Fails with [`ListenError::Unaddressable`] if `local` is not one of
+    /// the interface's addresses.
+    pub fn bind_addr(
+        handle: &ReactorHandle,
+        local: IpAddress,
+        port: u16,
+        rx_buffer_size: usize,
+        tx_buffer_size: usize,
+    ) -> Result<Self, TcpListenError> {
+        Self::bind_addr_with_backlog(handle, local, port, rx_buffer_size, tx_buffer_size, 2)
+    }
+
     /// Creates a new TcpListener with a specified backlog size.
     ///
     /// The backlog determines how many simultaneous connection attempts can be
     /// handled before `accept()` is called. For a single-threaded server, set
     /// this to the maximum expected burst of concurrent connections.
+    ///
+    /// Every backlog socket counts toward the reactor's socket and memory
+    /// caps. If the whole backlog does not fit, no sockets are created and
+    /// [`TcpListenError::TooManySockets`] or [`TcpListenError::MemoryLimit`]
+    /// is returned.
     pub fn bind_with_backlog(
         handle: &ReactorHandle,
         port: u16,
         rx_buffer_size: usize,
         tx_buffer_size: usize,
         backlog: usize,
-    ) -> Result<Self, ListenError> {
+    ) -> Result<Self, TcpListenError> {
+        let endpoint = IpListenEndpoint { addr: None, port };
+        let options = TcpSocketOptions::new(rx_buffer_size, tx_buffer_size);
+        Self::bind_endpoint(handle, endpoint, options, backlog)
+    }
+
+    /// Creates a new TcpListener whose sockets, and so accepted streams, get
+    /// the buffers and tuning from `options`, with the default backlog of 2.
+    ///
+    /// A [`timeout`](TcpSocketOptions::timeout) also frees backlog sockets
+    /// whose handshake stalls, and accepted streams whose peer vanished.
+    pub fn bind_with_options(
+        handle: &ReactorHandle,
+        port: u16,
+        options: TcpSocketOptions,
+    ) -> Result<Self, TcpListenError> {
+        let endpoint = IpListenEndpoint { addr: None, port };
+        Self::bind_endpoint(handle, endpoint, options, 2)
+    }
+
+    /// [`bind_addr`](Self::bind_addr) with a specified backlog size; see
+    /// [`bind_with_backlog`](Self::bind_with_backlog).
+    pub fn bind_addr_with_backlog(
+        handle: &ReactorHandle,
+        local: IpAddress,
+        port: u16,
+        rx_buffer_size: usize,
+        tx_buffer_size: usize,
+        backlog: usize,
+    ) -> Result<Self, TcpListenError> {
+        let endpoint = Self::addr_endpoint(handle, local, port)?;
+        let options = TcpSocketOptions::new(rx_buffer_size, tx_buffer_size);
+        Self::bind_endpoint(handle, endpoint, options, backlog)
+    }
+
+    /// [`bind_addr`](Self::bind_addr) with the buffers and tuning from
+    /// `options`; see [`bind_with_options`](Self::bind_with_options).
+    pub fn bind_addr_with_options(
+        handle: &ReactorHandle,
+        local: IpAddress,
+        port: u16,
+        options: TcpSocketOptions,
+    ) -> Result<Self, TcpListenError> {
+        let endpoint = Self::addr_endpoint(handle, local, port)?;
+        Self::bind_endpoint(handle, endpoint, options, 2)
+    }
+
+    /// Listen endpoint for `local`, which must be unspecified or one of the
+    /// interface's addresses.
+    fn addr_endpoint(
+        handle: &ReactorHandle,
+        local: IpAddress,
+        port: u16,
+    ) -> Result<IpListenEndpoint, TcpListenError> {
+        let addr = (!local.is_unspecified()).then_some(local);
+        if let Some(addr) = addr
+            && !handle.inner.borrow().iface.has_ip_addr(addr)
+        {
+            return Err(ListenError::Unaddressable.into());
+        }
+        Ok(IpListenEndpoint { addr, port })
+    }
+
+    fn bind_endpoint(
+        handle: &ReactorHandle,
+        endpoint: IpListenEndpoint,
+        options: TcpSocketOptions,
+        backlog: usize,
+    ) -> Result<Self, TcpListenError> {
         let backlog = backlog.max(1); // At least 1 socket
         let mut inner = handle.inner.borrow_mut();
+        if let Some(max) = inner.max_sockets
+            && inner.socket_count() + backlog > max
+        {
+            return Err(TcpListenError::TooManySockets);
+        }
+        if !inner.has_buffer_capacity(options.buffer_bytes().saturating_mul(backlog)) {
+            return Err(TcpListenError::MemoryLimit);
+        }
         let mut handles = Vec::with_capacity(backlog);
 
         for _ in 0..backlog {
-            let h =
-                Self::create_listening_socket(&mut inner, port, rx_buffer_size, tx_buffer_size)?;
-            handles.push(h);
+            match Self::create_listening_socket(&mut inner, endpoint, &options) {
+                Ok(h) => handles.push(h),
+                Err(e) => {
+                    for h in handles {
+                        inner.listen_specs.remove(&h);
+                        inner.sockets.remove(h);
+                    }
+                    return Err(e.into());
+                }
+            }
         }
 
         Ok(TcpListener {
             handles,
+            backlog,
             reactor: handle.inner.clone(),
-            port,
-            rx_buffer_size,
-            tx_buffer_size,
+            endpoint,
+            options,
+            inflight: Rc::default(),
+            max_inflight: None,
         })
     }
 
     /// Create a new listening socket and add it to the reactor
     fn create_listening_socket(
         inner: &mut ReactorInner<DpdkDevice>,
-        port: u16,
-        rx_buffer_size: usize,
-        tx_buffer_size: usize,
+        endpoint: IpListenEndpoint,
+        options: &TcpSocketOptions,
     ) -> Result<SocketHandle, ListenError> {
-        let rx_buffer = tcp::SocketBuffer::new(vec![0; rx_buffer_size]);
-        let tx_buffer = tcp::SocketBuffer::new(vec![0; tx_buffer_size]);
-        let mut socket = tcp::Socket::new(rx_buffer, tx_buffer);
-        socket.listen(port)?;
+        let mut socket = options.socket();
+        socket.listen(endpoint)?;
         let handle = inner.sockets.add(socket);
+        inner.listen_specs.insert(
+            handle,
+            ListenSpec {
+                endpoint,
+                options: *options,
+                accept_waker: None,
+            },
+        );
         Ok(handle)
     }
 
+    /// Add listening sockets until the backlog is full again or the next one
+    /// would not fit under the reactor's caps. Returns true if it is full.
+    fn refill_backlog(
+        &mut self,
+        inner: &mut ReactorInner<DpdkDevice>,
+    ) -> Result<bool, ListenError> {
+        while self.handles.len() < self.backlog {
+            if !inner.has_socket_capacity()
+                || !inner.has_buffer_capacity(self.options.buffer_bytes())
+            {
+                return Ok(false);
+            }
+            let handle = Self::create_listening_socket(inner, self.endpoint, &self.options)?;
+            self.handles.push(handle);
+        }
+        Ok(true)
+    }
+
+    /// Give backlog sockets mid-handshake the current options too, for when
+    /// the reactor rebuilds one.
+    fn update_listen_specs(&self, inner: &mut ReactorInner<DpdkDevice>) {
+        for handle in &self.handles {
+            if let Some(spec) = inner.listen_specs.get_mut(handle) {
+                spec.options = self.options;
+            }
+        }
+    }
+
+    /// Handle to the reactor this listener is bound on.
+    pub fn reactor(&self) -> ReactorHandle {
+        ReactorHandle {
+            inner: self.reactor.clone(),
+        }
+    }
+
     /// Get the port this listener is bound to
     pub fn local_port(&self) -> u16 {
-        self.port
+        self.endpoint.port
+    }
+
+    /// Get the local endpoint this listener is bound to. Its address is
+    /// `None` if it accepts on every address of the interface.
+    pub fn endpoint(&self) -> IpListenEndpoint {
+        self.endpoint
     }
 
     /// Accept a new incoming connection.
@@ -369,10 +1700,32 @@ impl TcpListener {
     /// This waits for a client to connect and returns a `TcpStream` for the
     /// accepted connection. The listener remains valid and can accept more
     /// connections, similar to `std::net::TcpListener::accept()`.
+    ///
+    /// Handing out a connection needs a fresh socket to refill the backlog.
+    /// If the reactor is at its socket cap, the connection is reset instead
+    /// and [`TcpListenError::TooManySockets`] is returned; likewise
+    /// [`TcpListenError::MemoryLimit`] if the new socket's buffers do not fit
+    /// under the memory cap. The reset socket's slot is left empty rather
+    /// than allocated past the cap, so the backlog shrinks, and `accept`
+    /// refills it once the reactor has room again. The listener stays
+    /// usable, so servers should log and keep accepting.
+    ///
+    /// With [`set_max_inflight`](Self::set_max_inflight), waits while the
+    /// cap of live accepted streams is reached.
     pub fn accept(&mut self) -> AcceptFuture<'_> {
         AcceptFuture { listener: self }
     }
 
+    /// Accepted connections as a [`Stream`], for `while let Some(..)` loops
+    /// and stream combinators.
+    ///
+    /// Each item is what [`accept`](Self::accept) would return, including
+    /// its backlog refill and its errors. The stream never ends; an error
+    /// item leaves the listener usable, so keep polling after logging it.
+    pub fn incoming(&mut self) -> Incoming<'_> {
+        Incoming { listener: self }
+    }
+
     /// Check if a connection is pending (ready to be accepted)
     pub fn is_pending(&self) -> bool {
         let inner = self.reactor.borrow();
@@ -392,9 +1745,109 @@ impl TcpListener {
     }
 
     /// Get the backlog size (number of listening sockets)
+    ///
+    /// Below the size the listener was bound with while the reactor's caps
+    /// keep `accept` from refilling it.
     pub fn backlog(&self) -> usize {
         self.handles.len()
     }
+
+    /// Buffer sizes `(rx, tx)` given to newly accepted connections.
+    pub fn buffer_sizes(&self) -> (usize, usize) {
+        (self.options.rx_buffer_size, self.options.tx_buffer_size)
+    }
+
+    /// Options given to newly accepted connections.
+    pub fn options(&self) -> TcpSocketOptions {
+        self.options
+    }
+
+    /// Disable (`true`) or re-enable (`false`) Nagle's algorithm on accepted
+    /// connections; see [`TcpStream::set_nodelay`].
+    ///
+    /// Applies to every backlog socket, including ones mid-handshake, so all
+    /// streams accepted from now on inherit it. Streams already accepted
+    /// keep their own setting.
+    pub fn set_nodelay(&mut self, enabled: bool) {
+        self.options.nagle = !enabled;
+        let mut inner = self.reactor.borrow_mut();
+        for &handle in &self.handles {
+            inner
+                .sockets
+                .get_mut::<tcp::Socket>(handle)
+                .set_nagle_enabled(!enabled);
+        }
+        self.update_listen_specs(&mut inner);
+    }
+
+    /// Returns true if accepted connections get Nagle disabled.
+    pub fn nodelay(&self) -> bool {
+        !self.options.nagle
+    }
+
+    /// Cap the number of accepted streams alive at once.
+    ///
+    /// While `max` streams from this listener are not yet dropped, `accept`
+    /// waits instead of handing out connections or refilling the backlog.
+    /// Handshakes complete on the backlog sockets and stay queued there;
+    /// once those are all taken, further SYNs go unanswered. Dropping a
+    /// stream wakes the pending accept. This bounds the sockets and buffers
+    /// a connection flood can pin downstream. Lowering the cap below the
+    /// current count closes nothing. `None` (the default) removes the cap.
+    pub fn set_max_inflight(&mut self, max: Option<usize>) {
+        self.max_inflight = max;
+        if let Some(waker) = self.inflight.waker.take() {
+            waker.wake();
+        }
+    }
+
+    /// The current in-flight cap, if any.
+    pub fn max_inflight(&self) -> Option<usize> {
+        self.max_inflight
+    }
+
+    /// Number of streams accepted from this listener and not yet dropped.
+    pub fn inflight(&self) -> usize {
+        self.inflight.live.get()
+    }
+
+    /// Change the buffer sizes used for connections accepted from now on.
+    ///
+    /// Backlog sockets that are still idle (`Listen`) are recreated with the
+    /// new sizes right away. Sockets already mid-handshake, and streams
+    /// already accepted, keep the buffers they were created with. If growing
+    /// a socket's buffers would exceed the reactor's memory cap, it stops
+    /// there with [`TcpListenError::MemoryLimit`]; sockets already swapped
+    /// keep the new sizes.
+    pub fn set_buffer_sizes(
+        &mut self,
+        rx_buffer_size: usize,
+        tx_buffer_size: usize,
+    ) -> Result<(), TcpListenError> {
+        self.options.rx_buffer_size = rx_buffer_size;
+        self.options.tx_buffer_size = tx_buffer_size;
+
+        let mut inner = self.reactor.borrow_mut();
+        self.update_listen_specs(&mut inner);
+        for handle in self.handles.iter_mut() {
+            let old = inner.sockets.get::<tcp::Socket>(*handle);
+            if old.state() != State::Listen {
+                continue;
+            }
+            let growth = (rx_buffer_size + tx_buffer_size)
+                .saturating_sub(old.recv_capacity() + old.send_capacity());
+            if !inner.has_buffer_capacity(growth) {
+                return Err(TcpListenError::MemoryLimit);
+            }
+            // Swap one for one, so the socket count does not grow
+            let new_handle =
+                Self::create_listening_socket(&mut inner, self.endpoint, &self.options)?;
+            let old_handle = std::mem::replace(handle, new_handle);
+            inner.listen_specs.remove(&old_handle);
+            inner.sockets.remove(old_handle);
+        }
+        Ok(())
+    }
 }
 
 impl Drop for TcpListener {
@@ -403,6 +1856,7 @@ impl Drop for TcpListener {
 
         // Close all listening sockets
         for &handle in &self.handles {
+            inner.listen_specs.remove(&handle);
             let socket = inner.sockets.get_mut::<tcp::Socket>(handle);
             if socket.state() != State::Closed {
                 socket.abort();
@@ -424,11 +1878,21 @@ pub struct AcceptFuture<'a> {
 }
 
 impl<'a> Future for AcceptFuture<'a> {
-    type Output = Result<TcpStream, ListenError>;
+    type Output = Result<TcpStream, TcpListenError>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = self.get_mut();
 
+        // At the in-flight cap, leave connections queued on the backlog
+        // sockets until an accepted stream is dropped
+        let inflight = &this.listener.inflight;
+        if let Some(max) = this.listener.max_inflight
+            && inflight.live.get() >= max
+        {
+            inflight.waker.set(Some(cx.waker().clone()));
+            return Poll::Pending;
+        }
+
         // Find a socket that has an established connection
         let established_idx = {
             let inner = this.listener.reactor.borrow();
@@ -455,38 +1919,71 @@ impl<'a> Future for AcceptFuture<'a> {
 
                 // Get the connected socket handle
                 let connected_handle = this.listener.handles[idx];
+                inner.listen_specs.remove(&connected_handle);
+
+                // At a cap the extra socket is not available: reset the
+                // peer and leave its slot empty until there is room. The
+                // reset socket is reaped by the reactor after its RST goes
+                // out, so usage never goes past the cap.
+                let at_cap = if !inner.has_socket_capacity() {
+                    Some(TcpListenError::TooManySockets)
+                } else if !inner.has_buffer_capacity(this.listener.options.buffer_bytes()) {
+                    Some(TcpListenError::MemoryLimit)
+                } else {
+                    None
+                };
+                if let Some(err) = at_cap {
+                    inner
+                        .sockets
+                        .get_mut::<tcp::Socket>(connected_handle)
+                        .abort();
+                    inner.orphaned_closing.push(connected_handle);
+                    this.listener.handles.swap_remove(idx);
+                    return Poll::Ready(Err(err));
+                }
 
                 // Create a new listening socket to replace it
                 let new_handle = TcpListener::create_listening_socket(
                     &mut inner,
-                    this.listener.port,
-                    this.listener.rx_buffer_size,
-                    this.listener.tx_buffer_size,
+                    this.listener.endpoint,
+                    &this.listener.options,
                 )?;
 
                 // Replace the connected handle with the new listening one
                 this.listener.handles[idx] = new_handle;
+                inner.connections_accepted += 1;
 
                 drop(inner);
 
                 // Create a TcpStream from the connected socket
-                let stream =
+                let mut stream =
                     TcpStream::from_handle(connected_handle, this.listener.reactor.clone());
+                inflight.live.set(inflight.live.get() + 1);
+                stream.inflight = Some(inflight.clone());
 
                 Poll::Ready(Ok(stream))
             }
             None => {
                 // No established connection yet
-                let mut inner = this.listener.reactor.borrow_mut();
+                let reactor = this.listener.reactor.clone();
+                let mut inner = reactor.borrow_mut();
+
+                // Refill slots left empty at a cap, or have the reactor wake
+                // us once the next socket fits
+                if !this.listener.refill_backlog(&mut inner)? {
+                    let bytes = this.listener.options.buffer_bytes();
+                    inner.capacity_waiters.push((cx.waker().clone(), bytes));
+                }
 
                 // Check if all sockets are dead
-                let all_dead = this.listener.handles.iter().all(|&h| {
-                    let socket = inner.sockets.get::<tcp::Socket>(h);
-                    matches!(socket.state(), State::Closed | State::TimeWait)
-                });
+                let all_dead = !this.listener.handles.is_empty()
+                    && this.listener.handles.iter().all(|&h| {
+                        let socket = inner.sockets.get::<tcp::Socket>(h);
+                        matches!(socket.state(), State::Closed | State::TimeWait)
+                    });
 
                 if all_dead {
-                    return Poll::Ready(Err(ListenError::Unaddressable));
+                    return Poll::Ready(Err(ListenError::Unaddressable.into()));
                 }
 
                 // Register wakers on all listening sockets and wait.
@@ -495,7 +1992,11 @@ impl<'a> Future for AcceptFuture<'a> {
                 for &handle in &this.listener.handles {
                     let socket = inner.sockets.get_mut::<tcp::Socket>(handle);
                     socket.register_recv_waker(cx.waker());
+                    if let Some(spec) = inner.listen_specs.get_mut(&handle) {
+                        spec.accept_waker = Some(cx.waker().clone());
+                    }
                 }
+                inner.note_pending();
 
                 Poll::Pending
             }
@@ -503,6 +2004,60 @@ impl<'a> Future for AcceptFuture<'a> {
     }
 }
 
+/// Future returned by [`TcpStream::peek`].
+pub struct TcpPeekFuture<'a> {
+    stream: &'a TcpStream,
+    buf: &'a mut [u8],
+}
+
+impl Future for TcpPeekFuture<'_> {
+    type Output = io::Result<usize>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        this.stream.poll_peek(cx, this.buf)
+    }
+}
+
+/// Future returned by [`TcpStream::send_vectored`].
+pub struct TcpSendVectoredFuture<'a> {
+    stream: &'a TcpStream,
+    bufs: &'a [IoSlice<'a>],
+    /// Bytes of `bufs` already queued
+    written: usize,
+}
+
+impl Future for TcpSendVectoredFuture<'_> {
+    type Output = io::Result<usize>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let total: usize = this.bufs.iter().map(|b| b.len()).sum();
+        while this.written < total {
+            match this.stream.poll_send_vectored(cx, this.bufs, this.written) {
+                Poll::Ready(Ok(n)) => this.written += n,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+        Poll::Ready(Ok(total))
+    }
+}
+
+/// Stream of accepted connections, from [`TcpListener::incoming`].
+pub struct Incoming<'a> {
+    listener: &'a mut TcpListener,
+}
+
+impl Stream for Incoming<'_> {
+    type Item = Result<TcpStream, TcpListenError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        Pin::new(&mut this.listener.accept()).poll(cx).map(Some)
+    }
+}
+
 /// Future for waiting until a stream is connected
 pub struct WaitConnectedFuture<'a> {
     socket: &'a TcpStream,
@@ -524,13 +2079,146 @@ impl<'a> Future for WaitConnectedFuture<'a> {
             // Still connecting - register waker and wait
             State::SynSent | State::SynReceived => {
                 socket.register_send_waker(cx.waker());
+                inner.note_pending();
                 Poll::Pending
             }
             // Other states - keep waiting
             _ => {
                 socket.register_send_waker(cx.waker());
+                inner.note_pending();
                 Poll::Pending
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_smoltcp_endpoint() {
+        let v4: SocketAddr = "192.168.1.1:8080".parse().unwrap();
+        assert_eq!(smoltcp_endpoint(v4), (IpAddress::v4(192, 168, 1, 1), 8080));
+        let v6: SocketAddr = "[fd00::1]:8080".parse().unwrap();
+        assert_eq!(
+            smoltcp_endpoint(v6),
+            (IpAddress::v6(0xfd00, 0, 0, 0, 0, 0, 0, 1), 8080)
+        );
+        let mapped: SocketAddr = "[::ffff:192.168.1.1]:8080".parse().unwrap();
+        assert_eq!(
+            smoltcp_endpoint(mapped),
+            (IpAddress::v4(192, 168, 1, 1), 8080)
+        );
+    }
+
+    #[test]
+    fn test_recv_error_by_state() {
+        assert_eq!(TcpStreamError::for_recv(State::SynSent), None);
+        assert_eq!(TcpStreamError::for_recv(State::SynReceived), None);
+        assert_eq!(
+            TcpStreamError::for_recv(State::Closed),
+            Some(TcpStreamError::ConnectionReset)
+        );
+        assert_eq!(
+            TcpStreamError::for_recv(State::Listen),
+            Some(TcpStreamError::NotConnected)
+        );
+    }
+
+    #[test]
+    fn test_send_error_by_state() {
+        assert_eq!(TcpStreamError::for_send(State::SynSent, false), None);
+        for state in [
+            State::FinWait1,
+            State::FinWait2,
+            State::Closing,
+            State::LastAck,
+            State::TimeWait,
+        ] {
+            assert_eq!(
+                TcpStreamError::for_send(state, true),
+                Some(TcpStreamError::Shutdown),
+                "{state}"
+            );
+        }
+        assert_eq!(
+            TcpStreamError::for_send(State::Closed, true),
+            Some(TcpStreamError::Shutdown)
+        );
+        assert_eq!(
+            TcpStreamError::for_send(State::Closed, false),
+            Some(TcpStreamError::ConnectionReset)
+        );
+        assert_eq!(
+            TcpStreamError::for_send(State::Listen, false),
+            Some(TcpStreamError::NotConnected)
+        );
+    }
+
+    #[test]
+    fn test_io_error_kind() {
+        let err: io::Error = TcpStreamError::ConnectionReset.into();
+        assert_eq!(err.kind(), io::ErrorKind::ConnectionReset);
+        let inner = err
+            .get_ref()
+            .and_then(|e| e.downcast_ref::<TcpStreamError>());
+        assert_eq!(inner, Some(&TcpStreamError::ConnectionReset));
+        assert_eq!(
+            io::Error::from(TcpStreamError::Shutdown).kind(),
+            io::ErrorKind::BrokenPipe
+        );
+    }
+
+    #[test]
+    fn test_socket_options_defaults() {
+        let options = TcpSocketOptions::new(1024, 2048);
+        assert_eq!(options.buffer_bytes(), 3072);
+        assert_eq!(options.keep_alive, None);
+        assert_eq!(options.timeout, None);
+        assert_eq!(options.ack_delay, Some(DEFAULT_ACK_DELAY));
+        assert!(options.nagle);
+
+        // The defaults are smoltcp's own
+        let socket = options.socket();
+        let fresh = tcp::Socket::new(
+            tcp::SocketBuffer::new(vec![0; 1]),
+            tcp::SocketBuffer::new(vec![0; 1]),
+        );
+        assert_eq!(socket.ack_delay(), fresh.ack_delay());
+        assert_eq!(socket.hop_limit(), fresh.hop_limit());
+        assert_eq!(socket.nagle_enabled(), fresh.nagle_enabled());
+
+        let options = options
+            .keep_alive(Some(Duration::from_secs(5)))
+            .timeout(Some(Duration::from_secs(20)))
+            .ack_delay(None)
+            .nagle(false)
+            .hop_limit(Some(8));
+        let socket = options.socket();
+        assert_eq!(socket.keep_alive(), Some(Duration::from_secs(5).into()));
+        assert_eq!(socket.timeout(), Some(Duration::from_secs(20).into()));
+        assert_eq!(socket.ack_delay(), None);
+        assert!(!socket.nagle_enabled());
+        assert_eq!(socket.hop_limit(), Some(8));
+    }
+
+    #[test]
+    #[should_panic(expected = "hop limit must not be zero")]
+    fn test_socket_options_zero_hop_limit() {
+        let _ = TcpSocketOptions::new(1024, 1024).hop_limit(Some(0));
+    }
+
+    #[test]
+    fn test_nonblocking_error_io_kind() {
+        assert!(NonblockingError::WouldBlock.is_would_block());
+        assert_eq!(
+            io::Error::from(NonblockingError::WouldBlock).kind(),
+            io::ErrorKind::WouldBlock
+        );
+        assert_eq!(
+            io::Error::from(NonblockingError::Stream(TcpStreamError::ConnectionReset)).kind(),
+            io::ErrorKind::ConnectionReset
+        );
+    }
+}
@@ -4,8 +4,8 @@ use crate::device::DpdkDevice;
 use crate::runtime::{ReactorHandle, ReactorInner};
 use smoltcp::iface::SocketHandle;
 use smoltcp::socket::udp::{self, BindError, RecvError, SendError, UdpMetadata};
-use smoltcp::wire::IpEndpoint;
-use std::cell::RefCell;
+use smoltcp::wire::{IpAddress, IpEndpoint};
+use std::cell::{Cell, RefCell};
 use std::future::Future;
 use std::pin::Pin;
 use std::rc::Rc;
@@ -17,10 +17,23 @@ use std::task::{Context, Poll};
 /// send and receive datagrams asynchronously.
 ///
 /// Unlike TCP, UDP is connectionless. You can send to and receive from
-/// any endpoint without establishing a connection first.
+/// any endpoint without establishing a connection first. [`connect`](Self::connect)
+/// fixes a default peer instead, as with `std::net::UdpSocket::connect`.
+///
+/// # Migrating from the metadata `send_to`/`recv_from`
+///
+/// `send_to` and `recv_from` used to take and return smoltcp endpoint
+/// metadata. They now follow `std::net` and take or return the address and
+/// port; the metadata versions are [`send_with_meta`](Self::send_with_meta)
+/// and [`recv_with_meta`](Self::recv_with_meta), with the old signatures.
+/// Code that passed an [`IpEndpoint`] or destructured `(len, meta)` only
+/// needs the method renamed.
 pub struct UdpSocket {
     handle: SocketHandle,
     reactor: Rc<RefCell<ReactorInner<DpdkDevice>>>,
+    /// Peer set by `connect`: the destination of `send`, and the only
+    /// source datagrams are accepted from.
+    peer: Cell<Option<IpEndpoint>>,
 }
 
 impl UdpSocket {
@@ -58,6 +71,7 @@ impl UdpSocket {
         Ok(UdpSocket {
             handle: socket_handle,
             reactor: handle.inner.clone(),
+            peer: Cell::new(None),
         })
     }
 
@@ -80,24 +94,111 @@ impl UdpSocket {
         socket.endpoint()
     }
 
-    /// Send a datagram to the specified endpoint asynchronously.
+    /// Fix `remote:port` as this socket's peer.
+    ///
+    /// Afterwards [`send`](Self::send) goes to the peer, and datagrams from
+    /// any other source are dropped as they are received, by
+    /// [`recv`](Self::recv), [`recv_from`](Self::recv_from) and
+    /// [`recv_with_meta`](Self::recv_with_meta) alike.
+    /// [`send_to`](Self::send_to) still reaches any destination. Calling it
+    /// again switches peers. No packet is sent.
+    ///
+    /// Fails with [`SendError::Unaddressable`] for an unspecified address
+    /// or port 0.
+    pub fn connect(&self, remote: IpAddress, port: u16) -> Result<(), SendError> {
+        if remote.is_unspecified() || port == 0 {
+            return Err(SendError::Unaddressable);
+        }
+        self.peer.set(Some(IpEndpoint::new(remote, port)));
+        Ok(())
+    }
+
+    /// The peer set by [`connect`](Self::connect), if any.
+    pub fn peer_addr(&self) -> Option<IpEndpoint> {
+        self.peer.get()
+    }
+
+    /// Send a datagram to the connected peer.
     ///
-    /// Returns the number of bytes sent when the operation completes.
-    pub fn send_to<'a>(&'a self, data: &'a [u8], endpoint: IpEndpoint) -> UdpSendFuture<'a> {
+    /// Fails with [`SendError::Unaddressable`] if the socket is not
+    /// connected.
+    pub fn send<'a>(&'a self, data: &'a [u8]) -> UdpSendFuture<'a> {
         UdpSendFuture {
             socket: self,
             data,
-            endpoint,
+            meta: self.peer.get().map(UdpMetadata::from),
         }
     }
 
-    /// Receive a datagram asynchronously.
+    /// Receive a datagram, without its metadata.
+    ///
+    /// On a connected socket only the peer's datagrams are returned; see
+    /// [`connect`](Self::connect). Otherwise the same as
+    /// [`recv_from`](Self::recv_from).
+    pub async fn recv(&self, buf: &mut [u8]) -> Result<usize, RecvError> {
+        self.recv_with_meta(buf).await.map(|(len, _)| len)
+    }
+
+    /// Send a datagram to `addr:port`.
     ///
-    /// Returns the number of bytes received and the source endpoint.
-    pub fn recv_from<'a>(&'a self, buf: &'a mut [u8]) -> UdpRecvFuture<'a> {
+    /// Returns the number of bytes sent when the operation completes. The
+    /// source address is picked by the interface; use
+    /// [`send_with_meta`](Self::send_with_meta) to choose it.
+    pub fn send_to<'a>(&'a self, data: &'a [u8], addr: IpAddress, port: u16) -> UdpSendFuture<'a> {
+        self.send_with_meta(data, IpEndpoint::new(addr, port))
+    }
+
+    /// Receive a datagram, returning its length and the sender's address and
+    /// port.
+    ///
+    /// A connected socket skips datagrams from other sources. Use
+    /// [`recv_with_meta`](Self::recv_with_meta) to also learn which local
+    /// address the datagram was sent to.
+    pub async fn recv_from(&self, buf: &mut [u8]) -> Result<(usize, IpAddress, u16), RecvError> {
+        let (len, meta) = self.recv_with_meta(buf).await?;
+        Ok((len, meta.endpoint.addr, meta.endpoint.port))
+    }
+
+    /// Send a datagram as described by `meta`.
+    ///
+    /// Accepts an [`IpEndpoint`] or a full [`UdpMetadata`]. Setting
+    /// `local_address` picks the source address, so a server on a multi-IP
+    /// or anycast interface can answer from the address it was reached on.
+    pub fn send_with_meta<'a>(
+        &'a self,
+        data: &'a [u8],
+        meta: impl Into<UdpMetadata>,
+    ) -> UdpSendFuture<'a> {
+        UdpSendFuture {
+            socket: self,
+            data,
+            meta: Some(meta.into()),
+        }
+    }
+
+    /// Receive a datagram with its [`UdpMetadata`].
+    ///
+    /// Returns the number of bytes received and the metadata, which holds the
+    /// source endpoint and the local address the datagram was sent to. A
+    /// connected socket skips datagrams from other sources.
+    pub fn recv_with_meta<'a>(&'a self, buf: &'a mut [u8]) -> UdpRecvFuture<'a> {
         UdpRecvFuture { socket: self, buf }
     }
 
+    /// Wait until every queued datagram has been handed to the NIC.
+    ///
+    /// Resolves once smoltcp's transmit buffer is empty and the device has
+    /// pushed the resulting frames into the hardware TX ring, so dropping
+    /// the socket afterwards loses nothing. Queued datagrams are sent right
+    /// away rather than on the next reactor pass.
+    ///
+    /// This says nothing about delivery: the frames can still be dropped on
+    /// the wire or by the receiver. A datagram whose destination has no
+    /// neighbor entry yet waits for address resolution, and so does `flush`.
+    pub fn flush(&self) -> UdpFlushFuture<'_> {
+        UdpFlushFuture { socket: self }
+    }
+
     /// Close the socket.
     pub fn close(&self) {
         let mut inner = self.reactor.borrow_mut();
@@ -119,21 +220,26 @@ impl Drop for UdpSocket {
 pub struct UdpSendFuture<'a> {
     socket: &'a UdpSocket,
     data: &'a [u8],
-    endpoint: IpEndpoint,
+    /// `None` for `send` on an unconnected socket
+    meta: Option<UdpMetadata>,
 }
 
 impl Future for UdpSendFuture<'_> {
     type Output = Result<usize, SendError>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let Some(meta) = self.meta else {
+            return Poll::Ready(Err(SendError::Unaddressable));
+        };
         let mut inner = self.socket.reactor.borrow_mut();
         let socket = inner.sockets.get_mut::<udp::Socket>(self.socket.handle);
 
-        match socket.send_slice(self.data, self.endpoint) {
+        match socket.send_slice(self.data, meta) {
             Ok(()) => Poll::Ready(Ok(self.data.len())),
             Err(SendError::BufferFull) => {
                 // Register waker and wait
                 socket.register_send_waker(cx.waker());
+                inner.note_pending();
                 Poll::Pending
             }
             Err(e) => Poll::Ready(Err(e)),
@@ -141,6 +247,45 @@ impl Future for UdpSendFuture<'_> {
     }
 }
 
+/// Future for [`UdpSocket::flush`]
+pub struct UdpFlushFuture<'a> {
+    socket: &'a UdpSocket,
+}
+
+impl Future for UdpFlushFuture<'_> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut inner = self.socket.reactor.borrow_mut();
+        if inner
+            .sockets
+            .get::<udp::Socket>(self.socket.handle)
+            .send_queue()
+            > 0
+        {
+            inner.egress_now();
+        }
+
+        let socket = inner.sockets.get_mut::<udp::Socket>(self.socket.handle);
+        if socket.send_queue() > 0 {
+            // Woken as smoltcp dispatches the remaining datagrams
+            socket.register_send_waker(cx.waker());
+            inner.note_pending();
+            return Poll::Pending;
+        }
+
+        inner.device.flush_tx();
+        if inner.device.tx_pending() > 0 {
+            // The TX ring is full; the reactor's next flush wakes us once
+            // the backlog has gone out
+            inner.device.register_tx_drain_waker(cx.waker());
+            inner.note_pending();
+            return Poll::Pending;
+        }
+        Poll::Ready(())
+    }
+}
+
 /// Future for receiving UDP data
 pub struct UdpRecvFuture<'a> {
     socket: &'a UdpSocket,
@@ -153,15 +298,20 @@ impl Future for UdpRecvFuture<'_> {
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let mut inner = self.socket.reactor.borrow_mut();
         let socket = inner.sockets.get_mut::<udp::Socket>(self.socket.handle);
+        let peer = self.socket.peer.get();
 
-        match socket.recv_slice(self.buf) {
-            Ok((len, metadata)) => Poll::Ready(Ok((len, metadata))),
-            Err(RecvError::Exhausted) => {
-                // No data available, register waker and wait
-                socket.register_recv_waker(cx.waker());
-                Poll::Pending
+        loop {
+            match socket.recv_slice(self.buf) {
+                Ok((_, metadata)) if peer.is_some_and(|p| p != metadata.endpoint) => continue,
+                Ok((len, metadata)) => return Poll::Ready(Ok((len, metadata))),
+                Err(RecvError::Exhausted) => break,
+                Err(e) => return Poll::Ready(Err(e)),
             }
-            Err(e) => Poll::Ready(Err(e)),
         }
+
+        // No data available, register waker and wait
+        socket.register_recv_waker(cx.waker());
+        inner.note_pending();
+        Poll::Pending
     }
 }
@@ -0,0 +1,324 @@
+//! Host topology helpers for sizing queues and lcores.
+//!
+//! These run before EAL initialization: they query the kernel (ethtool) and
+//! the CPU count to decide how many RX/TX queues to configure and which
+//! `-l` core list to pass to [`EalBuilder::core_list`](crate::api::rte::eal::EalBuilder::core_list).
+//! [`verify_isolation`] checks the chosen cores against the kernel's
+//! `isolcpus` set.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use dpdk_net::api::rte::eal::EalBuilder;
+//! use dpdk_net::topology::recommend_queues;
+//!
+//! let plan = recommend_queues("eth1", Some(8)).expect("Failed to query queues");
+//! let _eal = EalBuilder::new()
+//!     .core_list(&plan.core_list)
+//!     .init()
+//!     .expect("EAL init failed");
+//! ```
+
+/// Channel information from ethtool
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EthtoolChannels {
+    /// Maximum supported RX-only channels
+    pub max_rx: u32,
+    /// Maximum supported TX-only channels
+    pub max_tx: u32,
+    /// Maximum supported other channels
+    pub max_other: u32,
+    /// Maximum supported combined channels (RX+TX on same queue)
+    pub max_combined: u32,
+    /// Current RX-only channels
+    pub rx_count: u32,
+    /// Current TX-only channels
+    pub tx_count: u32,
+    /// Current other channels
+    pub other_count: u32,
+    /// Current combined channels
+    pub combined_count: u32,
+}
+
+/// Get ethtool channel information for a network interface.
+///
+/// This uses the SIOCETHTOOL ioctl to query channel counts,
+/// equivalent to running `ethtool -l <interface>`.
+///
+/// # Example
+/// ```no_run
+/// use dpdk_net::topology::get_ethtool_channels;
+///
+/// let channels = get_ethtool_channels("eth1").unwrap();
+/// println!("Max combined queues: {}", channels.max_combined);
+/// println!("Current combined queues: {}", channels.combined_count);
+/// ```
+pub fn get_ethtool_channels(interface: &str) -> Result<EthtoolChannels, String> {
+    use nix::libc;
+    use std::ffi::CString;
+
+    // ethtool command constants
+    const ETHTOOL_GCHANNELS: u32 = 0x0000003c;
+    const SIOCETHTOOL: libc::c_ulong = 0x8946;
+
+    // struct ethtool_channels from linux/ethtool.h
+    #[repr(C)]
+    struct EthtoolChannelsRaw {
+        cmd: u32,
+        max_rx: u32,
+        max_tx: u32,
+        max_other: u32,
+        max_combined: u32,
+        rx_count: u32,
+        tx_count: u32,
+        other_count: u32,
+        combined_count: u32,
+    }
+
+    // Create a socket for the ioctl
+    let sock = unsafe { libc::socket(libc::AF_INET, libc::SOCK_DGRAM, 0) };
+    if sock < 0 {
+        return Err("Failed to create socket".to_string());
+    }
+
+    // Ensure socket is closed when we're done
+    struct SocketGuard(i32);
+    impl Drop for SocketGuard {
+        fn drop(&mut self) {
+            unsafe { libc::close(self.0) };
+        }
+    }
+    let _guard = SocketGuard(sock);
+
+    // Prepare ethtool_channels struct
+    let mut channels = EthtoolChannelsRaw {
+        cmd: ETHTOOL_GCHANNELS,
+        max_rx: 0,
+        max_tx: 0,
+        max_other: 0,
+        max_combined: 0,
+        rx_count: 0,
+        tx_count: 0,
+        other_count: 0,
+        combined_count: 0,
+    };
+
+    // Prepare ifreq struct
+    let mut ifr: libc::ifreq = unsafe { std::mem::zeroed() };
+
+    // Copy interface name
+    let ifname = CString::new(interface).map_err(|_| "Invalid interface name")?;
+    let ifname_bytes = ifname.as_bytes_with_nul();
+    if ifname_bytes.len() > libc::IFNAMSIZ {
+        return Err("Interface name too long".to_string());
+    }
+    unsafe {
+        std::ptr::copy_nonoverlapping(
+            ifname_bytes.as_ptr(),
+            ifr.ifr_name.as_mut_ptr().cast::<u8>(),
+            ifname_bytes.len(),
+        );
+    }
+
+    // Set ifr_data to point to our ethtool_channels struct
+    ifr.ifr_ifru.ifru_data = &mut channels as *mut _ as *mut libc::c_char;
+
+    // Make the ioctl call
+    let ret = unsafe { libc::ioctl(sock, SIOCETHTOOL, &mut ifr) };
+    if ret < 0 {
+        let errno = std::io::Error::last_os_error();
+        return Err(format!("ioctl SIOCETHTOOL failed: {}", errno));
+    }
+
+    Ok(EthtoolChannels {
+        max_rx: channels.max_rx,
+        max_tx: channels.max_tx,
+        max_other: channels.max_other,
+        max_combined: channels.max_combined,
+        rx_count: channels.rx_count,
+        tx_count: channels.tx_count,
+        other_count: channels.other_count,
+        combined_count: channels.combined_count,
+    })
+}
+
+/// Queue and lcore sizing decision.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueuePlan {
+    /// Number of RX/TX queue pairs (and lcores) to use.
+    pub queues: usize,
+    /// EAL core list covering `queues` lcores, e.g. `"0-3"`.
+    pub core_list: String,
+}
+
+impl QueuePlan {
+    /// Apply the sizing policy to known inputs.
+    ///
+    /// The queue count is the smallest of the hardware queue count, the CPU
+    /// count, and `max` (if given), and never less than 1.
+    pub fn new(hw_queues: usize, cpus: usize, max: Option<usize>) -> Self {
+        let mut queues = hw_queues.min(cpus);
+        if let Some(max) = max {
+            queues = queues.min(max);
+        }
+        let queues = queues.max(1);
+        let core_list = if queues == 1 {
+            "0".to_string()
+        } else {
+            format!("0-{}", queues - 1)
+        };
+        Self { queues, core_list }
+    }
+}
+
+/// Number of hardware queues usable for combined RX/TX on `interface`.
+///
+/// Prefers the current combined channel count, falling back to the smaller of
+/// the RX-only and TX-only counts for NICs that do not report combined channels.
+pub fn hw_queue_count(interface: &str) -> crate::Result<usize> {
+    let ch = get_ethtool_channels(interface)?;
+    let count = if ch.combined_count > 0 {
+        ch.combined_count
+    } else {
+        ch.rx_count.min(ch.tx_count)
+    };
+    Ok(count as usize)
+}
+
+/// Recommend a queue count and EAL core list for `interface`.
+///
+/// Consults ethtool for the hardware queue count and
+/// [`std::thread::available_parallelism`] for the CPU count, capped by `max`.
+/// Call this before EAL initialization.
+pub fn recommend_queues(interface: &str, max: Option<usize>) -> crate::Result<QueuePlan> {
+    let hw_queues = hw_queue_count(interface)?;
+    let cpus = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    let plan = QueuePlan::new(hw_queues, cpus, max);
+    tracing::debug!(
+        interface,
+        hw_queues,
+        cpus,
+        max,
+        queues = plan.queues,
+        "Queue plan"
+    );
+    Ok(plan)
+}
+
+/// Kernel list of CPUs removed from the general scheduler (`isolcpus=`).
+const ISOLATED_CPUS_PATH: &str = "/sys/devices/system/cpu/isolated";
+
+/// Parse a kernel CPU list such as `"2-5,8"` into CPU IDs.
+///
+/// This is the format of `/sys/devices/system/cpu/isolated` and the other
+/// cpulist files in sysfs. An empty (or all-whitespace) list is valid.
+pub fn parse_cpu_list(list: &str) -> Result<Vec<usize>, String> {
+    let mut cpus = Vec::new();
+    for part in list.trim().split(',').filter(|p| !p.is_empty()) {
+        let parse = |s: &str| {
+            s.trim()
+                .parse::<usize>()
+                .map_err(|_| format!("Invalid CPU list entry: {part:?}"))
+        };
+        match part.split_once('-') {
+            Some((first, last)) => {
+                let (first, last) = (parse(first)?, parse(last)?);
+                if first > last {
+                    return Err(format!("Invalid CPU range: {part:?}"));
+                }
+                cpus.extend(first..=last);
+            }
+            None => cpus.push(parse(part)?),
+        }
+    }
+    Ok(cpus)
+}
+
+/// Which of a set of cores are isolated from the kernel scheduler.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IsolationReport {
+    /// Checked cores that are in the kernel's isolated set.
+    pub isolated: Vec<usize>,
+    /// Checked cores the scheduler may still place other tasks on.
+    pub shared: Vec<usize>,
+}
+
+impl IsolationReport {
+    /// Split `core_ids` by membership in `isolated_cpus`.
+    pub fn new(core_ids: &[usize], isolated_cpus: &[usize]) -> Self {
+        let (isolated, shared) = core_ids
+            .iter()
+            .copied()
+            .partition(|cpu| isolated_cpus.contains(cpu));
+        Self { isolated, shared }
+    }
+
+    /// Returns true if every checked core is isolated.
+    pub fn is_isolated(&self) -> bool {
+        self.shared.is_empty()
+    }
+}
+
+/// Check `core_ids` against the kernel's isolated CPU list.
+///
+/// Reads `/sys/devices/system/cpu/isolated` and logs a warning naming any
+/// core that is not isolated: a polling lcore that shares its CPU with other
+/// tasks gets preempted, which shows up as latency spikes rather than as an
+/// error. Pass the CPUs the lcores are pinned to
+/// ([`Lcore::cpu_id`](crate::api::rte::lcore::Lcore::cpu_id)).
+///
+/// Returns an error if the sysfs file cannot be read or parsed.
+pub fn verify_isolation(core_ids: &[usize]) -> crate::Result<IsolationReport> {
+    let list = std::fs::read_to_string(ISOLATED_CPUS_PATH)
+        .map_err(|e| format!("Failed to read {ISOLATED_CPUS_PATH}: {e}"))?;
+    let report = IsolationReport::new(core_ids, &parse_cpu_list(&list)?);
+    if !report.is_isolated() {
+        tracing::warn!(
+            shared = ?report.shared,
+            isolated = ?report.isolated,
+            "Cores are not isolated from the kernel scheduler (isolcpus)"
+        );
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cpu_list() {
+        assert_eq!(parse_cpu_list("2-5,8\n").unwrap(), vec![2, 3, 4, 5, 8]);
+        assert_eq!(parse_cpu_list("0").unwrap(), vec![0]);
+        assert_eq!(parse_cpu_list("\n").unwrap(), Vec::<usize>::new());
+        assert!(parse_cpu_list("3-1").is_err());
+        assert!(parse_cpu_list("a").is_err());
+    }
+
+    #[test]
+    fn test_isolation_report() {
+        let report = IsolationReport::new(&[1, 2, 3], &[2, 3, 4]);
+        assert_eq!(report.isolated, vec![2, 3]);
+        assert_eq!(report.shared, vec![1]);
+        assert!(!report.is_isolated());
+        assert!(IsolationReport::new(&[2], &[2, 3]).is_isolated());
+    }
+
+    #[test]
+    fn test_queue_plan_caps() {
+        assert_eq!(QueuePlan::new(8, 4, None).queues, 4);
+        assert_eq!(QueuePlan::new(4, 16, None).queues, 4);
+        assert_eq!(QueuePlan::new(8, 16, Some(2)).queues, 2);
+        assert_eq!(QueuePlan::new(8, 16, Some(2)).core_list, "0-1");
+    }
+
+    #[test]
+    fn test_queue_plan_minimum_one() {
+        let plan = QueuePlan::new(0, 4, None);
+        assert_eq!(plan.queues, 1);
+        assert_eq!(plan.core_list, "0");
+        assert_eq!(QueuePlan::new(4, 4, Some(0)).queues, 1);
+    }
+}
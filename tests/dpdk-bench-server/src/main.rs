@@ -181,6 +181,7 @@ fn run_dpdk_server(
 ) {
     use dpdk_net::api::rte::eal::EalBuilder;
     use dpdk_net::socket::TcpListener;
+    use dpdk_net::topology::{QueuePlan, recommend_queues};
     use dpdk_net_test::app::http_server::Http1Server;
     use dpdk_net_test::manual::tcp::{get_default_gateway, get_interface_ipv4, get_pci_addr};
     use dpdk_net_util::{DpdkApp, WorkerContext};
@@ -200,22 +201,16 @@ fn run_dpdk_server(
         get_default_gateway().unwrap_or(Ipv4Address::new(10, 0, 0, 1))
     };
 
-    // Detect hardware queues: use explicit value if provided, otherwise auto-detect
-    let num_queues = if let Some(queues) = hw_queues {
-        queues
+    // Size queues: use explicit hardware queue count if provided, otherwise auto-detect
+    let plan = if let Some(queues) = hw_queues {
+        let cpus = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        QueuePlan::new(queues, cpus, max_queues)
     } else {
-        dpdk_net_test::util::get_ethtool_channels(interface)
-            .map(|ch| ch.combined_count as usize)
-            .expect("Failed to get hardware queues via ethtool")
+        recommend_queues(interface, max_queues).expect("Failed to get hardware queues via ethtool")
     };
-
-    // Apply max_queues cap
-    let num_queues = if let Some(max) = max_queues {
-        num_queues.min(max)
-    } else {
-        num_queues
-    };
-    let core_list = format!("0-{}", num_queues.saturating_sub(1));
+    let core_list = plan.core_list;
 
     // Initialize DPDK EAL with core list matching queue count
     let pci_addr = get_pci_addr(interface).expect("Failed to get PCI address");
@@ -241,9 +236,11 @@ fn run_dpdk_server(
         .run(move |ctx: WorkerContext| {
             let cancel = cancel.clone();
             async move {
-                let listener =
+                let mut listener =
                     TcpListener::bind_with_backlog(&ctx.reactor, port, 16384, 16384, backlog)
                         .expect("Failed to bind listener");
+                // Small responses should not wait on the previous one's ACK
+                listener.set_nodelay(true);
                 Http1Server::new(
                     listener,
                     cancel,